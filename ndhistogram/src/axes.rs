@@ -1,7 +1,39 @@
-use super::axis::Axis;
+use super::axis::{Axis, Compatible, StrAxis};
+use crate::{compare::Tolerance, error::BinaryOperationError};
 
 /// Axes provided an interface for a set of ND dimensional set of histograms.
-pub trait Axes: Axis {}
+pub trait Axes: Axis {
+    /// The number of bins of each axis, including underflow and overflow bins.
+    ///
+    /// Bins are numbered with the first axis varying fastest, so the bin at
+    /// position `(i, j, k)` has index `i + shape[0] * (j + shape[1] * k)`.
+    fn shape(&self) -> Vec<usize> {
+        vec![self.num_bins()]
+    }
+}
+
+/// The index of a bin after the axes have grown from the old shape to the new shape.
+///
+/// Axes only grow by adding bins after their existing bins (see [Axis::grow]),
+/// so each bin keeps its index on every axis.
+pub(crate) fn grown_index(index: usize, old_shape: &[usize], new_shape: &[usize]) -> usize {
+    let mut index = index;
+    let mut stride = 1;
+    let mut grown = 0;
+    for (old, new) in old_shape.iter().zip(new_shape) {
+        grown += index % old * stride;
+        index /= old;
+        stride *= new;
+    }
+    grown
+}
+
+impl<X: StrAxis> StrAxis for AxesTuple<(X,)> {
+    #[inline]
+    fn index_str(&self, value: &str) -> Option<usize> {
+        self.axes.0.index_str(value)
+    }
+}
 
 /// Container for a set of [Axis] that implements [Axes].
 
@@ -19,18 +51,6 @@ impl<T> AxesTuple<T> {
     }
 }
 
-// Count idents in macro from: <https://danielkeep.github.io/tlborm/book/blk-counting.html>
-macro_rules! count_idents {
-    ($($idents:ident),* $(,)*) => {
-        {
-            #[allow(dead_code, non_camel_case_types)]
-            enum Idents { $($idents,)* __CountIdentsLast }
-            const COUNT: usize = Idents::__CountIdentsLast as usize;
-            COUNT
-        }
-    };
-}
-
 macro_rules! impl_axes {
     () => {
 
@@ -39,6 +59,9 @@ macro_rules! impl_axes {
         ( $type_parameter:ident: $index:tt, ) => {
 
         impl<X: Axis> Axes for AxesTuple<(X,)> {
+            fn shape(&self) -> Vec<usize> {
+                vec![self.axes.0.num_bins()]
+            }
         }
 
         impl<X:Axis> From<(X,)> for AxesTuple<(X,)> {
@@ -65,6 +88,27 @@ macro_rules! impl_axes {
                 self.axes.0.bin(index)
             }
 
+            fn num_dim(&self) -> usize {
+                self.axes.0.num_dim()
+            }
+
+            fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+                self.axes.0.check_compatible(&other.axes.0)
+            }
+
+            fn grow(&mut self, coordinate: &Self::Coordinate) -> bool {
+                let grew = self.axes.0.grow(coordinate);
+                if grew {
+                    self.shape = vec![self.axes.0.num_bins()];
+                }
+                grew
+            }
+        }
+
+        impl<X: Compatible> Compatible for AxesTuple<(X,)> {
+            fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+                self.axes.0.is_compatible(&other.axes.0, tolerance)
+            }
         }
 
         impl_axes!();
@@ -72,6 +116,9 @@ macro_rules! impl_axes {
     //( $( ($nth_index:tt => $nth_type_parameter:ident), )+ ) => {
         ( $($nth_type_parameter:ident: $nth_index:tt, )+ ) => {
         impl<$($nth_type_parameter: Axis),*> Axes for AxesTuple<($($nth_type_parameter),*)> {
+            fn shape(&self) -> Vec<usize> {
+                vec![$(self.axes.$nth_index.num_bins()),*]
+            }
         }
 
         impl<$($nth_type_parameter: Axis),*> From<($($nth_type_parameter),*)> for AxesTuple<($($nth_type_parameter),*)> {
@@ -103,7 +150,31 @@ macro_rules! impl_axes {
             }
 
             fn num_dim(&self) -> usize {
-                count_idents!($($nth_type_parameter,)*)
+                0 $(+ self.axes.$nth_index.num_dim())*
+            }
+
+            fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+                // the error reports the dimension, which is after all dimensions of the preceding axes
+                let num_dims = [$(self.axes.$nth_index.num_dim()),*];
+                $(
+                    self.axes.$nth_index.check_compatible(&other.axes.$nth_index).map_err(|error| match error {
+                        BinaryOperationError::IncompatibleAxis { axis, reason } => {
+                            let offset: usize = num_dims[..$nth_index].iter().sum();
+                            BinaryOperationError::IncompatibleAxis { axis: offset + axis, reason }
+                        }
+                        error => error,
+                    })?;
+                )*
+                Ok(())
+            }
+
+            fn grow(&mut self, coordinate: &Self::Coordinate) -> bool {
+                // grow every axis, not only up to the first one that grows
+                let grew = [$(self.axes.$nth_index.grow(&coordinate.$nth_index)),*];
+                if grew.contains(&true) {
+                    self.shape = [$(self.axes.$nth_index.num_bins()),*].iter().scan(1, |acc, nbin| {*acc *= *nbin; Some(*acc)}).collect();
+                }
+                grew.contains(&true)
             }
 
             fn bin(&self, index: usize) -> Option<Self::BinInterval> {
@@ -123,6 +194,12 @@ macro_rules! impl_axes {
             }
         }
 
+        impl<$($nth_type_parameter: Compatible),*> Compatible for AxesTuple<($($nth_type_parameter),*)> {
+            fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+                $(self.axes.$nth_index.is_compatible(&other.axes.$nth_index, tolerance))&&*
+            }
+        }
+
         impl_axes!(@REMOVELAST $([$nth_index AND $nth_type_parameter],)*);
     };
 
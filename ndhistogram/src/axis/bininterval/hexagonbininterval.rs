@@ -0,0 +1,58 @@
+use std::fmt::{Display, Formatter};
+
+/// A bin interval that covers a hexagon in a plane.
+///
+/// Used by the [Hexagonal](crate::axis::Hexagonal) axis.
+/// The overflow bin covers all points outside the hexagons of the axis.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum HexagonBinInterval<T> {
+    /// An interval to represent all points outside the hexagons.
+    Overflow,
+    /// A hexagon.
+    Hexagon {
+        /// The centre of the hexagon.
+        centre: (T, T),
+        /// The vertices of the hexagon, in anticlockwise order.
+        vertices: [(T, T); 6],
+    },
+}
+
+impl<T> HexagonBinInterval<T> {
+    /// A factory method to create a new hexagon bin interval.
+    pub fn new(centre: (T, T), vertices: [(T, T); 6]) -> Self {
+        Self::Hexagon { centre, vertices }
+    }
+
+    /// A factory method to create a new overflow bin interval.
+    pub fn overflow() -> Self {
+        Self::Overflow
+    }
+
+    /// Returns the centre of the hexagon, or None for the overflow bin.
+    pub fn centre(&self) -> Option<&(T, T)> {
+        match self {
+            Self::Overflow => None,
+            Self::Hexagon { centre, .. } => Some(centre),
+        }
+    }
+
+    /// Returns the vertices of the hexagon, or None for the overflow bin.
+    pub fn vertices(&self) -> Option<&[(T, T); 6]> {
+        match self {
+            Self::Overflow => None,
+            Self::Hexagon { vertices, .. } => Some(vertices),
+        }
+    }
+}
+
+impl<T: Display> Display for HexagonBinInterval<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Overflow => write!(f, "{{overflow}}"),
+            Self::Hexagon {
+                centre: (x, y),
+                vertices: _,
+            } => write!(f, "{{hexagon at ({x}, {y})}}"),
+        }
+    }
+}
@@ -1,3 +1,4 @@
 #[allow(clippy::module_inception)]
 pub(crate) mod bininterval;
+pub(crate) mod hexagonbininterval;
 pub(crate) mod singlevaluebininterval;
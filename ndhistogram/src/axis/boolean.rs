@@ -0,0 +1,83 @@
+use crate::{compare::Tolerance, error::BinaryOperationError};
+
+use super::{Axis, Compatible, SingleValueBinInterval};
+use std::fmt::Display;
+
+/// An axis with exactly two bins, false and true.
+///
+/// Similar to a [CategoryNoFlow](super::CategoryNoFlow) of `[false, true]`,
+/// but the bin index is computed directly from the coordinate without hashing,
+/// and the axis stores no data.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram};
+/// use ndhistogram::axis::{Axis, Boolean, SingleValueBinInterval};
+/// let axis = Boolean::new();
+/// assert_eq!(axis.index(&false), Some(0));
+/// assert_eq!(axis.index(&true), Some(1));
+/// assert_eq!(axis.bin(1), Some(SingleValueBinInterval::new(true)));
+/// assert_eq!(axis.bin(2), None);
+///
+/// let mut hist = ndhistogram!(Boolean::new());
+/// hist.fill(&true);
+/// assert_eq!(hist.value(&true), Some(&1.0));
+/// ```
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Boolean;
+
+impl Boolean {
+    /// Factory method to create a boolean axis.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Axis for Boolean {
+    type Coordinate = bool;
+    type BinInterval = SingleValueBinInterval<bool>;
+
+    #[inline]
+    fn index(&self, coordinate: &Self::Coordinate) -> Option<usize> {
+        Some(usize::from(*coordinate))
+    }
+
+    fn num_bins(&self) -> usize {
+        2
+    }
+
+    fn bin(&self, index: usize) -> Option<Self::BinInterval> {
+        match index {
+            0 => Some(SingleValueBinInterval::new(false)),
+            1 => Some(SingleValueBinInterval::new(true)),
+            _ => None,
+        }
+    }
+
+    fn check_compatible(&self, _other: &Self) -> Result<(), BinaryOperationError> {
+        Ok(())
+    }
+}
+
+impl Compatible for Boolean {
+    fn is_compatible(&self, _other: &Self, _tolerance: &Tolerance) -> bool {
+        true
+    }
+}
+
+impl<'a> IntoIterator for &'a Boolean {
+    type Item = (usize, <Boolean as Axis>::BinInterval);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Display for Boolean {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{{{false}}, {{true}}}}")
+    }
+}
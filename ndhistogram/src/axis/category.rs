@@ -1,8 +1,11 @@
 use std::hash::Hash;
 use std::{collections::HashMap, fmt::Display};
 
-use super::Axis;
+use crate::compare::Tolerance;
+use crate::error::{AxisMismatch, BinaryOperationError};
+
 use super::SingleValueBinInterval;
+use super::{incompatible, Axis, Compatible};
 
 // Type-bound alias
 pub trait Value: Eq + Hash + Clone {}
@@ -71,6 +74,11 @@ impl<T: Value> Category<T> {
     pub fn new<I: IntoIterator<Item = T>>(values: I) -> Self {
         Self::constructor(values)
     }
+
+    /// Iterate over the category values in bin order (excluding the overflow bin).
+    pub fn categories(&self) -> impl Iterator<Item = &T> {
+        (0..self.len()).filter_map(move |index| self.get_value(index))
+    }
 }
 
 impl<T: Value> Axis for Category<T> {
@@ -100,6 +108,22 @@ impl<T: Value> Axis for Category<T> {
             }
         }
     }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        if self.len() != other.len() {
+            incompatible(AxisMismatch::NumberOfBins)
+        } else if self != other {
+            incompatible(AxisMismatch::Categories)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T: Value> Compatible for Category<T> {
+    fn is_compatible(&self, other: &Self, _tolerance: &Tolerance) -> bool {
+        self == other
+    }
 }
 
 impl<T: Display + Value> Display for Category<T> {
@@ -1,4 +1,6 @@
-use super::{category::Value, Axis, Category, SingleValueBinInterval};
+use crate::{compare::Tolerance, error::BinaryOperationError};
+
+use super::{category::Value, Axis, Category, Compatible, SingleValueBinInterval};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
@@ -35,6 +37,11 @@ impl<T: Value> CategoryNoFlow<T> {
             axis: Category::new(values),
         }
     }
+
+    /// Iterate over the category values in bin order.
+    pub fn categories(&self) -> impl Iterator<Item = &T> {
+        self.axis.categories()
+    }
 }
 
 impl<T: Value> Axis for CategoryNoFlow<T> {
@@ -60,6 +67,16 @@ impl<T: Value> Axis for CategoryNoFlow<T> {
             SingleValueBinInterval::Bin { value: _ } => Some(bin),
         }
     }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        self.axis.check_compatible(&other.axis)
+    }
+}
+
+impl<T: Value> Compatible for CategoryNoFlow<T> {
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.axis.is_compatible(&other.axis, tolerance)
+    }
 }
 
 impl<'a, T: Value> IntoIterator for &'a CategoryNoFlow<T> {
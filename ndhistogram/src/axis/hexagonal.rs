@@ -0,0 +1,331 @@
+use std::fmt::{Debug, Display};
+
+use num_traits::Float;
+
+use crate::{
+    compare::{ApproxEq, Tolerance},
+    error::{AxisError, AxisMismatch, BinaryOperationError},
+};
+
+use super::{incompatible, Axis, Compatible, HexagonBinInterval};
+
+/// The orientation of the hexagons of a [Hexagonal] axis.
+#[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexagonOrientation {
+    /// Hexagons with a vertex at the top, arranged in rows along x.
+    #[default]
+    PointyTop,
+    /// Hexagons with an edge at the top, arranged in columns along y.
+    FlatTop,
+}
+
+/// A two-dimensional axis that bins (x, y) coordinates into a grid of regular hexagons.
+///
+/// Hexagons of a given size, the distance from their centre to each vertex,
+/// are arranged in rows (or columns for [HexagonOrientation::FlatTop]) with
+/// every other row offset by half a hexagon.
+/// The centre of the first hexagon is at the low corner of the given x and y
+/// ranges, and enough hexagons are included to cover the whole rectangle.
+/// A coordinate is mapped to the hexagon that contains it.
+/// Coordinates outside all of the hexagons, including NaN and infinities,
+/// are mapped to an overflow bin after all of the hexagons.
+///
+/// Hexagons are numbered with x varying fastest, first along the bottom row.
+/// As this single axis covers two dimensions, [Axis::num_dim] is 2.
+///
+/// Histograms with this axis may be written with [io::binary](crate::io::binary)
+/// and serde, but not with [io::csv](crate::io::csv), `io::uhi` or `io::numpy`,
+/// which describe each axis with one-dimensional bins.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram};
+/// use ndhistogram::axis::{Axis, HexagonOrientation, Hexagonal};
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let axis = Hexagonal::new(1.0, HexagonOrientation::PointyTop, (0.0, 10.0), (0.0, 5.0))?;
+/// assert_eq!(axis.grid_size(), (7, 4));
+/// assert_eq!(axis.index(&(0.1, -0.1)), Some(0));
+/// assert_eq!(axis.index(&(20.0, 0.0)), Some(28));
+/// let centre = axis.bin(1).unwrap().centre().copied();
+/// assert_eq!(centre, Some((3.0f64.sqrt(), 0.0)));
+///
+/// let mut hist = ndhistogram!(axis);
+/// hist.fill(&(1.5, 0.2));
+/// assert_eq!(hist.value(&(1.8, -0.1)), Some(&1.0));
+/// assert_eq!(hist.axes().num_dim(), 2);
+/// # Ok(()) }
+/// ```
+#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hexagonal<T = f64> {
+    size: T,
+    orientation: HexagonOrientation,
+    x: (T, T),
+    y: (T, T),
+    num_x: usize,
+    num_y: usize,
+}
+
+impl<T: Float> Hexagonal<T> {
+    /// Factory method to create a hexagonal axis covering the rectangle of the x and y ranges.
+    ///
+    /// The size is the distance from the centre of each hexagon to its vertices
+    /// and must be positive, otherwise an error is returned.
+    /// The ranges must be finite and not empty, otherwise an error is returned.
+    pub fn new(
+        size: T,
+        orientation: HexagonOrientation,
+        x: (T, T),
+        y: (T, T),
+    ) -> Result<Self, AxisError> {
+        if !(size > T::zero() && size.is_finite()) {
+            return Err(AxisError::InvalidStepSize);
+        }
+        let ordered = |(low, high): (T, T)| {
+            if low == high || !low.is_finite() || !high.is_finite() {
+                return Err(AxisError::InvalidAxisRange);
+            }
+            Ok(if low > high { (high, low) } else { (low, high) })
+        };
+        let (x, y) = (ordered(x)?, ordered(y)?);
+        let mut axis = Self {
+            size,
+            orientation,
+            x,
+            y,
+            num_x: 1,
+            num_y: 1,
+        };
+        // along rows each hexagon covers one width, but every other row is
+        // offset by half a width, and each row covers half a size beyond its centres
+        let (row_span, across_span) = axis.grid_position((x.1, y.1));
+        let (width, height) = axis.spacing();
+        let half = T::from(0.5).unwrap();
+        let along = (row_span / width + half).ceil();
+        let across = ((across_span - size * half) / height).ceil().max(T::zero()) + T::one();
+        let along = along.to_usize().ok_or(AxisError::InvalidNumberOfBins)?;
+        let across = across.to_usize().ok_or(AxisError::InvalidNumberOfBins)?;
+        along
+            .checked_mul(across)
+            .and_then(|it| it.checked_add(1))
+            .ok_or(AxisError::InvalidNumberOfBins)?;
+        (axis.num_x, axis.num_y) = match orientation {
+            HexagonOrientation::PointyTop => (along, across),
+            HexagonOrientation::FlatTop => (across, along),
+        };
+        Ok(axis)
+    }
+
+    /// The distance from the centre of each hexagon to its vertices.
+    pub fn size(&self) -> T {
+        self.size
+    }
+
+    /// The orientation of the hexagons.
+    pub fn orientation(&self) -> HexagonOrientation {
+        self.orientation
+    }
+
+    /// The range of x covered by the hexagons.
+    pub fn x_range(&self) -> (T, T) {
+        self.x
+    }
+
+    /// The range of y covered by the hexagons.
+    pub fn y_range(&self) -> (T, T) {
+        self.y
+    }
+
+    /// The number of hexagons along x and along y.
+    pub fn grid_size(&self) -> (usize, usize) {
+        (self.num_x, self.num_y)
+    }
+
+    /// The distance between neighbouring hexagons along a row, and between rows.
+    fn spacing(&self) -> (T, T) {
+        let three = T::from(3.0).unwrap();
+        (
+            self.size * three.sqrt(),
+            self.size * three / (T::one() + T::one()),
+        )
+    }
+
+    /// Position relative to the first hexagon, along and across the rows.
+    fn grid_position(&self, (x, y): (T, T)) -> (T, T) {
+        let (x, y) = (x - self.x.0, y - self.y.0);
+        match self.orientation {
+            HexagonOrientation::PointyTop => (x, y),
+            HexagonOrientation::FlatTop => (y, x),
+        }
+    }
+
+    /// Inverse of [Hexagonal::grid_position].
+    fn plane_position(&self, (along, across): (T, T)) -> (T, T) {
+        let (x, y) = match self.orientation {
+            HexagonOrientation::PointyTop => (along, across),
+            HexagonOrientation::FlatTop => (across, along),
+        };
+        (x + self.x.0, y + self.y.0)
+    }
+
+    /// The number of hexagons along and across the rows.
+    fn grid_shape(&self) -> (usize, usize) {
+        match self.orientation {
+            HexagonOrientation::PointyTop => (self.num_x, self.num_y),
+            HexagonOrientation::FlatTop => (self.num_y, self.num_x),
+        }
+    }
+
+    /// Bin index of the hexagon at a position in the row and a row.
+    fn grid_index(&self, along: usize, across: usize) -> usize {
+        match self.orientation {
+            HexagonOrientation::PointyTop => along + self.num_x * across,
+            HexagonOrientation::FlatTop => across + self.num_x * along,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.num_x * self.num_y
+    }
+}
+
+impl<T: Float> Axis for Hexagonal<T> {
+    type Coordinate = (T, T);
+    type BinInterval = HexagonBinInterval<T>;
+
+    #[inline]
+    fn index(&self, coordinate: &Self::Coordinate) -> Option<usize> {
+        let overflow = Some(self.len());
+        let (along, across) = self.grid_position(*coordinate);
+        let (width, height) = self.spacing();
+        let two = T::one() + T::one();
+        // round the axial coordinates of the hexagonal grid to the nearest hexagon
+        let row = across / height;
+        let diagonal = along / width - row / two;
+        let third = -diagonal - row;
+        let (mut rounded_row, mut rounded_diagonal) = (row.round(), diagonal.round());
+        let rounded_third = third.round();
+        let row_error = (rounded_row - row).abs();
+        let diagonal_error = (rounded_diagonal - diagonal).abs();
+        let third_error = (rounded_third - third).abs();
+        if diagonal_error > row_error && diagonal_error > third_error {
+            rounded_diagonal = -rounded_row - rounded_third;
+        } else if row_error > third_error {
+            rounded_row = -rounded_diagonal - rounded_third;
+        }
+        let (Some(row), Some(diagonal)) = (rounded_row.to_i64(), rounded_diagonal.to_i64()) else {
+            return overflow;
+        };
+        let position = diagonal + row.div_euclid(2);
+        let (num_along, num_across) = self.grid_shape();
+        match (usize::try_from(position), usize::try_from(row)) {
+            (Ok(position), Ok(row)) if position < num_along && row < num_across => {
+                Some(self.grid_index(position, row))
+            }
+            _ => overflow,
+        }
+    }
+
+    fn num_bins(&self) -> usize {
+        self.len() + 1
+    }
+
+    fn bin(&self, index: usize) -> Option<Self::BinInterval> {
+        if index == self.len() {
+            return Some(HexagonBinInterval::overflow());
+        }
+        if index > self.len() {
+            return None;
+        }
+        let (ix, iy) = (index % self.num_x, index / self.num_x);
+        let (position, row) = match self.orientation {
+            HexagonOrientation::PointyTop => (ix, iy),
+            HexagonOrientation::FlatTop => (iy, ix),
+        };
+        let (width, height) = self.spacing();
+        let half = T::from(0.5).unwrap();
+        let offset = if row % 2 == 1 { half } else { T::zero() };
+        let along = width * (T::from(position)? + offset);
+        let across = height * T::from(row)?;
+        let centre = self.plane_position((along, across));
+        // anticlockwise from the vertex after the positive x direction
+        let (half_width, half_size) = (width * half, self.size * half);
+        let offsets = match self.orientation {
+            HexagonOrientation::PointyTop => [
+                (half_width, half_size),
+                (T::zero(), self.size),
+                (-half_width, half_size),
+                (-half_width, -half_size),
+                (T::zero(), -self.size),
+                (half_width, -half_size),
+            ],
+            HexagonOrientation::FlatTop => [
+                (self.size, T::zero()),
+                (half_size, half_width),
+                (-half_size, half_width),
+                (-self.size, T::zero()),
+                (-half_size, -half_width),
+                (half_size, -half_width),
+            ],
+        };
+        let vertices = offsets.map(|(dx, dy)| (centre.0 + dx, centre.1 + dy));
+        Some(HexagonBinInterval::new(centre, vertices))
+    }
+
+    fn num_dim(&self) -> usize {
+        2
+    }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        if self.grid_size() != other.grid_size() {
+            incompatible(AxisMismatch::NumberOfBins)
+        } else if self.orientation != other.orientation || self.size != other.size {
+            incompatible(AxisMismatch::Binning)
+        } else if self.x != other.x || self.y != other.y {
+            incompatible(AxisMismatch::Range)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T: Float + ApproxEq> Compatible for Hexagonal<T> {
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.grid_size() == other.grid_size()
+            && self.orientation == other.orientation
+            && self.size.approx_eq(&other.size, tolerance)
+            && self.x.0.approx_eq(&other.x.0, tolerance)
+            && self.x.1.approx_eq(&other.x.1, tolerance)
+            && self.y.0.approx_eq(&other.y.0, tolerance)
+            && self.y.1.approx_eq(&other.y.1, tolerance)
+    }
+}
+
+impl<T: Display> Display for Hexagonal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Axis{{# bins={}x{}, x range=[{}, {}), y range=[{}, {}), size={}, orientation={:?}, class={}}}",
+            self.num_x,
+            self.num_y,
+            self.x.0,
+            self.x.1,
+            self.y.0,
+            self.y.1,
+            self.size,
+            self.orientation,
+            stringify!(Hexagonal)
+        )
+    }
+}
+
+impl<'a, T: Float> IntoIterator for &'a Hexagonal<T> {
+    type Item = (usize, <Hexagonal<T> as Axis>::BinInterval);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
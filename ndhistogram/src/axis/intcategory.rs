@@ -0,0 +1,330 @@
+use std::fmt::{Debug, Display};
+
+use num_traits::PrimInt;
+
+use crate::compare::Tolerance;
+use crate::error::{AxisMismatch, BinaryOperationError};
+
+use super::SingleValueBinInterval;
+use super::{incompatible, Axis, Compatible};
+
+/// Values spanning up to this many values per category are looked up in a table.
+const DENSE_VALUES_PER_CATEGORY: usize = 4;
+/// Values spanning up to this many values are always looked up in a table.
+const MIN_DENSE_VALUES: usize = 256;
+/// Marks the entries of a lookup table that are not categories.
+const NOT_A_CATEGORY: usize = usize::MAX;
+
+/// Map from category value to bin index.
+#[derive(Clone)]
+enum Lookup<T> {
+    /// Bin index of every value between the lowest and highest category, offset by the lowest.
+    Dense { low: i128, bins: Vec<usize> },
+    /// Categories in increasing order with their bin index.
+    Sorted(Vec<(T, usize)>),
+}
+
+impl<T: PrimInt> Lookup<T> {
+    fn new(categories: &[T]) -> Self {
+        let values: Option<Vec<i128>> = categories.iter().map(|it| it.to_i128()).collect();
+        if let Some(values) = values {
+            let low = values.iter().copied().min().unwrap_or(0);
+            let high = values.iter().copied().max().unwrap_or(-1);
+            let max_span = (DENSE_VALUES_PER_CATEGORY * categories.len()).max(MIN_DENSE_VALUES);
+            if high
+                .checked_sub(low)
+                .is_some_and(|span| span < max_span as i128)
+            {
+                let mut bins = vec![NOT_A_CATEGORY; (high - low + 1) as usize];
+                for (index, value) in values.iter().enumerate() {
+                    bins[(value - low) as usize] = index;
+                }
+                return Self::Dense { low, bins };
+            }
+        }
+        Self::sorted(categories)
+    }
+
+    fn sorted(categories: &[T]) -> Self {
+        let mut sorted: Vec<_> = categories.iter().copied().zip(0..).collect();
+        sorted.sort_unstable();
+        Self::Sorted(sorted)
+    }
+
+    /// Add a value with its bin index, which is also the number of existing categories.
+    ///
+    /// A dense table is widened to at least twice its length, so adding categories
+    /// one at a time takes amortized constant time.
+    /// Returns false if the categories are too sparse for a dense table,
+    /// in which case the lookup has to be replaced by a sorted one.
+    fn insert(&mut self, value: T, index: usize) -> bool {
+        match self {
+            Self::Dense { low, bins } => {
+                let Some(value) = value.to_i128() else {
+                    return false;
+                };
+                let max_span = (DENSE_VALUES_PER_CATEGORY * (index + 1)).max(MIN_DENSE_VALUES);
+                if !widen(low, bins, value, max_span) {
+                    return false;
+                }
+                bins[(value - *low) as usize] = index;
+                true
+            }
+            Self::Sorted(sorted) => {
+                let position = sorted
+                    .binary_search_by(|(it, _)| it.cmp(&value))
+                    .unwrap_or_else(|it| it);
+                sorted.insert(position, (value, index));
+                true
+            }
+        }
+    }
+
+    #[inline]
+    fn get(&self, value: &T) -> Option<usize> {
+        match self {
+            Self::Dense { low, bins } => {
+                let offset = value.to_i128()?.checked_sub(*low)?;
+                let index = *bins.get(usize::try_from(offset).ok()?)?;
+                (index != NOT_A_CATEGORY).then_some(index)
+            }
+            Self::Sorted(sorted) => {
+                let position = sorted.binary_search_by(|(it, _)| it.cmp(value)).ok()?;
+                Some(sorted[position].1)
+            }
+        }
+    }
+}
+
+/// Widen a dense table so that it includes the value, growing it to at least twice its length.
+///
+/// Returns false if the table would have to span more than max_span values.
+fn widen(low: &mut i128, bins: &mut Vec<usize>, value: i128, max_span: usize) -> bool {
+    if bins.is_empty() {
+        *low = value;
+        bins.push(NOT_A_CATEGORY);
+        return true;
+    }
+    let len = bins.len() as i128;
+    let high = *low + (len - 1);
+    if (*low..=high).contains(&value) {
+        return true;
+    }
+    let needed = if value < *low {
+        high.checked_sub(value)
+    } else {
+        value.checked_sub(*low)
+    };
+    let Some(needed) = needed.map(|it| it + 1).filter(|it| *it <= max_span as i128) else {
+        return false;
+    };
+    let doubled = needed.max((2 * len).min(max_span as i128));
+    if value < *low {
+        let (new_low, new_len) = match high.checked_sub(doubled - 1) {
+            Some(new_low) => (new_low, doubled),
+            None => (value, needed),
+        };
+        bins.splice(
+            0..0,
+            std::iter::repeat(NOT_A_CATEGORY).take((new_len - len) as usize),
+        );
+        *low = new_low;
+    } else {
+        let new_len = match low.checked_add(doubled - 1) {
+            Some(_) => doubled,
+            None => needed,
+        };
+        bins.resize(new_len as usize, NOT_A_CATEGORY);
+    }
+    true
+}
+
+/// An axis to represent a set of integer categories with an overflow bin.
+///
+/// Similar to a [Category](crate::axis::Category) of integers, but the bin of
+/// a value is found without hashing.
+/// If the categories are dense, for example channel numbers `0..4096`,
+/// the bin is read from a table indexed by the offset of the value from the
+/// lowest category.
+/// Otherwise, for example for particle ID codes, the bin is found by a binary
+/// search of the sorted categories.
+///
+/// Values that are not categories are mapped to the overflow bin.
+/// See [IntCategoryNoFlow](crate::axis::IntCategoryNoFlow) for a variant
+/// without an overflow bin, which may also grow to include new values.
+/// This axis cannot grow, as new categories would have to take the place of its
+/// overflow bin.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::axis::{Axis, IntCategory, SingleValueBinInterval};
+/// let channels = IntCategory::new(0..4096);
+/// assert_eq!(channels.index(&17), Some(17));
+/// assert_eq!(channels.index(&5000), Some(4096));
+/// let particles = IntCategory::new(vec![11, -11, 13, -13, 2212]);
+/// assert_eq!(particles.index(&-13), Some(3));
+/// assert_eq!(particles.index(&22), Some(5));
+/// assert_eq!(particles.bin(4), Some(SingleValueBinInterval::new(2212)));
+/// assert_eq!(particles.bin(5), Some(SingleValueBinInterval::overflow()));
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "Vec<T>",
+        into = "Vec<T>",
+        bound(
+            serialize = "T: PrimInt + serde::Serialize",
+            deserialize = "T: PrimInt + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct IntCategory<T> {
+    categories: Vec<T>,
+    lookup: Lookup<T>,
+}
+
+impl<T: PrimInt> IntCategory<T> {
+    /// Factory method to create an integer category axis with an overflow bin.
+    ///
+    /// Takes an iterator over the values of each category, in bin order.
+    /// Repeated values are ignored.
+    /// All other values will be mapped to the overflow bin.
+    pub fn new<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let values: Vec<T> = values.into_iter().collect();
+        // keep the first of any repeated values
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_unstable_by_key(|it| (values[*it], *it));
+        let mut repeated = vec![false; values.len()];
+        for pair in order.windows(2) {
+            repeated[pair[1]] = values[pair[0]] == values[pair[1]];
+        }
+        let categories: Vec<T> = values
+            .into_iter()
+            .zip(repeated)
+            .filter_map(|(value, repeated)| (!repeated).then_some(value))
+            .collect();
+        let lookup = Lookup::new(&categories);
+        Self { categories, lookup }
+    }
+
+    /// Iterate over the category values in bin order (excluding the overflow bin).
+    pub fn categories(&self) -> impl ExactSizeIterator<Item = &T> {
+        self.categories.iter()
+    }
+
+    /// The bin index of a category value, or None if it is not a category.
+    #[inline]
+    pub(super) fn get_index(&self, value: &T) -> Option<usize> {
+        self.lookup.get(value)
+    }
+
+    /// Add a category after all of the existing categories.
+    pub(super) fn push(&mut self, value: T) {
+        let index = self.len();
+        self.categories.push(value);
+        if !self.lookup.insert(value, index) {
+            self.lookup = Lookup::sorted(&self.categories);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.categories.len()
+    }
+}
+
+impl<T: PrimInt> Default for IntCategory<T> {
+    fn default() -> Self {
+        Self::new([])
+    }
+}
+
+impl<T: PrimInt> From<Vec<T>> for IntCategory<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self::new(values)
+    }
+}
+
+impl<T> From<IntCategory<T>> for Vec<T> {
+    fn from(axis: IntCategory<T>) -> Self {
+        axis.categories
+    }
+}
+
+impl<T: PartialEq> PartialEq for IntCategory<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.categories == other.categories
+    }
+}
+
+impl<T: Eq> Eq for IntCategory<T> {}
+
+impl<T: Debug> Debug for IntCategory<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IntCategory")
+            .field("categories", &self.categories)
+            .finish()
+    }
+}
+
+impl<T: PrimInt> Axis for IntCategory<T> {
+    type Coordinate = T;
+
+    type BinInterval = SingleValueBinInterval<T>;
+
+    #[inline]
+    fn index(&self, coordinate: &Self::Coordinate) -> Option<usize> {
+        self.get_index(coordinate).or(Some(self.len()))
+    }
+
+    fn num_bins(&self) -> usize {
+        self.len() + 1
+    }
+
+    fn bin(&self, index: usize) -> Option<Self::BinInterval> {
+        match self.categories.get(index) {
+            Some(value) => Some(SingleValueBinInterval::new(*value)),
+            None if index == self.len() => Some(SingleValueBinInterval::overflow()),
+            None => None,
+        }
+    }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        if self.len() != other.len() {
+            incompatible(AxisMismatch::NumberOfBins)
+        } else if self != other {
+            incompatible(AxisMismatch::Categories)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T: PrimInt> Compatible for IntCategory<T> {
+    fn is_compatible(&self, other: &Self, _tolerance: &Tolerance) -> bool {
+        self == other
+    }
+}
+
+impl<T: Display + PrimInt> Display for IntCategory<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let comma_separated_list = self
+            .bins()
+            .take(10)
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{{{comma_separated_list}}}")
+    }
+}
+
+impl<'a, T: PrimInt> IntoIterator for &'a IntCategory<T> {
+    type Item = (usize, <IntCategory<T> as Axis>::BinInterval);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
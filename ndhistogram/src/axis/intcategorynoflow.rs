@@ -0,0 +1,155 @@
+use std::fmt::Display;
+
+use num_traits::PrimInt;
+
+use crate::{compare::Tolerance, error::BinaryOperationError};
+
+use super::{Axis, Compatible, IntCategory, SingleValueBinInterval};
+
+/// An axis to represent a set of integer categories without an overflow bin.
+///
+/// Similar to [IntCategory], however, no overflow bin is included.
+///
+/// An extendable axis, created with [IntCategoryNoFlow::extendable], adds a bin for
+/// each new value when a histogram is filled with
+/// [VecHistogram::fill_growing](crate::VecHistogram::fill_growing) or
+/// [HashHistogram::fill_growing](crate::HashHistogram::fill_growing), or grown with
+/// [VecHistogram::grow](crate::VecHistogram::grow) and
+/// [HashHistogram::grow](crate::HashHistogram::grow).
+/// [Histogram::fill](crate::Histogram::fill) does not add categories.
+///
+/// Only this axis without an overflow bin is extendable: the overflow bin of an
+/// [IntCategory] comes after its categories and already holds the values that are not
+/// categories, so a new category could neither be added after all existing bins nor
+/// take its values out of the overflow bin.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram};
+/// use ndhistogram::axis::{Axis, IntCategoryNoFlow, SingleValueBinInterval};
+/// let particles = IntCategoryNoFlow::new(vec![11, 13, 211]);
+/// assert_eq!(particles.index(&13), Some(1));
+/// assert_eq!(particles.index(&22), None);
+/// assert_eq!(particles.bin(3), None);
+///
+/// let mut hist = ndhistogram!(IntCategoryNoFlow::extendable(vec![11, 13]); u64);
+/// hist.fill(&22);
+/// assert_eq!(hist.value(&22), None);
+/// hist.fill_growing(&22);
+/// assert_eq!(hist.value(&22), Some(&1));
+/// assert_eq!(hist.axes().as_tuple().0.bin(2), Some(SingleValueBinInterval::new(22)));
+/// ```
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: PrimInt + serde::Serialize",
+        deserialize = "T: PrimInt + serde::Deserialize<'de>"
+    ))
+)]
+pub struct IntCategoryNoFlow<T: PrimInt> {
+    axis: IntCategory<T>,
+    extendable: bool,
+}
+
+impl<T: PrimInt> IntCategoryNoFlow<T> {
+    /// Factory method to create an integer category axis without an overflow bin.
+    ///
+    /// Takes a set of values that represent each category.
+    /// Repeated values are ignored.
+    /// All other values will not be included in this axis.
+    pub fn new<I: IntoIterator<Item = T>>(values: I) -> Self {
+        Self {
+            axis: IntCategory::new(values),
+            extendable: false,
+        }
+    }
+
+    /// Factory method to create an extendable integer category axis without an overflow bin.
+    ///
+    /// Takes a set of values that represent the initial categories, which may be empty.
+    /// A new category is added after the existing categories for each value
+    /// that the axis is grown with, see [Axis::grow].
+    /// Histograms grow their axes only when filled with
+    /// [VecHistogram::fill_growing](crate::VecHistogram::fill_growing) and
+    /// [HashHistogram::fill_growing](crate::HashHistogram::fill_growing).
+    pub fn extendable<I: IntoIterator<Item = T>>(values: I) -> Self {
+        Self {
+            axis: IntCategory::new(values),
+            extendable: true,
+        }
+    }
+
+    /// Returns true if new categories are added when the axis is grown.
+    pub fn is_extendable(&self) -> bool {
+        self.extendable
+    }
+
+    /// Iterate over the category values in bin order.
+    pub fn categories(&self) -> impl ExactSizeIterator<Item = &T> {
+        self.axis.categories()
+    }
+}
+
+impl<T: PrimInt> Axis for IntCategoryNoFlow<T> {
+    type Coordinate = T;
+    type BinInterval = SingleValueBinInterval<T>;
+
+    #[inline]
+    fn index(&self, coordinate: &Self::Coordinate) -> Option<usize> {
+        self.axis.get_index(coordinate)
+    }
+
+    fn num_bins(&self) -> usize {
+        self.axis.num_bins() - 1
+    }
+
+    fn bin(&self, index: usize) -> Option<Self::BinInterval> {
+        let bin = self.axis.bin(index)?;
+        match bin {
+            SingleValueBinInterval::Overflow => None,
+            SingleValueBinInterval::Bin { value: _ } => Some(bin),
+        }
+    }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        self.axis.check_compatible(&other.axis)
+    }
+
+    fn grow(&mut self, coordinate: &Self::Coordinate) -> bool {
+        if !self.extendable || self.axis.get_index(coordinate).is_some() {
+            return false;
+        }
+        self.axis.push(*coordinate);
+        true
+    }
+}
+
+impl<T: PrimInt> Compatible for IntCategoryNoFlow<T> {
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.axis.is_compatible(&other.axis, tolerance)
+    }
+}
+
+impl<'a, T: PrimInt> IntoIterator for &'a IntCategoryNoFlow<T> {
+    type Item = (usize, <IntCategoryNoFlow<T> as Axis>::BinInterval);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Display + PrimInt> Display for IntCategoryNoFlow<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let comma_separated_list = self
+            .bins()
+            .take(10)
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{{{comma_separated_list}}}")
+    }
+}
@@ -3,8 +3,14 @@
 //! This module contains implementations of [Axis] that are used to represent the axes of
 //! an N-dimensional [Histogram](crate::Histogram).
 //!
+use crate::{
+    compare::Tolerance,
+    error::{AxisMismatch, BinaryOperationError},
+};
+
 mod bininterval;
 pub use bininterval::bininterval::BinInterval;
+pub use bininterval::hexagonbininterval::HexagonBinInterval;
 pub use bininterval::singlevaluebininterval::SingleValueBinInterval;
 mod uniformcyclic;
 pub use uniformcyclic::UniformCyclic;
@@ -18,10 +24,26 @@ mod category;
 pub use category::Category;
 mod categorynoflow;
 pub use categorynoflow::CategoryNoFlow;
+mod intcategory;
+pub use intcategory::IntCategory;
+mod intcategorynoflow;
+pub use intcategorynoflow::IntCategoryNoFlow;
+mod orderedcategory;
+pub use orderedcategory::OrderedCategory;
+mod orderedcategorynoflow;
+pub use orderedcategorynoflow::OrderedCategoryNoFlow;
+mod strcategory;
+pub use strcategory::{StrAxis, StrCategory};
+mod strcategorynoflow;
+pub use strcategorynoflow::StrCategoryNoFlow;
 mod variable;
 pub use variable::Variable;
 mod variablenoflow;
 pub use variablenoflow::VariableNoFlow;
+mod boolean;
+pub use boolean::Boolean;
+mod hexagonal;
+pub use hexagonal::{HexagonOrientation, Hexagonal};
 
 type Iter<'a, BinInterval> = Box<dyn Iterator<Item = (usize, BinInterval)> + 'a>;
 type Indices = Box<dyn Iterator<Item = usize>>;
@@ -37,8 +59,16 @@ type Bins<'a, BinInterval> = Box<dyn Iterator<Item = BinInterval> + 'a>;
 /// - [Variable],
 /// - [VariableNoFlow],
 /// - [VariableCyclic],
-/// - [Category]
-/// - and [CategoryNoFlow].
+/// - [Category],
+/// - [CategoryNoFlow],
+/// - [IntCategory],
+/// - [IntCategoryNoFlow],
+/// - [OrderedCategory],
+/// - [OrderedCategoryNoFlow],
+/// - [StrCategory],
+/// - [StrCategoryNoFlow],
+/// - [Boolean]
+/// - and [Hexagonal], which bins two-dimensional coordinates.
 ///
 /// Most use cases should be covered by the builtin Axis implementations.
 /// However, you may implement the Axis trait if you have specialist needs.
@@ -124,10 +154,141 @@ pub trait Axis {
     }
 
     /// The number of dimensions that this object corresponds to.
-    /// For most Axis types this will simply be 1.
-    /// However, [Axes](crate::Axes) (i.e. a set of [Axis]) also implement [Axis]
-    /// and should return the number of [Axis] that it contains.
+    /// For most Axis types this will simply be 1, but an axis of several
+    /// coordinates, such as [Hexagonal], returns the number of coordinates.
+    /// [Axes](crate::Axes) (i.e. a set of [Axis]) also implement [Axis]
+    /// and should return the total number of dimensions of the [Axis] that it contains.
+    ///
+    /// This is the number of dimensions of the coordinate, which is not always the
+    /// number of axes: a histogram of a [Hexagonal] and a [Uniform] axis is 3D.
+    /// Histogram displays report this number, and functions that require 1D
+    /// histograms, such as [kolmogorov_smirnov](crate::compare::kolmogorov_smirnov),
+    /// reject any other.
     fn num_dim(&self) -> usize {
         1
     }
+
+    /// Check that this axis has the same binning as another axis of the same type.
+    ///
+    /// Returns a [BinaryOperationError] describing the first axis (dimension)
+    /// that differs and how it differs.
+    /// This is used by histogram binary operations (such as h1 + h2) to report
+    /// why two histograms cannot be combined.
+    ///
+    /// The default implementation only compares the number of bins.
+    /// The builtin axes override this to compare their full binning and give a
+    /// specific reason. User defined axes should do the same, otherwise
+    /// differences other than the number of bins are reported without a reason.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ndhistogram::axis::{Axis, Uniform};
+    /// use ndhistogram::error::{AxisMismatch, BinaryOperationError};
+    /// # fn main() -> Result<(), ndhistogram::Error> {
+    /// let axis = Uniform::new(10, 0.0, 1.0)?;
+    /// assert_eq!(
+    ///     axis.check_compatible(&Uniform::new(10, 0.0, 2.0)?),
+    ///     Err(BinaryOperationError::IncompatibleAxis { axis: 0, reason: AxisMismatch::Range })
+    /// );
+    /// # Ok(()) }
+    /// ```
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError>
+    where
+        Self: Sized,
+    {
+        if self.num_bins() != other.num_bins() {
+            return incompatible(AxisMismatch::NumberOfBins);
+        }
+        Ok(())
+    }
+
+    /// Add bins so that this axis contains the coordinate, if the axis is extendable.
+    ///
+    /// Returns true if any bins were added.
+    /// New bins must be added after all existing bins, so that the existing bins
+    /// keep their index. Histograms rely on this to keep their values when they grow,
+    /// see [VecHistogram::grow](crate::VecHistogram::grow) and
+    /// [HashHistogram::grow](crate::HashHistogram::grow).
+    ///
+    /// Axes never grow on their own: [Histogram::fill](crate::Histogram::fill) with a
+    /// coordinate outside of the axes does nothing, while
+    /// [VecHistogram::fill_growing](crate::VecHistogram::fill_growing) and
+    /// [HashHistogram::fill_growing](crate::HashHistogram::fill_growing) grow them first.
+    ///
+    /// The default implementation never adds bins.
+    /// See [IntCategoryNoFlow::extendable] for an extendable axis.
+    fn grow(&mut self, coordinate: &Self::Coordinate) -> bool {
+        let _ = coordinate;
+        false
+    }
+}
+
+/// An [Axis] that can check whether another axis has the same binning within a
+/// numerical tolerance.
+///
+/// Axes that are equal up to floating point rounding, for example two
+/// [Uniform] axes created with slightly different arithmetic or read back from
+/// a text format, are not equal according to [PartialEq] and so cannot be
+/// combined with the histogram binary operators.
+/// This trait is used by opt-in tolerant merges such as
+/// [VecHistogram::merge_with_tolerance](crate::VecHistogram::merge_with_tolerance)
+/// to accept these axes.
+///
+/// Bin edges are compared with [Tolerance::is_close].
+/// Axes without numerical edges, such as [Category], must be exactly equal.
+///
+/// This is a separate trait rather than a method of [Axis] as comparing
+/// edges within a tolerance requires the coordinate type to implement
+/// [ApproxEq](crate::compare::ApproxEq), which [Axis] does not require.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::axis::{Compatible, Uniform};
+/// use ndhistogram::compare::Tolerance;
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let axis1 = Uniform::new(10, 0.0, 0.3)?;
+/// let axis2 = Uniform::new(10, 0.0, 0.1 + 0.2)?;
+/// assert_ne!(axis1, axis2);
+/// assert!(axis1.is_compatible(&axis2, &Tolerance::default()));
+/// assert!(!axis1.is_compatible(&Uniform::new(10, 0.0, 0.4)?, &Tolerance::default()));
+/// # Ok(()) }
+/// ```
+pub trait Compatible: Axis {
+    /// Returns true if other has the same binning as this axis within the given tolerance.
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool;
+}
+
+/// Helper to build the error returned by [Axis::check_compatible] for a 1-dimensional axis.
+pub(crate) fn incompatible(reason: AxisMismatch) -> Result<(), BinaryOperationError> {
+    Err(BinaryOperationError::IncompatibleAxis { axis: 0, reason })
+}
+
+/// Check that two axes are compatible within a tolerance, returning a [BinaryOperationError] describing the difference if not.
+pub(crate) fn check_compatible_within<A: Compatible>(
+    lhs: &A,
+    rhs: &A,
+    tolerance: &Tolerance,
+) -> Result<(), BinaryOperationError> {
+    if lhs.is_compatible(rhs, tolerance) {
+        return Ok(());
+    }
+    lhs.check_compatible(rhs)?;
+    Err(BinaryOperationError::IncompatibleAxes)
+}
+
+/// Check that two axes are equal, returning a [BinaryOperationError] describing the difference if not.
+///
+/// Falls back to [BinaryOperationError::IncompatibleAxes] for axes that compare
+/// not equal but do not describe the difference in [Axis::check_compatible].
+pub(crate) fn check_equal<A: Axis + PartialEq>(
+    lhs: &A,
+    rhs: &A,
+) -> Result<(), BinaryOperationError> {
+    lhs.check_compatible(rhs)?;
+    if lhs != rhs {
+        return Err(BinaryOperationError::IncompatibleAxes);
+    }
+    Ok(())
 }
@@ -0,0 +1,182 @@
+use std::fmt::Display;
+use std::ops::{Bound, Range, RangeBounds};
+
+use crate::compare::Tolerance;
+use crate::error::{AxisMismatch, BinaryOperationError};
+
+use super::SingleValueBinInterval;
+use super::{incompatible, Axis, Compatible};
+
+// Type-bound alias
+pub trait Value: Ord + Clone {}
+impl<T: Ord + Clone> Value for T {}
+
+/// An axis to represent a set of ordered categories with an overflow bin.
+///
+/// Similar to [Category](crate::axis::Category), but the categories are kept
+/// in sorted order, so the bins are numbered, iterated and displayed in the
+/// order of their values, whatever order they were given in.
+/// The bins of all categories within a range of values are contiguous and are
+/// selected with [OrderedCategory::index_range].
+/// When displayed, only the first 10 bins are listed, followed by `...` and
+/// the total number of bins.
+///
+/// This axis also includes an overflow bin, after all of the categories, to
+/// include "other" values not given when the axis was constructed.
+/// See [OrderedCategoryNoFlow](crate::axis::OrderedCategoryNoFlow) for a variant
+/// that includes no overflow bin.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram};
+/// use ndhistogram::axis::{Axis, OrderedCategory, SingleValueBinInterval};
+/// let runs = OrderedCategory::new(vec![1210, 1002, 1105, 1001]);
+/// assert_eq!(runs.index(&1001), Some(0));
+/// assert_eq!(runs.index(&1210), Some(3));
+/// assert_eq!(runs.index(&1500), Some(4));
+/// assert_eq!(runs.bin(1), Some(SingleValueBinInterval::new(1002)));
+/// assert_eq!(runs.index_range(1002..=1105), 1..3);
+///
+/// let mut hist = ndhistogram!(runs; u64);
+/// hist.fill_with(&1002, 3);
+/// hist.fill_with(&1105, 4);
+/// let range = hist.axes().as_tuple().0.index_range(1100..);
+/// let total: u64 = range.filter_map(|index| hist.value_at_index(index)).sum();
+/// assert_eq!(total, 4);
+/// ```
+#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "Vec<T>",
+        into = "Vec<T>",
+        bound(
+            serialize = "T: Value + serde::Serialize",
+            deserialize = "T: Value + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct OrderedCategory<T> {
+    categories: Vec<T>,
+}
+
+impl<T: Value> OrderedCategory<T> {
+    /// Factory method to create an ordered category axis with an overflow bin.
+    ///
+    /// Takes an iterator over a set of values that represent each category,
+    /// in any order. Repeated values are ignored.
+    /// All other values will be mapped to the overflow bin.
+    pub fn new<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let mut categories: Vec<T> = values.into_iter().collect();
+        categories.sort();
+        categories.dedup();
+        Self { categories }
+    }
+
+    /// Iterate over the category values in increasing order (excluding the overflow bin).
+    pub fn categories(&self) -> impl ExactSizeIterator<Item = &T> {
+        self.categories.iter()
+    }
+
+    /// The bin indices of all categories with a value in the range.
+    ///
+    /// As the categories are sorted, these are a contiguous range of indices.
+    /// The range is empty if no category is in the range.
+    /// It never includes the overflow bin.
+    pub fn index_range<R: RangeBounds<T>>(&self, range: R) -> Range<usize> {
+        let start = match range.start_bound() {
+            Bound::Included(low) => self.categories.partition_point(|it| it < low),
+            Bound::Excluded(low) => self.categories.partition_point(|it| it <= low),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(high) => self.categories.partition_point(|it| it <= high),
+            Bound::Excluded(high) => self.categories.partition_point(|it| it < high),
+            Bound::Unbounded => self.len(),
+        };
+        start..end.max(start)
+    }
+
+    /// The bin index of a category value, or None if it is not a category.
+    pub(super) fn get_index(&self, value: &T) -> Option<usize> {
+        self.categories.binary_search(value).ok()
+    }
+
+    fn len(&self) -> usize {
+        self.categories.len()
+    }
+}
+
+impl<T: Value> From<Vec<T>> for OrderedCategory<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self::new(values)
+    }
+}
+
+impl<T> From<OrderedCategory<T>> for Vec<T> {
+    fn from(axis: OrderedCategory<T>) -> Self {
+        axis.categories
+    }
+}
+
+impl<T: Value> Axis for OrderedCategory<T> {
+    type Coordinate = T;
+
+    type BinInterval = SingleValueBinInterval<T>;
+
+    #[inline]
+    fn index(&self, coordinate: &Self::Coordinate) -> Option<usize> {
+        self.get_index(coordinate).or(Some(self.len()))
+    }
+
+    fn num_bins(&self) -> usize {
+        self.len() + 1
+    }
+
+    fn bin(&self, index: usize) -> Option<Self::BinInterval> {
+        match self.categories.get(index) {
+            Some(value) => Some(SingleValueBinInterval::new(value.clone())),
+            None if index == self.len() => Some(SingleValueBinInterval::overflow()),
+            None => None,
+        }
+    }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        if self.len() != other.len() {
+            incompatible(AxisMismatch::NumberOfBins)
+        } else if self != other {
+            incompatible(AxisMismatch::Categories)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T: Value> Compatible for OrderedCategory<T> {
+    fn is_compatible(&self, other: &Self, _tolerance: &Tolerance) -> bool {
+        self == other
+    }
+}
+
+impl<T: Display + Value> Display for OrderedCategory<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bins: Vec<_> = self.bins().take(11).map(|it| it.to_string()).collect();
+        if bins.len() > 10 {
+            bins.truncate(10);
+            bins.push(format!("... ({} bins)", self.num_bins()));
+        }
+        let comma_separated_list = bins.join(", ");
+        write!(f, "{{{comma_separated_list}}}")
+    }
+}
+
+impl<'a, T: Value> IntoIterator for &'a OrderedCategory<T> {
+    type Item = (usize, <OrderedCategory<T> as Axis>::BinInterval);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt::Display;
+use std::ops::{Range, RangeBounds};
+
+use crate::{compare::Tolerance, error::BinaryOperationError};
+
+use super::{orderedcategory::Value, Axis, Compatible, OrderedCategory, SingleValueBinInterval};
+
+/// An axis to represent a set of ordered categories without an overflow bin.
+///
+/// Similar to [OrderedCategory], however, no overflow bin is included.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::axis::{Axis, OrderedCategoryNoFlow, SingleValueBinInterval};
+/// let dates = OrderedCategoryNoFlow::new(vec!["2024-03-01", "2024-01-15", "2024-02-10"]);
+/// assert_eq!(dates.index(&"2024-01-15"), Some(0));
+/// assert_eq!(dates.index(&"2024-04-01"), None);
+/// assert_eq!(dates.bin(2), Some(SingleValueBinInterval::new("2024-03-01")));
+/// assert_eq!(dates.bin(3), None);
+/// assert_eq!(dates.index_range("2024-02".."2024-03"), 1..2);
+/// ```
+#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: Value + serde::Serialize",
+        deserialize = "T: Value + serde::Deserialize<'de>"
+    ))
+)]
+pub struct OrderedCategoryNoFlow<T> {
+    axis: OrderedCategory<T>,
+}
+
+impl<T: Value> OrderedCategoryNoFlow<T> {
+    /// Factory method to create an ordered category axis without an overflow bin.
+    ///
+    /// Takes a set of values that represent each category, in any order.
+    /// Repeated values are ignored.
+    /// All other values will not be included in this axis.
+    pub fn new<I: IntoIterator<Item = T>>(values: I) -> Self {
+        Self {
+            axis: OrderedCategory::new(values),
+        }
+    }
+
+    /// Iterate over the category values in increasing order.
+    pub fn categories(&self) -> impl ExactSizeIterator<Item = &T> {
+        self.axis.categories()
+    }
+
+    /// The bin indices of all categories with a value in the range.
+    ///
+    /// See [OrderedCategory::index_range].
+    pub fn index_range<R: RangeBounds<T>>(&self, range: R) -> Range<usize> {
+        self.axis.index_range(range)
+    }
+}
+
+impl<T: Value> Axis for OrderedCategoryNoFlow<T> {
+    type Coordinate = T;
+    type BinInterval = SingleValueBinInterval<T>;
+
+    #[inline]
+    fn index(&self, coordinate: &Self::Coordinate) -> Option<usize> {
+        self.axis.get_index(coordinate)
+    }
+
+    fn num_bins(&self) -> usize {
+        self.axis.num_bins() - 1
+    }
+
+    fn bin(&self, index: usize) -> Option<Self::BinInterval> {
+        let bin = self.axis.bin(index)?;
+        match bin {
+            SingleValueBinInterval::Overflow => None,
+            SingleValueBinInterval::Bin { value: _ } => Some(bin),
+        }
+    }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        self.axis.check_compatible(&other.axis)
+    }
+}
+
+impl<T: Value> Compatible for OrderedCategoryNoFlow<T> {
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.axis.is_compatible(&other.axis, tolerance)
+    }
+}
+
+impl<'a, T: Value> IntoIterator for &'a OrderedCategoryNoFlow<T> {
+    type Item = (usize, <OrderedCategoryNoFlow<T> as Axis>::BinInterval);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Display + Value> Display for OrderedCategoryNoFlow<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bins: Vec<_> = self.bins().take(11).map(|it| it.to_string()).collect();
+        if bins.len() > 10 {
+            bins.truncate(10);
+            bins.push(format!("... ({} bins)", self.num_bins()));
+        }
+        let comma_separated_list = bins.join(", ");
+        write!(f, "{{{comma_separated_list}}}")
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+
+use crate::compare::Tolerance;
+use crate::error::{AxisMismatch, BinaryOperationError};
+
+use super::SingleValueBinInterval;
+use super::{incompatible, Axis, Compatible};
+
+/// An axis to represent a set of string categories with an overflow bin.
+///
+/// Similar to a [Category](crate::axis::Category) of [String], but the bin of a
+/// label is also found from any `&str` with [StrAxis::index_str], so a histogram is
+/// filled from a `&str` without allocating a new [String] for each value,
+/// see [VecHistogram::fill_str](crate::VecHistogram::fill_str).
+/// Each label is stored once, in an [Arc], which is shared by the bins of
+/// the axis.
+///
+/// The coordinate is a [String], so that histograms of several axes can be filled
+/// from a coordinate whose strings are reused buffers, as in the example below.
+///
+/// Values that are not categories are mapped to the overflow bin.
+/// See [StrCategoryNoFlow](crate::axis::StrCategoryNoFlow) for a variant
+/// without an overflow bin.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram};
+/// use ndhistogram::axis::{Axis, StrAxis, StrCategory, SingleValueBinInterval};
+/// let particles = StrCategory::new(["electron", "muon", "tau"]);
+/// assert_eq!(particles.index(&"muon".to_string()), Some(1));
+/// assert_eq!(particles.index_str("photon"), Some(3));
+/// assert_eq!(particles.bin(2), Some(SingleValueBinInterval::new("tau".into())));
+/// assert_eq!(particles.bin(3), Some(SingleValueBinInterval::overflow()));
+///
+/// let mut hist = ndhistogram!(particles.clone(); u64);
+/// for particle in "electron muon electron".split(' ') {
+///     hist.fill_str(particle);
+/// }
+/// assert_eq!(hist.value_str("electron"), Some(&2));
+///
+/// let mut hist = ndhistogram!(particles, StrCategory::new(["barrel", "endcap"]); u64);
+/// let mut event = (String::new(), String::new());
+/// for line in "electron barrel\nmuon endcap\nelectron barrel".lines() {
+///     let (particle, region) = line.split_once(' ').unwrap();
+///     event.0.clear();
+///     event.0.push_str(particle);
+///     event.1.clear();
+///     event.1.push_str(region);
+///     hist.fill(&event);
+/// }
+/// assert_eq!(hist.value(&("electron".into(), "barrel".into())), Some(&2));
+/// assert_eq!(hist.value(&("muon".into(), "endcap".into())), Some(&1));
+/// ```
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "Vec<String>", into = "Vec<String>"))]
+pub struct StrCategory {
+    categories: Vec<Arc<str>>,
+    lookup: FxHashMap<Arc<str>, usize>,
+}
+
+/// An [Axis] of string categories, whose bin is found from any `&str`
+/// without allocating a [String] coordinate.
+///
+/// Implemented by [StrCategory], [StrCategoryNoFlow](crate::axis::StrCategoryNoFlow)
+/// and the axes of a one-dimensional histogram of either, for which
+/// [VecHistogram](crate::VecHistogram) and [HashHistogram](crate::HashHistogram)
+/// provide `fill_str`, `fill_with_str`, `fill_with_weighted_str` and `value_str`.
+pub trait StrAxis: Axis<Coordinate = String> {
+    /// The bin index of a label, as [Axis::index] of the label as a [String].
+    fn index_str(&self, value: &str) -> Option<usize>;
+}
+
+impl StrCategory {
+    /// Factory method to create a string category axis with an overflow bin.
+    ///
+    /// Takes an iterator over the labels of each category, in bin order.
+    /// Repeated labels are ignored.
+    /// All other values will be mapped to the overflow bin.
+    pub fn new<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Arc<str>>,
+    {
+        let mut axis = Self::default();
+        for value in values {
+            let value = value.into();
+            if axis.get_index(value.as_ref()).is_none() {
+                axis.push(value);
+            }
+        }
+        axis
+    }
+
+    /// Iterate over the category labels in bin order (excluding the overflow bin).
+    pub fn categories(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.categories.iter().map(|it| it.as_ref())
+    }
+
+    /// The bin index of a category label, or None if it is not a category.
+    ///
+    /// The labels are looked up by [Borrow](std::borrow::Borrow)`<str>`, so any
+    /// `&str` may be used, however long it lives.
+    #[inline]
+    pub(super) fn get_index(&self, value: &str) -> Option<usize> {
+        self.lookup.get(value).copied()
+    }
+
+    fn push(&mut self, value: Arc<str>) {
+        self.lookup.insert(Arc::clone(&value), self.len());
+        self.categories.push(value);
+    }
+
+    fn len(&self) -> usize {
+        self.categories.len()
+    }
+}
+
+impl From<Vec<String>> for StrCategory {
+    fn from(values: Vec<String>) -> Self {
+        Self::new(values)
+    }
+}
+
+impl From<StrCategory> for Vec<String> {
+    fn from(axis: StrCategory) -> Self {
+        axis.categories().map(String::from).collect()
+    }
+}
+
+impl PartialEq for StrCategory {
+    fn eq(&self, other: &Self) -> bool {
+        self.categories == other.categories
+    }
+}
+
+impl Eq for StrCategory {}
+
+impl Debug for StrCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrCategory")
+            .field("categories", &self.categories)
+            .finish()
+    }
+}
+
+impl Axis for StrCategory {
+    type Coordinate = String;
+
+    type BinInterval = SingleValueBinInterval<Arc<str>>;
+
+    #[inline]
+    fn index(&self, coordinate: &Self::Coordinate) -> Option<usize> {
+        self.index_str(coordinate)
+    }
+
+    fn num_bins(&self) -> usize {
+        self.len() + 1
+    }
+
+    fn bin(&self, index: usize) -> Option<Self::BinInterval> {
+        match self.categories.get(index) {
+            Some(value) => Some(SingleValueBinInterval::new(Arc::clone(value))),
+            None if index == self.len() => Some(SingleValueBinInterval::overflow()),
+            None => None,
+        }
+    }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        if self.len() != other.len() {
+            incompatible(AxisMismatch::NumberOfBins)
+        } else if self != other {
+            incompatible(AxisMismatch::Categories)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl StrAxis for StrCategory {
+    #[inline]
+    fn index_str(&self, value: &str) -> Option<usize> {
+        self.get_index(value).or(Some(self.len()))
+    }
+}
+
+impl Compatible for StrCategory {
+    fn is_compatible(&self, other: &Self, _tolerance: &Tolerance) -> bool {
+        self == other
+    }
+}
+
+impl Display for StrCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let comma_separated_list = self
+            .bins()
+            .take(10)
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{{{comma_separated_list}}}")
+    }
+}
+
+impl<'a> IntoIterator for &'a StrCategory {
+    type Item = (usize, <StrCategory as Axis>::BinInterval);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
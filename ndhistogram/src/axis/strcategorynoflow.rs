@@ -0,0 +1,113 @@
+use std::fmt::Display;
+
+use crate::{compare::Tolerance, error::BinaryOperationError};
+
+use super::{Axis, Compatible, SingleValueBinInterval, StrAxis, StrCategory};
+
+/// An axis to represent a set of string categories without an overflow bin.
+///
+/// Similar to [StrCategory], however, no overflow bin is included.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram};
+/// use ndhistogram::axis::{Axis, StrAxis, StrCategoryNoFlow};
+/// let particles = StrCategoryNoFlow::new(["electron", "muon", "tau"]);
+/// assert_eq!(particles.index_str("muon"), Some(1));
+/// assert_eq!(particles.index_str("photon"), None);
+/// assert_eq!(particles.bin(3), None);
+///
+/// let mut hist = ndhistogram!(particles; u64);
+/// hist.fill_str("tau");
+/// hist.fill(&"photon".to_string());
+/// assert_eq!(hist.value_str("tau"), Some(&1));
+/// assert_eq!(hist.value_str("photon"), None);
+/// ```
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrCategoryNoFlow {
+    axis: StrCategory,
+}
+
+impl StrCategoryNoFlow {
+    /// Factory method to create a string category axis without an overflow bin.
+    ///
+    /// Takes a set of labels that represent each category.
+    /// Repeated labels are ignored.
+    /// All other values will not be included in this axis.
+    pub fn new<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<std::sync::Arc<str>>,
+    {
+        Self {
+            axis: StrCategory::new(values),
+        }
+    }
+
+    /// Iterate over the category labels in bin order.
+    pub fn categories(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.axis.categories()
+    }
+}
+
+impl Axis for StrCategoryNoFlow {
+    type Coordinate = String;
+    type BinInterval = <StrCategory as Axis>::BinInterval;
+
+    #[inline]
+    fn index(&self, coordinate: &Self::Coordinate) -> Option<usize> {
+        self.index_str(coordinate)
+    }
+
+    fn num_bins(&self) -> usize {
+        self.axis.num_bins() - 1
+    }
+
+    fn bin(&self, index: usize) -> Option<Self::BinInterval> {
+        let bin = self.axis.bin(index)?;
+        match bin {
+            SingleValueBinInterval::Overflow => None,
+            SingleValueBinInterval::Bin { value: _ } => Some(bin),
+        }
+    }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        self.axis.check_compatible(&other.axis)
+    }
+}
+
+impl StrAxis for StrCategoryNoFlow {
+    #[inline]
+    fn index_str(&self, value: &str) -> Option<usize> {
+        self.axis.get_index(value)
+    }
+}
+
+impl Compatible for StrCategoryNoFlow {
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.axis.is_compatible(&other.axis, tolerance)
+    }
+}
+
+impl<'a> IntoIterator for &'a StrCategoryNoFlow {
+    type Item = (usize, <StrCategoryNoFlow as Axis>::BinInterval);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Display for StrCategoryNoFlow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let comma_separated_list = self
+            .bins()
+            .take(10)
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{{{comma_separated_list}}}")
+    }
+}
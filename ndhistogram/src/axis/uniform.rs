@@ -2,9 +2,12 @@ use std::fmt::{Debug, Display};
 
 use num_traits::{Float, Num, NumCast, NumOps};
 
-use crate::error::AxisError;
+use crate::{
+    compare::{ApproxEq, Tolerance},
+    error::{AxisError, AxisMismatch, BinaryOperationError},
+};
 
-use super::{Axis, BinInterval};
+use super::{incompatible, Axis, BinInterval, Compatible};
 
 /// An axis with equal sized bins.
 ///
@@ -137,6 +140,29 @@ impl<T: PartialOrd + NumCast + NumOps + Copy> Axis for Uniform<T> {
     fn indices(&self) -> Box<dyn Iterator<Item = usize>> {
         Box::new(0..self.num_bins())
     }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        if self.num != other.num {
+            incompatible(AxisMismatch::NumberOfBins)
+        } else if self.low != other.low || self.high != other.high {
+            incompatible(AxisMismatch::Range)
+        } else if self.step != other.step {
+            incompatible(AxisMismatch::Binning)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T> Compatible for Uniform<T>
+where
+    T: PartialOrd + NumCast + NumOps + Copy + ApproxEq,
+{
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.num == other.num
+            && self.low.approx_eq(&other.low, tolerance)
+            && self.high.approx_eq(&other.high, tolerance)
+    }
 }
 
 impl<T: Display> Display for Uniform<T> {
@@ -1,6 +1,9 @@
-use crate::error::AxisError;
+use crate::{
+    compare::{ApproxEq, Tolerance},
+    error::{AxisError, BinaryOperationError},
+};
 
-use super::{Axis, BinInterval, UniformNoFlow};
+use super::{Axis, BinInterval, Compatible, UniformNoFlow};
 use std::fmt::{Debug, Display};
 
 use num_traits::{Float, Num, NumCast, NumOps};
@@ -119,6 +122,19 @@ impl<T: PartialOrd + Num + NumCast + NumOps + Copy> Axis for UniformCyclic<T> {
     fn bin(&self, index: usize) -> Option<<Self as Axis>::BinInterval> {
         self.axis.bin(index)
     }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        self.axis.check_compatible(&other.axis)
+    }
+}
+
+impl<T> Compatible for UniformCyclic<T>
+where
+    T: PartialOrd + Num + NumCast + NumOps + Copy + ApproxEq,
+{
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.axis.is_compatible(&other.axis, tolerance)
+    }
 }
 
 impl<T> Display for UniformCyclic<T>
@@ -1,6 +1,9 @@
-use crate::error::AxisError;
+use crate::{
+    compare::{ApproxEq, Tolerance},
+    error::{AxisError, BinaryOperationError},
+};
 
-use super::{Axis, BinInterval, Uniform};
+use super::{Axis, BinInterval, Compatible, Uniform};
 use std::fmt::{Debug, Display};
 
 use num_traits::{Float, Num, NumCast, NumOps};
@@ -100,6 +103,19 @@ where
             BinInterval::Bin { start: _, end: _ } => Some(bin),
         }
     }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        self.axis.check_compatible(&other.axis)
+    }
+}
+
+impl<T> Compatible for UniformNoFlow<T>
+where
+    T: PartialOrd + NumCast + NumOps + Copy + ApproxEq,
+{
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.axis.is_compatible(&other.axis, tolerance)
+    }
 }
 
 impl<'a, T> IntoIterator for &'a UniformNoFlow<T>
@@ -1,8 +1,11 @@
 use std::{cmp::Ordering, fmt::Display};
 
-use crate::error::AxisError;
+use crate::{
+    compare::{ApproxEq, Tolerance},
+    error::{AxisError, AxisMismatch, BinaryOperationError},
+};
 
-use super::{Axis, BinInterval};
+use super::{incompatible, Axis, BinInterval, Compatible};
 
 /// An axis with variable sized bins.
 ///
@@ -75,6 +78,11 @@ where
             .last()
             .expect("Variable bin_edges can never be empty as new returns an error if it is")
     }
+
+    /// Bin edges of the axis in increasing order (excluding underflow and overflow bins).
+    pub fn bin_edges(&self) -> &[T] {
+        &self.bin_edges
+    }
 }
 
 impl<T> Axis for Variable<T>
@@ -115,6 +123,32 @@ where
             None
         }
     }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        if self.bin_edges.len() != other.bin_edges.len() {
+            incompatible(AxisMismatch::NumberOfBins)
+        } else if self.low() != other.low() || self.high() != other.high() {
+            incompatible(AxisMismatch::Range)
+        } else if self.bin_edges != other.bin_edges {
+            incompatible(AxisMismatch::BinEdges)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T> Compatible for Variable<T>
+where
+    T: PartialOrd + Copy + ApproxEq,
+{
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.bin_edges.len() == other.bin_edges.len()
+            && self
+                .bin_edges
+                .iter()
+                .zip(&other.bin_edges)
+                .all(|(l, r)| l.approx_eq(r, tolerance))
+    }
 }
 
 impl<T: Display + PartialOrd + Copy> Display for Variable<T> {
@@ -1,6 +1,9 @@
-use crate::error::AxisError;
+use crate::{
+    compare::{ApproxEq, Tolerance},
+    error::{AxisError, BinaryOperationError},
+};
 
-use super::{Axis, BinInterval, VariableNoFlow};
+use super::{Axis, BinInterval, Compatible, VariableNoFlow};
 use std::fmt::{Debug, Display};
 
 use num_traits::Num;
@@ -57,6 +60,12 @@ where
     pub fn high(&self) -> &T {
         self.axis.high()
     }
+
+    /// Bin edges of axis in increasing order
+    #[inline]
+    pub fn bin_edges(&self) -> &[T] {
+        self.axis.bin_edges()
+    }
 }
 
 impl<T> Axis for VariableCyclic<T>
@@ -87,6 +96,19 @@ where
     fn bin(&self, index: usize) -> Option<Self::BinInterval> {
         self.axis.bin(index)
     }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        self.axis.check_compatible(&other.axis)
+    }
+}
+
+impl<T> Compatible for VariableCyclic<T>
+where
+    T: PartialOrd + Copy + Num + ApproxEq,
+{
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.axis.is_compatible(&other.axis, tolerance)
+    }
 }
 
 impl<T> Display for VariableCyclic<T>
@@ -1,6 +1,9 @@
-use crate::error::AxisError;
+use crate::{
+    compare::{ApproxEq, Tolerance},
+    error::{AxisError, BinaryOperationError},
+};
 
-use super::{Axis, BinInterval, Variable};
+use super::{Axis, BinInterval, Compatible, Variable};
 
 use std::fmt::{Debug, Display};
 
@@ -53,6 +56,11 @@ impl<T: PartialOrd + Copy> VariableNoFlow<T> {
     pub fn high(&self) -> &T {
         self.axis.high()
     }
+
+    /// Return the bin edges in increasing order.
+    pub fn bin_edges(&self) -> &[T] {
+        self.axis.bin_edges()
+    }
 }
 
 impl<T: PartialOrd + Copy> Axis for VariableNoFlow<T> {
@@ -80,6 +88,16 @@ impl<T: PartialOrd + Copy> Axis for VariableNoFlow<T> {
             BinInterval::Bin { start: _, end: _ } => Some(bin),
         }
     }
+
+    fn check_compatible(&self, other: &Self) -> Result<(), BinaryOperationError> {
+        self.axis.check_compatible(&other.axis)
+    }
+}
+
+impl<T: PartialOrd + Copy + ApproxEq> Compatible for VariableNoFlow<T> {
+    fn is_compatible(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.axis.is_compatible(&other.axis, tolerance)
+    }
 }
 
 impl<'a, T: PartialOrd + Copy> IntoIterator for &'a VariableNoFlow<T> {
@@ -0,0 +1,107 @@
+//! Add up histograms in the ndhistogram binary format.
+//!
+//! ```text
+//! ndhist-merge [--run-length-encoding] OUTPUT INPUT...
+//! ```
+//!
+//! Each input may be a file or a directory of files. See
+//! [ndhistogram::io::binary::merge_binary].
+//! The output must not be one of the inputs.
+//! It is only replaced once all of the inputs have been merged, and is left
+//! unchanged if any of them cannot be merged.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use ndhistogram::io::{binary::merge_binary, merge::input_files};
+
+const USAGE: &str = "usage: ndhist-merge [--run-length-encoding] OUTPUT INPUT...";
+
+/// A file that is opened when it is first read, so that the number of inputs is not
+/// limited by the number of files that can be open at once.
+struct Input {
+    path: PathBuf,
+    file: Option<BufReader<File>>,
+}
+
+impl Read for Input {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => self
+                .file
+                .insert(BufReader::new(File::open(&self.path).map_err(|error| {
+                    io::Error::new(error.kind(), format!("{}: {error}", self.path.display()))
+                })?)),
+        };
+        file.read(buf)
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    let run_length_encoding = match args.iter().position(|arg| arg == "--run-length-encoding") {
+        Some(position) => {
+            args.remove(position);
+            true
+        }
+        None => false,
+    };
+    let Some((output, inputs)) = args.split_first().filter(|(_, inputs)| !inputs.is_empty()) else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    match input_files(inputs)
+        .and_then(|inputs| merge(Path::new(output), inputs, run_length_encoding))
+    {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("ndhist-merge: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Merge the inputs into a new file next to the output, which replaces the output only
+/// once every input has been merged, so that the output is never left half written.
+fn merge(output: &Path, inputs: Vec<PathBuf>, run_length_encoding: bool) -> io::Result<()> {
+    if let Ok(output) = fs::canonicalize(output) {
+        for input in &inputs {
+            if fs::canonicalize(input).is_ok_and(|input| input == output) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{}: output is also an input", input.display()),
+                ));
+            }
+        }
+    }
+    let temporary = temporary_path(output);
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&temporary)?;
+    let readers = inputs.into_iter().map(|path| Input { path, file: None });
+    let mut writer = BufWriter::new(file);
+    let result = merge_binary(readers, run_length_encoding, &mut writer)
+        .and_then(|()| writer.flush())
+        .and_then(|()| fs::rename(&temporary, output));
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// A hidden file in the same directory as the output, so that it can be renamed to the output.
+fn temporary_path(output: &Path) -> PathBuf {
+    let name = output.file_name().unwrap_or_default().to_string_lossy();
+    output.with_file_name(format!(".{name}.{}.tmp", std::process::id()))
+}
@@ -0,0 +1,427 @@
+//! A named collection of histograms of mixed types
+//!
+//! A [HistogramBook] maps names, such as `"electrons/pt"`, to histograms with any
+//! axes and bin values. Histograms are looked up by name and type, and the whole book can
+//! be merged with another book, for example one filled by a separate job, and written to
+//! and read from a single file.
+//!
+//! Books contain [VecHistogram]s and [HashHistogram]s with axes and values that can be
+//! stored in the [binary](crate::io::binary) format.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, Hist1D, axis::{Category, Uniform}, value::WeightedSum};
+//! use ndhistogram::book::HistogramBook;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut book = HistogramBook::new();
+//! book.insert("electrons/pt", ndhistogram!(Uniform::new(10, 0.0, 100.0)?; WeightedSum));
+//! book.insert("electrons/charge", ndhistogram!(Category::new(vec![-1, 1]); u64));
+//!
+//! let pt = book.get_mut::<Hist1D<Uniform, WeightedSum>>("electrons/pt").unwrap();
+//! pt.fill_with(&25.0, 0.5);
+//!
+//! // merge with the book of another job
+//! let other = book.clone();
+//! book.merge(&other)?;
+//!
+//! // write and read back the whole book
+//! let mut bytes = Vec::new();
+//! book.write_binary(true, &mut bytes)?;
+//! let mut read = other.clone();
+//! read.read_binary(bytes.as_slice())?;
+//! let pt = read.get::<Hist1D<Uniform, WeightedSum>>("electrons/pt").unwrap();
+//! assert_eq!(pt.value(&25.0).unwrap().get(), 1.0);
+//! # Ok(()) }
+//! ```
+
+use std::{
+    any::Any,
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    hash::BuildHasher,
+    io::{self, Read},
+    ops::AddAssign,
+};
+
+use crate::{
+    axis::check_equal,
+    error::{BinaryOperationError, BookError, ConversionError},
+    io::{
+        binary::{read_binary, write_binary, BinaryAxes, BinaryValue},
+        merge::Merge,
+    },
+    HashHistogram, Histogram, VecHistogram,
+};
+
+/// Magic number at the start of the book format.
+const MAGIC: [u8; 8] = *b"\x89NDB\r\n\x1a\n";
+/// Current version of the book format.
+const VERSION: u16 = 1;
+
+fn invalid_data(error: ConversionError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// A histogram that can be stored in a [HistogramBook].
+///
+/// This is implemented for [VecHistogram] and [HashHistogram] with axes that implement
+/// [BinaryAxes] and bin values that implement [BinaryValue] and `AddAssign<&Self>`.
+pub trait BookEntry: Any {
+    /// The histogram as [Any], to get back its concrete type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// The histogram as mutable [Any], to get back its concrete type.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// A boxed copy of the histogram.
+    fn clone_entry(&self) -> Box<dyn BookEntry>;
+
+    /// Check that the other histogram can be added to this histogram.
+    ///
+    /// Returns [BinaryOperationError::IncompatibleTypes] if the histograms have different
+    /// types, or the error of [Merge::merge] if they have different axes.
+    fn check_merge(&self, other: &dyn BookEntry) -> Result<(), BinaryOperationError>;
+
+    /// Add the other histogram to this histogram.
+    ///
+    /// Returns the same errors as [BookEntry::check_merge], in which case this histogram
+    /// is left unchanged.
+    fn merge_entry(&mut self, other: &dyn BookEntry) -> Result<(), BinaryOperationError>;
+
+    /// Write the histogram in the [binary](crate::io::binary) format.
+    fn write_entry(&self, run_length_encoding: bool, writer: &mut dyn io::Write) -> io::Result<()>;
+
+    /// Replace the histogram with one read in the [binary](crate::io::binary) format.
+    fn read_entry(&mut self, reader: &mut dyn io::Read) -> io::Result<()>;
+}
+
+impl dyn BookEntry {
+    /// The histogram as its concrete type, or None if it has a different type.
+    pub fn downcast_ref<H: BookEntry>(&self) -> Option<&H> {
+        self.as_any().downcast_ref()
+    }
+
+    /// The histogram as its mutable concrete type, or None if it has a different type.
+    pub fn downcast_mut<H: BookEntry>(&mut self) -> Option<&mut H> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+impl<A, V> BookEntry for VecHistogram<A, V>
+where
+    A: BinaryAxes + PartialEq + Clone + 'static,
+    V: BinaryValue + Clone + 'static,
+    for<'a> V: AddAssign<&'a V>,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_entry(&self) -> Box<dyn BookEntry> {
+        Box::new(self.clone())
+    }
+
+    fn check_merge(&self, other: &dyn BookEntry) -> Result<(), BinaryOperationError> {
+        let other: &Self = other
+            .downcast_ref()
+            .ok_or(BinaryOperationError::IncompatibleTypes)?;
+        check_equal(self.axes(), other.axes())
+    }
+
+    fn merge_entry(&mut self, other: &dyn BookEntry) -> Result<(), BinaryOperationError> {
+        let other: &Self = other
+            .downcast_ref()
+            .ok_or(BinaryOperationError::IncompatibleTypes)?;
+        self.merge(other)
+    }
+
+    fn write_entry(&self, run_length_encoding: bool, writer: &mut dyn io::Write) -> io::Result<()> {
+        write_binary(self, run_length_encoding, writer)
+    }
+
+    fn read_entry(&mut self, reader: &mut dyn io::Read) -> io::Result<()> {
+        *self = read_binary(reader)?;
+        Ok(())
+    }
+}
+
+impl<A, V, S> BookEntry for HashHistogram<A, V, S>
+where
+    HashHistogram<A, V, S>: Histogram<A, V>,
+    A: BinaryAxes + PartialEq + Clone + 'static,
+    V: BinaryValue + Clone + Default + PartialEq + 'static,
+    for<'a> V: AddAssign<&'a V>,
+    S: BuildHasher + Default + Clone + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_entry(&self) -> Box<dyn BookEntry> {
+        Box::new(self.clone())
+    }
+
+    fn check_merge(&self, other: &dyn BookEntry) -> Result<(), BinaryOperationError> {
+        let other: &Self = other
+            .downcast_ref()
+            .ok_or(BinaryOperationError::IncompatibleTypes)?;
+        check_equal(self.axes(), other.axes())
+    }
+
+    fn merge_entry(&mut self, other: &dyn BookEntry) -> Result<(), BinaryOperationError> {
+        let other: &Self = other
+            .downcast_ref()
+            .ok_or(BinaryOperationError::IncompatibleTypes)?;
+        self.merge(other)
+    }
+
+    fn write_entry(&self, run_length_encoding: bool, writer: &mut dyn io::Write) -> io::Result<()> {
+        write_binary(self, run_length_encoding, writer)
+    }
+
+    /// Empty bins are not stored in the histogram that is read.
+    fn read_entry(&mut self, reader: &mut dyn io::Read) -> io::Result<()> {
+        let hist: VecHistogram<A, V> = read_binary(reader)?;
+        let empty = V::default();
+        let values: HashMap<usize, V, S> = hist
+            .values()
+            .enumerate()
+            .filter(|(_, value)| **value != empty)
+            .map(|(index, value)| (index, value.clone()))
+            .collect();
+        *self = Self::from_map(hist.axes().clone(), values)
+            .map_err(|_| invalid_data(ConversionError::ShapeMismatch))?;
+        Ok(())
+    }
+}
+
+/// A collection of histograms of mixed types, looked up by name.
+///
+/// Histograms are kept and iterated in order of their names.
+/// See the [module documentation](self) for an example.
+///
+/// The types of the histograms are not stored by [HistogramBook::write_binary], so
+/// before [HistogramBook::read_binary] every histogram in the input must first be booked,
+/// under the same name and with the same type, for example by the same code that booked
+/// the histograms of the job that wrote them.
+#[derive(Default)]
+pub struct HistogramBook {
+    histograms: BTreeMap<String, Box<dyn BookEntry>>,
+}
+
+impl HistogramBook {
+    /// Factory method for an empty book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a histogram with the given name, returning the histogram that had that name
+    /// before, if any.
+    pub fn insert<H: BookEntry>(
+        &mut self,
+        name: impl Into<String>,
+        hist: H,
+    ) -> Option<Box<dyn BookEntry>> {
+        self.histograms.insert(name.into(), Box::new(hist))
+    }
+
+    /// Remove the histogram with the given name.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn BookEntry>> {
+        self.histograms.remove(name)
+    }
+
+    /// The histogram with the given name, or None if there is no histogram with this name
+    /// or it has a different type.
+    pub fn get<H: BookEntry>(&self, name: &str) -> Option<&H> {
+        self.histograms.get(name)?.downcast_ref()
+    }
+
+    /// The mutable histogram with the given name, or None if there is no histogram with
+    /// this name or it has a different type.
+    pub fn get_mut<H: BookEntry>(&mut self, name: &str) -> Option<&mut H> {
+        self.histograms.get_mut(name)?.downcast_mut()
+    }
+
+    /// Returns true if the book contains a histogram with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.histograms.contains_key(name)
+    }
+
+    /// Number of histograms in the book.
+    pub fn len(&self) -> usize {
+        self.histograms.len()
+    }
+
+    /// Returns true if the book contains no histograms.
+    pub fn is_empty(&self) -> bool {
+        self.histograms.is_empty()
+    }
+
+    /// Iterate over the names of the histograms.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.histograms.keys().map(String::as_str)
+    }
+
+    /// Iterate over the names and histograms.
+    ///
+    /// Use `downcast_ref` on each histogram to get its concrete type.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &dyn BookEntry)> {
+        self.histograms
+            .iter()
+            .map(|(name, hist)| (name.as_str(), hist.as_ref()))
+    }
+
+    /// Iterate over the names and mutable histograms.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut dyn BookEntry)> {
+        self.histograms
+            .iter_mut()
+            .map(|(name, hist)| (name.as_str(), hist.as_mut() as &mut dyn BookEntry))
+    }
+
+    /// Add each histogram of the other book to the histogram with the same name in this
+    /// book, and add a copy of the histograms that this book does not have.
+    ///
+    /// If a histogram of the other book has a different type or different axes than the
+    /// histogram with the same name, a [BookError] naming the first such histogram is
+    /// returned and this book is left unchanged.
+    pub fn merge(&mut self, other: &HistogramBook) -> Result<(), BookError> {
+        for (name, other_hist) in &other.histograms {
+            if let Some(hist) = self.histograms.get(name) {
+                hist.check_merge(other_hist.as_ref())
+                    .map_err(|source| BookError {
+                        name: name.clone(),
+                        source,
+                    })?;
+            }
+        }
+        for (name, other_hist) in &other.histograms {
+            match self.histograms.get_mut(name) {
+                Some(hist) => {
+                    hist.merge_entry(other_hist.as_ref())
+                        .map_err(|source| BookError {
+                            name: name.clone(),
+                            source,
+                        })?
+                }
+                None => {
+                    self.histograms
+                        .insert(name.clone(), other_hist.clone_entry());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Write all of the histograms, with run-length encoding of empty bins if
+    /// run_length_encoding is set.
+    ///
+    /// The book is written as a magic number (`\x89NDB\r\n\x1a\n`), a format version (u16)
+    /// and the number of histograms (u64), then for each histogram, its name as the number of
+    /// bytes (u64) followed by the UTF-8 bytes, and the histogram as the number of bytes (u64)
+    /// followed by the histogram in the [binary](crate::io::binary) format.
+    /// All numbers are little-endian.
+    pub fn write_binary<W: io::Write>(
+        &self,
+        run_length_encoding: bool,
+        mut writer: W,
+    ) -> io::Result<()> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(self.histograms.len() as u64).to_le_bytes())?;
+        let mut bytes = Vec::new();
+        for (name, hist) in &self.histograms {
+            bytes.clear();
+            hist.write_entry(run_length_encoding, &mut bytes)?;
+            writer.write_all(&(name.len() as u64).to_le_bytes())?;
+            writer.write_all(name.as_bytes())?;
+            writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+            writer.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Read histograms written by [HistogramBook::write_binary], replacing the histograms
+    /// with the same names.
+    ///
+    /// As the types of the histograms are not stored, each histogram in the input must
+    /// already be in this book with the type it was written with, for example by booking
+    /// the histograms in the same way as the job that wrote them.
+    /// Returns an error of kind [io::ErrorKind::InvalidData] containing
+    /// [ConversionError::NotFound] if the book has no histogram with the name of a
+    /// histogram in the input, or any of the errors of
+    /// [crate::io::binary::read_binary] if it has a different type.
+    /// The histograms are only replaced once all of them have been read, so this book is
+    /// left unchanged if an error is returned.
+    pub fn read_binary<R: io::Read>(&mut self, mut reader: R) -> io::Result<()> {
+        let mut header = [0; 18];
+        reader.read_exact(&mut header)?;
+        if header[..8] != MAGIC {
+            return Err(invalid_data(ConversionError::InvalidData));
+        }
+        if header[8..10] != VERSION.to_le_bytes() {
+            return Err(invalid_data(ConversionError::UnsupportedVersion));
+        }
+        let count = u64::from_le_bytes(header[10..].try_into().expect("count has 8 bytes"));
+        let mut read = BTreeMap::new();
+        for _ in 0..count {
+            let length = read_length(&mut reader)?;
+            let mut name = Vec::new();
+            (&mut reader).take(length).read_to_end(&mut name)?;
+            if name.len() as u64 != length {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            let name =
+                String::from_utf8(name).map_err(|_| invalid_data(ConversionError::InvalidData))?;
+            let mut hist = self
+                .histograms
+                .get(&name)
+                .ok_or_else(|| invalid_data(ConversionError::NotFound))?
+                .clone_entry();
+            let length = read_length(&mut reader)?;
+            let mut input = (&mut reader).take(length);
+            hist.read_entry(&mut input)?;
+            if input.limit() > 0 {
+                return Err(invalid_data(ConversionError::InvalidData));
+            }
+            read.insert(name, hist);
+        }
+        self.histograms.extend(read);
+        Ok(())
+    }
+}
+
+fn read_length<R: io::Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+impl Clone for HistogramBook {
+    fn clone(&self) -> Self {
+        Self {
+            histograms: self
+                .histograms
+                .iter()
+                .map(|(name, hist)| (name.clone(), hist.clone_entry()))
+                .collect(),
+        }
+    }
+}
+
+impl Debug for HistogramBook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HistogramBook")
+            .field("names", &self.histograms.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{axis::Axis, error::BinaryOperationError, Histogram};
+
+use super::{paired_values_1d, probability::anderson_darling_survival, TestResult};
+
+/// Two-sample Anderson-Darling test of two 1D histograms.
+///
+/// Computes the standardized k-sample Anderson-Darling statistic for
+/// discrete data with ties (the A²akN statistic) defined in
+/// F. W. Scholz and M. A. Stephens, "K-Sample Anderson–Darling Tests",
+/// Journal of the American Statistical Association, 82:399 (1987) 918-924.
+/// Each bin is treated as a set of tied observations.
+/// The returned statistic is the standardized (A² - 1) / σ, where σ² is the variance of A² under
+/// the null hypothesis.
+///
+/// Compared to [kolmogorov_smirnov](super::kolmogorov_smirnov)
+/// this test is more sensitive to differences in the tails of the distributions.
+/// The p-value is interpolated from the tables of Scholz and Stephens and is
+/// most accurate between 0.001 and 0.25.
+///
+/// Bins are ordered by their index, so this test is only meaningful
+/// for axes where the bin index is ordered by coordinate
+/// (such as [Uniform](crate::axis::Uniform) and [Variable](crate::axis::Variable)).
+/// The bin values are treated as unweighted counts.
+///
+/// Returns a [BinaryOperationError] if the histograms have different axes
+/// or are not 1-dimensional.
+/// If either histogram is empty, or the histograms contain fewer than 4
+/// entries in total, the variance of the statistic is undefined and the
+/// statistic and p-value are NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram, axis::Uniform, compare::anderson_darling};
+///
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let mut h1 = ndhistogram!(Uniform::new(10, 0.0, 10.0)?);
+/// let mut h2 = ndhistogram!(Uniform::new(10, 0.0, 10.0)?);
+/// for x in 0..10 {
+///     h1.fill_with(&f64::from(x), 100.0);
+///     h2.fill_with(&f64::from(x), 100.0);
+/// }
+/// let result = anderson_darling(&h1, &h2)?;
+/// assert!(result.p_value > 0.25);
+/// # Ok(()) }
+/// ```
+pub fn anderson_darling<A, V1, V2, H1, H2>(
+    h1: &H1,
+    h2: &H2,
+) -> Result<TestResult, BinaryOperationError>
+where
+    A: Axis + PartialEq,
+    H1: Histogram<A, V1>,
+    H2: Histogram<A, V2>,
+    V1: Clone + Into<f64>,
+    V2: Clone + Into<f64>,
+{
+    let values = paired_values_1d(h1, h2)?;
+    let n1: f64 = values.iter().map(|it| it.0).sum();
+    let n2: f64 = values.iter().map(|it| it.1).sum();
+    if n1 == 0.0 || n2 == 0.0 {
+        return Ok(TestResult {
+            statistic: f64::NAN,
+            p_value: f64::NAN,
+        });
+    }
+    let n = n1 + n2;
+
+    let mut cumulative1 = 0.0;
+    let mut cumulative2 = 0.0;
+    let mut sum1 = 0.0;
+    let mut sum2 = 0.0;
+    for (f1, f2) in values.iter().filter(|(f1, f2)| f1 + f2 > 0.0) {
+        let l = f1 + f2;
+        // use the mid-rank of each set of tied observations
+        let m1 = cumulative1 + f1 / 2.0;
+        let m2 = cumulative2 + f2 / 2.0;
+        let b = m1 + m2;
+        let denominator = b * (n - b) - n * l / 4.0;
+        if denominator > 0.0 {
+            sum1 += l * (n * m1 - n1 * b).powi(2) / denominator;
+            sum2 += l * (n * m2 - n2 * b).powi(2) / denominator;
+        }
+        cumulative1 += f1;
+        cumulative2 += f2;
+    }
+    let a2 = (n - 1.0) / (n * n) * (sum1 / n1 + sum2 / n2);
+    let Some(sigma) = standard_deviation(n, 1.0 / n1 + 1.0 / n2) else {
+        return Ok(TestResult {
+            statistic: f64::NAN,
+            p_value: f64::NAN,
+        });
+    };
+    let statistic = (a2 - 1.0) / sigma;
+    Ok(TestResult {
+        statistic,
+        p_value: anderson_darling_survival(statistic),
+    })
+}
+
+/// Standard deviation of the two-sample Anderson-Darling statistic for
+/// total sample size n, given the sum of the inverse sample sizes.
+///
+/// Returns None if n < 4, where the variance is undefined.
+fn standard_deviation(n: f64, sum_inverse_sample_sizes: f64) -> Option<f64> {
+    if n < 4.0 {
+        return None;
+    }
+    let (h, g) = harmonic_sums(n);
+    let k = 2.0;
+    let sum_inv = sum_inverse_sample_sizes;
+    let a = (4.0 * g - 6.0) * (k - 1.0) + (10.0 - 6.0 * g) * sum_inv;
+    let b = (2.0 * g - 4.0) * k * k + 8.0 * h * k + (2.0 * g - 14.0 * h - 4.0) * sum_inv - 8.0 * h
+        + 4.0 * g
+        - 6.0;
+    let c = (6.0 * h + 2.0 * g - 2.0) * k * k
+        + (4.0 * h - 4.0 * g + 6.0) * k
+        + (2.0 * h - 6.0) * sum_inv
+        + 4.0 * h;
+    let d = (2.0 * h + 6.0) * k * k - 4.0 * h * k;
+    let variance =
+        (a * n.powi(3) + b * n.powi(2) + c * n + d) / ((n - 1.0) * (n - 2.0) * (n - 3.0));
+    (variance > 0.0).then(|| variance.sqrt())
+}
+
+/// The sums h = Σᵢ 1/i for i in [1, n-1] and g = Σᵢ Σⱼ 1/((n-i) j) for 1 <= i < j <= n-1.
+///
+/// For large n, the asymptotic expansions are used.
+fn harmonic_sums(n: f64) -> (f64, f64) {
+    const EXACT_LIMIT: f64 = 1e6;
+    const EULER_MASCHERONI: f64 = 0.577_215_664_901_532_9;
+    if n > EXACT_LIMIT {
+        let m = n - 1.0;
+        let h = m.ln() + EULER_MASCHERONI + 1.0 / (2.0 * m) - 1.0 / (12.0 * m * m);
+        let g = std::f64::consts::PI * std::f64::consts::PI / 6.0;
+        return (h, g);
+    }
+    let n = (n.round() as usize).max(1);
+    // partial harmonic sums, partial[i] = Σⱼ 1/j for j in [1, i]
+    let partial: Vec<f64> = (0..n)
+        .scan(0.0, |acc, i| {
+            if i > 0 {
+                *acc += 1.0 / i as f64;
+            }
+            Some(*acc)
+        })
+        .collect();
+    let h = partial[n - 1];
+    let g = (1..n.saturating_sub(1))
+        .map(|i| (h - partial[i]) / (n - i) as f64)
+        .sum();
+    (h, g)
+}
@@ -0,0 +1,256 @@
+use std::fmt::{Debug, Display};
+
+use num_traits::ToPrimitive;
+
+use crate::{
+    axis::{check_equal, Axis},
+    error::BinaryOperationError,
+    Histogram,
+};
+
+/// Tolerances used to decide whether two values are approximately equal.
+///
+/// Two values a and b are approximately equal if
+///
+/// |a - b| <= absolute + relative * max(|a|, |b|)
+///
+/// The default tolerance is a relative tolerance of 1e-9 and an absolute
+/// tolerance of 1e-12, so that values near zero, such as a bin edge at 0.0
+/// and one at 1e-17 from floating point rounding, are also approximately equal.
+/// Pass an explicit tolerance when comparing values smaller than this.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tolerance {
+    /// Maximum absolute difference.
+    pub absolute: f64,
+    /// Maximum difference relative to the larger of the two values.
+    pub relative: f64,
+}
+
+impl Tolerance {
+    /// Factory method to create a tolerance with both absolute and relative tolerance.
+    pub fn new(absolute: f64, relative: f64) -> Self {
+        Self { absolute, relative }
+    }
+
+    /// Factory method to create a tolerance with only an absolute tolerance.
+    pub fn absolute(absolute: f64) -> Self {
+        Self::new(absolute, 0.0)
+    }
+
+    /// Factory method to create a tolerance with only a relative tolerance.
+    pub fn relative(relative: f64) -> Self {
+        Self::new(0.0, relative)
+    }
+
+    /// Returns true if a and b are equal within this tolerance.
+    ///
+    /// NaN is considered equal to NaN and infinities are equal if they have the same sign.
+    pub fn is_close(&self, a: f64, b: f64) -> bool {
+        if a == b || (a.is_nan() && b.is_nan()) {
+            return true;
+        }
+        if a.is_infinite() || b.is_infinite() {
+            return false;
+        }
+        (a - b).abs() <= self.absolute + self.relative * a.abs().max(b.abs())
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self::new(1e-12, 1e-9)
+    }
+}
+
+impl Display for Tolerance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "absolute tolerance={}, relative tolerance={}",
+            self.absolute, self.relative
+        )
+    }
+}
+
+/// Approximate equality of histogram bin values.
+///
+/// This is implemented for the primitive number types and for all of the bin
+/// value types in [value](crate::value).
+/// Implement this trait for user defined bin value types to use them with
+/// [approx_eq] and [crate::assert_histogram_approx_eq].
+pub trait ApproxEq {
+    /// Returns true if self and other are equal within the given tolerance.
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance) -> bool;
+}
+
+macro_rules! impl_approx_eq {
+    ($($type:ty),*) => {
+        $(
+            impl ApproxEq for $type {
+                fn approx_eq(&self, other: &Self, tolerance: &Tolerance) -> bool {
+                    match (self.to_f64(), other.to_f64()) {
+                        (Some(left), Some(right)) => tolerance.is_close(left, right),
+                        _ => self == other,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_approx_eq! {f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize}
+
+/// A bin whose values are not approximately equal. See [ApproxEqReport].
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinDifference<B, V> {
+    /// Bin number.
+    pub index: usize,
+    /// Bin interval. See [Axis::BinInterval].
+    pub bin: B,
+    /// Bin value of the expected histogram.
+    pub expected: V,
+    /// Bin value of the actual histogram.
+    pub actual: V,
+}
+
+/// Detailed comparison of the bin values of two histograms returned by [approx_eq_report].
+///
+/// The [Display] implementation lists every bin that differs and is intended
+/// to be printed when a regression test fails.
+#[derive(Clone, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApproxEqReport<B, V> {
+    /// Tolerance used in the comparison.
+    pub tolerance: Tolerance,
+    /// Total number of bins compared.
+    pub num_bins: usize,
+    /// All bins whose values are not approximately equal, ordered by bin index.
+    pub differences: Vec<BinDifference<B, V>>,
+}
+
+impl<B, V> ApproxEqReport<B, V> {
+    /// Returns true if all bins are approximately equal.
+    pub fn is_approx_eq(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+impl<B: Display, V: Debug> Display for ApproxEqReport<B, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of {} bins differ ({})",
+            self.differences.len(),
+            self.num_bins,
+            self.tolerance
+        )?;
+        for difference in &self.differences {
+            write!(
+                f,
+                "\n  index={}, bin={}, expected={:?}, actual={:?}",
+                difference.index, difference.bin, difference.expected, difference.actual
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare the bin values of two histograms with the same axes and report any
+/// bins that are not approximately equal.
+///
+/// Missing bins (for example in a [HashHistogram](crate::HashHistogram)) are
+/// compared as the default bin value.
+///
+/// Returns a [BinaryOperationError] if the histograms have different axes.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram, axis::Uniform, compare::{approx_eq_report, Tolerance}};
+///
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0)?);
+/// let mut actual = ndhistogram!(Uniform::new(10, 0.0, 1.0)?);
+/// expected.fill_with(&0.05, 0.3);
+/// actual.fill_with(&0.05, 0.1 + 0.2);
+/// actual.fill(&0.55);
+/// let report = approx_eq_report(&expected, &actual, Tolerance::default())?;
+/// assert_eq!(report.differences.len(), 1);
+/// assert_eq!(report.differences[0].index, 6);
+/// println!("{}", report);
+/// # Ok(()) }
+/// ```
+pub fn approx_eq_report<A, V, H1, H2>(
+    expected: &H1,
+    actual: &H2,
+    tolerance: Tolerance,
+) -> Result<ApproxEqReport<A::BinInterval, V>, BinaryOperationError>
+where
+    A: Axis + PartialEq,
+    H1: Histogram<A, V>,
+    H2: Histogram<A, V>,
+    V: ApproxEq + Clone + Default,
+{
+    check_equal(expected.axes(), actual.axes())?;
+    let axes = expected.axes();
+    let differences = axes
+        .iter()
+        .filter_map(|(index, bin)| {
+            let expected = expected.value_at_index(index).cloned().unwrap_or_default();
+            let actual = actual.value_at_index(index).cloned().unwrap_or_default();
+            if expected.approx_eq(&actual, &tolerance) {
+                None
+            } else {
+                Some(BinDifference {
+                    index,
+                    bin,
+                    expected,
+                    actual,
+                })
+            }
+        })
+        .collect();
+    Ok(ApproxEqReport {
+        tolerance,
+        num_bins: axes.num_bins(),
+        differences,
+    })
+}
+
+/// Returns true if two histograms have the same axes and all bin values are approximately equal.
+///
+/// See [approx_eq_report] for a detailed report of which bins differ.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram, axis::Uniform, compare::{approx_eq, Tolerance}};
+///
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0)?);
+/// let mut actual = ndhistogram!(Uniform::new(10, 0.0, 1.0)?);
+/// expected.fill_with(&0.05, 0.3);
+/// actual.fill_with(&0.05, 0.1 + 0.2);
+/// assert_ne!(expected, actual);
+/// assert!(approx_eq(&expected, &actual, Tolerance::default()));
+/// # Ok(()) }
+/// ```
+pub fn approx_eq<A, V, H1, H2>(expected: &H1, actual: &H2, tolerance: Tolerance) -> bool
+where
+    A: Axis + PartialEq,
+    H1: Histogram<A, V>,
+    H2: Histogram<A, V>,
+    V: ApproxEq + Default,
+{
+    if check_equal(expected.axes(), actual.axes()).is_err() {
+        return false;
+    }
+    let default = V::default();
+    expected.axes().indices().all(|index| {
+        let expected = expected.value_at_index(index).unwrap_or(&default);
+        let actual = actual.value_at_index(index).unwrap_or(&default);
+        expected.approx_eq(actual, &tolerance)
+    })
+}
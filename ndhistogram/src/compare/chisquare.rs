@@ -0,0 +1,245 @@
+use crate::{axis::Axis, error::BinaryOperationError, value::WeightedSum, Histogram};
+
+use super::{paired_values, probability::chi2_survival, to_f64, Chi2TestResult};
+
+fn to_result(chi2: f64, num_bins: usize) -> Chi2TestResult {
+    let ndf = num_bins.saturating_sub(1);
+    Chi2TestResult {
+        chi2,
+        ndf,
+        p_value: chi2_survival(chi2, ndf),
+    }
+}
+
+/// Sum of the weights and sum of the weights squared of a [WeightedSum].
+fn weighted_sum_parts<T: Copy + Into<f64>>(value: &WeightedSum<T>) -> (f64, f64) {
+    (value.sum().into(), value.variance().into())
+}
+
+/// Variance assigned to empty bins of a weighted histogram,
+/// equivalent to a single entry with the average weight of the histogram.
+fn empty_bin_variance(sum_of_weights: f64, sum_of_weights_squared: f64) -> f64 {
+    let average_weight = sum_of_weights_squared / sum_of_weights;
+    average_weight * average_weight
+}
+
+/// Chi-square test of two histograms filled with unweighted entries.
+///
+/// The bin values of both histograms are treated as Poisson distributed counts.
+/// Their normalizations are not required to be equal.
+/// The test statistic is
+///
+/// chi2 = 1/(N1 N2) Σ (N2 n1ᵢ - N1 n2ᵢ)² / (n1ᵢ + n2ᵢ)
+///
+/// where n1ᵢ, n2ᵢ are the bin values and N1, N2 are the sums over all bins.
+/// Bins that are empty in both histograms are skipped and do not contribute to the
+/// number of degrees of freedom.
+/// This is equivalent to the "UU" option of ROOT's `TH1::Chi2Test`.
+///
+/// Returns a [BinaryOperationError] if the histograms have different axes.
+/// If either histogram is empty, the statistic and p-value are NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram, axis::UniformNoFlow, compare::chi2_unweighted};
+///
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let mut h1 = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0)?);
+/// let mut h2 = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0)?);
+/// h1.fill_with(&0.5, 10.0);
+/// h1.fill_with(&1.5, 30.0);
+/// h2.fill_with(&0.5, 30.0);
+/// h2.fill_with(&1.5, 10.0);
+/// let result = chi2_unweighted(&h1, &h2)?;
+/// assert_eq!(result.chi2, 20.0);
+/// assert_eq!(result.ndf, 1);
+/// assert!(result.p_value < 1e-5);
+/// # Ok(()) }
+/// ```
+pub fn chi2_unweighted<A, V1, V2, H1, H2>(
+    h1: &H1,
+    h2: &H2,
+) -> Result<Chi2TestResult, BinaryOperationError>
+where
+    A: Axis + PartialEq,
+    H1: Histogram<A, V1>,
+    H2: Histogram<A, V2>,
+    V1: Clone + Into<f64>,
+    V2: Clone + Into<f64>,
+{
+    let values = paired_values(h1, h2, to_f64, to_f64)?;
+    let sum1: f64 = values.iter().map(|it| it.0).sum();
+    let sum2: f64 = values.iter().map(|it| it.1).sum();
+    if sum1 == 0.0 || sum2 == 0.0 {
+        return Ok(Chi2TestResult {
+            chi2: f64::NAN,
+            ndf: 0,
+            p_value: f64::NAN,
+        });
+    }
+    let mut num_bins = 0;
+    let chi2 = values
+        .iter()
+        .filter(|(n1, n2)| n1 + n2 != 0.0)
+        .inspect(|_| num_bins += 1)
+        .map(|(n1, n2)| {
+            let delta = sum2 * n1 - sum1 * n2;
+            delta * delta / (n1 + n2)
+        })
+        .sum::<f64>()
+        / (sum1 * sum2);
+    Ok(to_result(chi2, num_bins))
+}
+
+/// Chi-square test of an unweighted histogram with a weighted histogram.
+///
+/// The first histogram contains Poisson distributed counts.
+/// The second histogram contains [WeightedSum]s whose variance is used as the
+/// uncertainty on each bin.
+/// This implements the test described in
+/// N. D. Gagunashvili, "Comparison of weighted and unweighted histograms",
+/// [arXiv:physics/0605123](https://arxiv.org/abs/physics/0605123)
+/// and is equivalent to the "UW" option of ROOT's `TH1::Chi2Test`.
+///
+/// Bins that are empty in both histograms are skipped.
+/// Bins of the weighted histogram that are empty are assigned the variance of
+/// a single entry with the average weight of that histogram.
+///
+/// Returns a [BinaryOperationError] if the histograms have different axes.
+/// If either histogram is empty, the statistic and p-value are NaN.
+pub fn chi2_unweighted_weighted<A, V1, T2, H1, H2>(
+    h1: &H1,
+    h2: &H2,
+) -> Result<Chi2TestResult, BinaryOperationError>
+where
+    A: Axis + PartialEq,
+    H1: Histogram<A, V1>,
+    H2: Histogram<A, WeightedSum<T2>>,
+    V1: Clone + Into<f64>,
+    T2: Copy + Into<f64>,
+{
+    let values = paired_values(h1, h2, to_f64, weighted_sum_parts)?;
+    let sum1: f64 = values.iter().map(|it| it.0).sum();
+    let sum2: f64 = values.iter().map(|it| it.1 .0).sum();
+    let sum_variance2: f64 = values.iter().map(|it| it.1 .1).sum();
+    if sum1 == 0.0 || sum2 == 0.0 {
+        return Ok(Chi2TestResult {
+            chi2: f64::NAN,
+            ndf: 0,
+            p_value: f64::NAN,
+        });
+    }
+    let mut num_bins = 0;
+    let chi2 = values
+        .iter()
+        .filter(|(n1, (w2, _))| *n1 != 0.0 || *w2 != 0.0)
+        .inspect(|_| num_bins += 1)
+        .map(|(n1, (w2, variance2))| {
+            let variance2 = if *variance2 > 0.0 {
+                *variance2
+            } else {
+                empty_bin_variance(sum2, sum_variance2)
+            };
+            // maximum likelihood estimate of the probability of this bin
+            let a = sum2 * w2 - sum1 * variance2;
+            let probability =
+                (a + (a * a + 4.0 * sum2 * sum2 * variance2 * n1).sqrt()) / (2.0 * sum2 * sum2);
+            let expected1 = sum1 * probability;
+            let expected2 = sum2 * probability;
+            let chi2_1 = if expected1 > 0.0 {
+                (n1 - expected1) * (n1 - expected1) / expected1
+            } else {
+                0.0
+            };
+            chi2_1 + (w2 - expected2) * (w2 - expected2) / variance2
+        })
+        .sum();
+    Ok(to_result(chi2, num_bins))
+}
+
+/// Chi-square test of two weighted histograms.
+///
+/// The variance of each [WeightedSum] is used as the uncertainty on each bin.
+/// The test statistic is
+///
+/// chi2 = Σ (W1 w2ᵢ - W2 w1ᵢ)² / (W1² s2ᵢ² + W2² s1ᵢ²)
+///
+/// where w1ᵢ, w2ᵢ are the sum of weights in each bin, s1ᵢ², s2ᵢ² are the sum of weights squared
+/// and W1, W2 are the sums of weights over all bins.
+/// This implements the test described in
+/// N. D. Gagunashvili, "Comparison of weighted and unweighted histograms",
+/// [arXiv:physics/0605123](https://arxiv.org/abs/physics/0605123)
+/// and is equivalent to the "WW" option of ROOT's `TH1::Chi2Test`.
+///
+/// Bins that are empty in both histograms are skipped.
+/// Empty bins in only one histogram are assigned the variance of
+/// a single entry with the average weight of that histogram.
+///
+/// Returns a [BinaryOperationError] if the histograms have different axes.
+/// If either histogram is empty, the statistic and p-value are NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram, axis::UniformNoFlow, value::WeightedSum, compare::chi2_weighted};
+///
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let mut h1 = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0)?; WeightedSum);
+/// let mut h2 = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0)?; WeightedSum);
+/// for _ in 0..10 {
+///     h1.fill_with(&0.5, 2.0);
+///     h1.fill_with(&1.5, 2.0);
+///     h2.fill_with(&0.5, 0.5);
+///     h2.fill_with(&1.5, 0.5);
+/// }
+/// let result = chi2_weighted(&h1, &h2)?;
+/// assert_eq!(result.chi2, 0.0);
+/// assert_eq!(result.ndf, 1);
+/// # Ok(()) }
+/// ```
+pub fn chi2_weighted<A, T1, T2, H1, H2>(
+    h1: &H1,
+    h2: &H2,
+) -> Result<Chi2TestResult, BinaryOperationError>
+where
+    A: Axis + PartialEq,
+    H1: Histogram<A, WeightedSum<T1>>,
+    H2: Histogram<A, WeightedSum<T2>>,
+    T1: Copy + Into<f64>,
+    T2: Copy + Into<f64>,
+{
+    let values = paired_values(h1, h2, weighted_sum_parts, weighted_sum_parts)?;
+    let sum1: f64 = values.iter().map(|it| it.0 .0).sum();
+    let sum2: f64 = values.iter().map(|it| it.1 .0).sum();
+    let sum_variance1: f64 = values.iter().map(|it| it.0 .1).sum();
+    let sum_variance2: f64 = values.iter().map(|it| it.1 .1).sum();
+    if sum1 == 0.0 || sum2 == 0.0 {
+        return Ok(Chi2TestResult {
+            chi2: f64::NAN,
+            ndf: 0,
+            p_value: f64::NAN,
+        });
+    }
+    let mut num_bins = 0;
+    let chi2 = values
+        .iter()
+        .filter(|((w1, _), (w2, _))| *w1 != 0.0 || *w2 != 0.0)
+        .inspect(|_| num_bins += 1)
+        .map(|((w1, variance1), (w2, variance2))| {
+            let variance1 = if *variance1 > 0.0 {
+                *variance1
+            } else {
+                empty_bin_variance(sum1, sum_variance1)
+            };
+            let variance2 = if *variance2 > 0.0 {
+                *variance2
+            } else {
+                empty_bin_variance(sum2, sum_variance2)
+            };
+            let delta = sum1 * w2 - sum2 * w1;
+            delta * delta / (sum1 * sum1 * variance2 + sum2 * sum2 * variance1)
+        })
+        .sum();
+    Ok(to_result(chi2, num_bins))
+}
@@ -0,0 +1,77 @@
+use crate::{axis::Axis, error::BinaryOperationError, Histogram};
+
+use super::{paired_values_1d, probability::kolmogorov_survival, TestResult};
+
+/// Kolmogorov-Smirnov test of two 1D histograms.
+///
+/// The test statistic is the maximum distance between the normalized
+/// cumulative distributions of the two histograms,
+/// and the p-value is computed from the Kolmogorov distribution with
+/// the effective number of entries N1 N2 / (N1 + N2).
+/// This is equivalent to ROOT's `TH1::KolmogorovTest`.
+/// Note that as the test is performed on binned data the p-value is
+/// only approximate and will be conservative if the bins are wide
+/// compared to the features of the distributions.
+///
+/// Bins are ordered by their index, so this test is only meaningful
+/// for axes where the bin index is ordered by coordinate
+/// (such as [Uniform](crate::axis::Uniform) and [Variable](crate::axis::Variable)).
+///
+/// Returns a [BinaryOperationError] if the histograms have different axes
+/// or are not 1-dimensional.
+/// If either histogram is empty, the statistic and p-value are NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram, axis::Uniform, compare::kolmogorov_smirnov};
+///
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let mut h1 = ndhistogram!(Uniform::new(10, 0.0, 10.0)?);
+/// let mut h2 = ndhistogram!(Uniform::new(10, 0.0, 10.0)?);
+/// for x in 0..10 {
+///     h1.fill_with(&f64::from(x), 100.0);
+///     h2.fill_with(&f64::from(x), 10.0 * f64::from(x));
+/// }
+/// let result = kolmogorov_smirnov(&h1, &h2)?;
+/// assert!(result.p_value < 1e-6);
+/// # Ok(()) }
+/// ```
+pub fn kolmogorov_smirnov<A, V1, V2, H1, H2>(
+    h1: &H1,
+    h2: &H2,
+) -> Result<TestResult, BinaryOperationError>
+where
+    A: Axis + PartialEq,
+    H1: Histogram<A, V1>,
+    H2: Histogram<A, V2>,
+    V1: Clone + Into<f64>,
+    V2: Clone + Into<f64>,
+{
+    let values = paired_values_1d(h1, h2)?;
+    let sum1: f64 = values.iter().map(|it| it.0).sum();
+    let sum2: f64 = values.iter().map(|it| it.1).sum();
+    if sum1 == 0.0 || sum2 == 0.0 {
+        return Ok(TestResult {
+            statistic: f64::NAN,
+            p_value: f64::NAN,
+        });
+    }
+    let (statistic, _, _) = values.iter().fold(
+        (0.0_f64, 0.0, 0.0),
+        |(distance, cumulative1, cumulative2), (n1, n2)| {
+            let cumulative1 = cumulative1 + n1 / sum1;
+            let cumulative2 = cumulative2 + n2 / sum2;
+            (
+                distance.max((cumulative1 - cumulative2).abs()),
+                cumulative1,
+                cumulative2,
+            )
+        },
+    );
+    let z = statistic * (sum1 * sum2 / (sum1 + sum2)).sqrt();
+    Ok(TestResult {
+        statistic,
+        p_value: kolmogorov_survival(z),
+    })
+}
@@ -0,0 +1,140 @@
+//! Statistical tests and approximate equality to compare histograms
+//!
+//! This module contains goodness-of-fit tests that compare the bin values of
+//! two [Histogram]s with the same binning, for example a
+//! newly filled histogram with a reference histogram.
+//! Each test returns the test statistic and a p-value.
+//! If the two histograms have different [Axes](crate::Axes), a
+//! [BinaryOperationError] is returned.
+//!
+//! The following tests are provided:
+//!
+//! - [chi2_unweighted], [chi2_unweighted_weighted] and [chi2_weighted]: chi-square tests
+//!   for unweighted-unweighted, unweighted-weighted and weighted-weighted histograms.
+//!   These follow the methods used in [ROOT](https://root.cern.ch/doc/master/classTH1.html)'s `TH1::Chi2Test`.
+//! - [kolmogorov_smirnov]: Kolmogorov-Smirnov test for 1D histograms.
+//! - [anderson_darling]: two-sample Anderson-Darling test for 1D histograms.
+//!
+//! It also provides [approx_eq] and [approx_eq_report] to check that two histograms
+//! are equal within some [Tolerance], for example in regression tests where exact floating point
+//! equality is too strict. See also [assert_histogram_approx_eq](crate::assert_histogram_approx_eq).
+//!
+//! All bins of the histogram, including any underflow and overflow bins, are used in the comparison.
+//! Use an axis without flow bins (eg [UniformNoFlow](crate::axis::UniformNoFlow))
+//! if under/overflow should not be compared.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, axis::Uniform, compare::chi2_unweighted};
+//!
+//! # fn main() -> Result<(), ndhistogram::Error> {
+//! let mut reference = ndhistogram!(Uniform::new(4, 0.0, 4.0)?);
+//! let mut run = ndhistogram!(Uniform::new(4, 0.0, 4.0)?);
+//! for (x, n) in [(0.5, 10.0), (1.5, 20.0), (2.5, 30.0), (3.5, 40.0)] {
+//!     reference.fill_with(&x, n);
+//!     run.fill_with(&x, n);
+//! }
+//! let result = chi2_unweighted(&reference, &run)?;
+//! assert_eq!(result.chi2, 0.0);
+//! assert_eq!(result.ndf, 3);
+//! assert_eq!(result.p_value, 1.0);
+//! # Ok(()) }
+//! ```
+
+mod andersondarling;
+pub use andersondarling::anderson_darling;
+mod approx;
+pub use approx::{approx_eq, approx_eq_report, ApproxEq, ApproxEqReport, BinDifference, Tolerance};
+mod chisquare;
+pub use chisquare::{chi2_unweighted, chi2_unweighted_weighted, chi2_weighted};
+mod kolmogorovsmirnov;
+pub use kolmogorovsmirnov::kolmogorov_smirnov;
+mod probability;
+
+use crate::{
+    axis::{check_equal, Axis},
+    error::BinaryOperationError,
+    Histogram,
+};
+
+/// Result of a chi-square comparison of two histograms.
+#[derive(Copy, Default, Clone, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chi2TestResult {
+    /// The chi-square test statistic.
+    pub chi2: f64,
+    /// Number of degrees of freedom.
+    /// This is the number of compared bins minus one.
+    pub ndf: usize,
+    /// Probability of observing a chi-square at least this large
+    /// if both histograms are drawn from the same distribution.
+    pub p_value: f64,
+}
+
+/// Result of a comparison of two histograms that does not have degrees of freedom,
+/// such as [kolmogorov_smirnov] and [anderson_darling].
+#[derive(Copy, Default, Clone, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestResult {
+    /// The test statistic.
+    pub statistic: f64,
+    /// Probability of observing a test statistic at least this large
+    /// if both histograms are drawn from the same distribution.
+    pub p_value: f64,
+}
+
+/// Read the values of each bin of two histograms with identical axes.
+///
+/// Bins that are missing (eg in sparse histograms) are treated as zero.
+fn paired_values<A, V1, V2, H1, H2, T1, T2>(
+    h1: &H1,
+    h2: &H2,
+    f1: impl Fn(&V1) -> T1,
+    f2: impl Fn(&V2) -> T2,
+) -> Result<Vec<(T1, T2)>, BinaryOperationError>
+where
+    A: Axis + PartialEq,
+    H1: Histogram<A, V1>,
+    H2: Histogram<A, V2>,
+    T1: Default,
+    T2: Default,
+{
+    check_equal(h1.axes(), h2.axes())?;
+    Ok(h1
+        .axes()
+        .indices()
+        .map(|index| {
+            (
+                h1.value_at_index(index).map(&f1).unwrap_or_default(),
+                h2.value_at_index(index).map(&f2).unwrap_or_default(),
+            )
+        })
+        .collect())
+}
+
+/// As [paired_values] for unweighted 1D histograms.
+fn paired_values_1d<A, V1, V2, H1, H2>(
+    h1: &H1,
+    h2: &H2,
+) -> Result<Vec<(f64, f64)>, BinaryOperationError>
+where
+    A: Axis + PartialEq,
+    H1: Histogram<A, V1>,
+    H2: Histogram<A, V2>,
+    V1: Clone + Into<f64>,
+    V2: Clone + Into<f64>,
+{
+    let num_dim = h1.axes().num_dim();
+    if num_dim != 1 {
+        return Err(BinaryOperationError::UnsupportedNumberOfDimensions {
+            expected: 1,
+            actual: num_dim,
+        });
+    }
+    paired_values(h1, h2, to_f64, to_f64)
+}
+
+fn to_f64<V: Clone + Into<f64>>(value: &V) -> f64 {
+    value.clone().into()
+}
@@ -0,0 +1,165 @@
+use std::{
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+};
+
+use super::DisplayValue;
+use crate::{
+    axis::{Axis, BinInterval, HexagonBinInterval, SingleValueBinInterval},
+    Axes, Histogram,
+};
+
+/// Shade characters for values up to 25%, 50%, 75% and 100% of the maximum.
+const SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+/// Colours of the 256-colour ANSI palette, from dark blue to yellow.
+const COLOURS: [u8; 8] = [54, 61, 25, 31, 36, 71, 148, 226];
+
+/// A bin interval that can tell whether it is an underflow or overflow bin.
+pub trait FlowBin {
+    /// True for underflow and overflow bins.
+    fn is_flow(&self) -> bool;
+}
+
+impl<T> FlowBin for BinInterval<T> {
+    fn is_flow(&self) -> bool {
+        !matches!(self, Self::Bin { .. })
+    }
+}
+
+impl<T> FlowBin for SingleValueBinInterval<T> {
+    fn is_flow(&self) -> bool {
+        matches!(self, Self::Overflow)
+    }
+}
+
+impl<T> FlowBin for HexagonBinInterval<T> {
+    fn is_flow(&self) -> bool {
+        matches!(self, Self::Overflow)
+    }
+}
+
+/// A terminal heatmap of a 2D histogram.
+///
+/// Each bin is drawn as two shade characters, with the bins of the first axis as columns
+/// and the bins of the second axis as rows, with the last bin at the top.
+/// Bins with a value of zero or less are left blank,
+/// other bins are shaded by their value as a fraction of the maximum value.
+/// Rows are labelled with the bins of the second axis,
+/// and columns are numbered with a key of the bins of the first axis below the grid.
+/// Bin labels are formatted with the precision of the formatter, 2 decimal places by default.
+///
+/// See the [module documentation](crate::display) for an example.
+#[derive(Debug)]
+pub struct Heatmap<'a, A, V, H> {
+    hist: &'a H,
+    colour: bool,
+    flow: bool,
+    phantom: PhantomData<fn(&A, &V)>,
+}
+
+impl<'a, A, V, H> Heatmap<'a, A, V, H>
+where
+    H: Histogram<A, V>,
+    A: Axes,
+{
+    /// Factory method for a heatmap of the histogram, without colours and including flow bins.
+    pub fn new(hist: &'a H) -> Self {
+        Self {
+            hist,
+            colour: false,
+            flow: true,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Colour the shade characters with ANSI escape codes for 256-colour terminals.
+    pub fn colour(mut self, colour: bool) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    /// Include the underflow and overflow bins in the heatmap.
+    pub fn show_flow(mut self, flow: bool) -> Self {
+        self.flow = flow;
+        self
+    }
+}
+
+impl<A, V, H, X, Y> Display for Heatmap<'_, A, V, H>
+where
+    H: Histogram<A, V>,
+    A: Axes + Axis<BinInterval = (X, Y)>,
+    V: DisplayValue,
+    X: FlowBin + Display,
+    Y: FlowBin + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        let shape = self.hist.axes().shape();
+        let value = |column: usize, row: usize| {
+            self.hist
+                .value_at_index(column + shape[0] * row)
+                .map_or(0.0, DisplayValue::central_value)
+        };
+        let bin = |column: usize, row: usize| self.hist.axes().bin(column + shape[0] * row);
+        let columns: Vec<(usize, String)> = (0..shape[0])
+            .filter_map(|column| {
+                let (x, _) = bin(column, 0)?;
+                (self.flow || !x.is_flow()).then(|| (column, format!("{x:.precision$}")))
+            })
+            .collect();
+        let rows: Vec<(usize, String)> = (0..shape[1])
+            .rev()
+            .filter_map(|row| {
+                let (_, y) = bin(0, row)?;
+                (self.flow || !y.is_flow()).then(|| (row, format!("{y:.precision$}")))
+            })
+            .collect();
+        let max = rows
+            .iter()
+            .flat_map(|(row, _)| columns.iter().map(|(column, _)| value(*column, *row)))
+            .filter(|it| !it.is_nan())
+            .fold(0.0, f64::max);
+        write!(
+            f,
+            "Heatmap({}x{} bins, max={})",
+            columns.len(),
+            rows.len(),
+            max
+        )?;
+        let width = rows.iter().map(|(_, it)| it.chars().count()).max();
+        let width = width.unwrap_or(0);
+        for (row, label) in &rows {
+            write!(f, "\n{label:>width$} |")?;
+            for (column, _) in &columns {
+                write_cell(f, value(*column, *row) / max, self.colour)?;
+            }
+            write!(f, "|")?;
+        }
+        write!(f, "\n{:width$} +{}+", "", "-".repeat(2 * columns.len()))?;
+        write!(f, "\n{:width$}  ", "")?;
+        for number in 0..columns.len() {
+            write!(f, " {}", number % 10)?;
+        }
+        for (number, (_, label)) in columns.iter().enumerate() {
+            write!(f, "\n{number:>3}: {label}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the two characters of a cell with a value as a fraction of the maximum.
+fn write_cell(f: &mut Formatter<'_>, fraction: f64, colour: bool) -> std::fmt::Result {
+    if fraction.is_nan() || fraction <= 0.0 {
+        return write!(f, "  ");
+    }
+    let fraction = fraction.min(1.0);
+    let shade = SHADES[((fraction * 4.0).ceil() as usize).clamp(1, 4) - 1];
+    if colour {
+        let colour = COLOURS[((fraction * 8.0).ceil() as usize).clamp(1, 8) - 1];
+        write!(f, "\x1b[38;5;{colour}m{shade}{shade}\x1b[0m")
+    } else {
+        write!(f, "{shade}{shade}")
+    }
+}
@@ -0,0 +1,44 @@
+//! Text renderings of histograms for display in a terminal
+//!
+//! The [Display](std::fmt::Display) implementations of [VecHistogram](crate::VecHistogram)
+//! and [HashHistogram](crate::HashHistogram) list the bins of a histogram with any number
+//! of dimensions.
+//! The bin values are displayed through the [DisplayValue] trait, which gives the
+//! central value of a bin and its uncertainty, if any.
+//! This module also provides renderings that are specific to the dimensionality of the histogram:
+//!
+//! - [Heatmap]: a grid of Unicode shade characters showing the values of a 2D histogram,
+//!   optionally with ANSI colours.
+//!
+//! [Summary] is a report of a histogram with any number of dimensions,
+//! with a table of the bin values and their uncertainties, totals and means.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, axis::Uniform, display::Heatmap};
+//!
+//! # fn main() -> Result<(), ndhistogram::Error> {
+//! let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0)?, Uniform::new(2, 0.0, 2.0)?);
+//! hist.fill_with(&(0.5, 0.5), 4.0);
+//! hist.fill(&(1.5, 1.5));
+//! let heatmap = Heatmap::new(&hist).show_flow(false);
+//! assert_eq!(
+//!     format!("{heatmap:.1}"),
+//!     "Heatmap(2x2 bins, max=4)
+//! [1.0, 2.0) |  ░░|
+//! [0.0, 1.0) |██  |
+//!            +----+
+//!              0 1
+//!   0: [0.0, 1.0)
+//!   1: [1.0, 2.0)"
+//! );
+//! # Ok(()) }
+//! ```
+
+mod heatmap;
+pub use heatmap::{FlowBin, Heatmap};
+mod summary;
+pub use summary::{Summary, SummaryAxes, SummaryBin};
+mod value;
+pub use value::DisplayValue;
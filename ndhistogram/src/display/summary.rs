@@ -0,0 +1,338 @@
+use std::{
+    fmt::{Display, Formatter, LowerExp},
+    marker::PhantomData,
+};
+
+use num_traits::NumCast;
+
+use super::{DisplayValue, FlowBin};
+use crate::{
+    axis::{Axis, BinInterval, HexagonBinInterval, SingleValueBinInterval},
+    AxesTuple, Histogram,
+};
+
+/// A bin interval that can be described in a [Summary].
+pub trait SummaryBin: FlowBin {
+    /// Append a label for the bin on each axis, with the given number of decimal places,
+    /// in scientific notation if exp is set.
+    fn push_labels(&self, precision: usize, exp: bool, labels: &mut Vec<String>);
+
+    /// Append the center of the bin on each axis, or None for flow bins and for axes
+    /// without a numeric position.
+    fn push_centers(&self, centers: &mut Vec<Option<f64>>);
+}
+
+impl<T: Display + LowerExp + NumCast + Copy> SummaryBin for BinInterval<T> {
+    fn push_labels(&self, precision: usize, exp: bool, labels: &mut Vec<String>) {
+        labels.push(if exp {
+            format!("{self:.precision$e}")
+        } else {
+            format!("{self:.precision$}")
+        });
+    }
+
+    fn push_centers(&self, centers: &mut Vec<Option<f64>>) {
+        let center = match self {
+            Self::Bin { start, end } => start
+                .to_f64()
+                .zip(end.to_f64())
+                .map(|(start, end)| (start + end) / 2.0),
+            _ => None,
+        };
+        centers.push(center.filter(|it| it.is_finite()));
+    }
+}
+
+impl<T: Display> SummaryBin for SingleValueBinInterval<T> {
+    fn push_labels(&self, _precision: usize, _exp: bool, labels: &mut Vec<String>) {
+        labels.push(self.to_string());
+    }
+
+    fn push_centers(&self, centers: &mut Vec<Option<f64>>) {
+        centers.push(None);
+    }
+}
+
+// A hexagon is labelled by its centre. As it has no position along a single axis,
+// it has no center.
+impl<T: Display + LowerExp> SummaryBin for HexagonBinInterval<T> {
+    fn push_labels(&self, precision: usize, exp: bool, labels: &mut Vec<String>) {
+        labels.push(match self.centre() {
+            Some((x, y)) if exp => format!("{{hexagon at ({x:.precision$e}, {y:.precision$e})}}"),
+            Some((x, y)) => format!("{{hexagon at ({x:.precision$}, {y:.precision$})}}"),
+            None => self.to_string(),
+        });
+    }
+
+    fn push_centers(&self, centers: &mut Vec<Option<f64>>) {
+        centers.push(None);
+    }
+}
+
+/// A set of axes that can be described in a [Summary].
+pub trait SummaryAxes: Axis {
+    /// Append the [Display] line of each axis.
+    fn push_descriptions(&self, descriptions: &mut Vec<String>);
+}
+
+// Implements FlowBin and SummaryBin for every tuple from 2 elements, and SummaryAxes for
+// every AxesTuple, up to the full list of type parameters.
+macro_rules! impl_summary {
+    ([$($done:ident: $done_index:tt),*] $next:ident: $next_index:tt $(, $rest:ident: $rest_index:tt)*) => {
+        impl_summary!(@IMPL $($done: $done_index,)* $next: $next_index);
+        impl_summary!([$($done: $done_index,)* $next: $next_index] $($rest: $rest_index),*);
+    };
+    ([$($done:ident: $done_index:tt),*]) => {};
+    (@IMPL $T:ident: $index:tt) => {
+        impl_summary!(@AXES $T: $index);
+    };
+    (@IMPL $($T:ident: $index:tt),+) => {
+        impl<$($T: FlowBin),+> FlowBin for ($($T,)+) {
+            fn is_flow(&self) -> bool {
+                $(self.$index.is_flow())||+
+            }
+        }
+
+        impl<$($T: SummaryBin),+> SummaryBin for ($($T,)+) {
+            fn push_labels(&self, precision: usize, exp: bool, labels: &mut Vec<String>) {
+                $(self.$index.push_labels(precision, exp, labels);)+
+            }
+
+            fn push_centers(&self, centers: &mut Vec<Option<f64>>) {
+                $(self.$index.push_centers(centers);)+
+            }
+        }
+
+        impl_summary!(@AXES $($T: $index),+);
+    };
+    (@AXES $($T:ident: $index:tt),+) => {
+        impl<$($T: Axis + Display),+> SummaryAxes for AxesTuple<($($T,)+)>
+        where
+            AxesTuple<($($T,)+)>: Axis,
+        {
+            fn push_descriptions(&self, descriptions: &mut Vec<String>) {
+                $(descriptions.push(self.as_tuple().$index.to_string());)+
+            }
+        }
+    };
+}
+
+impl_summary! {
+    [] X: 0,
+    Y: 1,
+    Z: 2,
+    T: 3,
+    D4: 4,
+    D5: 5,
+    D6: 6,
+    D7: 7,
+    D8: 8,
+    D9: 9,
+    D10: 10,
+    D11: 11,
+    D12: 12,
+    D13: 13,
+    D14: 14,
+    D15: 15,
+    D16: 16,
+    D17: 17,
+    D18: 18,
+    D19: 19,
+    D20: 20
+}
+
+/// A text report of a histogram.
+///
+/// The report contains:
+///
+/// - the [Display] line of each axis,
+/// - a table of the bins, with a column for the bin on each axis and the value of the bin
+///   with its uncertainty, if the value type has one (see [DisplayValue]),
+/// - the total of the values in range, in the underflow and overflow bins, and overall,
+/// - the number of entries (see [DisplayValue::entries]) and of non-empty bins,
+/// - the mean of the bin centers along each axis, weighted by the bin values of the bins
+///   in range. Axes without a numeric position along a single axis, such as categories
+///   and [Hexagonal](crate::axis::Hexagonal) axes, have no mean.
+///
+/// Bins that are not stored by a sparse histogram are left out of the table.
+/// Numbers and bin edges are formatted with the precision of the formatter,
+/// 2 decimal places by default, and in scientific notation with `{:e}`.
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram, axis::UniformNoFlow, value::WeightedSum};
+///
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let mut hist = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0)?; WeightedSum);
+/// hist.fill_with(&0.5, 3.0);
+/// hist.fill_with(&0.5, 4.0);
+/// hist.fill_with(&1.5, 2.0);
+/// assert_eq!(
+///     format!("{:.1}", hist.summary()),
+///     "Summary(1D, 2 bins)
+/// axis 0: Axis{# bins=2, range=[0, 2), class=UniformNoFlow}
+/// axis 0     | value
+/// [0.0, 1.0) | 7.0 ± 5.0
+/// [1.0, 2.0) | 2.0 ± 2.0
+/// total: 9.0 (in range: 9.0, flow: 0.0)
+/// entries: 3.0, non-empty bins: 2
+/// mean: axis 0 = 0.7"
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct Summary<'a, A, V, H> {
+    hist: &'a H,
+    phantom: PhantomData<fn(&A, &V)>,
+}
+
+impl<'a, A, V, H> Summary<'a, A, V, H>
+where
+    H: Histogram<A, V>,
+    A: Axis,
+{
+    /// Factory method for a summary of the histogram.
+    pub fn new(hist: &'a H) -> Self {
+        Self {
+            hist,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<A, V, H> Summary<'_, A, V, H>
+where
+    H: Histogram<A, V>,
+    A: SummaryAxes,
+    A::BinInterval: SummaryBin,
+    V: DisplayValue,
+{
+    fn write(&self, f: &mut Formatter<'_>, exp: bool) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        let number = |value: f64| {
+            if exp {
+                format!("{value:.precision$e}")
+            } else {
+                format!("{value:.precision$}")
+            }
+        };
+        let axes = self.hist.axes();
+        write!(f, "Summary({}D, {} bins)", axes.num_dim(), axes.num_bins())?;
+        let mut descriptions = Vec::new();
+        axes.push_descriptions(&mut descriptions);
+        for (axis, description) in descriptions.iter().enumerate() {
+            write!(f, "\naxis {axis}: {description}")?;
+        }
+
+        let mut header: Vec<String> = (0..descriptions.len())
+            .map(|axis| format!("axis {axis}"))
+            .collect();
+        header.push("value".to_string());
+        let mut rows = Vec::new();
+        let (mut in_range, mut flow, mut entries, mut non_empty) = (0.0, 0.0, 0.0, 0);
+        let mut weighted_centers = vec![0.0; descriptions.len()];
+        let mut numeric = vec![true; descriptions.len()];
+        for (index, bin) in axes.iter() {
+            let Some(value) = self.hist.value_at_index(index) else {
+                continue;
+            };
+            let central_value = value.central_value();
+            let mut cells = Vec::new();
+            bin.push_labels(precision, exp, &mut cells);
+            cells.push(match value.uncertainty() {
+                Some(uncertainty) => {
+                    format!("{} ± {}", number(central_value), number(uncertainty))
+                }
+                None => number(central_value),
+            });
+            rows.push(cells);
+            if bin.is_flow() {
+                flow += central_value;
+            } else {
+                in_range += central_value;
+                let mut centers = Vec::new();
+                bin.push_centers(&mut centers);
+                for (axis, center) in centers.into_iter().enumerate() {
+                    match center {
+                        Some(center) => weighted_centers[axis] += central_value * center,
+                        None => numeric[axis] = false,
+                    }
+                }
+            }
+            entries += value.entries();
+            if central_value != 0.0 {
+                non_empty += 1;
+            }
+        }
+
+        let widths: Vec<usize> = (0..header.len())
+            .map(|column| {
+                std::iter::once(&header)
+                    .chain(&rows)
+                    .map(|cells| cells[column].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        for cells in std::iter::once(&header).chain(&rows) {
+            let (value, bins) = cells.split_last().expect("there is a value column");
+            writeln!(f)?;
+            for (cell, width) in bins.iter().zip(&widths) {
+                write!(f, "{cell:<width$} ")?;
+            }
+            write!(f, "| {value}")?;
+        }
+
+        write!(
+            f,
+            "\ntotal: {} (in range: {}, flow: {})",
+            number(in_range + flow),
+            number(in_range),
+            number(flow)
+        )?;
+        write!(
+            f,
+            "\nentries: {}, non-empty bins: {non_empty}",
+            number(entries)
+        )?;
+        let means: Vec<String> = weighted_centers
+            .iter()
+            .zip(&numeric)
+            .enumerate()
+            .map(|(axis, (weighted_center, numeric))| {
+                let mean = if *numeric {
+                    number(weighted_center / in_range)
+                } else {
+                    "-".to_string()
+                };
+                format!("axis {axis} = {mean}")
+            })
+            .collect();
+        write!(f, "\nmean: {}", means.join(", "))
+    }
+}
+
+impl<A, V, H> Display for Summary<'_, A, V, H>
+where
+    H: Histogram<A, V>,
+    A: SummaryAxes,
+    A::BinInterval: SummaryBin,
+    V: DisplayValue,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write(f, false)
+    }
+}
+
+impl<A, V, H> LowerExp for Summary<'_, A, V, H>
+where
+    H: Histogram<A, V>,
+    A: SummaryAxes,
+    A::BinInterval: SummaryBin,
+    V: DisplayValue,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write(f, true)
+    }
+}
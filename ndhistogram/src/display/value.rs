@@ -0,0 +1,154 @@
+use num_traits::{Float, NumCast, Signed, ToPrimitive};
+
+use crate::value::{Mean, Sum, WeightedMean, WeightedSum};
+
+/// A bin value that can be displayed as a central value with an optional uncertainty.
+///
+/// This is implemented for the primitive number types and all of the types in
+/// [value](crate::value), and is required by the [Display](std::fmt::Display)
+/// implementations of the histograms and by the renderings in [display](crate::display).
+/// Implement it for a user-defined bin value type to display histograms of that type.
+///
+/// The histogram [Display](std::fmt::Display) implementations previously required
+/// `V: Clone + Into<f64>`.
+/// A blanket implementation for those types is not possible as it would conflict with
+/// the implementations for `u64`, `i64` and the other integers that do not convert into [f64].
+/// User-defined bin value types that relied on [Into]<[f64]> should implement this trait instead:
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Fill, Histogram, axis::Uniform, display::DisplayValue};
+///
+/// #[derive(Clone, Default)]
+/// struct Counter(u32);
+///
+/// impl Fill for Counter {
+///     fn fill(&mut self) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// impl From<Counter> for f64 {
+///     fn from(counter: Counter) -> f64 {
+///         counter.0.into()
+///     }
+/// }
+///
+/// impl DisplayValue for Counter {
+///     fn central_value(&self) -> f64 {
+///         self.clone().into()
+///     }
+/// }
+///
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0)?; Counter);
+/// hist.fill(&0.5);
+/// assert!(hist.to_string().starts_with("VecHistogram1D(4 bins, sum=1)"));
+/// # Ok(()) }
+/// ```
+pub trait DisplayValue {
+    /// The central value of the bin.
+    fn central_value(&self) -> f64;
+
+    /// The uncertainty on the central value, if any.
+    fn uncertainty(&self) -> Option<f64> {
+        None
+    }
+
+    /// The number of entries in the bin.
+    ///
+    /// By default this is the central value, which is the number of entries of bins
+    /// that count unweighted fills.
+    fn entries(&self) -> f64 {
+        self.central_value()
+    }
+}
+
+macro_rules! impl_display_value_for_primitive {
+    ($($T:ty),*) => {
+        $(
+            impl DisplayValue for $T {
+                fn central_value(&self) -> f64 {
+                    self.to_f64().unwrap_or(f64::NAN)
+                }
+            }
+        )*
+    };
+}
+
+impl_display_value_for_primitive! {i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64}
+
+impl<T: NumCast + Copy> DisplayValue for Sum<T> {
+    fn central_value(&self) -> f64 {
+        self.get().to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// The Poisson uncertainty, see [Sum::variance].
+    fn uncertainty(&self) -> Option<f64> {
+        self.variance().to_f64().map(f64::sqrt)
+    }
+}
+
+impl<T: NumCast + Copy> DisplayValue for WeightedSum<T> {
+    fn central_value(&self) -> f64 {
+        self.get().to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// The square root of the sum of weights squared, see [WeightedSum::variance].
+    fn uncertainty(&self) -> Option<f64> {
+        self.variance().to_f64().map(f64::sqrt)
+    }
+
+    /// The effective number of entries, the squared sum of weights divided by the sum of
+    /// weights squared.
+    fn entries(&self) -> f64 {
+        let variance = self.variance().to_f64().unwrap_or(f64::NAN);
+        if variance == 0.0 {
+            0.0
+        } else {
+            self.central_value().powi(2) / variance
+        }
+    }
+}
+
+impl<T, O, C> DisplayValue for Mean<T, O, C>
+where
+    T: Copy,
+    O: From<T> + From<C> + Float + Signed,
+    C: Copy + ToPrimitive,
+{
+    fn central_value(&self) -> f64 {
+        self.get().to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// The standard error of the mean, see [Mean::standard_error_of_mean].
+    fn uncertainty(&self) -> Option<f64> {
+        self.standard_error_of_mean().to_f64()
+    }
+
+    /// The number of samples, see [Mean::num_samples].
+    fn entries(&self) -> f64 {
+        self.num_samples().to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+impl<T, W, O, C> DisplayValue for WeightedMean<T, W, O, C>
+where
+    T: Copy,
+    W: Copy,
+    O: From<T> + From<W> + From<C> + Float + Signed,
+    C: Copy + ToPrimitive,
+{
+    fn central_value(&self) -> f64 {
+        self.get().to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// The standard error of the mean, see [WeightedMean::standard_error_of_mean].
+    fn uncertainty(&self) -> Option<f64> {
+        self.standard_error_of_mean().to_f64()
+    }
+
+    /// The number of samples, see [WeightedMean::num_samples].
+    fn entries(&self) -> f64 {
+        self.num_samples().to_f64().unwrap_or(f64::NAN)
+    }
+}
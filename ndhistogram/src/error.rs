@@ -5,6 +5,7 @@ use thiserror::Error;
 /// the specific details of the cause of the error.
 #[derive(Error, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum Error {
     /// Returned if a Histogram binary operation fails (such as h1 + h2).
     #[error(transparent)]
@@ -12,14 +13,75 @@ pub enum Error {
     /// Returned if an Axis cannot be created due to invalid input parameters.
     #[error(transparent)]
     AxisError(#[from] AxisError),
+    /// Returned if a Histogram cannot be converted to or from another format.
+    #[error(transparent)]
+    ConversionError(#[from] ConversionError),
 }
 
+/// Returned if a Histogram binary operation fails (such as h1 + h2),
+/// for example because the two histograms have incompatible binning.
 #[derive(Error, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-/// Returned if a Histogram binary operation fails (such as h1 + h2).
-/// for example because the two histograms have incompatible binning.
-#[error("histogram binary operation failed (check binning?)")]
-pub struct BinaryOperationError;
+#[non_exhaustive]
+pub enum BinaryOperationError {
+    /// The histograms have different binning along one of their axes.
+    #[error("histogram binary operation failed: axis {axis} has {reason}")]
+    IncompatibleAxis {
+        /// Index of the first axis (dimension) that differs.
+        ///
+        /// This counts the dimensions of the coordinate, see
+        /// [Axis::num_dim](crate::axis::Axis::num_dim), so an axis that covers
+        /// several dimensions, such as a [Hexagonal](crate::axis::Hexagonal) axis,
+        /// is reported as its first dimension and moves the index of every later axis.
+        axis: usize,
+        /// How the axes differ.
+        reason: AxisMismatch,
+    },
+    /// The histograms have different axes but the axes do not describe how they differ.
+    ///
+    /// This is only returned for user defined axes that do not override
+    /// [Axis::check_compatible](crate::axis::Axis::check_compatible).
+    #[error("histogram binary operation failed: histograms have different axes")]
+    IncompatibleAxes,
+    /// The histograms have different axis or bin value types.
+    ///
+    /// This is only returned for histograms whose types are not known at compile time,
+    /// such as the histograms of a [HistogramBook](crate::book::HistogramBook).
+    #[error("histogram binary operation failed: histograms have different types")]
+    IncompatibleTypes,
+    /// The operation is not supported for histograms with this number of dimensions.
+    #[error("histogram binary operation failed: expected {expected}-dimensional histograms but found {actual} dimensions")]
+    UnsupportedNumberOfDimensions {
+        /// Number of dimensions supported by the operation.
+        expected: usize,
+        /// Number of dimensions of the histograms.
+        actual: usize,
+    },
+}
+
+/// Describes how the binning of two axes of the same type differ.
+///
+/// See [Axis::check_compatible](crate::axis::Axis::check_compatible).
+#[derive(Error, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum AxisMismatch {
+    /// The axes have a different number of bins.
+    #[error("different number of bins")]
+    NumberOfBins,
+    /// The axes have the same number of bins but cover a different range.
+    #[error("different range")]
+    Range,
+    /// The axes have the same number of bins and range but different bin edges.
+    #[error("different bin edges")]
+    BinEdges,
+    /// The axes have the same number of bins but different categories.
+    #[error("different categories")]
+    Categories,
+    /// The axes differ in some other way.
+    #[error("different binning")]
+    Binning,
+}
 
 /// Errors that can occur when creating an Axis, usually due to invalid input parameters.
 #[derive(Error, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -41,3 +103,63 @@ pub enum AxisError {
     #[error("failed to sort bin_edges. The list of axis bin edges must be sortable.")]
     FailedToSortBinEdges,
 }
+
+/// Errors that can occur when converting a Histogram to or from another format.
+/// See [io](crate::io).
+#[derive(Error, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// The input was written with an unsupported version of the format.
+    #[error("unsupported format version")]
+    UnsupportedVersion,
+    /// An axis in the input cannot be converted to the requested axis type.
+    #[error("axis {0} cannot be converted to the requested axis type")]
+    IncompatibleAxis(usize),
+    /// The input has a different number of axes than the requested histogram type.
+    #[error("expected {expected} axes but found {actual}")]
+    WrongNumberOfAxes {
+        /// Number of axes of the requested histogram type.
+        expected: usize,
+        /// Number of axes in the input.
+        actual: usize,
+    },
+    /// The bin value storage cannot be converted to the requested bin value type.
+    #[error("bin values cannot be converted to the requested bin value type")]
+    IncompatibleStorage,
+    /// The number or shape of the bin values does not match the axes.
+    #[error("shape of the bin values does not match the axes")]
+    ShapeMismatch,
+    /// A value cannot be represented in the output type.
+    #[error("value cannot be represented in the output type")]
+    ValueOutOfRange,
+    /// A row of a table could not be parsed.
+    /// Rows of text tables are numbered from 1, starting with the header row,
+    /// and rows of record batches are numbered from 0.
+    #[error("invalid row {0}")]
+    InvalidRow(usize),
+    /// A column of a table is missing or has the wrong type.
+    /// Columns are numbered from 0.
+    #[error("invalid column {0}")]
+    InvalidColumn(usize),
+    /// The input is malformed or uses an unsupported feature of the format.
+    #[error("invalid or unsupported input data")]
+    InvalidData,
+    /// The input does not contain an object with the requested name.
+    #[error("object not found")]
+    NotFound,
+    /// An axis in the input has invalid parameters.
+    #[error(transparent)]
+    AxisError(#[from] AxisError),
+}
+
+/// Returned if histograms of a [HistogramBook](crate::book::HistogramBook) cannot be merged.
+#[derive(Error, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[error("histogram {name:?} cannot be merged: {source}")]
+pub struct BookError {
+    /// Name of the first histogram that cannot be merged.
+    pub name: String,
+    /// Why the histograms cannot be merged.
+    pub source: BinaryOperationError,
+}
@@ -7,7 +7,15 @@ use std::{
 };
 
 use super::histogram::{Histogram, Iter, IterMut, ValuesMut};
-use crate::{axis::Axis, error::AxisError, Axes, Item};
+use crate::{
+    axes::grown_index,
+    axis::{check_compatible_within, Axis, Compatible, StrAxis},
+    compare::Tolerance,
+    display::{DisplayValue, Summary},
+    error::AxisError,
+    io::merge::Merge,
+    Axes, Fill, FillWith, FillWithWeighted, Item,
+};
 
 use rustc_hash::FxHasher;
 
@@ -100,6 +108,171 @@ impl<A: Axis, V, S: std::hash::BuildHasher> HashHistogram<A, V, S> {
     }
 }
 
+impl<A: Axes, V, S: BuildHasher> HashHistogram<A, V, S> {
+    /// Grow the axes so that the histogram contains the coordinate, see [Axis::grow].
+    ///
+    /// The existing bin values are kept.
+    /// Returns true if any axis grew.
+    /// [Histogram::fill] never grows the axes, see [HashHistogram::fill_growing] to
+    /// grow them while filling.
+    /// Axes that are not extendable never grow, so coordinates outside of them are still
+    /// not contained in the histogram.
+    ///
+    /// When only the last axis grows, the stored values keep their index.
+    /// When any other axis grows, all stored values are moved to their new index.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ndhistogram::{Histogram, sparsehistogram, axis::IntCategoryNoFlow};
+    /// let mut hist = sparsehistogram!(IntCategoryNoFlow::extendable(vec![1, 2]); u64);
+    /// hist.fill(&2);
+    /// hist.grow(&10);
+    /// hist.fill(&10);
+    /// assert_eq!(hist.value(&2), Some(&1));
+    /// assert_eq!(hist.value(&10), Some(&1));
+    /// ```
+    pub fn grow(&mut self, coordinate: &A::Coordinate) -> bool {
+        let shape = self.axes.shape();
+        if !self.axes.grow(coordinate) {
+            return false;
+        }
+        let new_shape = self.axes.shape();
+        let last = shape.len() - 1;
+        if shape[..last] == new_shape[..last] {
+            // every bin keeps its index
+            return true;
+        }
+        let values: Vec<_> = self.values.drain().collect();
+        self.values.extend(
+            values
+                .into_iter()
+                .map(|(index, value)| (grown_index(index, &shape, &new_shape), value)),
+        );
+        true
+    }
+}
+
+impl<A: Axes, V: Default, S: BuildHasher> HashHistogram<A, V, S> {
+    /// The bin index of a coordinate, growing the axes if they do not contain it.
+    fn index_growing(&mut self, coordinate: &A::Coordinate) -> Option<usize> {
+        match self.axes.index(coordinate) {
+            Some(index) => Some(index),
+            None if self.grow(coordinate) => self.axes.index(coordinate),
+            None => None,
+        }
+    }
+
+    /// Fill the histogram like [Histogram::fill], growing the axes first if they do not
+    /// contain the coordinate, see [HashHistogram::grow].
+    ///
+    /// The coordinate is only looked up once when the axes already contain it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ndhistogram::{Histogram, sparsehistogram, axis::IntCategoryNoFlow};
+    /// let mut hist = sparsehistogram!(IntCategoryNoFlow::extendable(vec![1, 2]); u64);
+    /// hist.fill_growing(&2);
+    /// hist.fill_growing(&10);
+    /// assert_eq!(hist.value(&2), Some(&1));
+    /// assert_eq!(hist.value(&10), Some(&1));
+    /// ```
+    pub fn fill_growing(&mut self, coordinate: &A::Coordinate)
+    where
+        V: Fill,
+    {
+        if let Some(index) = self.index_growing(coordinate) {
+            self.values.entry(index).or_default().fill();
+        }
+    }
+
+    /// Fill the histogram like [Histogram::fill_with], growing the axes first if they
+    /// do not contain the coordinate, see [HashHistogram::fill_growing].
+    pub fn fill_with_growing<D>(&mut self, coordinate: &A::Coordinate, data: D)
+    where
+        V: FillWith<D>,
+    {
+        if let Some(index) = self.index_growing(coordinate) {
+            self.values.entry(index).or_default().fill_with(data);
+        }
+    }
+
+    /// Fill the histogram like [Histogram::fill_with_weighted], growing the axes first
+    /// if they do not contain the coordinate, see [HashHistogram::fill_growing].
+    pub fn fill_with_weighted_growing<D, W>(
+        &mut self,
+        coordinate: &A::Coordinate,
+        data: D,
+        weight: W,
+    ) where
+        V: FillWithWeighted<D, W>,
+    {
+        if let Some(index) = self.index_growing(coordinate) {
+            self.values
+                .entry(index)
+                .or_default()
+                .fill_with_weighted(data, weight);
+        }
+    }
+}
+
+impl<A: StrAxis, V: Default, S: BuildHasher> HashHistogram<A, V, S> {
+    /// The bin value of a string category, as [Histogram::value] without allocating
+    /// a [String], see [StrAxis].
+    pub fn value_str(&self, label: &str) -> Option<&V> {
+        self.values.get(&self.axes.index_str(label)?)
+    }
+
+    /// Fill the bin of a string category like [Histogram::fill], without allocating
+    /// a [String], see [StrAxis].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ndhistogram::{Histogram, sparsehistogram, axis::StrCategoryNoFlow};
+    /// let mut hist = sparsehistogram!(StrCategoryNoFlow::new(["electron", "muon"]); u64);
+    /// for particle in "muon electron muon photon".split(' ') {
+    ///     hist.fill_str(particle);
+    /// }
+    /// assert_eq!(hist.value_str("muon"), Some(&2));
+    /// assert_eq!(hist.value_str("photon"), None);
+    /// ```
+    pub fn fill_str(&mut self, label: &str)
+    where
+        V: Fill,
+    {
+        if let Some(index) = self.axes.index_str(label) {
+            self.values.entry(index).or_default().fill();
+        }
+    }
+
+    /// Fill the bin of a string category like [Histogram::fill_with], without
+    /// allocating a [String], see [StrAxis].
+    pub fn fill_with_str<D>(&mut self, label: &str, data: D)
+    where
+        V: FillWith<D>,
+    {
+        if let Some(index) = self.axes.index_str(label) {
+            self.values.entry(index).or_default().fill_with(data);
+        }
+    }
+
+    /// Fill the bin of a string category like [Histogram::fill_with_weighted], without
+    /// allocating a [String], see [StrAxis].
+    pub fn fill_with_weighted_str<D, W>(&mut self, label: &str, data: D, weight: W)
+    where
+        V: FillWithWeighted<D, W>,
+    {
+        if let Some(index) = self.axes.index_str(label) {
+            self.values
+                .entry(index)
+                .or_default()
+                .fill_with_weighted(data, weight);
+        }
+    }
+}
+
 impl<A: Axis, V: Default, S: BuildHasher> Histogram<A, V> for HashHistogram<A, V, S> {
     #[inline]
     fn axes(&self) -> &A {
@@ -208,19 +381,28 @@ where
     }
 }
 
+impl<A: Axis, V, S> HashHistogram<A, V, S>
+where
+    HashHistogram<A, V, S>: Histogram<A, V>,
+{
+    /// A report of the histogram with a table of the bin values, totals and means.
+    ///
+    /// See [Summary] for a description of the report.
+    pub fn summary(&self) -> Summary<'_, A, V, Self> {
+        Summary::new(self)
+    }
+}
+
 impl<A: Axis, V, S> Display for HashHistogram<A, V, S>
 where
     HashHistogram<A, V, S>: Histogram<A, V>,
-    V: Clone + Into<f64>,
+    V: DisplayValue,
     A::BinInterval: Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let sum = self
             .values()
-            .map(|it| {
-                let x: f64 = it.clone().into();
-                x
-            })
+            .map(DisplayValue::central_value)
             .fold(0.0, |it, value| it + value);
         write!(
             f,
@@ -264,9 +446,7 @@ macro_rules! impl_binary_op_with_immutable_borrow {
             /// # Ok(()) }
             /// ```
             fn $method(self, rhs: &HashHistogram<A, V, S>) -> Self::Output {
-                if self.axes() != rhs.axes() {
-                    return Err(crate::error::BinaryOperationError);
-                }
+                crate::axis::check_equal(self.axes(), rhs.axes())?;
                 let indices: HashSet<usize> = self.values.keys().chain(rhs.values.keys()).copied().collect();
                 let values: HashMap<usize, V, S> = indices.into_iter().map(|index| {
                     let left = self.values.get(&index);
@@ -326,9 +506,7 @@ macro_rules! impl_binary_op_with_owned {
             /// # Ok(()) }
             /// ```
             fn $method(mut self, rhs: &HashHistogram<A, V, S>) -> Self::Output {
-                if self.axes() != rhs.axes() {
-                    return Err(crate::error::BinaryOperationError);
-                }
+                crate::axis::check_equal(self.axes(), rhs.axes())?;
                 for (index, rhs_value) in rhs.values.iter() {
                     let lhs_value = self.values.entry(*index).or_default();
                     *lhs_value $assignmathsymbol rhs_value
@@ -345,7 +523,7 @@ impl_binary_op_with_owned! {Mul, mul, MulAssign, *, *=, 2.0}
 impl_binary_op_with_owned! {Div, div, DivAssign, /, /=, 2.0}
 
 macro_rules! impl_binary_op_assign {
-    ($Trait:tt, $method:tt, $ValueAssignTrait:tt, $mathsymbol:tt, $testresult:tt) => {
+    ($Trait:tt, $method:tt, $try_method:tt, $ValueAssignTrait:tt, $mathsymbol:tt, $testresult:tt) => {
         impl<A: Axis + PartialEq, V, S> $Trait<&HashHistogram<A, V, S>> for HashHistogram<A, V, S>
         where
             HashHistogram<A, V, S>: Histogram<A, V>,
@@ -361,7 +539,8 @@ macro_rules! impl_binary_op_assign {
             ///
             /// Panics if the histograms have incompatible axes.
             /// To handle this failure mode at runtime, use the non-assign
-            /// version of this operation, which returns an Result.
+            /// version of this operation, which returns an Result,
+            #[doc=concat!("or [HashHistogram::", stringify!($try_method), "].")]
             ///
             /// # Examples
             ///
@@ -377,22 +556,115 @@ macro_rules! impl_binary_op_assign {
             /// # Ok(()) }
             /// ```
             fn $method(&mut self, rhs: &HashHistogram<A, V, S>) {
-                if self.axes() != rhs.axes() {
-                    panic!("Cannot combine HashHistograms with incompatible axes.");
+                if let Err(error) = self.$try_method(rhs) {
+                    panic!("Cannot combine HashHistograms with incompatible axes: {error}");
                 }
+            }
+        }
+
+        impl<A: Axis + PartialEq, V, S> HashHistogram<A, V, S>
+        where
+            HashHistogram<A, V, S>: Histogram<A, V>,
+            V: Default,
+            S: BuildHasher,
+            for<'a> V: $ValueAssignTrait<&'a V>,
+        {
+            /// Combine the right-hand histogram with the left-hand histogram,
+            #[doc=concat!("mutating the left-hand histogram, as `", stringify!($mathsymbol), "` but without panicking.")]
+            ///
+            /// If the histograms have incompatible axes, a [BinaryOperationError](crate::error::BinaryOperationError)
+            /// is returned and the left-hand histogram is left unchanged.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use ndhistogram::{Histogram, sparsehistogram, axis::Uniform};
+            /// # fn main() -> Result<(), ndhistogram::Error> {
+            /// let mut hist1 = sparsehistogram!(Uniform::<f64>::new(10, -5.0, 5.0)?);
+            /// let mut hist2 = sparsehistogram!(Uniform::<f64>::new(10, -5.0, 5.0)?);
+            /// let hist3 = sparsehistogram!(Uniform::<f64>::new(10, -5.0, 6.0)?);
+            /// hist1.fill_with(&0.0, 2.0);
+            /// hist2.fill(&0.0);
+            #[doc=concat!("hist1.", stringify!($try_method), "(&hist2)?;")]
+            #[doc=concat!("assert_eq!(hist1.value(&0.0).unwrap(), &", stringify!($testresult), ");")]
+            #[doc=concat!("assert!(hist1.", stringify!($try_method), "(&hist3).is_err());")]
+            #[doc=concat!("assert_eq!(hist1.value(&0.0).unwrap(), &", stringify!($testresult), ");")]
+            /// # Ok(()) }
+            /// ```
+            pub fn $try_method(&mut self, rhs: &HashHistogram<A, V, S>) -> Result<(), crate::error::BinaryOperationError> {
+                crate::axis::check_equal(self.axes(), rhs.axes())?;
                 for (index, rhs_value) in rhs.values.iter() {
                     let lhs_value = self.values.entry(*index).or_default();
                     *lhs_value $mathsymbol rhs_value
                 }
+                Ok(())
             }
         }
     };
 }
 
-impl_binary_op_assign! {AddAssign, add_assign, AddAssign, +=, 3.0}
-impl_binary_op_assign! {SubAssign, sub_assign, SubAssign, -=, 1.0}
-impl_binary_op_assign! {MulAssign, mul_assign, MulAssign, *=, 2.0}
-impl_binary_op_assign! {DivAssign, div_assign, DivAssign, /=, 2.0}
+impl_binary_op_assign! {AddAssign, add_assign, try_add_assign, AddAssign, +=, 3.0}
+impl_binary_op_assign! {SubAssign, sub_assign, try_sub_assign, SubAssign, -=, 1.0}
+impl_binary_op_assign! {MulAssign, mul_assign, try_mul_assign, MulAssign, *=, 2.0}
+impl_binary_op_assign! {DivAssign, div_assign, try_div_assign, DivAssign, /=, 2.0}
+
+impl<A: Compatible, V, S> HashHistogram<A, V, S>
+where
+    HashHistogram<A, V, S>: Histogram<A, V>,
+    V: Default,
+    S: BuildHasher,
+    for<'a> V: AddAssign<&'a V>,
+{
+    /// Add the right-hand histogram to this histogram, accepting axes whose
+    /// bin edges are equal within the given tolerance.
+    ///
+    /// This is like [HashHistogram::try_add_assign] but the axes are compared
+    /// with [Compatible::is_compatible] rather than [PartialEq], so that
+    /// histograms whose bin edges differ only by floating point rounding can be merged.
+    /// The axes of this histogram are kept.
+    ///
+    /// If the axes are not compatible, a [BinaryOperationError](crate::error::BinaryOperationError)
+    /// is returned and this histogram is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ndhistogram::{Histogram, sparsehistogram, axis::Uniform, compare::Tolerance};
+    /// # fn main() -> Result<(), ndhistogram::Error> {
+    /// let mut hist1 = sparsehistogram!(Uniform::new(10, 0.0, 0.3)?);
+    /// let mut hist2 = sparsehistogram!(Uniform::new(10, 0.0, 0.1 + 0.2)?);
+    /// hist1.fill(&0.05);
+    /// hist2.fill(&0.05);
+    /// assert!(hist1.try_add_assign(&hist2).is_err());
+    /// hist1.merge_with_tolerance(&hist2, &Tolerance::default())?;
+    /// assert_eq!(hist1.value(&0.05), Some(&2.0));
+    /// # Ok(()) }
+    /// ```
+    pub fn merge_with_tolerance(
+        &mut self,
+        rhs: &HashHistogram<A, V, S>,
+        tolerance: &Tolerance,
+    ) -> Result<(), crate::error::BinaryOperationError> {
+        check_compatible_within(self.axes(), rhs.axes(), tolerance)?;
+        for (index, rhs_value) in rhs.values.iter() {
+            let lhs_value = self.values.entry(*index).or_default();
+            *lhs_value += rhs_value
+        }
+        Ok(())
+    }
+}
+
+impl<A: Axis + PartialEq, V, S> Merge for HashHistogram<A, V, S>
+where
+    HashHistogram<A, V, S>: Histogram<A, V>,
+    V: Default,
+    S: BuildHasher,
+    for<'a> V: AddAssign<&'a V>,
+{
+    fn merge(&mut self, other: &Self) -> Result<(), crate::error::BinaryOperationError> {
+        self.try_add_assign(other)
+    }
+}
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
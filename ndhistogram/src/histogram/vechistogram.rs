@@ -4,7 +4,15 @@ use std::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
 };
 
-use crate::{axis::Axis, error::AxisError, Axes};
+use crate::{
+    axes::grown_index,
+    axis::{check_compatible_within, Axis, Compatible, StrAxis},
+    compare::Tolerance,
+    display::{DisplayValue, Summary},
+    error::AxisError,
+    io::merge::Merge,
+    Axes, Fill, FillWith, FillWithWeighted,
+};
 
 use super::histogram::{Histogram, Item, Iter, IterMut, ValuesMut};
 
@@ -30,6 +38,177 @@ impl<A: Axis, V: Default + Clone> VecHistogram<A, V> {
     }
 }
 
+impl<A: Axes, V: Default + Clone> VecHistogram<A, V> {
+    /// Grow the axes so that the histogram contains the coordinate, see [Axis::grow].
+    ///
+    /// The existing bin values are kept and new bins have the default value.
+    /// Returns true if any axis grew.
+    /// [Histogram::fill] never grows the axes, see [VecHistogram::fill_growing] to
+    /// grow them while filling.
+    /// Axes that are not extendable never grow, so coordinates outside of them are still
+    /// not contained in the histogram.
+    ///
+    /// When only the last axis grows, the values are extended in place, so growing
+    /// one bin at a time takes amortised constant time.
+    /// When any other axis grows, all values are moved to their new index.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ndhistogram::{Histogram, ndhistogram, axis::{Axis, IntCategoryNoFlow, Uniform}};
+    /// # fn main() -> Result<(), ndhistogram::Error> {
+    /// let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0)?, IntCategoryNoFlow::extendable(vec![]); i32);
+    /// assert!(hist.grow(&(0.5, 7)));
+    /// assert!(!hist.grow(&(0.5, 7)));
+    /// assert_eq!(hist.value(&(0.5, 7)), Some(&0));
+    /// assert_eq!(hist.axes().num_bins(), 4);
+    /// # Ok(()) }
+    /// ```
+    pub fn grow(&mut self, coordinate: &A::Coordinate) -> bool {
+        let shape = self.axes.shape();
+        if !self.axes.grow(coordinate) {
+            return false;
+        }
+        let new_shape = self.axes.shape();
+        let last = shape.len() - 1;
+        if shape[..last] == new_shape[..last] {
+            // every bin keeps its index, so the new bins are only appended
+            self.values.resize(self.axes.num_bins(), V::default());
+            return true;
+        }
+        let mut values = vec![V::default(); self.axes.num_bins()];
+        for (index, value) in std::mem::take(&mut self.values).into_iter().enumerate() {
+            values[grown_index(index, &shape, &new_shape)] = value;
+        }
+        self.values = values;
+        true
+    }
+
+    /// Mutable access to the bin value at a coordinate, growing the axes if they do
+    /// not contain it.
+    fn value_mut_growing(&mut self, coordinate: &A::Coordinate) -> Option<&mut V> {
+        let index = match self.axes.index(coordinate) {
+            Some(index) => index,
+            None if self.grow(coordinate) => self.axes.index(coordinate)?,
+            None => return None,
+        };
+        self.values.get_mut(index)
+    }
+
+    /// Fill the histogram like [Histogram::fill], growing the axes first if they do not
+    /// contain the coordinate, see [VecHistogram::grow].
+    ///
+    /// The coordinate is only looked up once when the axes already contain it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ndhistogram::{Histogram, ndhistogram, axis::{Axis, IntCategoryNoFlow, Uniform}};
+    /// # fn main() -> Result<(), ndhistogram::Error> {
+    /// let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0)?, IntCategoryNoFlow::extendable(vec![]); i32);
+    /// for channel in [7, 3, 7] {
+    ///     hist.fill_growing(&(0.5, channel));
+    /// }
+    /// assert_eq!(hist.value(&(0.5, 7)), Some(&2));
+    /// assert_eq!(hist.value(&(0.5, 3)), Some(&1));
+    /// assert_eq!(hist.axes().num_bins(), 8);
+    /// # Ok(()) }
+    /// ```
+    pub fn fill_growing(&mut self, coordinate: &A::Coordinate)
+    where
+        V: Fill,
+    {
+        if let Some(value) = self.value_mut_growing(coordinate) {
+            value.fill()
+        }
+    }
+
+    /// Fill the histogram like [Histogram::fill_with], growing the axes first if they
+    /// do not contain the coordinate, see [VecHistogram::fill_growing].
+    pub fn fill_with_growing<D>(&mut self, coordinate: &A::Coordinate, data: D)
+    where
+        V: FillWith<D>,
+    {
+        if let Some(value) = self.value_mut_growing(coordinate) {
+            value.fill_with(data)
+        }
+    }
+
+    /// Fill the histogram like [Histogram::fill_with_weighted], growing the axes first
+    /// if they do not contain the coordinate, see [VecHistogram::fill_growing].
+    pub fn fill_with_weighted_growing<D, W>(
+        &mut self,
+        coordinate: &A::Coordinate,
+        data: D,
+        weight: W,
+    ) where
+        V: FillWithWeighted<D, W>,
+    {
+        if let Some(value) = self.value_mut_growing(coordinate) {
+            value.fill_with_weighted(data, weight)
+        }
+    }
+}
+
+impl<A: StrAxis, V> VecHistogram<A, V> {
+    /// The bin value of a string category, as [Histogram::value] without allocating
+    /// a [String], see [StrAxis].
+    pub fn value_str(&self, label: &str) -> Option<&V> {
+        self.values.get(self.axes.index_str(label)?)
+    }
+
+    /// Fill the bin of a string category like [Histogram::fill], without allocating
+    /// a [String], see [StrAxis].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ndhistogram::{Histogram, ndhistogram, axis::StrCategory};
+    /// let mut hist = ndhistogram!(StrCategory::new(["electron", "muon"]); u64);
+    /// let mut line = String::new();
+    /// for particle in ["muon", "electron", "muon"] {
+    ///     line.clear();
+    ///     line.push_str(particle);
+    ///     hist.fill_str(line.trim());
+    /// }
+    /// assert_eq!(hist.value_str("muon"), Some(&2));
+    /// ```
+    pub fn fill_str(&mut self, label: &str)
+    where
+        V: Fill,
+    {
+        if let Some(value) = self.value_str_mut(label) {
+            value.fill()
+        }
+    }
+
+    /// Fill the bin of a string category like [Histogram::fill_with], without
+    /// allocating a [String], see [StrAxis].
+    pub fn fill_with_str<D>(&mut self, label: &str, data: D)
+    where
+        V: FillWith<D>,
+    {
+        if let Some(value) = self.value_str_mut(label) {
+            value.fill_with(data)
+        }
+    }
+
+    /// Fill the bin of a string category like [Histogram::fill_with_weighted], without
+    /// allocating a [String], see [StrAxis].
+    pub fn fill_with_weighted_str<D, W>(&mut self, label: &str, data: D, weight: W)
+    where
+        V: FillWithWeighted<D, W>,
+    {
+        if let Some(value) = self.value_str_mut(label) {
+            value.fill_with_weighted(data, weight)
+        }
+    }
+
+    fn value_str_mut(&mut self, label: &str) -> Option<&mut V> {
+        self.values.get_mut(self.axes.index_str(label)?)
+    }
+}
+
 impl<A: Axis, V> Histogram<A, V> for VecHistogram<A, V> {
     fn value(&self, coordinate: &A::Coordinate) -> Option<&V> {
         let index = self.axes.index(coordinate)?;
@@ -99,9 +278,18 @@ impl<'a, A: Axis, V: 'a> IntoIterator for &'a mut VecHistogram<A, V> {
     }
 }
 
+impl<A: Axis, V> VecHistogram<A, V> {
+    /// A report of the histogram with a table of the bin values, totals and means.
+    ///
+    /// See [Summary] for a description of the report.
+    pub fn summary(&self) -> Summary<'_, A, V, Self> {
+        Summary::new(self)
+    }
+}
+
 impl<A: Axis, V> Display for VecHistogram<A, V>
 where
-    V: Clone + Into<f64>,
+    V: DisplayValue,
     A::BinInterval: Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -109,10 +297,7 @@ where
 
         let sum = self
             .values()
-            .map(|it| {
-                let x: f64 = it.clone().into();
-                x
-            })
+            .map(DisplayValue::central_value)
             .fold(0.0, |it, value| it + value);
         write!(
             f,
@@ -125,10 +310,11 @@ where
             .iter()
             .take(50)
             .map(|item| {
-                (item.bin, {
-                    let x: f64 = item.value.clone().into();
-                    x
-                })
+                (
+                    item.bin,
+                    item.value.central_value(),
+                    item.value.uncertainty(),
+                )
             })
             .collect();
         let scale = values
@@ -138,9 +324,17 @@ where
             .unwrap_or(f64::INFINITY);
         values
             .into_iter()
-            .map(|(bin, value)| (bin, 50.0 * (value / scale)))
-            .map(|(bin, value)| (format!("{bin:.precision$}"), "#".repeat(value as usize)))
-            .map(|(bin, value)| write!(f, "\n{bin:>16} | {value}"))
+            .map(|(bin, value, uncertainty)| {
+                let bar = "#".repeat((50.0 * (value / scale)) as usize);
+                (format!("{bin:.precision$}"), bar, value, uncertainty)
+            })
+            .map(|(bin, bar, value, uncertainty)| match uncertainty {
+                Some(uncertainty) => write!(
+                    f,
+                    "\n{bin:>16} | {bar:<50} {value:.precision$} ± {uncertainty:.precision$}"
+                ),
+                None => write!(f, "\n{bin:>16} | {bar}"),
+            })
             .filter_map(Result::ok)
             .count();
         Ok(())
@@ -175,9 +369,7 @@ where
     /// # Ok(()) }
     /// ```
     fn $method(self, rhs: &VecHistogram<A, V>) -> Self::Output {
-        if self.axes() != rhs.axes() {
-            return Err(crate::error::BinaryOperationError);
-        }
+        crate::axis::check_equal(self.axes(), rhs.axes())?;
         let values = self
             .values
             .iter()
@@ -256,9 +448,7 @@ macro_rules! impl_binary_op_with_owned {
             /// # Ok(()) }
             /// ```
             fn $method(mut self, rhs: &VecHistogram<A, V>) -> Self::Output {
-                if self.axes() != rhs.axes() {
-                    return Err(crate::error::BinaryOperationError);
-                }
+                crate::axis::check_equal(self.axes(), rhs.axes())?;
                 self.values
                     .iter_mut()
                     .zip(rhs.values.iter())
@@ -275,7 +465,7 @@ impl_binary_op_with_owned! {Mul, mul, MulAssign, *, *=, 2.0}
 impl_binary_op_with_owned! {Div, div, DivAssign, /, /=, 2.0}
 
 macro_rules! impl_binary_op_assign {
-    ($Trait:tt, $method:tt, $ValueAssignTrait:tt, $mathsymbol:tt, $testresult:tt) => {
+    ($Trait:tt, $method:tt, $try_method:tt, $ValueAssignTrait:tt, $mathsymbol:tt, $testresult:tt) => {
         impl<A: Axis + PartialEq, V> $Trait<&VecHistogram<A, V>> for VecHistogram<A, V>
         where
             for<'a> V: $ValueAssignTrait<&'a V>,
@@ -287,7 +477,8 @@ macro_rules! impl_binary_op_assign {
             ///
             /// Panics if the histograms have incompatible axes.
             /// To handle this failure mode at runtime, use the non-assign
-            /// version of this operation, which returns an Result.
+            /// version of this operation, which returns an Result,
+            #[doc=concat!("or [VecHistogram::", stringify!($try_method), "].")]
             ///
             /// # Examples
             ///
@@ -303,22 +494,106 @@ macro_rules! impl_binary_op_assign {
             /// # Ok(()) }
             /// ```
             fn $method(&mut self, rhs: &VecHistogram<A, V>) {
-                if self.axes() != rhs.axes() {
-                    panic!("Cannot combine VecHistograms with incompatible axes.");
+                if let Err(error) = self.$try_method(rhs) {
+                    panic!("Cannot combine VecHistograms with incompatible axes: {error}");
                 }
+            }
+        }
+
+        impl<A: Axis + PartialEq, V> VecHistogram<A, V>
+        where
+            for<'a> V: $ValueAssignTrait<&'a V>,
+        {
+            /// Combine the right-hand histogram with the left-hand histogram,
+            #[doc=concat!("mutating the left-hand histogram, as `", stringify!($mathsymbol), "` but without panicking.")]
+            ///
+            /// If the histograms have incompatible axes, a [BinaryOperationError](crate::error::BinaryOperationError)
+            /// is returned and the left-hand histogram is left unchanged.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use ndhistogram::{Histogram, ndhistogram, axis::Uniform};
+            /// # fn main() -> Result<(), ndhistogram::Error> {
+            /// let mut hist1 = ndhistogram!(Uniform::<f64>::new(10, -5.0, 5.0)?);
+            /// let mut hist2 = ndhistogram!(Uniform::<f64>::new(10, -5.0, 5.0)?);
+            /// let hist3 = ndhistogram!(Uniform::<f64>::new(10, -5.0, 6.0)?);
+            /// hist1.fill_with(&0.0, 2.0);
+            /// hist2.fill(&0.0);
+            #[doc=concat!("hist1.", stringify!($try_method), "(&hist2)?;")]
+            #[doc=concat!("assert_eq!(hist1.value(&0.0).unwrap(), &", stringify!($testresult), ");")]
+            #[doc=concat!("assert!(hist1.", stringify!($try_method), "(&hist3).is_err());")]
+            #[doc=concat!("assert_eq!(hist1.value(&0.0).unwrap(), &", stringify!($testresult), ");")]
+            /// # Ok(()) }
+            /// ```
+            pub fn $try_method(&mut self, rhs: &VecHistogram<A, V>) -> Result<(), crate::error::BinaryOperationError> {
+                crate::axis::check_equal(self.axes(), rhs.axes())?;
                 self.values
                     .iter_mut()
                     .zip(rhs.values.iter())
                     .for_each(|(l, r)| *l $mathsymbol &r);
+                Ok(())
             }
         }
     };
 }
 
-impl_binary_op_assign! {AddAssign, add_assign, AddAssign, +=, 3.0}
-impl_binary_op_assign! {SubAssign, sub_assign, SubAssign, -=, 1.0}
-impl_binary_op_assign! {MulAssign, mul_assign, MulAssign, *=, 2.0}
-impl_binary_op_assign! {DivAssign, div_assign, DivAssign, /=, 2.0}
+impl_binary_op_assign! {AddAssign, add_assign, try_add_assign, AddAssign, +=, 3.0}
+impl_binary_op_assign! {SubAssign, sub_assign, try_sub_assign, SubAssign, -=, 1.0}
+impl_binary_op_assign! {MulAssign, mul_assign, try_mul_assign, MulAssign, *=, 2.0}
+impl_binary_op_assign! {DivAssign, div_assign, try_div_assign, DivAssign, /=, 2.0}
+
+impl<A: Compatible, V> VecHistogram<A, V>
+where
+    for<'a> V: AddAssign<&'a V>,
+{
+    /// Add the right-hand histogram to this histogram, accepting axes whose
+    /// bin edges are equal within the given tolerance.
+    ///
+    /// This is like [VecHistogram::try_add_assign] but the axes are compared
+    /// with [Compatible::is_compatible] rather than [PartialEq], so that
+    /// histograms whose bin edges differ only by floating point rounding can be merged.
+    /// The axes of this histogram are kept.
+    ///
+    /// If the axes are not compatible, a [BinaryOperationError](crate::error::BinaryOperationError)
+    /// is returned and this histogram is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ndhistogram::{Histogram, ndhistogram, axis::Uniform, compare::Tolerance};
+    /// # fn main() -> Result<(), ndhistogram::Error> {
+    /// let mut hist1 = ndhistogram!(Uniform::new(10, 0.0, 0.3)?);
+    /// let mut hist2 = ndhistogram!(Uniform::new(10, 0.0, 0.1 + 0.2)?);
+    /// hist1.fill(&0.05);
+    /// hist2.fill(&0.05);
+    /// assert!(hist1.try_add_assign(&hist2).is_err());
+    /// hist1.merge_with_tolerance(&hist2, &Tolerance::default())?;
+    /// assert_eq!(hist1.value(&0.05), Some(&2.0));
+    /// # Ok(()) }
+    /// ```
+    pub fn merge_with_tolerance(
+        &mut self,
+        rhs: &VecHistogram<A, V>,
+        tolerance: &Tolerance,
+    ) -> Result<(), crate::error::BinaryOperationError> {
+        check_compatible_within(self.axes(), rhs.axes(), tolerance)?;
+        self.values
+            .iter_mut()
+            .zip(rhs.values.iter())
+            .for_each(|(l, r)| *l += r);
+        Ok(())
+    }
+}
+
+impl<A: Axis + PartialEq, V> Merge for VecHistogram<A, V>
+where
+    for<'a> V: AddAssign<&'a V>,
+{
+    fn merge(&mut self, other: &Self) -> Result<(), crate::error::BinaryOperationError> {
+        self.try_add_assign(other)
+    }
+}
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -366,6 +641,9 @@ impl<A, V> VecHistogram<A, V> {
     }
 
     /// Get a reference to the backing data as a slice.
+    ///
+    /// The values are ordered by bin index, with the first axis varying fastest
+    /// (see [Axes::shape]).
     pub fn as_slice(&self) -> &[V] {
         &self.values
     }
@@ -438,6 +716,70 @@ impl<A, V> VecHistogram<A, V> {
     }
 }
 
+impl<A: Axes, V> VecHistogram<A, V> {
+    /// An N-dimensional [ndarray view](ndarray::ArrayViewD) of the histogram values
+    /// with one dimension per axis.
+    ///
+    /// The length of each dimension is the [num_bins](Axis::num_bins) of the
+    /// corresponding axis, including the underflow and overflow bins.
+    ///
+    /// This requires the "ndarray" [crate feature](index.html#crate-feature-flags) to be enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ndhistogram::{ndhistogram, Histogram, axis::Uniform};
+    ///
+    /// # fn main() -> Result<(), ndhistogram::Error> {
+    /// let mut hist = ndhistogram!(Uniform::new(10, 0.0, 1.0)?, Uniform::new(5, 0.0, 1.0)?);
+    /// hist.fill(&(0.05, 0.5));
+    /// let view = hist.as_array_view();
+    /// assert_eq!(view.shape(), &[12, 7]);
+    /// assert_eq!(view[[1, 3]], 1.0);
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "ndarray")]
+    pub fn as_array_view(&self) -> ndarray::ArrayViewD<'_, V> {
+        use ndarray::ShapeBuilder;
+        ndarray::ArrayViewD::from_shape(ndarray::IxDyn(&self.axes.shape()).f(), &self.values)
+            .expect("number of values matches the axes shape")
+    }
+
+    /// A mutable N-dimensional [ndarray view](ndarray::ArrayViewMutD) of the histogram values.
+    ///
+    /// See [VecHistogram::as_array_view].
+    ///
+    /// This requires the "ndarray" [crate feature](index.html#crate-feature-flags) to be enabled.
+    #[cfg(feature = "ndarray")]
+    pub fn as_array_view_mut(&mut self) -> ndarray::ArrayViewMutD<'_, V> {
+        use ndarray::ShapeBuilder;
+        ndarray::ArrayViewMutD::from_shape(ndarray::IxDyn(&self.axes.shape()).f(), &mut self.values)
+            .expect("number of values matches the axes shape")
+    }
+
+    /// Construct a VecHistogram from an [ndarray::Array] and Axes.
+    ///
+    /// The array must have one dimension per axis, with the length of each
+    /// dimension equal to the [num_bins](Axis::num_bins) of the corresponding axis,
+    /// as returned by [VecHistogram::as_array_view].
+    ///
+    /// Returns ConversionError::ShapeMismatch if the array does not have this shape.
+    ///
+    /// This requires the "ndarray" [crate feature](index.html#crate-feature-flags) to be enabled.
+    #[cfg(feature = "ndarray")]
+    pub fn from_array<D: ndarray::Dimension>(
+        axes: A,
+        array: ndarray::Array<V, D>,
+    ) -> Result<Self, crate::Error> {
+        if array.shape() != axes.shape() {
+            return Err(crate::error::ConversionError::ShapeMismatch.into());
+        }
+        // reversing the axes puts the first axis fastest in logical order
+        let values = array.reversed_axes().into_iter().collect();
+        Ok(Self { axes, values })
+    }
+}
+
 impl<A, V> From<VecHistogram<A, V>> for Vec<V> {
     fn from(value: VecHistogram<A, V>) -> Self {
         value.values
@@ -0,0 +1,489 @@
+//! Conversion of histograms to and from Apache Arrow record batches
+//!
+//! [to_record_batch] converts any [Histogram] to a [RecordBatch] with one row per bin
+//! and the columns:
+//!
+//! - `index`: the bin index, as an unsigned 64-bit integer.
+//! - For each axis N, a struct column `axisN` with the bin on that axis:
+//!   the fields `start` and `end` for axes with [BinInterval]s, where underflow and
+//!   overflow bins have a start of `-inf` or an end of `inf`, respectively,
+//!   or the string field `value` for axes with [SingleValueBinInterval]s,
+//!   which is null for the overflow bin.
+//! - The bin value columns given by [ArrowValue::fields], for example
+//!   `sum` and `variance` for [WeightedSum] or `mean`, `error` and `count` for [Mean].
+//!
+//! The record batch may be written to Parquet files with the `ArrowWriter` of the
+//! [parquet](https://docs.rs/parquet) crate, or passed to any other Arrow-based tool.
+//!
+//! A [VecHistogram] is rebuilt from one or more record batches, such as those read back
+//! from a Parquet file, with [from_record_batches] given its axes.
+//! Only the `index` and value columns are used; the bin columns are informative.
+//! Bins that do not appear in the batches are set to their default value.
+//!
+//! Requires the "arrow" feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, Hist1D, axis::Uniform, value::WeightedSum};
+//! use ndhistogram::io::arrow::{from_record_batch, to_record_batch};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let axis = Uniform::new(2, 0.0, 2.0)?;
+//! let mut hist = ndhistogram!(axis.clone(); WeightedSum);
+//! hist.fill_with(&0.5, 3.0);
+//! let batch = to_record_batch(&hist)?;
+//! assert_eq!(batch.num_rows(), 4);
+//! assert_eq!(batch.schema().field(2).name(), "sum");
+//! let read: Hist1D<Uniform, WeightedSum> = from_record_batch((axis,).into(), &batch)?;
+//! assert_eq!(read, hist);
+//! # Ok(()) }
+//! ```
+
+use std::{fmt::Display, sync::Arc};
+
+use arrow_array::{
+    types::{
+        Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type,
+        UInt32Type, UInt64Type, UInt8Type,
+    },
+    Array, ArrayRef, Float64Array, PrimitiveArray, RecordBatch, StringArray, StructArray,
+    UInt64Array,
+};
+use arrow_schema::{ArrowError, DataType, Field, Fields, Schema};
+use num_traits::{Float, NumCast, Signed};
+
+use crate::{
+    axis::{Axis, BinInterval, SingleValueBinInterval},
+    error::ConversionError,
+    io::{mean_from_summary, weighted_mean_from_summary},
+    value::{Mean, Sum, WeightedMean, WeightedSum},
+    Axes, Histogram, Item, VecHistogram,
+};
+
+/// A bin interval that can be written as columns of a [RecordBatch].
+pub trait ArrowBin {
+    /// Append the fields of the columns of this bin.
+    ///
+    /// The axis number is used for single axis bins and ignored for tuples,
+    /// which number their axes from 0.
+    fn push_fields(axis: usize, fields: &mut Vec<Field>);
+
+    /// Append the columns that describe the bins, with one row per bin.
+    fn push_columns(bins: &[&Self], columns: &mut Vec<ArrayRef>);
+}
+
+fn interval_fields() -> Fields {
+    Fields::from(vec![
+        Field::new("start", DataType::Float64, false),
+        Field::new("end", DataType::Float64, false),
+    ])
+}
+
+impl<T: NumCast + Copy> ArrowBin for BinInterval<T> {
+    fn push_fields(axis: usize, fields: &mut Vec<Field>) {
+        fields.push(Field::new(
+            format!("axis{axis}"),
+            DataType::Struct(interval_fields()),
+            false,
+        ));
+    }
+
+    fn push_columns(bins: &[&Self], columns: &mut Vec<ArrayRef>) {
+        let to_f64 = |value: &T| value.to_f64().unwrap_or(f64::NAN);
+        let (start, end): (Vec<f64>, Vec<f64>) = bins
+            .iter()
+            .map(|bin| match bin {
+                Self::Underflow { end } => (f64::NEG_INFINITY, to_f64(end)),
+                Self::Overflow { start } => (to_f64(start), f64::INFINITY),
+                Self::Bin { start, end } => (to_f64(start), to_f64(end)),
+            })
+            .unzip();
+        columns.push(Arc::new(StructArray::new(
+            interval_fields(),
+            vec![
+                Arc::new(Float64Array::from(start)),
+                Arc::new(Float64Array::from(end)),
+            ],
+            None,
+        )));
+    }
+}
+
+fn single_value_fields() -> Fields {
+    Fields::from(vec![Field::new("value", DataType::Utf8, true)])
+}
+
+impl<T: Display> ArrowBin for SingleValueBinInterval<T> {
+    fn push_fields(axis: usize, fields: &mut Vec<Field>) {
+        fields.push(Field::new(
+            format!("axis{axis}"),
+            DataType::Struct(single_value_fields()),
+            false,
+        ));
+    }
+
+    fn push_columns(bins: &[&Self], columns: &mut Vec<ArrayRef>) {
+        let values: StringArray = bins
+            .iter()
+            .map(|bin| bin.value().map(ToString::to_string))
+            .collect();
+        columns.push(Arc::new(StructArray::new(
+            single_value_fields(),
+            vec![Arc::new(values)],
+            None,
+        )));
+    }
+}
+
+// Implements ArrowBin for every tuple from 2 elements up to the full list of type parameters.
+macro_rules! impl_arrow_bin {
+    ([$($done:ident: $done_index:tt),*] $next:ident: $next_index:tt $(, $rest:ident: $rest_index:tt)*) => {
+        impl_arrow_bin!(@IMPL $($done: $done_index,)* $next: $next_index);
+        impl_arrow_bin!([$($done: $done_index,)* $next: $next_index] $($rest: $rest_index),*);
+    };
+    ([$($done:ident: $done_index:tt),*]) => {};
+    (@IMPL $T:ident: $index:tt) => {};
+    (@IMPL $($T:ident: $index:tt),+) => {
+        impl<$($T: ArrowBin),+> ArrowBin for ($($T,)+) {
+            fn push_fields(_axis: usize, fields: &mut Vec<Field>) {
+                $($T::push_fields($index, fields);)+
+            }
+
+            fn push_columns(bins: &[&Self], columns: &mut Vec<ArrayRef>) {
+                $($T::push_columns(&bins.iter().map(|bin| &bin.$index).collect::<Vec<_>>(), columns);)+
+            }
+        }
+    };
+}
+
+impl_arrow_bin! {
+    [] X: 0,
+    Y: 1,
+    Z: 2,
+    T: 3,
+    D4: 4,
+    D5: 5,
+    D6: 6,
+    D7: 7,
+    D8: 8,
+    D9: 9,
+    D10: 10,
+    D11: 11,
+    D12: 12,
+    D13: 13,
+    D14: 14,
+    D15: 15,
+    D16: 16,
+    D17: 17,
+    D18: 18,
+    D19: 19,
+    D20: 20
+}
+
+/// A bin value that can be written to and read from columns of a [RecordBatch].
+pub trait ArrowValue: Sized {
+    /// Fields of the value columns.
+    fn fields() -> Vec<Field>;
+
+    /// One column for each field, with one row per value.
+    fn columns(values: &[&Self]) -> Vec<ArrayRef>;
+
+    /// Create a value from a row of the value columns.
+    ///
+    /// Returns None if the columns have the wrong type or the row is null.
+    fn from_columns(columns: &[&ArrayRef], row: usize) -> Option<Self>;
+}
+
+macro_rules! impl_arrow_value_for_primitive {
+    ($($T:ty => $ArrowType:ty),*) => {
+        $(
+            impl ArrowValue for $T {
+                fn fields() -> Vec<Field> {
+                    vec![Field::new("value", <$ArrowType as arrow_array::ArrowPrimitiveType>::DATA_TYPE, false)]
+                }
+
+                fn columns(values: &[&Self]) -> Vec<ArrayRef> {
+                    vec![Arc::new(PrimitiveArray::<$ArrowType>::from_iter_values(
+                        values.iter().map(|value| **value),
+                    ))]
+                }
+
+                fn from_columns(columns: &[&ArrayRef], row: usize) -> Option<Self> {
+                    let column = columns.first()?.as_any().downcast_ref::<PrimitiveArray<$ArrowType>>()?;
+                    column.is_valid(row).then(|| column.value(row))
+                }
+            }
+        )*
+    };
+}
+
+impl_arrow_value_for_primitive! {
+    i8 => Int8Type, i16 => Int16Type, i32 => Int32Type, i64 => Int64Type,
+    u8 => UInt8Type, u16 => UInt16Type, u32 => UInt32Type, u64 => UInt64Type,
+    f32 => Float32Type, f64 => Float64Type
+}
+
+impl<T: ArrowValue + Copy> ArrowValue for Sum<T> {
+    fn fields() -> Vec<Field> {
+        T::fields()
+    }
+
+    fn columns(values: &[&Self]) -> Vec<ArrayRef> {
+        let sums: Vec<T> = values.iter().map(|value| value.get()).collect();
+        T::columns(&sums.iter().collect::<Vec<_>>())
+    }
+
+    fn from_columns(columns: &[&ArrayRef], row: usize) -> Option<Self> {
+        T::from_columns(columns, row).map(Self::with_sum)
+    }
+}
+
+impl<T: ArrowValue + Copy> ArrowValue for WeightedSum<T> {
+    fn fields() -> Vec<Field> {
+        ["sum", "variance"]
+            .iter()
+            .flat_map(|name| T::fields().into_iter().map(|field| field.with_name(*name)))
+            .collect()
+    }
+
+    fn columns(values: &[&Self]) -> Vec<ArrayRef> {
+        let sums: Vec<T> = values.iter().map(|value| value.sum()).collect();
+        let variances: Vec<T> = values.iter().map(|value| value.variance()).collect();
+        let mut columns = T::columns(&sums.iter().collect::<Vec<_>>());
+        columns.extend(T::columns(&variances.iter().collect::<Vec<_>>()));
+        columns
+    }
+
+    fn from_columns(columns: &[&ArrayRef], row: usize) -> Option<Self> {
+        match columns {
+            [sum, variance] => Some(Self::with_sum_and_variance(
+                T::from_columns(&[sum], row)?,
+                T::from_columns(&[variance], row)?,
+            )),
+            _ => None,
+        }
+    }
+}
+
+fn float64_fields(names: &[&str]) -> Vec<Field> {
+    names
+        .iter()
+        .map(|name| Field::new(*name, DataType::Float64, false))
+        .collect()
+}
+
+fn float64_columns<V>(values: &[&V], getters: &[&dyn Fn(&V) -> f64]) -> Vec<ArrayRef> {
+    getters
+        .iter()
+        .map(|getter| {
+            Arc::new(Float64Array::from_iter_values(
+                values.iter().map(|value| getter(value)),
+            )) as ArrayRef
+        })
+        .collect()
+}
+
+fn float64_row(columns: &[&ArrayRef], row: usize) -> Option<Vec<f64>> {
+    columns
+        .iter()
+        .map(|column| {
+            let column = column.as_any().downcast_ref::<Float64Array>()?;
+            column.is_valid(row).then(|| column.value(row))
+        })
+        .collect()
+}
+
+fn to_f64<T: NumCast>(value: T) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// The Mean is read back from its mean, standard error of the mean and number of samples,
+/// so values read from a record batch may differ from the original by floating point rounding
+/// errors.
+impl<T, O, C> ArrowValue for Mean<T, O, C>
+where
+    T: NumCast + Copy,
+    O: From<T> + From<C> + Float + Signed,
+    C: NumCast + Copy,
+{
+    fn fields() -> Vec<Field> {
+        float64_fields(&["mean", "error", "count"])
+    }
+
+    fn columns(values: &[&Self]) -> Vec<ArrayRef> {
+        float64_columns(
+            values,
+            &[
+                &|value: &Self| to_f64(value.get()),
+                &|value: &Self| to_f64(value.standard_error_of_mean()),
+                &|value: &Self| to_f64(value.num_samples()),
+            ],
+        )
+    }
+
+    fn from_columns(columns: &[&ArrayRef], row: usize) -> Option<Self> {
+        let [mean, error, count] = float64_row(columns, row)?[..] else {
+            return None;
+        };
+        mean_from_summary(mean, error, count).ok()
+    }
+}
+
+/// The WeightedMean is read back from its mean, standard error of the mean, sums of weights and
+/// number of samples, so values read from a record batch may differ from the original by floating
+/// point rounding errors.
+impl<T, W, O, C> ArrowValue for WeightedMean<T, W, O, C>
+where
+    T: NumCast + Copy,
+    W: NumCast + Copy,
+    O: From<T> + From<W> + From<C> + Float + Signed,
+    C: NumCast + Copy,
+{
+    fn fields() -> Vec<Field> {
+        float64_fields(&[
+            "mean",
+            "error",
+            "sum_of_weights",
+            "sum_of_weights_squared",
+            "count",
+        ])
+    }
+
+    fn columns(values: &[&Self]) -> Vec<ArrayRef> {
+        float64_columns(
+            values,
+            &[
+                &|value: &Self| to_f64(value.get()),
+                &|value: &Self| to_f64(value.standard_error_of_mean()),
+                &|value: &Self| to_f64(value.sum_of_weights()),
+                &|value: &Self| to_f64(value.sum_of_weights_squared()),
+                &|value: &Self| to_f64(value.num_samples()),
+            ],
+        )
+    }
+
+    fn from_columns(columns: &[&ArrayRef], row: usize) -> Option<Self> {
+        let [mean, error, sumw, sumw2, count] = float64_row(columns, row)?[..] else {
+            return None;
+        };
+        weighted_mean_from_summary(mean, error, sumw, sumw2, count).ok()
+    }
+}
+
+/// The Arrow schema of the record batches of histograms with axes A and values V.
+///
+/// See the [module documentation](self) for a description of the columns.
+pub fn schema<A, V>() -> Schema
+where
+    A: Axis,
+    A::BinInterval: ArrowBin,
+    V: ArrowValue,
+{
+    let mut fields = vec![Field::new("index", DataType::UInt64, false)];
+    A::BinInterval::push_fields(0, &mut fields);
+    fields.extend(V::fields());
+    Schema::new(fields)
+}
+
+/// Convert a histogram to a record batch with one row per bin.
+///
+/// Rows are in order of increasing bin index.
+/// See the [module documentation](self) for a description of the columns.
+pub fn to_record_batch<A, V, H>(hist: &H) -> Result<RecordBatch, ArrowError>
+where
+    H: Histogram<A, V>,
+    A: Axis,
+    A::BinInterval: ArrowBin,
+    V: ArrowValue,
+{
+    let mut items: Vec<Item<A::BinInterval, &V>> = hist.iter().collect();
+    items.sort_by_key(|item| item.index);
+    let indices: UInt64Array = items.iter().map(|item| Some(item.index as u64)).collect();
+    let mut columns: Vec<ArrayRef> = vec![Arc::new(indices)];
+    A::BinInterval::push_columns(
+        &items.iter().map(|item| &item.bin).collect::<Vec<_>>(),
+        &mut columns,
+    );
+    columns.extend(V::columns(
+        &items.iter().map(|item| item.value).collect::<Vec<_>>(),
+    ));
+    RecordBatch::try_new(Arc::new(schema::<A, V>()), columns)
+}
+
+/// Read a histogram with the given axes from a record batch.
+///
+/// See [from_record_batches].
+pub fn from_record_batch<A, V>(
+    axes: A,
+    batch: &RecordBatch,
+) -> Result<VecHistogram<A, V>, ConversionError>
+where
+    A: Axes,
+    A::BinInterval: ArrowBin,
+    V: ArrowValue + Default,
+{
+    from_record_batches(axes, [batch])
+}
+
+/// Read a histogram with the given axes from a sequence of record batches.
+///
+/// Returns [ConversionError::InvalidColumn] if the name or data type of a column does not
+/// match the [schema] for the axes and value type, and [ConversionError::InvalidRow] if a row
+/// has an invalid or repeated bin index or null values.
+/// Rows are numbered from 0 across all batches.
+/// See the [module documentation](self) for a description of the columns.
+pub fn from_record_batches<'a, A, V>(
+    axes: A,
+    batches: impl IntoIterator<Item = &'a RecordBatch>,
+) -> Result<VecHistogram<A, V>, ConversionError>
+where
+    A: Axes,
+    A::BinInterval: ArrowBin,
+    V: ArrowValue + Default,
+{
+    let expected = schema::<A, V>();
+    let num_value_columns = V::fields().len();
+    let mut values: Vec<Option<V>> = (0..axes.num_bins()).map(|_| None).collect();
+    let mut first_row = 0;
+    for batch in batches {
+        check_schema(&expected, &batch.schema())?;
+        let indices = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .ok_or(ConversionError::InvalidColumn(0))?;
+        let value_columns: Vec<&ArrayRef> = batch.columns()
+            [batch.num_columns() - num_value_columns..]
+            .iter()
+            .collect();
+        for row in 0..batch.num_rows() {
+            let invalid = ConversionError::InvalidRow(first_row + row);
+            let value = indices
+                .is_valid(row)
+                .then(|| indices.value(row))
+                .and_then(|index| values.get_mut(usize::try_from(index).ok()?))
+                .filter(|value| value.is_none())
+                .ok_or(invalid)?;
+            *value = Some(V::from_columns(&value_columns, row).ok_or(invalid)?);
+        }
+        first_row += batch.num_rows();
+    }
+    let values = values.into_iter().map(Option::unwrap_or_default).collect();
+    VecHistogram::from_vec(axes, values).map_err(|_| ConversionError::ShapeMismatch)
+}
+
+fn check_schema(expected: &Schema, actual: &Schema) -> Result<(), ConversionError> {
+    for (position, expected) in expected.fields().iter().enumerate() {
+        match actual.fields().get(position) {
+            Some(actual)
+                if actual.name() == expected.name()
+                    && actual.data_type().equals_datatype(expected.data_type()) => {}
+            _ => return Err(ConversionError::InvalidColumn(position)),
+        }
+    }
+    if actual.fields().len() > expected.fields().len() {
+        return Err(ConversionError::InvalidColumn(expected.fields().len()));
+    }
+    Ok(())
+}
@@ -0,0 +1,410 @@
+use num_traits::{Float, PrimInt};
+
+use crate::{
+    axis::{
+        Axis, Boolean, Category, CategoryNoFlow, HexagonOrientation, Hexagonal, IntCategory,
+        IntCategoryNoFlow, OrderedCategory, OrderedCategoryNoFlow, StrCategory, StrCategoryNoFlow,
+        Uniform, UniformCyclic, UniformNoFlow, Variable, VariableCyclic, VariableNoFlow,
+    },
+    error::ConversionError,
+    Axes, AxesTuple,
+};
+
+use super::value::BinaryElement;
+
+/// Codes of the axis types in the format.
+const UNIFORM: u8 = 1;
+const UNIFORM_NO_FLOW: u8 = 2;
+const UNIFORM_CYCLIC: u8 = 3;
+const VARIABLE: u8 = 4;
+const VARIABLE_NO_FLOW: u8 = 5;
+const VARIABLE_CYCLIC: u8 = 6;
+const CATEGORY: u8 = 7;
+const CATEGORY_NO_FLOW: u8 = 8;
+const BOOLEAN: u8 = 9;
+const INT_CATEGORY: u8 = 10;
+const INT_CATEGORY_NO_FLOW: u8 = 11;
+const INT_CATEGORY_EXTENDABLE: u8 = 12;
+const ORDERED_CATEGORY: u8 = 13;
+const ORDERED_CATEGORY_NO_FLOW: u8 = 14;
+const HEXAGONAL: u8 = 15;
+const HEXAGONAL_FLAT_TOP: u8 = 16;
+
+/// Element code of string categories.
+const STRING: u8 = 16;
+/// Element code of axes without parameters.
+const NONE: u8 = 0;
+
+/// An [Axis] that can be written to and read from the binary format.
+///
+/// Each axis is stored as a code of the axis type and the element code of its
+/// parameters, followed by the parameters:
+/// the number of bins and range of uniform axes, the bin edges of variable axes
+/// and the values of category axes. A [Boolean] axis has no parameters.
+/// An extendable [IntCategoryNoFlow] has its own code, as does a [Hexagonal] axis of
+/// each orientation, which stores its size and x and y ranges.
+/// A [StrCategory] is stored like a [Category] of [String], so either may be read as the other.
+pub trait BinaryAxis: Axis + Sized {
+    /// Append the description of the axis.
+    fn write_axis(&self, out: &mut Vec<u8>);
+
+    /// Read an axis from the front of the input, advancing the input past it.
+    ///
+    /// Returns [ConversionError::IncompatibleAxis] if the stored axis has a different type.
+    /// The axis number is always 0 and is updated by [BinaryAxes::read_axes].
+    fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError>;
+}
+
+/// A set of [Axes] that can be written to and read from the binary format.
+///
+/// This is implemented for [AxesTuple] of axes that implement [BinaryAxis].
+pub trait BinaryAxes: Axes + Sized {
+    /// Append the number of axes followed by the description of each axis.
+    fn write_axes(&self, out: &mut Vec<u8>);
+
+    /// Read the axes from the front of the input, advancing the input past them.
+    fn read_axes(input: &mut &[u8]) -> Result<Self, ConversionError>;
+}
+
+/// Take the next bytes of the input.
+pub(crate) fn take<'a>(input: &mut &'a [u8], size: usize) -> Result<&'a [u8], ConversionError> {
+    if input.len() < size {
+        return Err(ConversionError::InvalidData);
+    }
+    let (bytes, rest) = input.split_at(size);
+    *input = rest;
+    Ok(bytes)
+}
+
+/// Read the next element of the input.
+pub(crate) fn read<T: BinaryElement>(input: &mut &[u8]) -> Result<T, ConversionError> {
+    T::read_le(take(input, T::SIZE)?).ok_or(ConversionError::ValueOutOfRange)
+}
+
+fn write_length(length: usize, out: &mut Vec<u8>) {
+    (length as u64).write_le(out);
+}
+
+fn read_length(input: &mut &[u8]) -> Result<usize, ConversionError> {
+    read::<u64>(input)?
+        .try_into()
+        .map_err(|_| ConversionError::InvalidData)
+}
+
+/// Check the axis and element codes at the front of the input.
+fn read_header(input: &mut &[u8], axis: u8, element: u8) -> Result<(), ConversionError> {
+    let codes = take(input, 2)?;
+    if codes != [axis, element] {
+        return Err(ConversionError::IncompatibleAxis(0));
+    }
+    Ok(())
+}
+
+fn write_uniform<T: BinaryElement>(axis: u8, num: usize, low: T, high: T, out: &mut Vec<u8>) {
+    out.extend_from_slice(&[axis, T::CODE]);
+    write_length(num, out);
+    low.write_le(out);
+    high.write_le(out);
+}
+
+fn read_uniform<T: BinaryElement>(
+    axis: u8,
+    input: &mut &[u8],
+) -> Result<(usize, T, T), ConversionError> {
+    read_header(input, axis, T::CODE)?;
+    Ok((read_length(input)?, read(input)?, read(input)?))
+}
+
+fn write_elements<'a, T: BinaryElement + 'a>(
+    axis: u8,
+    elements: impl ExactSizeIterator<Item = &'a T>,
+    out: &mut Vec<u8>,
+) {
+    out.extend_from_slice(&[axis, T::CODE]);
+    write_length(elements.len(), out);
+    elements.for_each(|it| it.write_le(out));
+}
+
+fn read_elements<T: BinaryElement>(axis: u8, input: &mut &[u8]) -> Result<Vec<T>, ConversionError> {
+    read_header(input, axis, T::CODE)?;
+    let length = read_length(input)?;
+    if length > input.len() / T::SIZE.max(1) {
+        return Err(ConversionError::InvalidData);
+    }
+    (0..length).map(|_| read(input)).collect()
+}
+
+fn write_strings(
+    axis: u8,
+    strings: impl ExactSizeIterator<Item = impl AsRef<str>>,
+    out: &mut Vec<u8>,
+) {
+    out.extend_from_slice(&[axis, STRING]);
+    write_length(strings.len(), out);
+    for string in strings {
+        let string = string.as_ref();
+        write_length(string.len(), out);
+        out.extend_from_slice(string.as_bytes());
+    }
+}
+
+fn read_strings(axis: u8, input: &mut &[u8]) -> Result<Vec<String>, ConversionError> {
+    read_header(input, axis, STRING)?;
+    let length = read_length(input)?;
+    if length > input.len() / 8 {
+        return Err(ConversionError::InvalidData);
+    }
+    (0..length)
+        .map(|_| {
+            let size = read_length(input)?;
+            String::from_utf8(take(input, size)?.to_vec()).map_err(|_| ConversionError::InvalidData)
+        })
+        .collect()
+}
+
+macro_rules! impl_binary_axis_for_uniform {
+    ($($Axis:ident: $code:expr, $num_flow_bins:expr),*) => {
+        $(
+            impl<T: Float + BinaryElement> BinaryAxis for $Axis<T> {
+                fn write_axis(&self, out: &mut Vec<u8>) {
+                    let num = self.num_bins() - $num_flow_bins;
+                    write_uniform($code, num, *self.low(), *self.high(), out);
+                }
+
+                fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError> {
+                    let (num, low, high) = read_uniform($code, input)?;
+                    Ok(Self::new(num, low, high)?)
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_axis_for_uniform! {
+    Uniform: UNIFORM, 2,
+    UniformNoFlow: UNIFORM_NO_FLOW, 0,
+    UniformCyclic: UNIFORM_CYCLIC, 0
+}
+
+macro_rules! impl_binary_axis_for_variable {
+    ($($Axis:ident: $code:expr),*) => {
+        $(
+            impl<T> BinaryAxis for $Axis<T>
+            where
+                T: PartialOrd + BinaryElement,
+                Self: Axis,
+            {
+                fn write_axis(&self, out: &mut Vec<u8>) {
+                    write_elements($code, self.bin_edges().iter(), out);
+                }
+
+                fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError> {
+                    Ok(Self::new(read_elements($code, input)?)?)
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_axis_for_variable! {
+    Variable: VARIABLE,
+    VariableNoFlow: VARIABLE_NO_FLOW,
+    VariableCyclic: VARIABLE_CYCLIC
+}
+
+macro_rules! impl_binary_axis_for_category {
+    ($bound:tt $($Axis:ident: $code:expr),*) => {
+        $(impl_binary_axis_for_category!(@IMPL $bound $Axis: $code);)*
+    };
+    (@IMPL [$($bound:tt)+] $Axis:ident: $code:expr) => {
+        impl BinaryAxis for $Axis<String> {
+            fn write_axis(&self, out: &mut Vec<u8>) {
+                let categories: Vec<_> = self.categories().collect();
+                write_strings($code, categories.into_iter(), out);
+            }
+
+            fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError> {
+                Ok(Self::new(read_strings($code, input)?))
+            }
+        }
+
+        impl<T> BinaryAxis for $Axis<T>
+        where
+            T: BinaryElement + $($bound)+,
+        {
+            fn write_axis(&self, out: &mut Vec<u8>) {
+                let categories: Vec<_> = self.categories().collect();
+                write_elements($code, categories.into_iter(), out);
+            }
+
+            fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError> {
+                Ok(Self::new(read_elements::<T>($code, input)?))
+            }
+        }
+    };
+}
+
+impl_binary_axis_for_category! {
+    [Eq + std::hash::Hash]
+    Category: CATEGORY,
+    CategoryNoFlow: CATEGORY_NO_FLOW
+}
+
+impl_binary_axis_for_category! {
+    [Ord]
+    OrderedCategory: ORDERED_CATEGORY,
+    OrderedCategoryNoFlow: ORDERED_CATEGORY_NO_FLOW
+}
+
+// String categories are written like a category of strings, so either may be read back.
+macro_rules! impl_binary_axis_for_str_category {
+    ($($Axis:ident: $code:expr),*) => {
+        $(
+            impl BinaryAxis for $Axis {
+                fn write_axis(&self, out: &mut Vec<u8>) {
+                    write_strings($code, self.categories(), out);
+                }
+
+                fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError> {
+                    Ok(Self::new(read_strings($code, input)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_axis_for_str_category! {
+    StrCategory: CATEGORY,
+    StrCategoryNoFlow: CATEGORY_NO_FLOW
+}
+
+impl<T: PrimInt + BinaryElement> BinaryAxis for IntCategory<T> {
+    fn write_axis(&self, out: &mut Vec<u8>) {
+        write_elements(INT_CATEGORY, self.categories(), out);
+    }
+
+    fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError> {
+        Ok(Self::new(read_elements::<T>(INT_CATEGORY, input)?))
+    }
+}
+
+impl<T: PrimInt + BinaryElement> BinaryAxis for IntCategoryNoFlow<T> {
+    fn write_axis(&self, out: &mut Vec<u8>) {
+        let code = if self.is_extendable() {
+            INT_CATEGORY_EXTENDABLE
+        } else {
+            INT_CATEGORY_NO_FLOW
+        };
+        write_elements(code, self.categories(), out);
+    }
+
+    fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError> {
+        if input.first() == Some(&INT_CATEGORY_EXTENDABLE) {
+            Ok(Self::extendable(read_elements::<T>(
+                INT_CATEGORY_EXTENDABLE,
+                input,
+            )?))
+        } else {
+            Ok(Self::new(read_elements::<T>(INT_CATEGORY_NO_FLOW, input)?))
+        }
+    }
+}
+
+impl BinaryAxis for Boolean {
+    fn write_axis(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&[BOOLEAN, NONE]);
+    }
+
+    fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError> {
+        read_header(input, BOOLEAN, NONE)?;
+        Ok(Self::new())
+    }
+}
+
+impl<T: Float + BinaryElement> BinaryAxis for Hexagonal<T> {
+    fn write_axis(&self, out: &mut Vec<u8>) {
+        let code = match self.orientation() {
+            HexagonOrientation::PointyTop => HEXAGONAL,
+            HexagonOrientation::FlatTop => HEXAGONAL_FLAT_TOP,
+        };
+        let (x, y) = (self.x_range(), self.y_range());
+        write_elements(code, [self.size(), x.0, x.1, y.0, y.1].iter(), out);
+    }
+
+    fn read_axis(input: &mut &[u8]) -> Result<Self, ConversionError> {
+        let (code, orientation) = if input.first() == Some(&HEXAGONAL_FLAT_TOP) {
+            (HEXAGONAL_FLAT_TOP, HexagonOrientation::FlatTop)
+        } else {
+            (HEXAGONAL, HexagonOrientation::PointyTop)
+        };
+        match read_elements::<T>(code, input)?[..] {
+            [size, x_low, x_high, y_low, y_high] => Ok(Self::new(
+                size,
+                orientation,
+                (x_low, x_high),
+                (y_low, y_high),
+            )?),
+            _ => Err(ConversionError::InvalidData),
+        }
+    }
+}
+
+// Implements BinaryAxes for every AxesTuple from 1 dimension up to the full list of type parameters.
+macro_rules! impl_binary_axes {
+    ([$($done:ident: $done_index:tt),*] $next:ident: $next_index:tt $(, $rest:ident: $rest_index:tt)*) => {
+        impl_binary_axes!(@IMPL $($done: $done_index,)* $next: $next_index);
+        impl_binary_axes!([$($done: $done_index,)* $next: $next_index] $($rest: $rest_index),*);
+    };
+    ([$($done:ident: $done_index:tt),*]) => {};
+    (@IMPL $($T:ident: $index:tt),+) => {
+        impl<$($T: BinaryAxis),+> BinaryAxes for AxesTuple<($($T,)+)>
+        where
+            Self: Axes,
+        {
+            fn write_axes(&self, out: &mut Vec<u8>) {
+                let axes = self.as_tuple();
+                write_length([$($index),+].len(), out);
+                $(axes.$index.write_axis(out);)+
+            }
+
+            fn read_axes(input: &mut &[u8]) -> Result<Self, ConversionError> {
+                let expected = [$($index),+].len();
+                let actual = read_length(input)?;
+                if actual != expected {
+                    return Err(ConversionError::WrongNumberOfAxes { expected, actual });
+                }
+                Ok(($(
+                    $T::read_axis(input).map_err(|error| match error {
+                        ConversionError::IncompatibleAxis(_) => ConversionError::IncompatibleAxis($index),
+                        other => other,
+                    })?,
+                )+)
+                    .into())
+            }
+        }
+    };
+}
+
+impl_binary_axes! {
+    [] X: 0,
+    Y: 1,
+    Z: 2,
+    T: 3,
+    D4: 4,
+    D5: 5,
+    D6: 6,
+    D7: 7,
+    D8: 8,
+    D9: 9,
+    D10: 10,
+    D11: 11,
+    D12: 12,
+    D13: 13,
+    D14: 14,
+    D15: 15,
+    D16: 16,
+    D17: 17,
+    D18: 18,
+    D19: 19,
+    D20: 20
+}
@@ -0,0 +1,494 @@
+//! A compact, versioned binary format for histograms
+//!
+//! [write_binary] writes a histogram to any [io::Write] and [read_binary] reads it back
+//! from any [io::Read] into a [VecHistogram], streaming the bin values in blocks so that
+//! histograms with millions of bins are never held twice in memory.
+//! [merge_binary] adds up histograms in the binary format without knowing the types of
+//! their axes and values, which is what the `ndhist-merge` command line tool does.
+//!
+//! The format is stable: files written by this version of ndhistogram can be read by all
+//! later versions. All numbers are little-endian.
+//!
+//! | Field        | Size           | Content                                                        |
+//! |--------------|----------------|----------------------------------------------------------------|
+//! | magic number | 8 bytes        | `\x89NDH\r\n\x1a\n`                                            |
+//! | version      | u16            | format version, currently 1                                    |
+//! | flags        | u16            | bit 0 is set if the values are run-length encoded              |
+//! | axes length  | u64            | number of bytes of the axes                                    |
+//! | axes         | axes length    | number of axes (u64), then each axis (see [BinaryAxis])        |
+//! | value layout | 4 bytes        | kind of bin value and its element codes (see [BinaryValue])    |
+//! | num values   | u64            | number of bins, including flow bins                            |
+//! | values       | rest of stream | bin values in order of bin index                               |
+//!
+//! Without run-length encoding the values are stored one after the other.
+//! With run-length encoding they are stored as a sequence of runs, each made of the number
+//! of empty values (u64), the number of stored values (u64) and the stored values.
+//! At least one of the two numbers of each run is non-zero.
+//! A value is empty if all of its stored bytes are zero, as for the default value of all
+//! of the types in [value](crate::value).
+//! Run-length encoding makes histograms with many empty bins much smaller, at the cost of
+//! 16 bytes for each run of non-empty bins.
+//!
+//! Bins that are not stored by a sparse histogram are written as empty values.
+//!
+//! Errors in the input, such as a wrong magic number or an axis of a different type than
+//! the requested histogram, are returned as an [io::Error] of kind
+//! [io::ErrorKind::InvalidData] containing a [ConversionError].
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, Hist2D, axis::{Uniform, Variable}, value::WeightedSum};
+//! use ndhistogram::io::binary::{read_binary, write_binary};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut hist = ndhistogram!(Uniform::new(1000, 0.0, 1.0)?, Variable::new(vec![0.0, 1.0, 10.0])?; WeightedSum);
+//! hist.fill_with(&(0.5, 5.0), 2.0);
+//! let mut bytes = Vec::new();
+//! write_binary(&hist, true, &mut bytes)?;
+//! assert!(bytes.len() < 200);
+//! let read: Hist2D<Uniform, Variable, WeightedSum> = read_binary(bytes.as_slice())?;
+//! assert_eq!(read, hist);
+//! # Ok(()) }
+//! ```
+
+mod axis;
+mod value;
+
+pub use axis::{BinaryAxes, BinaryAxis};
+pub use value::{BinaryElement, BinaryValue};
+
+use std::io::{self, Read};
+
+use crate::{
+    error::{BinaryOperationError, ConversionError},
+    Histogram, VecHistogram,
+};
+
+use axis::{read, take};
+
+/// Magic number at the start of the format.
+const MAGIC: [u8; 8] = *b"\x89NDH\r\n\x1a\n";
+/// Current version of the format.
+const VERSION: u16 = 1;
+/// Flag of run-length encoded values.
+const RUN_LENGTH_ENCODED: u16 = 1;
+/// Number of bytes of values written or read at a time.
+const BLOCK_SIZE: usize = 1 << 16;
+
+fn invalid_data(error: ConversionError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Reserve space for a number of additional elements, returning an error rather than
+/// aborting if they do not fit in memory.
+///
+/// The input may declare any number of bins, so buffers are only grown as the values
+/// are read, and never by the number of bins in the header.
+fn try_reserve<T>(buffer: &mut Vec<T>, additional: usize) -> io::Result<()> {
+    buffer
+        .try_reserve(additional)
+        .map_err(|error| io::Error::new(io::ErrorKind::OutOfMemory, error))
+}
+
+/// Everything in the format before the bin values.
+struct Header {
+    flags: u16,
+    axes: Vec<u8>,
+    layout: [u8; 4],
+    num_values: usize,
+}
+
+impl Header {
+    fn new(run_length_encoding: bool, axes: Vec<u8>, layout: [u8; 4], num_values: usize) -> Self {
+        let flags = if run_length_encoding {
+            RUN_LENGTH_ENCODED
+        } else {
+            0
+        };
+        Self {
+            flags,
+            axes,
+            layout,
+            num_values,
+        }
+    }
+
+    fn write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut header = MAGIC.to_vec();
+        VERSION.write_le(&mut header);
+        self.flags.write_le(&mut header);
+        (self.axes.len() as u64).write_le(&mut header);
+        header.extend_from_slice(&self.axes);
+        header.extend_from_slice(&self.layout);
+        (self.num_values as u64).write_le(&mut header);
+        writer.write_all(&header)
+    }
+
+    fn read<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let mut start = [0; 20];
+        reader.read_exact(&mut start)?;
+        let mut input = &start[..];
+        if take(&mut input, MAGIC.len()).map_err(invalid_data)? != MAGIC {
+            return Err(invalid_data(ConversionError::InvalidData));
+        }
+        let version: u16 = read(&mut input).map_err(invalid_data)?;
+        let flags: u16 = read(&mut input).map_err(invalid_data)?;
+        if version != VERSION || flags & !RUN_LENGTH_ENCODED != 0 {
+            return Err(invalid_data(ConversionError::UnsupportedVersion));
+        }
+        let axes_length: u64 = read(&mut input).map_err(invalid_data)?;
+
+        let mut axes = Vec::new();
+        reader.take(axes_length).read_to_end(&mut axes)?;
+        if axes.len() as u64 != axes_length {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let mut end = [0; 12];
+        reader.read_exact(&mut end)?;
+        let (layout, num_values) = end.split_at(4);
+        let num_values = u64::read_le(num_values)
+            .and_then(|it| usize::try_from(it).ok())
+            .ok_or_else(|| invalid_data(ConversionError::InvalidData))?;
+        Ok(Self {
+            flags,
+            axes,
+            layout: layout.try_into().expect("layout has 4 bytes"),
+            num_values,
+        })
+    }
+
+    fn is_run_length_encoded(&self) -> bool {
+        self.flags & RUN_LENGTH_ENCODED != 0
+    }
+}
+
+/// Bin values in the order that they are read.
+enum Block<'a> {
+    /// A number of empty values.
+    Empty(usize),
+    /// The bytes of a number of stored values.
+    Stored(&'a [u8]),
+}
+
+/// Read the bin values after the header, a block at a time.
+fn read_blocks<R: io::Read>(
+    reader: &mut R,
+    header: &Header,
+    size: usize,
+    mut f: impl FnMut(Block<'_>) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut buffer = Vec::new();
+    if !header.is_run_length_encoded() {
+        return read_stored(reader, header.num_values, size, &mut buffer, &mut f);
+    }
+    let mut index = 0;
+    while index < header.num_values {
+        let mut run = [0; 16];
+        reader.read_exact(&mut run)?;
+        let mut input = &run[..];
+        let num_empty: u64 = read(&mut input).map_err(invalid_data)?;
+        let num_stored: u64 = read(&mut input).map_err(invalid_data)?;
+        let remaining = (header.num_values - index) as u64;
+        // every run must make progress, and must not go beyond the last value
+        if (num_empty == 0 && num_stored == 0)
+            || num_empty > remaining
+            || num_stored > remaining - num_empty
+        {
+            return Err(invalid_data(ConversionError::InvalidData));
+        }
+        let (num_empty, num_stored) = (num_empty as usize, num_stored as usize);
+        if num_empty > 0 {
+            f(Block::Empty(num_empty))?;
+        }
+        read_stored(reader, num_stored, size, &mut buffer, &mut f)?;
+        index += num_empty + num_stored;
+    }
+    Ok(())
+}
+
+fn read_stored<R: io::Read>(
+    reader: &mut R,
+    count: usize,
+    size: usize,
+    buffer: &mut Vec<u8>,
+    f: &mut impl FnMut(Block<'_>) -> io::Result<()>,
+) -> io::Result<()> {
+    let per_block = (BLOCK_SIZE / size).max(1);
+    let mut remaining = count;
+    while remaining > 0 {
+        let num = remaining.min(per_block);
+        buffer.resize(num * size, 0);
+        reader.read_exact(buffer)?;
+        f(Block::Stored(buffer))?;
+        remaining -= num;
+    }
+    Ok(())
+}
+
+/// Write the bin values after the header, with each value appended to a block by encode.
+fn write_values<T, W: io::Write>(
+    values: impl Iterator<Item = T>,
+    encode: impl Fn(T, &mut Vec<u8>),
+    run_length_encoding: bool,
+    mut writer: W,
+) -> io::Result<()> {
+    let mut block = Vec::with_capacity(2 * BLOCK_SIZE);
+    if !run_length_encoding {
+        for value in values {
+            encode(value, &mut block);
+            if block.len() >= BLOCK_SIZE {
+                writer.write_all(&block)?;
+                block.clear();
+            }
+        }
+        return writer.write_all(&block);
+    }
+
+    let mut empty: u64 = 0;
+    let mut stored: u64 = 0;
+    let mut flush = |empty: &mut u64, stored: &mut u64, block: &mut Vec<u8>| {
+        let mut run = Vec::with_capacity(16);
+        empty.write_le(&mut run);
+        stored.write_le(&mut run);
+        writer.write_all(&run)?;
+        writer.write_all(block)?;
+        (*empty, *stored) = (0, 0);
+        block.clear();
+        io::Result::Ok(())
+    };
+    for value in values {
+        let start = block.len();
+        encode(value, &mut block);
+        if block[start..].iter().all(|byte| *byte == 0) {
+            block.truncate(start);
+            if stored > 0 {
+                flush(&mut empty, &mut stored, &mut block)?;
+            }
+            empty += 1;
+        } else {
+            stored += 1;
+            if block.len() >= BLOCK_SIZE {
+                flush(&mut empty, &mut stored, &mut block)?;
+            }
+        }
+    }
+    if empty > 0 || stored > 0 {
+        flush(&mut empty, &mut stored, &mut block)?;
+    }
+    Ok(())
+}
+
+/// Write a histogram in the binary format, with run-length encoding of empty bins if
+/// run_length_encoding is set.
+///
+/// See the [module documentation](self) for a description of the format.
+pub fn write_binary<A, V, H, W>(
+    hist: &H,
+    run_length_encoding: bool,
+    mut writer: W,
+) -> io::Result<()>
+where
+    H: Histogram<A, V>,
+    A: BinaryAxes,
+    V: BinaryValue,
+    W: io::Write,
+{
+    let mut axes = Vec::new();
+    hist.axes().write_axes(&mut axes);
+    let num_values = hist.axes().num_bins();
+    Header::new(run_length_encoding, axes, V::LAYOUT, num_values).write(&mut writer)?;
+    write_values(
+        (0..num_values).map(|index| hist.value_at_index(index)),
+        |value, block| match value {
+            Some(value) => value.write_value(block),
+            None => block.resize(block.len() + V::SIZE, 0),
+        },
+        run_length_encoding,
+        writer,
+    )
+}
+
+/// Read a histogram in the binary format.
+///
+/// Returns an error of kind [io::ErrorKind::InvalidData] containing a [ConversionError]
+/// if the input is not in the binary format or does not match the requested histogram:
+///
+/// - [ConversionError::UnsupportedVersion] if the input was written with a later version
+///   of the format,
+/// - [ConversionError::WrongNumberOfAxes] or [ConversionError::IncompatibleAxis] if the
+///   axes have different types than the requested axes,
+/// - [ConversionError::IncompatibleStorage] if the bin values have a different type than
+///   the requested values,
+/// - [ConversionError::ShapeMismatch] if the number of values does not match the axes,
+/// - [ConversionError::InvalidData] if the input is malformed.
+///
+/// An input that ends early returns an error of kind [io::ErrorKind::UnexpectedEof],
+/// whatever number of bins its axes declare, and a histogram too large to be held in
+/// memory returns an error of kind [io::ErrorKind::OutOfMemory].
+///
+/// See the [module documentation](self) for a description of the format.
+pub fn read_binary<A, V, R>(mut reader: R) -> io::Result<VecHistogram<A, V>>
+where
+    A: BinaryAxes,
+    V: BinaryValue + Clone,
+    R: io::Read,
+{
+    let header = Header::read(&mut reader)?;
+    let mut input = header.axes.as_slice();
+    let axes = A::read_axes(&mut input).map_err(invalid_data)?;
+    if !input.is_empty() {
+        return Err(invalid_data(ConversionError::InvalidData));
+    }
+    if header.layout != V::LAYOUT {
+        return Err(invalid_data(ConversionError::IncompatibleStorage));
+    }
+    if header.num_values != axes.num_bins() {
+        return Err(invalid_data(ConversionError::ShapeMismatch));
+    }
+
+    let out_of_range = || invalid_data(ConversionError::ValueOutOfRange);
+    let empty = V::read_value(&vec![0; V::SIZE]).ok_or_else(out_of_range)?;
+    let mut values = Vec::new();
+    read_blocks(&mut reader, &header, V::SIZE, |block| {
+        match block {
+            Block::Empty(count) => {
+                try_reserve(&mut values, count)?;
+                values.resize(values.len() + count, empty.clone());
+            }
+            Block::Stored(bytes) => {
+                try_reserve(&mut values, bytes.len() / V::SIZE)?;
+                for bytes in bytes.chunks_exact(V::SIZE) {
+                    values.push(V::read_value(bytes).ok_or_else(out_of_range)?);
+                }
+            }
+        }
+        Ok(())
+    })?;
+    VecHistogram::from_vec(axes, values).map_err(|_| invalid_data(ConversionError::ShapeMismatch))
+}
+
+/// Add up histograms in the binary format and write the sum in the binary format,
+/// with run-length encoding of empty bins if run_length_encoding is set.
+///
+/// The types of the axes and values do not need to be known: every component of the bin
+/// values, such as the sum of weights and the sum of weights squared of a
+/// [WeightedSum](crate::value::WeightedSum), is added up, which gives the same result as
+/// reading the histograms and adding them with
+/// [VecHistogram::try_add_assign](crate::VecHistogram::try_add_assign).
+/// As the axes are not decoded, they are compared by their encoding, so axes must be
+/// exactly equal, and an incompatibility is reported without the differing axis.
+/// When the types are known, prefer [merge_readers](crate::io::merge::merge_readers)
+/// with [read_binary], which compares the decoded axes.
+/// Only the sum and one input are held in memory at a time.
+///
+/// Returns an error of kind [io::ErrorKind::InvalidInput] if there are no inputs,
+/// and of kind [io::ErrorKind::InvalidData] containing
+///
+/// - [BinaryOperationError::IncompatibleAxes] if an input has different axes than the first,
+/// - [ConversionError::IncompatibleStorage] if an input has a different type of bin
+///   values than the first,
+/// - [ConversionError::ValueOutOfRange] if an integer sum overflows,
+///
+/// or any of the errors of [read_binary].
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram, Hist1D, axis::Uniform};
+/// use ndhistogram::io::binary::{merge_binary, read_binary, write_binary};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut inputs = Vec::new();
+/// for x in [0.5, 0.5, 1.5] {
+///     let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0)?);
+///     hist.fill(&x);
+///     let mut bytes = Vec::new();
+///     write_binary(&hist, false, &mut bytes)?;
+///     inputs.push(bytes);
+/// }
+/// let mut output = Vec::new();
+/// merge_binary(inputs.iter().map(|it| it.as_slice()), true, &mut output)?;
+/// let merged: Hist1D<Uniform> = read_binary(output.as_slice())?;
+/// assert_eq!(merged.value(&0.5), Some(&2.0));
+/// assert_eq!(merged.value(&1.5), Some(&1.0));
+/// # Ok(()) }
+/// ```
+pub fn merge_binary<R, W>(
+    readers: impl IntoIterator<Item = R>,
+    run_length_encoding: bool,
+    mut writer: W,
+) -> io::Result<()>
+where
+    R: io::Read,
+    W: io::Write,
+{
+    let mut readers = readers.into_iter();
+    let mut first = readers
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no histograms to merge"))?;
+    let header = Header::read(&mut first)?;
+    let elements = value::element_adders(header.layout)
+        .ok_or_else(|| invalid_data(ConversionError::IncompatibleStorage))?;
+    let size: usize = elements.iter().map(|(size, _)| size).sum();
+    let mut sums = Vec::new();
+    read_blocks(&mut first, &header, size, |block| {
+        match block {
+            Block::Empty(count) => {
+                let length = count
+                    .checked_mul(size)
+                    .ok_or_else(|| io::Error::from(io::ErrorKind::OutOfMemory))?;
+                try_reserve(&mut sums, length)?;
+                sums.resize(sums.len() + length, 0);
+            }
+            Block::Stored(bytes) => {
+                try_reserve(&mut sums, bytes.len())?;
+                sums.extend_from_slice(bytes);
+            }
+        }
+        Ok(())
+    })?;
+
+    for mut reader in readers {
+        let other = Header::read(&mut reader)?;
+        if other.axes != header.axes || other.num_values != header.num_values {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                BinaryOperationError::IncompatibleAxes,
+            ));
+        }
+        if other.layout != header.layout {
+            return Err(invalid_data(ConversionError::IncompatibleStorage));
+        }
+        let mut sums = sums.chunks_exact_mut(size);
+        read_blocks(&mut reader, &other, size, |block| {
+            match block {
+                Block::Empty(count) => {
+                    sums.nth(count - 1);
+                }
+                Block::Stored(bytes) => {
+                    for (sum, value) in sums.by_ref().zip(bytes.chunks_exact(size)) {
+                        value::add_elements(&elements, sum, value)
+                            .ok_or_else(|| invalid_data(ConversionError::ValueOutOfRange))?;
+                    }
+                }
+            }
+            Ok(())
+        })?;
+    }
+
+    let header = Header::new(
+        run_length_encoding,
+        header.axes,
+        header.layout,
+        header.num_values,
+    );
+    header.write(&mut writer)?;
+    write_values(
+        sums.chunks_exact(size),
+        |value, block| block.extend_from_slice(value),
+        run_length_encoding,
+        writer,
+    )
+}
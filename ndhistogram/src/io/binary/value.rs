@@ -0,0 +1,288 @@
+use num_traits::{NumOps, Signed};
+
+use crate::value::{Mean, Sum, WeightedMean, WeightedSum};
+
+/// A primitive number that is stored in little-endian byte order.
+///
+/// Each type has a fixed code in the format, so that values are only read back as the type
+/// they were written as. `isize` and `usize` are stored as `i64` and `u64`.
+pub trait BinaryElement: Copy {
+    /// Code of the type in the format.
+    const CODE: u8;
+    /// Number of bytes of a stored element.
+    const SIZE: usize;
+
+    /// Append the little-endian bytes of the element.
+    fn write_le(self, out: &mut Vec<u8>);
+
+    /// Read an element from exactly [Self::SIZE](BinaryElement::SIZE) bytes.
+    ///
+    /// Returns None if the stored value cannot be represented by this type.
+    fn read_le(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_binary_element {
+    ($($T:ty: $code:expr),*) => {
+        $(
+            impl BinaryElement for $T {
+                const CODE: u8 = $code;
+                const SIZE: usize = std::mem::size_of::<$T>();
+
+                fn write_le(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_le(bytes: &[u8]) -> Option<Self> {
+                    Some(Self::from_le_bytes(bytes.try_into().ok()?))
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_element! {
+    i8: 1, i16: 2, i32: 3, i64: 4, u8: 5, u16: 6, u32: 7, u64: 8, f32: 9, f64: 10, i128: 11, u128: 12
+}
+
+impl BinaryElement for isize {
+    const CODE: u8 = i64::CODE;
+    const SIZE: usize = <i64 as BinaryElement>::SIZE;
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        (self as i64).write_le(out);
+    }
+
+    fn read_le(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(i64::read_le(bytes)?).ok()
+    }
+}
+
+impl BinaryElement for usize {
+    const CODE: u8 = u64::CODE;
+    const SIZE: usize = <u64 as BinaryElement>::SIZE;
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        (self as u64).write_le(out);
+    }
+
+    fn read_le(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(u64::read_le(bytes)?).ok()
+    }
+}
+
+/// Kind of bin value with a single number, a primitive number or a [Sum].
+pub(crate) const SINGLE: u8 = 1;
+/// Kind of bin value of a [WeightedSum].
+pub(crate) const WEIGHTED_SUM: u8 = 2;
+/// Kind of bin value of a [Mean].
+pub(crate) const MEAN: u8 = 3;
+/// Kind of bin value of a [WeightedMean].
+pub(crate) const WEIGHTED_MEAN: u8 = 4;
+
+/// A bin value that is stored as a fixed size block of [BinaryElement]s.
+///
+/// Primitive numbers and [Sum] are stored as the same kind of value,
+/// so either can be read back as the other.
+pub trait BinaryValue: Sized {
+    /// The kind of value followed by the element codes of its values, weights and counts,
+    /// with 0 for components that the value does not have.
+    const LAYOUT: [u8; 4];
+    /// Number of bytes of a stored value.
+    const SIZE: usize;
+
+    /// Append the bytes of the value.
+    fn write_value(&self, out: &mut Vec<u8>);
+
+    /// Read a value from exactly [Self::SIZE](BinaryValue::SIZE) bytes.
+    ///
+    /// Returns None if a stored element cannot be represented by the value type.
+    fn read_value(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_binary_value_for_primitive {
+    ($($T:ty),*) => {
+        $(
+            impl BinaryValue for $T {
+                const LAYOUT: [u8; 4] = [SINGLE, <$T>::CODE, 0, 0];
+                const SIZE: usize = <$T as BinaryElement>::SIZE;
+
+                fn write_value(&self, out: &mut Vec<u8>) {
+                    self.write_le(out);
+                }
+
+                fn read_value(bytes: &[u8]) -> Option<Self> {
+                    Self::read_le(bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_value_for_primitive! {i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64}
+
+impl<T: BinaryElement> BinaryValue for Sum<T> {
+    const LAYOUT: [u8; 4] = [SINGLE, T::CODE, 0, 0];
+    const SIZE: usize = T::SIZE;
+
+    fn write_value(&self, out: &mut Vec<u8>) {
+        self.get().write_le(out);
+    }
+
+    fn read_value(bytes: &[u8]) -> Option<Self> {
+        Some(Self::with_sum(T::read_le(bytes)?))
+    }
+}
+
+impl<T: BinaryElement> BinaryValue for WeightedSum<T> {
+    const LAYOUT: [u8; 4] = [WEIGHTED_SUM, T::CODE, 0, 0];
+    const SIZE: usize = 2 * T::SIZE;
+
+    fn write_value(&self, out: &mut Vec<u8>) {
+        self.sum().write_le(out);
+        self.variance().write_le(out);
+    }
+
+    fn read_value(bytes: &[u8]) -> Option<Self> {
+        let (sum, variance) = bytes.split_at(T::SIZE);
+        Some(Self::with_sum_and_variance(
+            T::read_le(sum)?,
+            T::read_le(variance)?,
+        ))
+    }
+}
+
+impl<T, O, C> BinaryValue for Mean<T, O, C>
+where
+    T: BinaryElement,
+    O: From<T> + From<C> + NumOps + Signed + Copy,
+    C: BinaryElement,
+{
+    const LAYOUT: [u8; 4] = [MEAN, T::CODE, 0, C::CODE];
+    const SIZE: usize = 2 * T::SIZE + C::SIZE;
+
+    fn write_value(&self, out: &mut Vec<u8>) {
+        self.sum().write_le(out);
+        self.sum_of_squares().write_le(out);
+        self.num_samples().write_le(out);
+    }
+
+    fn read_value(bytes: &[u8]) -> Option<Self> {
+        let (sum, rest) = bytes.split_at(T::SIZE);
+        let (sum_of_squares, count) = rest.split_at(T::SIZE);
+        Some(Self::with_sums(
+            T::read_le(sum)?,
+            T::read_le(sum_of_squares)?,
+            C::read_le(count)?,
+        ))
+    }
+}
+
+impl<T, W, O, C> BinaryValue for WeightedMean<T, W, O, C>
+where
+    T: BinaryElement,
+    W: BinaryElement,
+    O: From<T> + From<W> + From<C> + NumOps + Signed + Copy,
+    C: BinaryElement,
+{
+    const LAYOUT: [u8; 4] = [WEIGHTED_MEAN, T::CODE, W::CODE, C::CODE];
+    const SIZE: usize = 2 * T::SIZE + 2 * W::SIZE + C::SIZE;
+
+    fn write_value(&self, out: &mut Vec<u8>) {
+        self.sum_of_weighted_values().write_le(out);
+        self.sum_of_weighted_squared_values().write_le(out);
+        self.sum_of_weights().write_le(out);
+        self.sum_of_weights_squared().write_le(out);
+        self.num_samples().write_le(out);
+    }
+
+    fn read_value(bytes: &[u8]) -> Option<Self> {
+        let (sum_of_weighted_values, rest) = bytes.split_at(T::SIZE);
+        let (sum_of_weighted_squared_values, rest) = rest.split_at(T::SIZE);
+        let (sum_of_weights, rest) = rest.split_at(W::SIZE);
+        let (sum_of_weights_squared, count) = rest.split_at(W::SIZE);
+        Some(Self::with_sums(
+            T::read_le(sum_of_weighted_values)?,
+            T::read_le(sum_of_weighted_squared_values)?,
+            W::read_le(sum_of_weights)?,
+            W::read_le(sum_of_weights_squared)?,
+            C::read_le(count)?,
+        ))
+    }
+}
+
+/// Adds one stored element to another, returning None if the sum overflows.
+type AddElement = fn(&mut [u8], &[u8]) -> Option<()>;
+
+trait Accumulate: Sized {
+    fn accumulate(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_accumulate {
+    ($($T:ty: $add:expr),*) => {
+        $(
+            impl Accumulate for $T {
+                fn accumulate(self, other: Self) -> Option<Self> {
+                    $add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_accumulate! {
+    i8: i8::checked_add, i16: i16::checked_add, i32: i32::checked_add, i64: i64::checked_add,
+    i128: i128::checked_add, u8: u8::checked_add, u16: u16::checked_add, u32: u32::checked_add,
+    u64: u64::checked_add, u128: u128::checked_add,
+    f32: |lhs: f32, rhs: f32| Some(lhs + rhs), f64: |lhs: f64, rhs: f64| Some(lhs + rhs)
+}
+
+fn add_element<T: BinaryElement + Accumulate>(sum: &mut [u8], value: &[u8]) -> Option<()> {
+    let total = T::read_le(sum)?.accumulate(T::read_le(value)?)?;
+    let mut bytes = Vec::with_capacity(T::SIZE);
+    total.write_le(&mut bytes);
+    sum.copy_from_slice(&bytes);
+    Some(())
+}
+
+fn element_adder(code: u8) -> Option<(usize, AddElement)> {
+    macro_rules! match_code {
+        ($($T:ty),*) => {
+            match code {
+                $(code if code == <$T>::CODE => {
+                    Some((<$T as BinaryElement>::SIZE, add_element::<$T> as AddElement))
+                })*
+                _ => None,
+            }
+        };
+    }
+    match_code!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64)
+}
+
+/// The size and adder of each element of a stored value with the given layout,
+/// or None if the layout is not known.
+pub(crate) fn element_adders(layout: [u8; 4]) -> Option<Vec<(usize, AddElement)>> {
+    let [kind, value, weight, count] = layout;
+    let codes = match kind {
+        SINGLE => vec![value],
+        WEIGHTED_SUM => vec![value, value],
+        MEAN => vec![value, value, count],
+        WEIGHTED_MEAN => vec![value, value, weight, weight, count],
+        _ => return None,
+    };
+    codes.into_iter().map(element_adder).collect()
+}
+
+/// Add each element of a stored value to the elements of a stored sum.
+pub(crate) fn add_elements(
+    elements: &[(usize, AddElement)],
+    mut sum: &mut [u8],
+    mut value: &[u8],
+) -> Option<()> {
+    for (size, add) in elements {
+        let (sum_element, sum_rest) = std::mem::take(&mut sum).split_at_mut(*size);
+        let (value_element, value_rest) = value.split_at(*size);
+        add(sum_element, value_element)?;
+        (sum, value) = (sum_rest, value_rest);
+    }
+    Some(())
+}
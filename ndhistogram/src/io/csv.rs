@@ -0,0 +1,501 @@
+//! Export to and import from delimited text tables (CSV and TSV)
+//!
+//! Each bin of a [Histogram] is written as one row of a table with the columns:
+//!
+//! - `index`: the bin index.
+//! - For each axis N, the bin on that axis:
+//!   `axisN_start` and `axisN_end` for axes with [BinInterval]s, where underflow and
+//!   overflow bins have a start of `-inf` or an end of `inf`, respectively,
+//!   or `axisN` for axes with [SingleValueBinInterval]s, where the overflow bin is empty.
+//! - The bin value columns given by [CsvValue::column_names], for example
+//!   `sum` and `variance` for [WeightedSum] or `mean`, `error` and `count` for [Mean].
+//!
+//! The first row is a header with the column names.
+//! A [Hexagonal](crate::axis::Hexagonal) axis, whose bins are two-dimensional,
+//! does not implement [CsvBin], so histograms with such an axis cannot be written.
+//! Cells that contain the delimiter, a quote or a line break are quoted following
+//! [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180).
+//! The output may be read by spreadsheets or, for example, `pandas.read_csv`.
+//!
+//! A [VecHistogram] is rebuilt from a table with [read_csv] given its axes
+//! and any [io::Read] source.
+//! Only the `index` and value columns are used; the bin columns are informative.
+//! Bins that do not appear in the table are set to their default value.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, Hist1D, axis::Uniform};
+//! use ndhistogram::io::csv::{read_csv, write_csv};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let axis = Uniform::new(2, 0.0, 2.0)?;
+//! let mut hist = ndhistogram!(axis.clone(); i32);
+//! hist.fill_with(&0.5, 3);
+//! let mut table = Vec::new();
+//! write_csv(&hist, &mut table)?;
+//! let table = String::from_utf8(table)?;
+//! assert_eq!(
+//!     table,
+//!     "index,axis0_start,axis0_end,value\n\
+//!      0,-inf,0,0\n\
+//!      1,0,1,3\n\
+//!      2,1,2,0\n\
+//!      3,2,inf,0\n"
+//! );
+//! let read: Hist1D<Uniform, i32> = read_csv((axis,).into(), table.as_bytes())?;
+//! assert_eq!(read, hist);
+//! # Ok(()) }
+//! ```
+
+use std::{fmt::Display, io, str::FromStr};
+
+use num_traits::{Float, NumCast, Signed};
+
+use crate::{
+    axis::{Axis, BinInterval, SingleValueBinInterval},
+    error::ConversionError,
+    io::{mean_from_summary, weighted_mean_from_summary},
+    value::{Mean, Sum, WeightedMean, WeightedSum},
+    Axes, Histogram, Item, VecHistogram,
+};
+
+/// A bin interval that can be written as cells of a table.
+pub trait CsvBin {
+    /// Append the names of the columns of this bin.
+    ///
+    /// The axis number is used for single axis bins and ignored for tuples,
+    /// which number their axes from 0.
+    fn push_column_names(axis: usize, names: &mut Vec<String>);
+
+    /// Append the cells that describe this bin.
+    fn push_cells(&self, cells: &mut Vec<String>);
+}
+
+impl<T: Display> CsvBin for BinInterval<T> {
+    fn push_column_names(axis: usize, names: &mut Vec<String>) {
+        names.push(format!("axis{axis}_start"));
+        names.push(format!("axis{axis}_end"));
+    }
+
+    fn push_cells(&self, cells: &mut Vec<String>) {
+        let (start, end) = match self {
+            Self::Underflow { end } => ("-inf".to_string(), end.to_string()),
+            Self::Overflow { start } => (start.to_string(), "inf".to_string()),
+            Self::Bin { start, end } => (start.to_string(), end.to_string()),
+        };
+        cells.push(start);
+        cells.push(end);
+    }
+}
+
+impl<T: Display> CsvBin for SingleValueBinInterval<T> {
+    fn push_column_names(axis: usize, names: &mut Vec<String>) {
+        names.push(format!("axis{axis}"));
+    }
+
+    fn push_cells(&self, cells: &mut Vec<String>) {
+        cells.push(self.value().map(ToString::to_string).unwrap_or_default());
+    }
+}
+
+// Implements CsvBin for every tuple from 2 elements up to the full list of type parameters.
+macro_rules! impl_csv_bin {
+    ([$($done:ident: $done_index:tt),*] $next:ident: $next_index:tt $(, $rest:ident: $rest_index:tt)*) => {
+        impl_csv_bin!(@IMPL $($done: $done_index,)* $next: $next_index);
+        impl_csv_bin!([$($done: $done_index,)* $next: $next_index] $($rest: $rest_index),*);
+    };
+    ([$($done:ident: $done_index:tt),*]) => {};
+    (@IMPL $T:ident: $index:tt) => {};
+    (@IMPL $($T:ident: $index:tt),+) => {
+        impl<$($T: CsvBin),+> CsvBin for ($($T,)+) {
+            fn push_column_names(_axis: usize, names: &mut Vec<String>) {
+                $($T::push_column_names($index, names);)+
+            }
+
+            fn push_cells(&self, cells: &mut Vec<String>) {
+                $(self.$index.push_cells(cells);)+
+            }
+        }
+    };
+}
+
+impl_csv_bin! {
+    [] X: 0,
+    Y: 1,
+    Z: 2,
+    T: 3,
+    D4: 4,
+    D5: 5,
+    D6: 6,
+    D7: 7,
+    D8: 8,
+    D9: 9,
+    D10: 10,
+    D11: 11,
+    D12: 12,
+    D13: 13,
+    D14: 14,
+    D15: 15,
+    D16: 16,
+    D17: 17,
+    D18: 18,
+    D19: 19,
+    D20: 20
+}
+
+/// A bin value that can be written to and read from cells of a table.
+pub trait CsvValue: Sized {
+    /// Names of the value columns.
+    fn column_names() -> &'static [&'static str];
+
+    /// Append one cell for each value column.
+    fn push_cells(&self, cells: &mut Vec<String>);
+
+    /// Create a value from the value cells.
+    ///
+    /// Returns None if the cells cannot be parsed.
+    fn from_cells(cells: &[&str]) -> Option<Self>;
+}
+
+macro_rules! impl_csv_value_for_primitive {
+    ($($T:ty),*) => {
+        $(
+            impl CsvValue for $T {
+                fn column_names() -> &'static [&'static str] {
+                    &["value"]
+                }
+
+                fn push_cells(&self, cells: &mut Vec<String>) {
+                    cells.push(self.to_string());
+                }
+
+                fn from_cells(cells: &[&str]) -> Option<Self> {
+                    cells.first()?.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_csv_value_for_primitive! {i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64}
+
+impl<T: Copy + Display + FromStr> CsvValue for Sum<T> {
+    fn column_names() -> &'static [&'static str] {
+        &["value"]
+    }
+
+    fn push_cells(&self, cells: &mut Vec<String>) {
+        cells.push(self.get().to_string());
+    }
+
+    fn from_cells(cells: &[&str]) -> Option<Self> {
+        Some(Self::with_sum(cells.first()?.parse().ok()?))
+    }
+}
+
+impl<T: Copy + Display + FromStr> CsvValue for WeightedSum<T> {
+    fn column_names() -> &'static [&'static str] {
+        &["sum", "variance"]
+    }
+
+    fn push_cells(&self, cells: &mut Vec<String>) {
+        cells.push(self.sum().to_string());
+        cells.push(self.variance().to_string());
+    }
+
+    fn from_cells(cells: &[&str]) -> Option<Self> {
+        match cells {
+            [sum, variance] => Some(Self::with_sum_and_variance(
+                sum.parse().ok()?,
+                variance.parse().ok()?,
+            )),
+            _ => None,
+        }
+    }
+}
+
+fn parse_f64(cell: &str) -> Option<f64> {
+    cell.parse().ok()
+}
+
+/// The Mean is read back from its mean, standard error of the mean and number of samples,
+/// so values read from a table may differ from the original by floating point rounding errors.
+impl<T, O, C> CsvValue for Mean<T, O, C>
+where
+    T: NumCast + Copy,
+    O: From<T> + From<C> + Float + Signed + Display,
+    C: NumCast + Copy + Display,
+{
+    fn column_names() -> &'static [&'static str] {
+        &["mean", "error", "count"]
+    }
+
+    fn push_cells(&self, cells: &mut Vec<String>) {
+        cells.push(self.get().to_string());
+        cells.push(self.standard_error_of_mean().to_string());
+        cells.push(self.num_samples().to_string());
+    }
+
+    fn from_cells(cells: &[&str]) -> Option<Self> {
+        let [mean, error, count] = cells else {
+            return None;
+        };
+        mean_from_summary(parse_f64(mean)?, parse_f64(error)?, parse_f64(count)?).ok()
+    }
+}
+
+/// The WeightedMean is read back from its mean, standard error of the mean, sums of weights and
+/// number of samples, so values read from a table may differ from the original by floating point
+/// rounding errors.
+impl<T, W, O, C> CsvValue for WeightedMean<T, W, O, C>
+where
+    T: NumCast + Copy,
+    W: NumCast + Copy + Display,
+    O: From<T> + From<W> + From<C> + Float + Signed + Display,
+    C: NumCast + Copy + Display,
+{
+    fn column_names() -> &'static [&'static str] {
+        &[
+            "mean",
+            "error",
+            "sum_of_weights",
+            "sum_of_weights_squared",
+            "count",
+        ]
+    }
+
+    fn push_cells(&self, cells: &mut Vec<String>) {
+        cells.push(self.get().to_string());
+        cells.push(self.standard_error_of_mean().to_string());
+        cells.push(self.sum_of_weights().to_string());
+        cells.push(self.sum_of_weights_squared().to_string());
+        cells.push(self.num_samples().to_string());
+    }
+
+    fn from_cells(cells: &[&str]) -> Option<Self> {
+        let [mean, error, sumw, sumw2, count] = cells else {
+            return None;
+        };
+        weighted_mean_from_summary(
+            parse_f64(mean)?,
+            parse_f64(error)?,
+            parse_f64(sumw)?,
+            parse_f64(sumw2)?,
+            parse_f64(count)?,
+        )
+        .ok()
+    }
+}
+
+/// Write a histogram as comma separated values.
+///
+/// See the [module documentation](self) for a description of the columns.
+pub fn write_csv<A, V, H, W>(hist: &H, writer: W) -> io::Result<()>
+where
+    H: Histogram<A, V>,
+    A: Axis,
+    A::BinInterval: CsvBin,
+    V: CsvValue,
+    W: io::Write,
+{
+    write_delimited(hist, writer, ',')
+}
+
+/// Write a histogram as tab separated values.
+///
+/// See the [module documentation](self) for a description of the columns.
+pub fn write_tsv<A, V, H, W>(hist: &H, writer: W) -> io::Result<()>
+where
+    H: Histogram<A, V>,
+    A: Axis,
+    A::BinInterval: CsvBin,
+    V: CsvValue,
+    W: io::Write,
+{
+    write_delimited(hist, writer, '\t')
+}
+
+/// Write a histogram as a table with the given delimiter.
+///
+/// Rows are written in order of increasing bin index.
+/// See the [module documentation](self) for a description of the columns.
+pub fn write_delimited<A, V, H, W>(hist: &H, mut writer: W, delimiter: char) -> io::Result<()>
+where
+    H: Histogram<A, V>,
+    A: Axis,
+    A::BinInterval: CsvBin,
+    V: CsvValue,
+    W: io::Write,
+{
+    write_row(&mut writer, &header::<A, V>(), delimiter)?;
+    let mut items: Vec<Item<A::BinInterval, &V>> = hist.iter().collect();
+    items.sort_by_key(|item| item.index);
+    let mut cells = Vec::new();
+    for item in items {
+        cells.clear();
+        cells.push(item.index.to_string());
+        item.bin.push_cells(&mut cells);
+        item.value.push_cells(&mut cells);
+        write_row(&mut writer, &cells, delimiter)?;
+    }
+    Ok(())
+}
+
+/// Read a histogram with the given axes from comma separated values.
+///
+/// See [read_delimited] for the errors that may be returned and the
+/// [module documentation](self) for a description of the columns.
+pub fn read_csv<A, V, R>(axes: A, reader: R) -> io::Result<VecHistogram<A, V>>
+where
+    A: Axes,
+    A::BinInterval: CsvBin,
+    V: CsvValue + Default,
+    R: io::Read,
+{
+    read_delimited(axes, reader, ',')
+}
+
+/// Read a histogram with the given axes from tab separated values.
+///
+/// See [read_delimited] for the errors that may be returned and the
+/// [module documentation](self) for a description of the columns.
+pub fn read_tsv<A, V, R>(axes: A, reader: R) -> io::Result<VecHistogram<A, V>>
+where
+    A: Axes,
+    A::BinInterval: CsvBin,
+    V: CsvValue + Default,
+    R: io::Read,
+{
+    read_delimited(axes, reader, '\t')
+}
+
+/// Read a histogram with the given axes from a table with the given delimiter.
+///
+/// The whole input is read before it is parsed.
+/// Errors from the reader are returned unchanged.
+/// The input must be UTF-8, otherwise an error of kind [io::ErrorKind::InvalidData] is returned.
+/// An error of kind [io::ErrorKind::InvalidData] wrapping [ConversionError::InvalidRow]
+/// is returned if the header does not match the axes and value type,
+/// or if a row has the wrong number of cells, an invalid or repeated bin index, or
+/// values that cannot be parsed.
+/// See the [module documentation](self) for a description of the columns.
+pub fn read_delimited<A, V, R>(
+    axes: A,
+    mut reader: R,
+    delimiter: char,
+) -> io::Result<VecHistogram<A, V>>
+where
+    A: Axes,
+    A::BinInterval: CsvBin,
+    V: CsvValue + Default,
+    R: io::Read,
+{
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    parse_delimited(axes, &input, delimiter)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+fn parse_delimited<A, V>(
+    axes: A,
+    input: &str,
+    delimiter: char,
+) -> Result<VecHistogram<A, V>, ConversionError>
+where
+    A: Axes,
+    A::BinInterval: CsvBin,
+    V: CsvValue + Default,
+{
+    let mut rows = parse_rows(input, delimiter).into_iter();
+    let header = header::<A, V>();
+    match rows.next() {
+        Some((_, row)) if row == header => {}
+        _ => return Err(ConversionError::InvalidRow(1)),
+    }
+    let num_value_columns = V::column_names().len();
+    let mut values: Vec<Option<V>> = (0..axes.num_bins()).map(|_| None).collect();
+    for (row_number, row) in rows {
+        let invalid = ConversionError::InvalidRow(row_number);
+        if row.len() != header.len() {
+            return Err(invalid);
+        }
+        let value = row[0]
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| values.get_mut(index))
+            .filter(|value| value.is_none())
+            .ok_or(invalid)?;
+        let cells: Vec<&str> = row[row.len() - num_value_columns..]
+            .iter()
+            .map(String::as_str)
+            .collect();
+        *value = Some(V::from_cells(&cells).ok_or(invalid)?);
+    }
+    let values = values.into_iter().map(Option::unwrap_or_default).collect();
+    VecHistogram::from_vec(axes, values).map_err(|_| ConversionError::ShapeMismatch)
+}
+
+fn header<A, V>() -> Vec<String>
+where
+    A: Axis,
+    A::BinInterval: CsvBin,
+    V: CsvValue,
+{
+    let mut names = vec!["index".to_string()];
+    A::BinInterval::push_column_names(0, &mut names);
+    names.extend(V::column_names().iter().map(ToString::to_string));
+    names
+}
+
+fn write_row<W: io::Write>(writer: &mut W, cells: &[String], delimiter: char) -> io::Result<()> {
+    for (position, cell) in cells.iter().enumerate() {
+        if position > 0 {
+            write!(writer, "{delimiter}")?;
+        }
+        if cell.contains([delimiter, '"', '\n', '\r']) {
+            write!(writer, "\"{}\"", cell.replace('"', "\"\""))?;
+        } else {
+            write!(writer, "{cell}")?;
+        }
+    }
+    writeln!(writer)
+}
+
+/// Split the input into rows of cells, paired with the row number starting from 1.
+///
+/// Quoted cells may contain the delimiter, escaped quotes ("") and line breaks.
+/// Empty lines are skipped.
+fn parse_rows(input: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut row_number = 1;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' => quoted = !quoted,
+            _ if quoted => cell.push(c),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                if !row.is_empty() || !cell.is_empty() {
+                    row.push(std::mem::take(&mut cell));
+                    rows.push((row_number, std::mem::take(&mut row)));
+                }
+                row_number += 1;
+            }
+            _ if c == delimiter => row.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    if !row.is_empty() || !cell.is_empty() {
+        row.push(cell);
+        rows.push((row_number, row));
+    }
+    rows
+}
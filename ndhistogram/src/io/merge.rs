@@ -0,0 +1,133 @@
+//! Merging of many serialized histograms
+//!
+//! [merge_readers] and [merge_files] read histograms of the same type one at a time,
+//! in any format, and add them up bin by bin, like the `hadd` tool of ROOT.
+//! Only the sum and one input are held in memory at a time.
+//!
+//! Histograms in the [binary](super::binary) format can also be merged without knowing
+//! their type with [merge_binary](super::binary::merge_binary) or with the `ndhist-merge`
+//! command line tool:
+//!
+//! ```text
+//! ndhist-merge [--run-length-encoding] OUTPUT INPUT...
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, Hist1D, axis::Uniform};
+//! use ndhistogram::io::{binary::{read_binary, write_binary}, merge::merge_readers};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut inputs = Vec::new();
+//! for x in [0.5, 0.5, 1.5] {
+//!     let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0)?);
+//!     hist.fill(&x);
+//!     let mut bytes = Vec::new();
+//!     write_binary(&hist, false, &mut bytes)?;
+//!     inputs.push(bytes);
+//! }
+//! let merged: Hist1D<Uniform> = merge_readers(inputs.iter().map(|it| it.as_slice()), read_binary)?;
+//! assert_eq!(merged.value(&0.5), Some(&2.0));
+//! assert_eq!(merged.value(&1.5), Some(&1.0));
+//! # Ok(()) }
+//! ```
+
+use std::{
+    fs::{self, File},
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+};
+
+use crate::error::BinaryOperationError;
+#[cfg(doc)]
+use crate::{HashHistogram, VecHistogram};
+
+/// A histogram that other histograms of the same type can be added to.
+///
+/// This is implemented for [VecHistogram] and [HashHistogram] with
+/// [VecHistogram::try_add_assign] and [HashHistogram::try_add_assign], so it is
+/// available for all bin value types that implement `AddAssign<&Self>`, such as the
+/// primitive numbers and the types in [value](crate::value).
+pub trait Merge {
+    /// Add the other histogram to this histogram.
+    ///
+    /// If the histograms have different axes, a [BinaryOperationError] is returned
+    /// and this histogram is left unchanged.
+    fn merge(&mut self, other: &Self) -> Result<(), BinaryOperationError>;
+}
+
+fn merge_inputs<H: Merge, T>(
+    inputs: impl IntoIterator<Item = T>,
+    mut read: impl FnMut(T) -> io::Result<H>,
+) -> io::Result<H> {
+    let mut inputs = inputs.into_iter();
+    let first = inputs
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no histograms to merge"))?;
+    let mut sum = read(first)?;
+    for input in inputs {
+        sum.merge(&read(input)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    }
+    Ok(sum)
+}
+
+/// Read a histogram from each reader with read and add them up.
+///
+/// Returns an error of kind [io::ErrorKind::InvalidInput] if there are no readers,
+/// any error returned by read, and an error of kind [io::ErrorKind::InvalidData]
+/// containing a [BinaryOperationError] if a histogram has different axes than the first.
+pub fn merge_readers<H, R>(
+    readers: impl IntoIterator<Item = R>,
+    read: impl FnMut(R) -> io::Result<H>,
+) -> io::Result<H>
+where
+    H: Merge,
+    R: io::Read,
+{
+    merge_inputs(readers, read)
+}
+
+/// Read a histogram from each file with read and add them up.
+///
+/// Directories are replaced with the files in them, see [input_files].
+/// Each file is opened just before it is read, so any number of files can be merged.
+/// Returns the same errors as [merge_readers] and any error opening the files.
+pub fn merge_files<H, P>(
+    paths: impl IntoIterator<Item = P>,
+    mut read: impl FnMut(BufReader<File>) -> io::Result<H>,
+) -> io::Result<H>
+where
+    H: Merge,
+    P: AsRef<Path>,
+{
+    merge_inputs(input_files(paths)?, |path| {
+        read(BufReader::new(File::open(path)?))
+    })
+}
+
+/// List the files to merge, replacing each directory with the files in it, in order of
+/// their names.
+///
+/// Subdirectories are not searched.
+pub fn input_files<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        if path.is_dir() {
+            let mut entries = Vec::new();
+            for entry in fs::read_dir(path)? {
+                let entry = entry?;
+                if entry.file_type()?.is_file() {
+                    entries.push(entry.path());
+                }
+            }
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+    Ok(files)
+}
@@ -0,0 +1,157 @@
+//! Import and export of histograms in other formats
+//!
+//! This module contains conversions between ndhistogram [Histogram](crate::Histogram)s
+//! and formats used by other histogramming libraries.
+//!
+//! - [arrow]: Apache Arrow record batches, which may be stored in Parquet files.
+//!   Requires the "arrow" feature.
+//! - [binary]: a compact, versioned binary format with optional run-length encoding of
+//!   empty bins.
+//! - [csv]: delimited text tables (CSV and TSV) with one row per bin.
+//! - [merge]: merging of many serialized histograms, in any of these formats, into one.
+//! - [numpy]: NumPy `.npy` and `.npz` files. Requires the "numpy" feature.
+//! - [prometheus]: the Prometheus text exposition format for 1D histograms.
+//! - [root]: TH1D, TH2D and TH3D histograms in ROOT files.
+//! - [svg]: SVG images of 1D and 2D histograms. Requires the "svg" feature.
+//! - [uhi]: the JSON serialization schema of the Scikit-HEP Unified Histogram
+//!   Interface, used by boost-histogram and hist. Requires the "serde" feature.
+//! - [yoda]: histograms and profiles in the YODA text format used by Rivet.
+
+use num_traits::{Float, NumCast, Signed};
+
+use crate::{
+    error::ConversionError,
+    value::{Mean, WeightedMean},
+};
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod binary;
+pub mod csv;
+pub mod merge;
+#[cfg(feature = "numpy")]
+pub mod numpy;
+pub mod prometheus;
+pub mod root;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(feature = "serde")]
+pub mod uhi;
+pub mod yoda;
+
+/// Maps bin positions in C (row-major) order to ndhistogram bin indices.
+///
+/// ndhistogram stores bins with the first axis varying fastest while C order has
+/// the last axis varying fastest.
+/// The returned vector contains, for each position in C order, the
+/// corresponding ndhistogram bin index for axes with the given number of bins.
+#[cfg(feature = "serde")]
+pub(crate) fn c_order_indices(shape: &[usize]) -> Vec<usize> {
+    let selected: Vec<Vec<usize>> = shape
+        .iter()
+        .map(|num_bins| (0..*num_bins).collect())
+        .collect();
+    c_order_selected_indices(&selected, shape)
+}
+
+/// As [c_order_indices] but only including the selected bin indices on each axis,
+/// for example to exclude the underflow and overflow bins.
+#[cfg(any(feature = "serde", feature = "numpy"))]
+pub(crate) fn c_order_selected_indices(selected: &[Vec<usize>], shape: &[usize]) -> Vec<usize> {
+    let strides: Vec<usize> = shape
+        .iter()
+        .scan(1, |stride, num_bins| {
+            let current = *stride;
+            *stride *= num_bins;
+            Some(current)
+        })
+        .collect();
+    let size = selected.iter().map(Vec::len).product();
+    let mut position = vec![0; selected.len()];
+    (0..size)
+        .map(|_| {
+            let index = position
+                .iter()
+                .zip(selected)
+                .zip(&strides)
+                .map(|((it, bins), stride)| bins[*it] * stride)
+                .sum();
+            // increment the multi-index with the last axis varying fastest
+            for (it, bins) in position.iter_mut().zip(selected).rev() {
+                *it += 1;
+                if *it < bins.len() {
+                    break;
+                }
+                *it = 0;
+            }
+            index
+        })
+        .collect()
+}
+
+/// Cast to T choosing the nearest representable value, so that integer
+/// types are rounded rather than truncated.
+pub(crate) fn nearest<T: NumCast>(value: f64) -> Result<T, ConversionError> {
+    let cast = T::from(value).ok_or(ConversionError::ValueOutOfRange)?;
+    match T::from(value.round()) {
+        Some(rounded)
+            if (rounded.to_f64().unwrap_or(f64::NAN) - value).abs()
+                < (cast.to_f64().unwrap_or(f64::NAN) - value).abs() =>
+        {
+            Ok(rounded)
+        }
+        _ => Ok(cast),
+    }
+}
+
+/// Rebuild a [Mean] from its mean, standard error of the mean and number of samples.
+pub(crate) fn mean_from_summary<T, O, C>(
+    mean: f64,
+    error: f64,
+    count: f64,
+) -> Result<Mean<T, O, C>, ConversionError>
+where
+    T: NumCast + Copy,
+    O: From<T> + From<C> + Float + Signed,
+    C: NumCast + Copy,
+{
+    if count == 0.0 {
+        return Ok(Mean::with_sums(nearest(0.0)?, nearest(0.0)?, nearest(0.0)?));
+    }
+    let variance = error * error * count;
+    Ok(Mean::with_sums(
+        nearest(mean * count)?,
+        nearest(count * (variance + mean * mean))?,
+        nearest(count)?,
+    ))
+}
+
+/// Rebuild a [WeightedMean] from its mean, standard error of the mean, sums of weights
+/// and number of samples.
+pub(crate) fn weighted_mean_from_summary<T, W, O, C>(
+    mean: f64,
+    error: f64,
+    sum_of_weights: f64,
+    sum_of_weights_squared: f64,
+    count: f64,
+) -> Result<WeightedMean<T, W, O, C>, ConversionError>
+where
+    T: NumCast + Copy,
+    W: NumCast + Copy,
+    O: From<T> + From<W> + From<C> + Float + Signed,
+    C: NumCast + Copy,
+{
+    let (sumw, sumw2) = (sum_of_weights, sum_of_weights_squared);
+    let (mean, variance) = if sumw != 0.0 && sumw2 != 0.0 {
+        (mean, error * error * sumw * sumw / sumw2)
+    } else {
+        (0.0, 0.0)
+    };
+    Ok(WeightedMean::with_sums(
+        nearest(mean * sumw)?,
+        nearest(sumw * (variance + mean * mean))?,
+        nearest(sumw)?,
+        nearest(sumw2)?,
+        nearest(count)?,
+    ))
+}
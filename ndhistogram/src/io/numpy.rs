@@ -0,0 +1,469 @@
+//! Export of histograms to NumPy `.npy` and `.npz` files
+//!
+//! [write_npy] writes the bin values of a [VecHistogram] as an N-dimensional
+//! [NPY](https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html) array
+//! in C order, with one dimension per axis. This reorders the values from the
+//! ndhistogram layout, where the first axis varies fastest.
+//!
+//! [write_npz] writes an `.npz` archive containing the bin values as `hist` and
+//! the bin edges of each axis as `edges_0`, `edges_1`, ...
+//! Without flow bins, this matches the output of `numpy.histogramdd`:
+//!
+//! ```python
+//! import numpy as np
+//! data = np.load("hist.npz")
+//! hist = data["hist"]
+//! edges = [data[f"edges_{i}"] for i in range(hist.ndim)]
+//! ```
+//!
+//! If flow bins are included, the edges of axes with underflow and overflow
+//! bins start with `-inf` and end with `inf`.
+//! Axes with discrete bins, such as [Category] and [Boolean],
+//! have edges 0, 1, ..., N for their N bins.
+//! A [Hexagonal](crate::axis::Hexagonal) axis does not correspond to a single
+//! array dimension, so histograms with such an axis cannot be exported.
+//!
+//! Values that are not primitive numbers may be exported with [write_npy_with]
+//! and [write_npz_with], for example to write the mean of a [Mean](crate::value::Mean).
+//!
+//! Requires the "numpy" feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, axis::Uniform, io::numpy::write_npz};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut hist = ndhistogram!(Uniform::new(10, 0.0, 1.0)?, Uniform::new(5, 0.0, 1.0)?);
+//! hist.fill(&(0.5, 0.5));
+//! let mut npz = Vec::new();
+//! // write the 10x5 histogram without flow bins
+//! write_npz(&hist, false, &mut npz)?;
+//! # Ok(()) }
+//! ```
+
+use std::io;
+
+use num_traits::{NumCast, PrimInt};
+
+use crate::{
+    axis::{
+        Axis, BinInterval, Boolean, Category, CategoryNoFlow, IntCategory, IntCategoryNoFlow,
+        OrderedCategory, OrderedCategoryNoFlow, SingleValueBinInterval, StrCategory,
+        StrCategoryNoFlow, Uniform, UniformCyclic, UniformNoFlow, Variable, VariableCyclic,
+        VariableNoFlow,
+    },
+    io::c_order_selected_indices,
+    Axes, AxesTuple, VecHistogram,
+};
+
+/// A primitive type that can be written as an element of an NPY array.
+pub trait NpyElement: Copy {
+    /// NumPy data type description, for example `<f8` for little-endian 64-bit floats.
+    const DESCR: &'static str;
+
+    /// Append the little-endian bytes of this value.
+    fn write_bytes(&self, bytes: &mut Vec<u8>);
+}
+
+macro_rules! impl_npy_element {
+    ($($T:ty => $descr:expr),*) => {
+        $(
+            impl NpyElement for $T {
+                const DESCR: &'static str = $descr;
+
+                fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                    bytes.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_npy_element! {
+    i8 => "|i1", i16 => "<i2", i32 => "<i4", i64 => "<i8",
+    u8 => "|u1", u16 => "<u2", u32 => "<u4", u64 => "<u8",
+    f32 => "<f4", f64 => "<f8"
+}
+
+/// An [Axis] that can describe its bins as NumPy bin edges.
+pub trait NpyAxis: Axis {
+    /// Indices of the bins that are not underflow or overflow bins, in increasing order.
+    fn inner_indices(&self) -> Vec<usize>;
+
+    /// Bin edges of the selected bins, with length one more than the number of bins.
+    ///
+    /// If flow is true, all bins are included, otherwise only the bins
+    /// returned by [NpyAxis::inner_indices].
+    fn edges(&self, flow: bool) -> Vec<f64>;
+}
+
+/// A set of [Axes] that can be written as NumPy arrays.
+///
+/// This is implemented for [AxesTuple] where each axis implements [NpyAxis].
+pub trait NpyAxes: Axes {
+    /// The bin indices of each axis to include in an array.
+    fn selected_indices(&self, flow: bool) -> Vec<Vec<usize>>;
+
+    /// The bin edges of each axis.
+    fn edges(&self, flow: bool) -> Vec<Vec<f64>>;
+}
+
+fn interval_edges<A, T>(axis: &A, flow: bool) -> Vec<f64>
+where
+    A: Axis<BinInterval = BinInterval<T>>,
+    T: NumCast + Copy,
+{
+    let to_f64 = |value: T| value.to_f64().unwrap_or(f64::NAN);
+    let mut edges = Vec::new();
+    for bin in axis.bins() {
+        let (start, end) = match bin {
+            BinInterval::Underflow { end } if flow => (f64::NEG_INFINITY, to_f64(end)),
+            BinInterval::Overflow { start } if flow => (to_f64(start), f64::INFINITY),
+            BinInterval::Bin { start, end } => (to_f64(start), to_f64(end)),
+            _ => continue,
+        };
+        if edges.is_empty() {
+            edges.push(start);
+        }
+        edges.push(end);
+    }
+    edges
+}
+
+fn interval_inner_indices<A, T>(axis: &A) -> Vec<usize>
+where
+    A: Axis<BinInterval = BinInterval<T>>,
+{
+    axis.indices()
+        .filter(|index| matches!(axis.bin(*index), Some(BinInterval::Bin { .. })))
+        .collect()
+}
+
+macro_rules! impl_npy_axis_for_interval_axis {
+    ($($Axis:ident),*) => {
+        $(
+            impl<T> NpyAxis for $Axis<T>
+            where
+                Self: Axis<BinInterval = BinInterval<T>>,
+                T: NumCast + Copy,
+            {
+                fn inner_indices(&self) -> Vec<usize> {
+                    interval_inner_indices(self)
+                }
+
+                fn edges(&self, flow: bool) -> Vec<f64> {
+                    interval_edges(self, flow)
+                }
+            }
+        )*
+    };
+}
+
+impl_npy_axis_for_interval_axis! {Uniform, UniformNoFlow, UniformCyclic, Variable, VariableNoFlow, VariableCyclic}
+
+macro_rules! impl_npy_axis_for_category_axis {
+    ([] $($Axis:ident),*) => {
+        $(
+            impl NpyAxis for $Axis {
+                impl_npy_axis_for_category_axis!(@BODY);
+            }
+        )*
+    };
+    (@IMPL [$($bound:tt)+] $Axis:ident) => {
+        impl<T> NpyAxis for $Axis<T>
+        where
+            Self: Axis<BinInterval = SingleValueBinInterval<T>>,
+            T: $($bound)+,
+        {
+            impl_npy_axis_for_category_axis!(@BODY);
+        }
+    };
+    (@BODY) => {
+        fn inner_indices(&self) -> Vec<usize> {
+            self.indices()
+                .filter(|index| matches!(self.bin(*index), Some(SingleValueBinInterval::Bin { .. })))
+                .collect()
+        }
+
+        fn edges(&self, flow: bool) -> Vec<f64> {
+            let num_bins = if flow { self.num_bins() } else { self.inner_indices().len() };
+            (0..=num_bins).map(|edge| edge as f64).collect()
+        }
+    };
+    ($bound:tt $($Axis:ident),*) => {
+        $(impl_npy_axis_for_category_axis!(@IMPL $bound $Axis);)*
+    };
+}
+
+impl_npy_axis_for_category_axis! {[Eq + std::hash::Hash] Category, CategoryNoFlow}
+impl_npy_axis_for_category_axis! {[PrimInt] IntCategory, IntCategoryNoFlow}
+impl_npy_axis_for_category_axis! {[Ord + Clone] OrderedCategory, OrderedCategoryNoFlow}
+impl_npy_axis_for_category_axis! {[] StrCategory, StrCategoryNoFlow}
+
+impl NpyAxis for Boolean {
+    fn inner_indices(&self) -> Vec<usize> {
+        self.indices().collect()
+    }
+
+    fn edges(&self, _flow: bool) -> Vec<f64> {
+        vec![0.0, 1.0, 2.0]
+    }
+}
+
+// Implements NpyAxes for every AxesTuple from 1 dimension up to the full list of type parameters.
+macro_rules! impl_npy_axes {
+    ([$($done:ident: $done_index:tt),*] $next:ident: $next_index:tt $(, $rest:ident: $rest_index:tt)*) => {
+        impl_npy_axes!(@IMPL $($done: $done_index,)* $next: $next_index);
+        impl_npy_axes!([$($done: $done_index,)* $next: $next_index] $($rest: $rest_index),*);
+    };
+    ([$($done:ident: $done_index:tt),*]) => {};
+    (@IMPL $($T:ident: $index:tt),+) => {
+        impl<$($T: NpyAxis),+> NpyAxes for AxesTuple<($($T,)+)>
+        where
+            Self: Axes,
+        {
+            fn selected_indices(&self, flow: bool) -> Vec<Vec<usize>> {
+                let axes = self.as_tuple();
+                vec![$(
+                    if flow {
+                        axes.$index.indices().collect()
+                    } else {
+                        axes.$index.inner_indices()
+                    }
+                ),+]
+            }
+
+            fn edges(&self, flow: bool) -> Vec<Vec<f64>> {
+                let axes = self.as_tuple();
+                vec![$(axes.$index.edges(flow)),+]
+            }
+        }
+    };
+}
+
+impl_npy_axes! {
+    [] X: 0,
+    Y: 1,
+    Z: 2,
+    T: 3,
+    D4: 4,
+    D5: 5,
+    D6: 6,
+    D7: 7,
+    D8: 8,
+    D9: 9,
+    D10: 10,
+    D11: 11,
+    D12: 12,
+    D13: 13,
+    D14: 14,
+    D15: 15,
+    D16: 16,
+    D17: 17,
+    D18: 18,
+    D19: 19,
+    D20: 20
+}
+
+/// The shape and bin values of a histogram in C order.
+///
+/// If flow is false, the underflow and overflow bins are excluded.
+pub fn c_order_values<A: NpyAxes, V>(
+    hist: &VecHistogram<A, V>,
+    flow: bool,
+) -> (Vec<usize>, Vec<&V>) {
+    let axes = crate::Histogram::axes(hist);
+    let selected = axes.selected_indices(flow);
+    let shape = selected.iter().map(Vec::len).collect();
+    let values = c_order_selected_indices(&selected, &axes.shape())
+        .into_iter()
+        .map(|index| &hist.as_slice()[index])
+        .collect();
+    (shape, values)
+}
+
+/// Encode an array in the NPY format version 1.0.
+fn npy_bytes<E: NpyElement>(shape: &[usize], values: impl IntoIterator<Item = E>) -> Vec<u8> {
+    let shape = match shape {
+        [num_bins] => format!("({num_bins},)"),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        E::DESCR,
+        shape
+    );
+    // pad so that the data is aligned to 64 bytes, with a newline as the last character
+    let preamble_length = 10;
+    let padding = 63 - (preamble_length + header.len()) % 64;
+    header.extend(std::iter::repeat(' ').take(padding));
+    header.push('\n');
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"\x93NUMPY\x01\x00");
+    bytes.extend_from_slice(
+        &u16::try_from(header.len())
+            .expect("NPY header length is at most 64 bytes per dimension")
+            .to_le_bytes(),
+    );
+    bytes.extend_from_slice(header.as_bytes());
+    for value in values {
+        value.write_bytes(&mut bytes);
+    }
+    bytes
+}
+
+/// Write the bin values of a histogram as an NPY array.
+///
+/// The array has one dimension per axis, in C order.
+/// If flow is false, the underflow and overflow bins are excluded.
+pub fn write_npy<A, V, W>(hist: &VecHistogram<A, V>, flow: bool, writer: W) -> io::Result<()>
+where
+    A: NpyAxes,
+    V: NpyElement,
+    W: io::Write,
+{
+    write_npy_with(hist, flow, writer, |value| *value)
+}
+
+/// Write a function of the bin values of a histogram as an NPY array.
+///
+/// See [write_npy].
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, Histogram, axis::Uniform, value::WeightedSum};
+/// use ndhistogram::io::numpy::write_npy_with;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut hist = ndhistogram!(Uniform::new(10, 0.0, 1.0)?; WeightedSum);
+/// hist.fill_with(&0.5, 2.0);
+/// let mut variances = Vec::new();
+/// write_npy_with(&hist, false, &mut variances, |value| value.variance())?;
+/// # Ok(()) }
+/// ```
+pub fn write_npy_with<A, V, W, E, F>(
+    hist: &VecHistogram<A, V>,
+    flow: bool,
+    mut writer: W,
+    f: F,
+) -> io::Result<()>
+where
+    A: NpyAxes,
+    W: io::Write,
+    E: NpyElement,
+    F: Fn(&V) -> E,
+{
+    let (shape, values) = c_order_values(hist, flow);
+    writer.write_all(&npy_bytes(&shape, values.into_iter().map(f)))
+}
+
+/// Write the bin values and bin edges of a histogram as an NPZ archive.
+///
+/// The bin values are stored as `hist` and the edges of axis N as `edges_N`.
+/// If flow is false, the underflow and overflow bins are excluded.
+pub fn write_npz<A, V, W>(hist: &VecHistogram<A, V>, flow: bool, writer: W) -> io::Result<()>
+where
+    A: NpyAxes,
+    V: NpyElement,
+    W: io::Write,
+{
+    write_npz_with(hist, flow, writer, |value| *value)
+}
+
+/// Write a function of the bin values and the bin edges of a histogram as an NPZ archive.
+///
+/// See [write_npz].
+pub fn write_npz_with<A, V, W, E, F>(
+    hist: &VecHistogram<A, V>,
+    flow: bool,
+    writer: W,
+    f: F,
+) -> io::Result<()>
+where
+    A: NpyAxes,
+    W: io::Write,
+    E: NpyElement,
+    F: Fn(&V) -> E,
+{
+    let (shape, values) = c_order_values(hist, flow);
+    let mut entries = vec![(
+        "hist.npy".to_string(),
+        npy_bytes(&shape, values.into_iter().map(f)),
+    )];
+    for (axis, edges) in crate::Histogram::axes(hist)
+        .edges(flow)
+        .into_iter()
+        .enumerate()
+    {
+        entries.push((
+            format!("edges_{axis}.npy"),
+            npy_bytes(&[edges.len()], edges),
+        ));
+    }
+    write_zip(writer, &entries)
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for byte in bytes {
+        crc ^= <u32 as From<u8>>::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Write an uncompressed zip archive, as written by `numpy.savez`.
+fn write_zip<W: io::Write>(mut writer: W, entries: &[(String, Vec<u8>)]) -> io::Result<()> {
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "NPZ archive exceeds 4 GiB");
+    let to_u32 = |value: usize| u32::try_from(value).map_err(|_| too_large());
+    let to_u16 = |value: usize| u16::try_from(value).map_err(|_| too_large());
+    let mut central_directory = Vec::new();
+    let mut offset = 0;
+    for (name, data) in entries {
+        // version needed, flags, compression (stored), modification time and date
+        let common = [20_u16, 0, 0, 0, 0x21]
+            .iter()
+            .flat_map(|it| it.to_le_bytes())
+            .chain(crc32(data).to_le_bytes())
+            .chain(to_u32(data.len())?.to_le_bytes())
+            .chain(to_u32(data.len())?.to_le_bytes())
+            .chain(to_u16(name.len())?.to_le_bytes())
+            .chain(0_u16.to_le_bytes())
+            .collect::<Vec<u8>>();
+        let mut local_header = b"PK\x03\x04".to_vec();
+        local_header.extend(&common);
+        local_header.extend(name.as_bytes());
+        writer.write_all(&local_header)?;
+        writer.write_all(data)?;
+
+        central_directory.extend(b"PK\x01\x02");
+        central_directory.extend(20_u16.to_le_bytes());
+        central_directory.extend(&common);
+        // comment length, disk number, internal and external attributes
+        central_directory.extend([0_u8; 10]);
+        central_directory.extend(to_u32(offset)?.to_le_bytes());
+        central_directory.extend(name.as_bytes());
+        offset += local_header.len() + data.len();
+    }
+    writer.write_all(&central_directory)?;
+    let mut end = b"PK\x05\x06".to_vec();
+    end.extend([0_u8; 4]);
+    end.extend(to_u16(entries.len())?.to_le_bytes());
+    end.extend(to_u16(entries.len())?.to_le_bytes());
+    end.extend(to_u32(central_directory.len())?.to_le_bytes());
+    end.extend(to_u32(offset)?.to_le_bytes());
+    end.extend([0_u8; 2]);
+    writer.write_all(&end)
+}
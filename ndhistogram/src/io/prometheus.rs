@@ -0,0 +1,287 @@
+//! Export of histograms in the Prometheus text exposition format
+//!
+//! [write_prometheus] writes a 1D histogram over an axis with [BinInterval]s, such as
+//! [Uniform](crate::axis::Uniform) or [Variable](crate::axis::Variable), as a
+//! Prometheus histogram metric:
+//!
+//! - one cumulative `<name>_bucket{le="..."}` line for the end of each bin, starting with
+//!   the end of the underflow bin, followed by `le="+Inf"` which includes the overflow bin,
+//! - `<name>_sum`, the sum of the observed values,
+//! - `<name>_count`, the total number of observations, including the underflow and
+//!   overflow bins.
+//!
+//! [write_prometheus_with_label] writes a 2D histogram whose second axis has
+//! [SingleValueBinInterval]s, such as [Category](crate::axis::Category), as one series of
+//! these lines for each category, with the category as the value of a label.
+//! The overflow bin of a [Category](crate::axis::Category) axis is written with an empty
+//! label value, which Prometheus treats as the label being absent.
+//!
+//! As ndhistogram does not keep track of the observed values, `<name>_sum` is
+//! estimated as if each observation were at the center of its bin, or at the edge of
+//! the underflow and overflow bins.
+//! Bins of ndhistogram include their start and exclude their end, while Prometheus buckets
+//! include their upper bound, so observations exactly on a bin edge are counted in the
+//! next bucket.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, axis::Variable};
+//! use ndhistogram::io::prometheus::write_prometheus;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut hist = ndhistogram!(Variable::new(vec![0.0, 0.1, 0.5])?; u64);
+//! hist.fill(&0.05);
+//! hist.fill(&0.25);
+//! hist.fill(&2.0);
+//! let mut output = Vec::new();
+//! write_prometheus(&hist, "request_duration_seconds", "Request duration.", &mut output)?;
+//! assert_eq!(
+//!     String::from_utf8(output)?,
+//!     "# HELP request_duration_seconds Request duration.\n# TYPE request_duration_seconds histogram\n\
+//!      request_duration_seconds_bucket{le=\"0\"} 0\n\
+//!      request_duration_seconds_bucket{le=\"0.1\"} 1\n\
+//!      request_duration_seconds_bucket{le=\"0.5\"} 2\n\
+//!      request_duration_seconds_bucket{le=\"+Inf\"} 3\n\
+//!      request_duration_seconds_sum 0.85\n\
+//!      request_duration_seconds_count 3\n"
+//! );
+//! # Ok(()) }
+//! ```
+
+use std::{fmt::Display, io};
+
+use num_traits::{NumCast, ToPrimitive};
+
+use crate::{
+    axis::{Axis, BinInterval, SingleValueBinInterval},
+    value::{Sum, WeightedSum},
+    Histogram,
+};
+
+/// A bin value that can be written as the number of observations in a bin.
+pub trait PrometheusValue {
+    /// The number of observations, or the sum of weights, in the bin.
+    fn observations(&self) -> f64;
+}
+
+macro_rules! impl_prometheus_value_for_primitive {
+    ($($T:ty),*) => {
+        $(
+            impl PrometheusValue for $T {
+                fn observations(&self) -> f64 {
+                    self.to_f64().unwrap_or(f64::NAN)
+                }
+            }
+        )*
+    };
+}
+
+impl_prometheus_value_for_primitive! {i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64}
+
+impl<T: NumCast + Copy> PrometheusValue for Sum<T> {
+    fn observations(&self) -> f64 {
+        self.get().to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+impl<T: NumCast + Copy> PrometheusValue for WeightedSum<T> {
+    fn observations(&self) -> f64 {
+        self.sum().to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+/// Write a 1D histogram as a Prometheus histogram metric.
+///
+/// See the [module documentation](self) for a description of the output.
+/// Returns an error of kind [io::ErrorKind::InvalidInput] if the name is not a valid
+/// metric name.
+pub fn write_prometheus<A, T, V, H, W>(
+    hist: &H,
+    name: &str,
+    help: &str,
+    mut writer: W,
+) -> io::Result<()>
+where
+    H: Histogram<A, V>,
+    A: Axis<BinInterval = BinInterval<T>>,
+    T: NumCast + Copy,
+    V: PrometheusValue,
+    W: io::Write,
+{
+    check_name(name, is_metric_name)?;
+    write_header(&mut writer, name, help)?;
+    let bins = sorted_bins(hist, |bin| bin);
+    write_series(&mut writer, name, "", &bins)
+}
+
+/// Write a 2D histogram as a Prometheus histogram metric with one series for each bin
+/// of the second axis, labelled with the bin value.
+///
+/// See the [module documentation](self) for a description of the output.
+/// Returns an error of kind [io::ErrorKind::InvalidInput] if the name is not a valid
+/// metric name or the label is not a valid label name.
+pub fn write_prometheus_with_label<A, T, L, V, H, W>(
+    hist: &H,
+    name: &str,
+    help: &str,
+    label: &str,
+    mut writer: W,
+) -> io::Result<()>
+where
+    H: Histogram<A, V>,
+    A: Axis<BinInterval = (BinInterval<T>, SingleValueBinInterval<L>)>,
+    T: NumCast + Copy,
+    L: Display,
+    V: PrometheusValue,
+    W: io::Write,
+{
+    check_name(name, is_metric_name)?;
+    check_name(label, |label| is_label_name(label) && label != "le")?;
+    write_header(&mut writer, name, help)?;
+    let bins = sorted_bins(hist, |(bin, category)| {
+        let value = category.value().map(ToString::to_string);
+        (bin, value.unwrap_or_default())
+    });
+    // bins are ordered with the first axis varying fastest, so each category is a
+    // contiguous run of bins
+    let mut start = 0;
+    while start < bins.len() {
+        let category = &bins[start].0 .1;
+        let end = bins[start..]
+            .iter()
+            .position(|((_, it), _)| it != category)
+            .map_or(bins.len(), |length| start + length);
+        let labels = format!("{}=\"{}\"", label, escape_label_value(category));
+        let series: Vec<_> = bins[start..end]
+            .iter()
+            .map(|((bin, _), observations)| (*bin, *observations))
+            .collect();
+        write_series(&mut writer, name, &labels, &series)?;
+        start = end;
+    }
+    Ok(())
+}
+
+/// The bins of the histogram in order of bin index with their number of observations,
+/// including bins that are not stored by sparse histograms.
+fn sorted_bins<A, V, H, B>(hist: &H, convert: impl Fn(A::BinInterval) -> B) -> Vec<(B, f64)>
+where
+    H: Histogram<A, V>,
+    A: Axis,
+    V: PrometheusValue,
+{
+    hist.axes()
+        .iter()
+        .map(|(index, bin)| {
+            let observations = hist
+                .value_at_index(index)
+                .map_or(0.0, PrometheusValue::observations);
+            (convert(bin), observations)
+        })
+        .collect()
+}
+
+fn write_header<W: io::Write>(writer: &mut W, name: &str, help: &str) -> io::Result<()> {
+    writeln!(writer, "# HELP {} {}", name, escape_help(help))?;
+    writeln!(writer, "# TYPE {} histogram", name)
+}
+
+/// Write the bucket, sum and count lines of the bins of one series.
+fn write_series<W: io::Write, T: NumCast + Copy>(
+    writer: &mut W,
+    name: &str,
+    labels: &str,
+    bins: &[(BinInterval<T>, f64)],
+) -> io::Result<()> {
+    let separator = if labels.is_empty() { "" } else { "," };
+    let braced = if labels.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", labels)
+    };
+    let mut count = 0.0;
+    let mut sum = 0.0;
+    for (bin, observations) in bins {
+        count += observations;
+        let coordinate = match bin {
+            BinInterval::Underflow { end } => to_f64(*end),
+            BinInterval::Overflow { start } => to_f64(*start),
+            BinInterval::Bin { start, end } => (to_f64(*start) + to_f64(*end)) / 2.0,
+        };
+        sum += observations * coordinate;
+        if let Some(end) = bin.end().map(to_f64).filter(|end| end.is_finite()) {
+            writeln!(
+                writer,
+                "{}_bucket{{{}{}le=\"{}\"}} {}",
+                name,
+                labels,
+                separator,
+                format_float(end),
+                format_float(count)
+            )?;
+        }
+    }
+    writeln!(
+        writer,
+        "{}_bucket{{{}{}le=\"+Inf\"}} {}",
+        name,
+        labels,
+        separator,
+        format_float(count)
+    )?;
+    writeln!(writer, "{}_sum{} {}", name, braced, format_float(sum))?;
+    writeln!(writer, "{}_count{} {}", name, braced, format_float(count))
+}
+
+fn to_f64<T: NumCast>(value: T) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Format a number as in the Prometheus text format, which spells out infinities and NaN.
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn escape_label_value(value: &str) -> String {
+    escape_help(value).replace('"', "\\\"")
+}
+
+fn is_metric_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+fn is_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn check_name(name: &str, is_valid: impl Fn(&str) -> bool) -> io::Result<()> {
+    if is_valid(name) {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid Prometheus name \"{}\"", name),
+        ))
+    }
+}
@@ -0,0 +1,248 @@
+use num_traits::Float;
+
+use crate::{
+    axis::{Axis, Uniform, UniformNoFlow, Variable, VariableNoFlow},
+    error::ConversionError,
+    Axes, AxesTuple,
+};
+
+/// The binning of a ROOT TAxis.
+///
+/// ROOT axes always have an underflow and an overflow bin in addition to
+/// the bins between the low and high edges.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TAxis {
+    /// Number of bins, excluding the underflow and overflow bins.
+    pub num_bins: usize,
+    /// Low edge of the first bin.
+    pub low: f64,
+    /// High edge of the last bin.
+    pub high: f64,
+    /// Bin edges for variable binning, or empty for fixed size bins.
+    pub edges: Vec<f64>,
+}
+
+impl TAxis {
+    /// An axis with num_bins fixed size bins in the range [low, high).
+    pub fn fixed(num_bins: usize, low: f64, high: f64) -> Self {
+        Self {
+            num_bins,
+            low,
+            high,
+            edges: Vec::new(),
+        }
+    }
+
+    /// An axis with variable binning given by a set of increasing bin edges.
+    pub fn variable(edges: Vec<f64>) -> Self {
+        Self {
+            num_bins: edges.len().saturating_sub(1),
+            low: edges.first().copied().unwrap_or_default(),
+            high: edges.last().copied().unwrap_or_default(),
+            edges,
+        }
+    }
+
+    /// The bin edges, computed from the low and high edges for fixed size bins.
+    pub fn bin_edges(&self) -> Vec<f64> {
+        if self.edges.is_empty() {
+            let width = (self.high - self.low) / self.num_bins as f64;
+            (0..=self.num_bins)
+                .map(|edge| self.low + edge as f64 * width)
+                .collect()
+        } else {
+            self.edges.clone()
+        }
+    }
+
+    /// The center of each bin, excluding the underflow and overflow bins.
+    pub(crate) fn bin_centers(&self) -> Vec<f64> {
+        self.bin_edges()
+            .windows(2)
+            .map(|edges| (edges[0] + edges[1]) / 2.0)
+            .collect()
+    }
+}
+
+/// An [Axis] that can be converted to and from a ROOT [TAxis].
+pub trait RootAxis: Axis + Sized {
+    /// Convert this axis to a ROOT axis.
+    fn to_root_axis(&self) -> Result<TAxis, ConversionError>;
+
+    /// The ROOT bin number of a bin index, where 0 is the underflow bin.
+    fn root_bin(&self, index: usize) -> usize;
+
+    /// Create an axis from a ROOT axis.
+    ///
+    /// Returns [ConversionError::IncompatibleAxis] if the axis cannot represent the
+    /// ROOT binning. The axis number is always 0 and is updated by
+    /// [RootAxes::from_root_axes].
+    fn from_root_axis(axis: &TAxis) -> Result<Self, ConversionError>;
+}
+
+/// A set of [Axes] that can be converted to and from ROOT axes.
+///
+/// This is implemented for [AxesTuple] with 1, 2 or 3 axes that implement [RootAxis],
+/// which correspond to TH1, TH2 and TH3 histograms.
+pub trait RootAxes: Axes + Sized {
+    /// Convert each axis to a ROOT axis.
+    fn to_root_axes(&self) -> Result<Vec<TAxis>, ConversionError>;
+
+    /// The ROOT global bin number of each bin index, in order of bin index.
+    fn root_bins(&self) -> Result<Vec<usize>, ConversionError>;
+
+    /// Create the axes from ROOT axes.
+    fn from_root_axes(axes: &[TAxis]) -> Result<Self, ConversionError>;
+}
+
+fn to_f64<T: Float>(value: T) -> Result<f64, ConversionError> {
+    value.to_f64().ok_or(ConversionError::ValueOutOfRange)
+}
+
+fn from_f64<T: Float>(value: f64) -> Result<T, ConversionError> {
+    T::from(value).ok_or(ConversionError::ValueOutOfRange)
+}
+
+fn fixed<T: Float>(num_bins: usize, low: T, high: T) -> Result<TAxis, ConversionError> {
+    Ok(TAxis::fixed(num_bins, to_f64(low)?, to_f64(high)?))
+}
+
+fn variable<T: Float>(edges: &[T]) -> Result<TAxis, ConversionError> {
+    Ok(TAxis::variable(
+        edges
+            .iter()
+            .copied()
+            .map(to_f64)
+            .collect::<Result<_, _>>()?,
+    ))
+}
+
+/// Get the parameters of a ROOT axis with fixed size bins.
+fn as_fixed<T: Float>(axis: &TAxis) -> Result<(usize, T, T), ConversionError> {
+    if axis.edges.is_empty() {
+        Ok((axis.num_bins, from_f64(axis.low)?, from_f64(axis.high)?))
+    } else {
+        Err(ConversionError::IncompatibleAxis(0))
+    }
+}
+
+fn as_variable<T: Float>(axis: &TAxis) -> Result<Vec<T>, ConversionError> {
+    axis.bin_edges().into_iter().map(from_f64).collect()
+}
+
+impl<T: Float> RootAxis for Uniform<T> {
+    fn to_root_axis(&self) -> Result<TAxis, ConversionError> {
+        fixed(self.num_bins() - 2, *self.low(), *self.high())
+    }
+
+    fn root_bin(&self, index: usize) -> usize {
+        index
+    }
+
+    fn from_root_axis(axis: &TAxis) -> Result<Self, ConversionError> {
+        let (num_bins, low, high) = as_fixed(axis)?;
+        Ok(Self::new(num_bins, low, high)?)
+    }
+}
+
+/// The contents of the ROOT underflow and overflow bins are not read.
+impl<T: Float> RootAxis for UniformNoFlow<T> {
+    fn to_root_axis(&self) -> Result<TAxis, ConversionError> {
+        fixed(self.num_bins(), *self.low(), *self.high())
+    }
+
+    fn root_bin(&self, index: usize) -> usize {
+        index + 1
+    }
+
+    fn from_root_axis(axis: &TAxis) -> Result<Self, ConversionError> {
+        let (num_bins, low, high) = as_fixed(axis)?;
+        Ok(Self::new(num_bins, low, high)?)
+    }
+}
+
+impl<T: Float> RootAxis for Variable<T> {
+    fn to_root_axis(&self) -> Result<TAxis, ConversionError> {
+        variable(self.bin_edges())
+    }
+
+    fn root_bin(&self, index: usize) -> usize {
+        index
+    }
+
+    fn from_root_axis(axis: &TAxis) -> Result<Self, ConversionError> {
+        Ok(Self::new(as_variable(axis)?)?)
+    }
+}
+
+/// The contents of the ROOT underflow and overflow bins are not read.
+impl<T: Float> RootAxis for VariableNoFlow<T> {
+    fn to_root_axis(&self) -> Result<TAxis, ConversionError> {
+        variable(self.bin_edges())
+    }
+
+    fn root_bin(&self, index: usize) -> usize {
+        index + 1
+    }
+
+    fn from_root_axis(axis: &TAxis) -> Result<Self, ConversionError> {
+        Ok(Self::new(as_variable(axis)?)?)
+    }
+}
+
+fn with_axis_number<T>(
+    result: Result<T, ConversionError>,
+    axis: usize,
+) -> Result<T, ConversionError> {
+    result.map_err(|error| match error {
+        ConversionError::IncompatibleAxis(_) => ConversionError::IncompatibleAxis(axis),
+        error => error,
+    })
+}
+
+macro_rules! impl_root_axes {
+    ($($T:ident: $index:tt),+) => {
+        impl<$($T: RootAxis),+> RootAxes for AxesTuple<($($T,)+)>
+        where
+            Self: Axes,
+        {
+            fn to_root_axes(&self) -> Result<Vec<TAxis>, ConversionError> {
+                let axes = self.as_tuple();
+                Ok(vec![$(axes.$index.to_root_axis()?),+])
+            }
+
+            fn root_bins(&self) -> Result<Vec<usize>, ConversionError> {
+                let axes = self.as_tuple();
+                let mut bins = vec![0];
+                let mut stride = 1;
+                $(
+                    let axis = &axes.$index;
+                    bins = (0..axis.num_bins())
+                        .flat_map(|index| {
+                            let offset = axis.root_bin(index) * stride;
+                            bins.iter().map(move |bin| bin + offset)
+                        })
+                        .collect();
+                    stride *= axis.to_root_axis()?.num_bins + 2;
+                )+
+                let _ = stride;
+                Ok(bins)
+            }
+
+            fn from_root_axes(axes: &[TAxis]) -> Result<Self, ConversionError> {
+                const NUM_AXES: usize = [$($index),+].len();
+                if axes.len() != NUM_AXES {
+                    return Err(ConversionError::WrongNumberOfAxes {
+                        expected: NUM_AXES,
+                        actual: axes.len(),
+                    });
+                }
+                Ok(($(with_axis_number($T::from_root_axis(&axes[$index]), $index)?,)+).into())
+            }
+        }
+    };
+}
+
+impl_root_axes! {X: 0}
+impl_root_axes! {X: 0, Y: 1}
+impl_root_axes! {X: 0, Y: 1, Z: 2}
@@ -0,0 +1,232 @@
+//! Big-endian buffers with the primitive encodings used in ROOT files.
+
+use crate::error::ConversionError;
+
+/// Flag set on byte counts in front of streamed objects.
+const BYTE_COUNT_MASK: u32 = 0x4000_0000;
+
+/// A buffer that ROOT data is streamed into.
+#[derive(Default, Debug)]
+pub(crate) struct WriteBuffer {
+    bytes: Vec<u8>,
+}
+
+impl WriteBuffer {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub(crate) fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub(crate) fn i16(&mut self, value: i16) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    pub(crate) fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    pub(crate) fn i32(&mut self, value: i32) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    pub(crate) fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    pub(crate) fn f32(&mut self, value: f32) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    pub(crate) fn f64(&mut self, value: f64) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    /// A TString: a one byte length, or 255 followed by a four byte length, then the characters.
+    pub(crate) fn string(&mut self, value: &str) {
+        match u8::try_from(value.len()) {
+            Ok(length) if length < 255 => self.u8(length),
+            _ => {
+                self.u8(255);
+                self.u32(value.len() as u32);
+            }
+        }
+        self.bytes(value.as_bytes());
+    }
+
+    /// A null-terminated class name.
+    pub(crate) fn class_name(&mut self, value: &str) {
+        self.bytes(value.as_bytes());
+        self.u8(0);
+    }
+
+    /// A TArrayD: the number of elements followed by the elements.
+    pub(crate) fn array_f64(&mut self, values: &[f64]) {
+        self.i32(values.len() as i32);
+        values.iter().for_each(|value| self.f64(*value));
+    }
+
+    /// Reserve a byte count, returning its position for [WriteBuffer::end_byte_count].
+    pub(crate) fn start_byte_count(&mut self) -> usize {
+        let position = self.len();
+        self.u32(0);
+        position
+    }
+
+    /// Fill in a byte count reserved at the position with the number of bytes written since.
+    pub(crate) fn end_byte_count(&mut self, position: usize) {
+        let count = (self.len() - position - 4) as u32 | BYTE_COUNT_MASK;
+        self.bytes[position..position + 4].copy_from_slice(&count.to_be_bytes());
+    }
+
+    /// Start a streamed object with a byte count and class version.
+    pub(crate) fn start_object(&mut self, version: i16) -> usize {
+        let position = self.start_byte_count();
+        self.i16(version);
+        position
+    }
+}
+
+/// A cursor over ROOT data.
+#[derive(Clone, Debug)]
+pub(crate) struct ReadBuffer<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+fn invalid() -> ConversionError {
+    ConversionError::InvalidData
+}
+
+impl<'a> ReadBuffer<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub(crate) fn at(bytes: &'a [u8], position: usize) -> Result<Self, ConversionError> {
+        if position > bytes.len() {
+            return Err(invalid());
+        }
+        Ok(Self { bytes, position })
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn seek(&mut self, position: usize) -> Result<(), ConversionError> {
+        if position > self.bytes.len() {
+            return Err(invalid());
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    pub(crate) fn bytes(&mut self, length: usize) -> Result<&'a [u8], ConversionError> {
+        let end = self.position.checked_add(length).ok_or_else(invalid)?;
+        let bytes = self.bytes.get(self.position..end).ok_or_else(invalid)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ConversionError> {
+        Ok(self
+            .bytes(N)?
+            .try_into()
+            .expect("slice has the requested length"))
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, ConversionError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    pub(crate) fn i16(&mut self) -> Result<i16, ConversionError> {
+        Ok(i16::from_be_bytes(self.array()?))
+    }
+
+    pub(crate) fn i32(&mut self) -> Result<i32, ConversionError> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, ConversionError> {
+        Ok(u32::from_be_bytes(self.array()?))
+    }
+
+    pub(crate) fn i64(&mut self) -> Result<i64, ConversionError> {
+        Ok(i64::from_be_bytes(self.array()?))
+    }
+
+    pub(crate) fn f32(&mut self) -> Result<f32, ConversionError> {
+        Ok(f32::from_be_bytes(self.array()?))
+    }
+
+    pub(crate) fn f64(&mut self) -> Result<f64, ConversionError> {
+        Ok(f64::from_be_bytes(self.array()?))
+    }
+
+    /// A non-negative four byte integer, such as a length or file position.
+    pub(crate) fn usize_32(&mut self) -> Result<usize, ConversionError> {
+        usize::try_from(self.i32()?).map_err(|_| invalid())
+    }
+
+    /// A non-negative eight byte integer, such as a file position.
+    pub(crate) fn usize_64(&mut self) -> Result<usize, ConversionError> {
+        usize::try_from(self.i64()?).map_err(|_| invalid())
+    }
+
+    pub(crate) fn string(&mut self) -> Result<String, ConversionError> {
+        let length = match self.u8()? {
+            255 => self.u32()? as usize,
+            length => length as usize,
+        };
+        String::from_utf8(self.bytes(length)?.to_vec()).map_err(|_| invalid())
+    }
+
+    /// A TArrayD.
+    pub(crate) fn array_f64(&mut self) -> Result<Vec<f64>, ConversionError> {
+        let length = self.usize_32()?;
+        (0..length).map(|_| self.f64()).collect()
+    }
+
+    /// A TArrayF, converted to double precision.
+    pub(crate) fn array_f32(&mut self) -> Result<Vec<f64>, ConversionError> {
+        let length = self.usize_32()?;
+        (0..length).map(|_| Ok(f64::from(self.f32()?))).collect()
+    }
+
+    /// Read the byte count and version in front of a streamed object.
+    ///
+    /// Returns the version and the position of the end of the object.
+    pub(crate) fn start_object(&mut self) -> Result<(i16, usize), ConversionError> {
+        let count = self.u32()?;
+        if count & BYTE_COUNT_MASK == 0 {
+            return Err(invalid());
+        }
+        let end = self.position + (count & !BYTE_COUNT_MASK) as usize;
+        let version = self.i16()?;
+        if end > self.bytes.len() {
+            return Err(invalid());
+        }
+        Ok((version, end))
+    }
+
+    /// Skip a streamed object using its byte count.
+    pub(crate) fn skip_object(&mut self) -> Result<(), ConversionError> {
+        let (_, end) = self.start_object()?;
+        self.seek(end)
+    }
+}
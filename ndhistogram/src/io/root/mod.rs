@@ -0,0 +1,537 @@
+//! Writing and reading histograms in ROOT files
+//!
+//! [RootFile] writes histograms as TH1D, TH2D and TH3D objects to files that can be
+//! opened with [ROOT](https://root.cern), for example with
+//!
+//! ```text
+//! TFile f("hist.root");
+//! TH1D* h = f.Get<TH1D>("hist");
+//! ```
+//!
+//! Histograms with 1, 2 or 3 [Uniform](crate::axis::Uniform), [UniformNoFlow](crate::axis::UniformNoFlow),
+//! [Variable](crate::axis::Variable) or [VariableNoFlow](crate::axis::VariableNoFlow) axes
+//! may be written, with fixed or variable binning on the ROOT axes.
+//! ROOT axes always have underflow and overflow bins, which are empty for axes without flow bins.
+//! Bin values of [WeightedSum](crate::value::WeightedSum) store their variance in the
+//! Sumw2 array of the ROOT histogram, while primitive numbers and [Sum](crate::value::Sum)
+//! are written without Sumw2.
+//! The number of entries of the ROOT histogram is set to the effective number of entries,
+//! `sum(w)^2 / sum(w^2)`, which is the number of fills for unweighted histograms.
+//!
+//! Only the subset of the ROOT file format needed for histograms is implemented:
+//! objects are written uncompressed, files are limited to 2 GB and no StreamerInfo
+//! record is written, so ROOT reads the histograms with its own TH1D, TH2D and TH3D
+//! class definitions.
+//! ROOT versions other than 6.22 may warn that schema evolution is not possible.
+//!
+//! [RootFile::from_bytes] reads uncompressed TH1, TH2 and TH3 histograms with
+//! double or float bin contents back from a file, including those written by [RootFile].
+//! Other objects are skipped.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, Hist1D, axis::Uniform, value::WeightedSum};
+//! use ndhistogram::io::root::RootFile;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut hist = ndhistogram!(Uniform::new(10, 0.0, 1.0)?; WeightedSum);
+//! hist.fill_with(&0.5, 2.0);
+//! let mut file = RootFile::new("hist.root");
+//! file.add("hist", "An example histogram", &hist)?;
+//! let bytes = file.to_bytes()?;
+//! // std::fs::write("hist.root", &bytes)?;
+//!
+//! let read = RootFile::from_bytes(&bytes)?;
+//! let read: Hist1D<Uniform, WeightedSum> = read.get("hist")?;
+//! assert_eq!(read, hist);
+//! # Ok(()) }
+//! ```
+
+mod axis;
+mod buffer;
+mod streamer;
+mod value;
+
+pub use axis::{RootAxes, RootAxis, TAxis};
+pub use value::RootValue;
+
+use std::{
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{error::ConversionError, Histogram, VecHistogram};
+
+use buffer::{ReadBuffer, WriteBuffer};
+use streamer::RootHistogram;
+
+/// ROOT version written to the file header, 6.22/06.
+const ROOT_VERSION: i32 = 62206;
+/// Position of the top directory record.
+const BEGIN: usize = 100;
+/// TKey version for files smaller than 2 GB.
+const KEY_VERSION: i16 = 4;
+/// TDirectory version for files smaller than 2 GB.
+const DIRECTORY_VERSION: i16 = 5;
+/// Size of the TDirectory record after the file name and title.
+const DIRECTORY_LENGTH: usize = 60;
+/// Versions of keys and directories with 64-bit file positions are offset by this amount.
+const BIG_FILE_VERSION: i16 = 1000;
+/// End of the free segment that covers the rest of the file.
+const LAST_FREE_BYTE: i32 = 2_000_000_000;
+
+/// A set of named histograms that can be written to and read from a ROOT file.
+///
+/// See the [module documentation](self).
+#[derive(Clone, PartialEq, Debug)]
+pub struct RootFile {
+    name: String,
+    histograms: Vec<RootHistogram>,
+}
+
+impl RootFile {
+    /// Factory method for an empty RootFile.
+    ///
+    /// The name is stored in the file and is usually the file name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            histograms: Vec::new(),
+        }
+    }
+
+    /// The name stored in the file.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The names of the histograms in the file, in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.histograms
+            .iter()
+            .map(|histogram| histogram.name.as_str())
+    }
+
+    /// The title of the histogram with the given name.
+    pub fn title(&self, name: &str) -> Option<&str> {
+        self.find(name).map(|histogram| histogram.title.as_str())
+    }
+
+    fn find(&self, name: &str) -> Option<&RootHistogram> {
+        self.histograms
+            .iter()
+            .find(|histogram| histogram.name == name)
+    }
+
+    /// Add a histogram with the given name and title.
+    ///
+    /// A histogram with the same name is replaced.
+    pub fn add<A, V, H>(&mut self, name: &str, title: &str, hist: &H) -> Result<(), ConversionError>
+    where
+        H: Histogram<A, V>,
+        A: RootAxes,
+        V: RootValue,
+    {
+        let axes = hist.axes().to_root_axes()?;
+        let root_bins = hist.axes().root_bins()?;
+        let num_cells = RootHistogram::num_cells(&axes);
+        let mut contents = vec![0.0; num_cells];
+        let mut sumw2 = vec![0.0; if V::HAS_SUMW2 { num_cells } else { 0 }];
+        let (mut total_sumw, mut total_sumw2) = (0.0, 0.0);
+        for item in hist.iter() {
+            let (sum_of_weights, sum_of_weights_squared) = item.value.to_root_value()?;
+            let bin = root_bins[item.index];
+            contents[bin] = sum_of_weights;
+            if let Some(sumw2) = sumw2.get_mut(bin) {
+                *sumw2 = sum_of_weights_squared;
+            }
+            total_sumw += sum_of_weights;
+            total_sumw2 += sum_of_weights_squared;
+        }
+        let histogram = RootHistogram {
+            name: name.to_string(),
+            title: title.to_string(),
+            axes,
+            contents,
+            sumw2,
+            entries: if total_sumw2 > 0.0 {
+                total_sumw * total_sumw / total_sumw2
+            } else {
+                total_sumw
+            },
+        };
+        self.insert(histogram);
+        Ok(())
+    }
+
+    fn insert(&mut self, histogram: RootHistogram) {
+        match self
+            .histograms
+            .iter_mut()
+            .find(|existing| existing.name == histogram.name)
+        {
+            Some(existing) => *existing = histogram,
+            None => self.histograms.push(histogram),
+        }
+    }
+
+    /// Get the histogram with the given name.
+    ///
+    /// Returns [ConversionError::NotFound] if there is no histogram with this name,
+    /// [ConversionError::WrongNumberOfAxes] if it has a different number of dimensions than A
+    /// and [ConversionError::IncompatibleAxis] if the binning of an axis cannot be represented
+    /// by the corresponding axis of A.
+    pub fn get<A, V>(&self, name: &str) -> Result<VecHistogram<A, V>, ConversionError>
+    where
+        A: RootAxes,
+        V: RootValue,
+    {
+        let histogram = self.find(name).ok_or(ConversionError::NotFound)?;
+        let axes = A::from_root_axes(&histogram.axes)?;
+        let values = axes
+            .root_bins()?
+            .into_iter()
+            .map(|bin| {
+                let sum_of_weights = *histogram
+                    .contents
+                    .get(bin)
+                    .ok_or(ConversionError::ShapeMismatch)?;
+                let sum_of_weights_squared =
+                    histogram.sumw2.get(bin).copied().unwrap_or(sum_of_weights);
+                V::from_root_value(sum_of_weights, sum_of_weights_squared)
+            })
+            .collect::<Result<Vec<_>, ConversionError>>()?;
+        VecHistogram::from_vec(axes, values).map_err(|_| ConversionError::ShapeMismatch)
+    }
+
+    /// Write the ROOT file.
+    pub fn write<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        let bytes = self
+            .to_bytes()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        writer.write_all(&bytes)
+    }
+
+    /// The contents of the ROOT file.
+    ///
+    /// Returns [ConversionError::ValueOutOfRange] if the file would be larger than 2 GB.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ConversionError> {
+        let datime = datime(SystemTime::now());
+        let uuid = uuid();
+        let file_key = KeyHeader::new("TFile", &self.name, "");
+        let nbytes_name = file_key.key_length() + string_length(&self.name) + string_length("");
+        let directory_length = nbytes_name - file_key.key_length() + DIRECTORY_LENGTH;
+
+        // lay out the records: the top directory, the histograms, the list of keys
+        // and the free segments
+        let mut position = BEGIN + file_key.key_length() + directory_length;
+        let mut objects = Vec::new();
+        for histogram in &self.histograms {
+            let mut payload = WriteBuffer::new();
+            histogram.write(&mut payload);
+            let mut key = KeyHeader::new(histogram.class_name(), &histogram.name, &histogram.title);
+            key.object_length = payload.len();
+            key.seek_key = position;
+            key.seek_directory = BEGIN;
+            position += key.key_length() + payload.len();
+            objects.push((key, payload.into_bytes()));
+        }
+        let mut keys_list = KeyHeader::new("TFile", &self.name, "");
+        keys_list.object_length = 4 + objects
+            .iter()
+            .map(|(key, _)| key.key_length())
+            .sum::<usize>();
+        keys_list.seek_key = position;
+        keys_list.seek_directory = BEGIN;
+        position += keys_list.num_bytes();
+        let mut free_segments = KeyHeader::new("TFile", &self.name, "");
+        free_segments.object_length = 10;
+        free_segments.seek_key = position;
+        free_segments.seek_directory = BEGIN;
+        let end = position + free_segments.num_bytes();
+        if end > i32::MAX as usize {
+            return Err(ConversionError::ValueOutOfRange);
+        }
+
+        let mut buffer = WriteBuffer::new();
+        buffer.bytes(b"root");
+        buffer.i32(ROOT_VERSION);
+        buffer.i32(BEGIN as i32);
+        buffer.i32(end as i32);
+        buffer.i32(free_segments.seek_key as i32);
+        buffer.i32(free_segments.num_bytes() as i32);
+        // number of free segments
+        buffer.i32(1);
+        buffer.i32(nbytes_name as i32);
+        // bytes per file position
+        buffer.u8(4);
+        // compression setting
+        buffer.i32(0);
+        // position and size of the StreamerInfo record
+        buffer.i32(0);
+        buffer.i32(0);
+        write_uuid(&mut buffer, &uuid);
+        buffer.bytes(&vec![0; BEGIN - buffer.len()]);
+
+        let mut file_key = file_key;
+        file_key.object_length = directory_length;
+        file_key.seek_key = BEGIN;
+        file_key.write(&mut buffer, datime);
+        buffer.string(&self.name);
+        buffer.string("");
+        buffer.i16(DIRECTORY_VERSION);
+        buffer.u32(datime);
+        buffer.u32(datime);
+        buffer.i32(keys_list.num_bytes() as i32);
+        buffer.i32(nbytes_name as i32);
+        buffer.i32(BEGIN as i32);
+        // parent directory
+        buffer.i32(0);
+        buffer.i32(keys_list.seek_key as i32);
+        write_uuid(&mut buffer, &uuid);
+        // space reserved for 64-bit file positions
+        buffer.bytes(&[0; 12]);
+
+        for (key, payload) in &objects {
+            key.write(&mut buffer, datime);
+            buffer.bytes(payload);
+        }
+
+        keys_list.write(&mut buffer, datime);
+        buffer.i32(objects.len() as i32);
+        for (key, _) in &objects {
+            key.write(&mut buffer, datime);
+        }
+
+        free_segments.write(&mut buffer, datime);
+        buffer.i16(1);
+        buffer.i32(end as i32);
+        buffer.i32(LAST_FREE_BYTE);
+        debug_assert_eq!(buffer.len(), end);
+        Ok(buffer.into_bytes())
+    }
+
+    /// Read the histograms in a ROOT file.
+    ///
+    /// Uncompressed TH1, TH2 and TH3 objects with double or float bin contents are read,
+    /// taking the highest cycle of each name, and other objects are skipped.
+    /// Returns [ConversionError::InvalidData] if the file structure cannot be read.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConversionError> {
+        let mut buffer = ReadBuffer::new(bytes);
+        if buffer.bytes(4)? != b"root" {
+            return Err(ConversionError::InvalidData);
+        }
+        let version = buffer.i32()?;
+        let begin = buffer.usize_32()?;
+        // end, position and size of the free segments and number of free segments
+        if version >= 1_000_000 {
+            buffer.bytes(8 + 8 + 4 + 4)?;
+        } else {
+            buffer.bytes(4 * 4)?;
+        }
+        let nbytes_name = buffer.usize_32()?;
+
+        let file_key = KeyHeader::read(&mut ReadBuffer::at(bytes, begin)?)?;
+        let mut directory = ReadBuffer::at(bytes, begin + nbytes_name)?;
+        let directory_version = directory.i16()?;
+        // creation and modification time, size of the keys list and of the name
+        directory.bytes(4 * 4)?;
+        let seek_keys = if directory_version > BIG_FILE_VERSION {
+            directory.bytes(8 * 2)?;
+            directory.usize_64()?
+        } else {
+            directory.bytes(4 * 2)?;
+            directory.usize_32()?
+        };
+
+        let mut keys_list = ReadBuffer::at(bytes, seek_keys)?;
+        KeyHeader::read(&mut keys_list)?;
+        let num_keys = keys_list.usize_32()?;
+        let keys = (0..num_keys)
+            .map(|_| KeyHeader::read(&mut keys_list))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut file = Self::new(&file_key.name);
+        let mut cycles: Vec<(String, i16)> = Vec::new();
+        for key in keys {
+            if !is_histogram_class(&key.class_name) {
+                continue;
+            }
+            let mut record = ReadBuffer::at(bytes, key.seek_key)?;
+            let record_key = KeyHeader::read(&mut record)?;
+            if record_key.num_bytes() != record_key.key_length + record_key.object_length {
+                // compressed objects are not supported
+                continue;
+            }
+            let start = record.position();
+            let payload = bytes
+                .get(start..start + record_key.object_length)
+                .ok_or(ConversionError::InvalidData)?;
+            let mut histogram =
+                RootHistogram::read(&mut ReadBuffer::new(payload), &key.class_name)?;
+            histogram.name = key.name.clone();
+            match cycles.iter_mut().find(|(name, _)| *name == key.name) {
+                Some((_, cycle)) if *cycle > key.cycle => continue,
+                Some((_, cycle)) => *cycle = key.cycle,
+                None => cycles.push((key.name.clone(), key.cycle)),
+            }
+            file.insert(histogram);
+        }
+        Ok(file)
+    }
+}
+
+fn is_histogram_class(class_name: &str) -> bool {
+    matches!(
+        class_name,
+        "TH1D" | "TH1F" | "TH2D" | "TH2F" | "TH3D" | "TH3F"
+    )
+}
+
+/// The header of a TKey, which precedes each record in a ROOT file.
+#[derive(Clone, Debug)]
+struct KeyHeader {
+    class_name: String,
+    name: String,
+    title: String,
+    key_length: usize,
+    object_length: usize,
+    num_bytes: usize,
+    cycle: i16,
+    seek_key: usize,
+    seek_directory: usize,
+}
+
+impl KeyHeader {
+    fn new(class_name: &str, name: &str, title: &str) -> Self {
+        let key_length = 4
+            + 2
+            + 4
+            + 4
+            + 2
+            + 2
+            + 4
+            + 4
+            + string_length(class_name)
+            + string_length(name)
+            + string_length(title);
+        Self {
+            class_name: class_name.to_string(),
+            name: name.to_string(),
+            title: title.to_string(),
+            key_length,
+            object_length: 0,
+            num_bytes: 0,
+            cycle: 1,
+            seek_key: 0,
+            seek_directory: 0,
+        }
+    }
+
+    fn key_length(&self) -> usize {
+        self.key_length
+    }
+
+    /// Size of the record, the key and the object.
+    fn num_bytes(&self) -> usize {
+        if self.num_bytes > 0 {
+            self.num_bytes
+        } else {
+            self.key_length + self.object_length
+        }
+    }
+
+    fn write(&self, buffer: &mut WriteBuffer, datime: u32) {
+        buffer.i32(self.num_bytes() as i32);
+        buffer.i16(KEY_VERSION);
+        buffer.i32(self.object_length as i32);
+        buffer.u32(datime);
+        buffer.i16(self.key_length as i16);
+        buffer.i16(self.cycle);
+        buffer.i32(self.seek_key as i32);
+        buffer.i32(self.seek_directory as i32);
+        buffer.string(&self.class_name);
+        buffer.string(&self.name);
+        buffer.string(&self.title);
+    }
+
+    fn read(buffer: &mut ReadBuffer<'_>) -> Result<Self, ConversionError> {
+        let num_bytes = buffer.usize_32()?;
+        let version = buffer.i16()?;
+        let object_length = buffer.usize_32()?;
+        buffer.u32()?;
+        let key_length =
+            usize::try_from(buffer.i16()?).map_err(|_| ConversionError::InvalidData)?;
+        let cycle = buffer.i16()?;
+        let (seek_key, seek_directory) = if version > BIG_FILE_VERSION {
+            (buffer.usize_64()?, buffer.usize_64()?)
+        } else {
+            (buffer.usize_32()?, buffer.usize_32()?)
+        };
+        Ok(Self {
+            class_name: buffer.string()?,
+            name: buffer.string()?,
+            title: buffer.string()?,
+            key_length,
+            object_length,
+            num_bytes,
+            cycle,
+            seek_key,
+            seek_directory,
+        })
+    }
+}
+
+/// Number of bytes of a TString.
+fn string_length(value: &str) -> usize {
+    if value.len() < 255 {
+        1 + value.len()
+    } else {
+        5 + value.len()
+    }
+}
+
+fn write_uuid(buffer: &mut WriteBuffer, uuid: &[u8; 16]) {
+    buffer.i16(1);
+    buffer.bytes(uuid);
+}
+
+/// An identifier for the file derived from the current time.
+fn uuid() -> [u8; 16] {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos())
+        .to_be_bytes()
+}
+
+/// The time in the packed TDatime format, in UTC.
+fn datime(time: SystemTime) -> u32 {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+    // convert days since 1970-01-01 to a civil date
+    // see <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    let packed = (year.saturating_sub(1995) << 26)
+        | (month << 22)
+        | (day << 17)
+        | ((seconds / 3600) << 12)
+        | ((seconds % 3600 / 60) << 6)
+        | (seconds % 60);
+    packed as u32
+}
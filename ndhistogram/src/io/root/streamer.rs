@@ -0,0 +1,363 @@
+//! Streaming of TH1D, TH2D and TH3D objects in the layout of ROOT 6 class versions.
+
+use crate::error::ConversionError;
+
+use super::{
+    axis::TAxis,
+    buffer::{ReadBuffer, WriteBuffer},
+};
+
+/// Bits of a TObject that is not referenced: kNotDeleted and kIsOnHeap.
+const TOBJECT_BITS: u32 = 0x0300_0000;
+/// Bit set on a TObject that is referenced, which is followed by a process ID.
+const TOBJECT_IS_REFERENCED: u32 = 0x10;
+/// Class tag of the first object of a class in a buffer.
+const NEW_CLASS_TAG: u32 = 0xFFFF_FFFF;
+/// Value of fMaximum and fMinimum when they are not set.
+const NO_LIMIT: f64 = -1111.0;
+
+/// A histogram as it is stored in a ROOT file.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct RootHistogram {
+    pub(crate) name: String,
+    pub(crate) title: String,
+    /// One axis for each dimension.
+    pub(crate) axes: Vec<TAxis>,
+    /// The sum of weights of each bin, in order of ROOT global bin number.
+    pub(crate) contents: Vec<f64>,
+    /// The sum of squared weights of each bin, or empty if Sumw2 is not enabled.
+    pub(crate) sumw2: Vec<f64>,
+    /// The number of entries.
+    pub(crate) entries: f64,
+}
+
+/// Sums of weights and weighted coordinates over the bins excluding underflow and overflow.
+#[derive(Default)]
+struct Statistics {
+    sumw: f64,
+    sumw2: f64,
+    /// Sums of w*x, w*y and w*z.
+    sumwx: [f64; 3],
+    /// Sums of w*x*x, w*y*y and w*z*z.
+    sumwx2: [f64; 3],
+    /// Sums of w*x*y, w*x*z and w*y*z.
+    sumwxy: [f64; 3],
+}
+
+impl RootHistogram {
+    pub(crate) fn class_name(&self) -> &'static str {
+        match self.axes.len() {
+            1 => "TH1D",
+            2 => "TH2D",
+            _ => "TH3D",
+        }
+    }
+
+    pub(crate) fn num_cells(axes: &[TAxis]) -> usize {
+        axes.iter().map(|axis| axis.num_bins + 2).product()
+    }
+
+    fn statistics(&self) -> Statistics {
+        let centers: Vec<Vec<f64>> = self.axes.iter().map(TAxis::bin_centers).collect();
+        let mut statistics = Statistics::default();
+        for (bin, weight) in self.contents.iter().enumerate() {
+            let mut coordinates = [0.0; 3];
+            let mut remainder = bin;
+            let mut inner = true;
+            for (axis, centers) in centers.iter().enumerate() {
+                let axis_bin = remainder % (centers.len() + 2);
+                remainder /= centers.len() + 2;
+                match axis_bin.checked_sub(1).and_then(|it| centers.get(it)) {
+                    Some(center) => coordinates[axis] = *center,
+                    None => inner = false,
+                }
+            }
+            if !inner {
+                continue;
+            }
+            let [x, y, z] = coordinates;
+            statistics.sumw += weight;
+            statistics.sumw2 += self.sumw2.get(bin).unwrap_or(weight);
+            for (axis, coordinate) in coordinates.iter().enumerate() {
+                statistics.sumwx[axis] += weight * coordinate;
+                statistics.sumwx2[axis] += weight * coordinate * coordinate;
+            }
+            statistics.sumwxy[0] += weight * x * y;
+            statistics.sumwxy[1] += weight * x * z;
+            statistics.sumwxy[2] += weight * y * z;
+        }
+        statistics
+    }
+
+    /// Stream the histogram as a TH1D, TH2D or TH3D object.
+    pub(crate) fn write(&self, buffer: &mut WriteBuffer) {
+        let statistics = self.statistics();
+        let object = buffer.start_object(match self.axes.len() {
+            1 => 3,
+            _ => 4,
+        });
+        match self.axes.len() {
+            1 => self.write_th1(buffer, &statistics),
+            2 => {
+                let th2 = buffer.start_object(5);
+                self.write_th1(buffer, &statistics);
+                buffer.f64(1.0);
+                buffer.f64(statistics.sumwx[1]);
+                buffer.f64(statistics.sumwx2[1]);
+                buffer.f64(statistics.sumwxy[0]);
+                buffer.end_byte_count(th2);
+            }
+            _ => {
+                let th3 = buffer.start_object(6);
+                self.write_th1(buffer, &statistics);
+                let att3d = buffer.start_object(1);
+                buffer.end_byte_count(att3d);
+                buffer.f64(statistics.sumwx[1]);
+                buffer.f64(statistics.sumwx2[1]);
+                buffer.f64(statistics.sumwxy[0]);
+                buffer.f64(statistics.sumwx[2]);
+                buffer.f64(statistics.sumwx2[2]);
+                buffer.f64(statistics.sumwxy[1]);
+                buffer.f64(statistics.sumwxy[2]);
+                buffer.end_byte_count(th3);
+            }
+        }
+        buffer.array_f64(&self.contents);
+        buffer.end_byte_count(object);
+    }
+
+    fn write_th1(&self, buffer: &mut WriteBuffer, statistics: &Statistics) {
+        let th1 = buffer.start_object(8);
+        write_tnamed(buffer, &self.name, &self.title);
+        // TAttLine: color, style and width
+        let attributes = buffer.start_object(2);
+        buffer.i16(602);
+        buffer.i16(1);
+        buffer.i16(1);
+        buffer.end_byte_count(attributes);
+        // TAttFill: color and style
+        let attributes = buffer.start_object(2);
+        buffer.i16(0);
+        buffer.i16(1001);
+        buffer.end_byte_count(attributes);
+        // TAttMarker: color, style and size
+        let attributes = buffer.start_object(2);
+        buffer.i16(1);
+        buffer.i16(1);
+        buffer.f32(1.0);
+        buffer.end_byte_count(attributes);
+        buffer.i32(Self::num_cells(&self.axes) as i32);
+        let default_axis = TAxis::fixed(1, 0.0, 1.0);
+        for (position, name) in ["xaxis", "yaxis", "zaxis"].iter().enumerate() {
+            write_taxis(
+                buffer,
+                name,
+                self.axes.get(position).unwrap_or(&default_axis),
+            );
+        }
+        // bar offset and width
+        buffer.i16(0);
+        buffer.i16(1000);
+        buffer.f64(self.entries);
+        buffer.f64(statistics.sumw);
+        buffer.f64(statistics.sumw2);
+        buffer.f64(statistics.sumwx[0]);
+        buffer.f64(statistics.sumwx2[0]);
+        buffer.f64(NO_LIMIT);
+        buffer.f64(NO_LIMIT);
+        // normalization factor
+        buffer.f64(0.0);
+        // contour levels
+        buffer.array_f64(&[]);
+        buffer.array_f64(&self.sumw2);
+        // draw option
+        buffer.string("");
+        write_empty_tlist(buffer);
+        // fill buffer size and the flag for an empty fill buffer
+        buffer.i32(0);
+        buffer.u8(0);
+        // bin error option kNormal and statistics overflow option kNeutral
+        buffer.i32(0);
+        buffer.i32(2);
+        buffer.end_byte_count(th1);
+    }
+
+    /// Read a histogram streamed as a TH1, TH2 or TH3 with double or float bin contents.
+    pub(crate) fn read(
+        buffer: &mut ReadBuffer<'_>,
+        class_name: &str,
+    ) -> Result<Self, ConversionError> {
+        let (dimensions, contents_type) = class_name
+            .strip_prefix("TH")
+            .and_then(|suffix| {
+                let mut chars = suffix.chars();
+                Some((chars.next()?.to_digit(10)?, chars.next()?, chars.next()))
+            })
+            .and_then(|(dimensions, contents_type, rest)| {
+                ((1..=3).contains(&dimensions) && rest.is_none())
+                    .then_some((dimensions as usize, contents_type))
+            })
+            .ok_or(ConversionError::InvalidData)?;
+        let (_, end) = buffer.start_object()?;
+        let mut histogram = if dimensions == 1 {
+            Self::read_th1(buffer, dimensions)?
+        } else {
+            let (_, th_end) = buffer.start_object()?;
+            let histogram = Self::read_th1(buffer, dimensions)?;
+            buffer.seek(th_end)?;
+            histogram
+        };
+        histogram.contents = match contents_type {
+            'D' => buffer.array_f64()?,
+            'F' => buffer.array_f32()?,
+            _ => return Err(ConversionError::InvalidData),
+        };
+        buffer.seek(end)?;
+        let num_cells = Self::num_cells(&histogram.axes);
+        if histogram.contents.len() != num_cells
+            || !(histogram.sumw2.is_empty() || histogram.sumw2.len() == num_cells)
+        {
+            return Err(ConversionError::ShapeMismatch);
+        }
+        Ok(histogram)
+    }
+
+    fn read_th1(buffer: &mut ReadBuffer<'_>, dimensions: usize) -> Result<Self, ConversionError> {
+        let (_, end) = buffer.start_object()?;
+        let (name, title) = read_tnamed(buffer)?;
+        // TAttLine, TAttFill and TAttMarker
+        for _ in 0..3 {
+            buffer.skip_object()?;
+        }
+        buffer.i32()?;
+        let mut axes = (0..3)
+            .map(|_| read_taxis(buffer))
+            .collect::<Result<Vec<_>, _>>()?;
+        axes.truncate(dimensions);
+        // bar offset and width
+        buffer.i16()?;
+        buffer.i16()?;
+        let entries = buffer.f64()?;
+        // statistics, limits and normalization factor
+        for _ in 0..7 {
+            buffer.f64()?;
+        }
+        // contour levels
+        buffer.array_f64()?;
+        let sumw2 = buffer.array_f64()?;
+        buffer.seek(end)?;
+        Ok(Self {
+            name,
+            title,
+            axes,
+            contents: Vec::new(),
+            sumw2,
+            entries,
+        })
+    }
+}
+
+fn write_tobject(buffer: &mut WriteBuffer) {
+    // TObject is streamed with a version but without a byte count
+    buffer.i16(1);
+    buffer.u32(0);
+    buffer.u32(TOBJECT_BITS);
+}
+
+fn write_tnamed(buffer: &mut WriteBuffer, name: &str, title: &str) {
+    let tnamed = buffer.start_object(1);
+    write_tobject(buffer);
+    buffer.string(name);
+    buffer.string(title);
+    buffer.end_byte_count(tnamed);
+}
+
+fn write_taxis(buffer: &mut WriteBuffer, name: &str, axis: &TAxis) {
+    let taxis = buffer.start_object(10);
+    write_tnamed(buffer, name, "");
+    // TAttAxis with the default style
+    let attributes = buffer.start_object(4);
+    buffer.i32(510);
+    buffer.i16(1);
+    buffer.i16(1);
+    buffer.i16(42);
+    buffer.f32(0.005);
+    buffer.f32(0.035);
+    buffer.f32(0.03);
+    buffer.f32(1.0);
+    buffer.f32(0.035);
+    buffer.i16(1);
+    buffer.i16(42);
+    buffer.end_byte_count(attributes);
+    buffer.i32(axis.num_bins as i32);
+    buffer.f64(axis.low);
+    buffer.f64(axis.high);
+    buffer.array_f64(&axis.edges);
+    // first and last bin of the displayed range, bits, time display and format
+    buffer.i32(0);
+    buffer.i32(0);
+    buffer.u16(0);
+    buffer.u8(0);
+    buffer.string("");
+    // null pointers to the bin labels and modified labels
+    buffer.u32(0);
+    buffer.u32(0);
+    buffer.end_byte_count(taxis);
+}
+
+/// Write a pointer to an empty TList, as used for the list of functions of a histogram.
+fn write_empty_tlist(buffer: &mut WriteBuffer) {
+    let pointer = buffer.start_byte_count();
+    buffer.u32(NEW_CLASS_TAG);
+    buffer.class_name("TList");
+    let tlist = buffer.start_object(5);
+    write_tobject(buffer);
+    buffer.string("");
+    buffer.i32(0);
+    buffer.end_byte_count(tlist);
+    buffer.end_byte_count(pointer);
+}
+
+fn read_tobject(buffer: &mut ReadBuffer<'_>) -> Result<(), ConversionError> {
+    // the version may be preceded by a byte count
+    let start = buffer.position();
+    if buffer.u32()? & 0x4000_0000 == 0 {
+        buffer.seek(start)?;
+    }
+    buffer.i16()?;
+    buffer.u32()?;
+    if buffer.u32()? & TOBJECT_IS_REFERENCED != 0 {
+        buffer.i16()?;
+    }
+    Ok(())
+}
+
+fn read_tnamed(buffer: &mut ReadBuffer<'_>) -> Result<(String, String), ConversionError> {
+    let (_, end) = buffer.start_object()?;
+    read_tobject(buffer)?;
+    let name = buffer.string()?;
+    let title = buffer.string()?;
+    buffer.seek(end)?;
+    Ok((name, title))
+}
+
+fn read_taxis(buffer: &mut ReadBuffer<'_>) -> Result<TAxis, ConversionError> {
+    let (_, end) = buffer.start_object()?;
+    // TNamed and TAttAxis
+    buffer.skip_object()?;
+    buffer.skip_object()?;
+    let num_bins = buffer.usize_32()?;
+    let low = buffer.f64()?;
+    let high = buffer.f64()?;
+    let edges = buffer.array_f64()?;
+    buffer.seek(end)?;
+    if !(edges.is_empty() || edges.len() == num_bins + 1) {
+        return Err(ConversionError::InvalidData);
+    }
+    Ok(TAxis {
+        num_bins,
+        low,
+        high,
+        edges,
+    })
+}
@@ -0,0 +1,84 @@
+use num_traits::NumCast;
+
+use crate::{
+    error::ConversionError,
+    io::nearest,
+    value::{Sum, WeightedSum},
+};
+
+/// A bin value that can be converted to and from the bin contents of a ROOT histogram.
+///
+/// ROOT histograms store the sum of weights of each bin and, if Sumw2 is enabled,
+/// the sum of squared weights.
+pub trait RootValue: Sized {
+    /// Whether the sums of squared weights are stored, enabling Sumw2 on the ROOT histogram.
+    const HAS_SUMW2: bool;
+
+    /// The sum of weights and sum of squared weights of the bin.
+    fn to_root_value(&self) -> Result<(f64, f64), ConversionError>;
+
+    /// Create a value from the sum of weights and sum of squared weights of the bin.
+    ///
+    /// If the ROOT histogram does not have Sumw2 enabled, the sum of squared weights
+    /// is equal to the sum of weights.
+    fn from_root_value(
+        sum_of_weights: f64,
+        sum_of_weights_squared: f64,
+    ) -> Result<Self, ConversionError>;
+}
+
+fn to_f64<T: NumCast>(value: T) -> Result<f64, ConversionError> {
+    value.to_f64().ok_or(ConversionError::ValueOutOfRange)
+}
+
+macro_rules! impl_root_value_for_primitive {
+    ($($T:ty),*) => {
+        $(
+            impl RootValue for $T {
+                const HAS_SUMW2: bool = false;
+
+                fn to_root_value(&self) -> Result<(f64, f64), ConversionError> {
+                    let value = to_f64(*self)?;
+                    Ok((value, value))
+                }
+
+                fn from_root_value(sum_of_weights: f64, _: f64) -> Result<Self, ConversionError> {
+                    nearest(sum_of_weights)
+                }
+            }
+        )*
+    };
+}
+
+impl_root_value_for_primitive! {i8, i16, i32, i64, u8, u16, u32, u64, f32, f64}
+
+impl<T: NumCast + Copy> RootValue for Sum<T> {
+    const HAS_SUMW2: bool = false;
+
+    fn to_root_value(&self) -> Result<(f64, f64), ConversionError> {
+        let value = to_f64(self.get())?;
+        Ok((value, value))
+    }
+
+    fn from_root_value(sum_of_weights: f64, _: f64) -> Result<Self, ConversionError> {
+        Ok(Self::with_sum(nearest(sum_of_weights)?))
+    }
+}
+
+impl<T: NumCast + Copy> RootValue for WeightedSum<T> {
+    const HAS_SUMW2: bool = true;
+
+    fn to_root_value(&self) -> Result<(f64, f64), ConversionError> {
+        Ok((to_f64(self.sum())?, to_f64(self.variance())?))
+    }
+
+    fn from_root_value(
+        sum_of_weights: f64,
+        sum_of_weights_squared: f64,
+    ) -> Result<Self, ConversionError> {
+        Ok(Self::with_sum_and_variance(
+            nearest(sum_of_weights)?,
+            nearest(sum_of_weights_squared)?,
+        ))
+    }
+}
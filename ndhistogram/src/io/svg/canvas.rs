@@ -0,0 +1,393 @@
+//! SVG drawing primitives, scales and colours used to render plots.
+
+use std::fmt::Write;
+
+/// Line colours of overlaid histograms.
+pub(crate) const PALETTE: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+
+/// Anchors of the viridis colour map used for heatmaps.
+const COLOUR_MAP: [(f64, [f64; 3]); 5] = [
+    (0.0, [68.0, 1.0, 84.0]),
+    (0.25, [59.0, 82.0, 139.0]),
+    (0.5, [33.0, 145.0, 140.0]),
+    (0.75, [94.0, 201.0, 98.0]),
+    (1.0, [253.0, 231.0, 37.0]),
+];
+
+/// The colour at a fraction between 0 and 1 of the colour map.
+pub(crate) fn colour(fraction: f64) -> String {
+    let fraction = if fraction.is_nan() {
+        0.0
+    } else {
+        fraction.clamp(0.0, 1.0)
+    };
+    let upper = COLOUR_MAP
+        .iter()
+        .position(|(anchor, _)| *anchor >= fraction)
+        .unwrap_or(COLOUR_MAP.len() - 1)
+        .max(1);
+    let (low, low_colour) = COLOUR_MAP[upper - 1];
+    let (high, high_colour) = COLOUR_MAP[upper];
+    let t = (fraction - low) / (high - low);
+    let [r, g, b] =
+        [0, 1, 2].map(|it| (low_colour[it] + t * (high_colour[it] - low_colour[it])).round() as u8);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// The region of the canvas, in pixels, where data is drawn.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Area {
+    pub(crate) left: f64,
+    pub(crate) top: f64,
+    pub(crate) right: f64,
+    pub(crate) bottom: f64,
+}
+
+/// Maps data values in a range onto pixel positions, linearly or logarithmically.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Scale {
+    low: f64,
+    high: f64,
+    log: bool,
+    start: f64,
+    end: f64,
+}
+
+impl Scale {
+    /// A scale mapping the data range [low, high] onto the pixel range [start, end].
+    pub(crate) fn new(range: (f64, f64), log: bool, start: f64, end: f64) -> Self {
+        Self {
+            low: range.0,
+            high: range.1,
+            log,
+            start,
+            end,
+        }
+    }
+
+    fn transform(&self, value: f64) -> f64 {
+        if self.log {
+            value.log10()
+        } else {
+            value
+        }
+    }
+
+    pub(crate) fn low(&self) -> f64 {
+        self.low
+    }
+
+    /// Whether the value can be drawn on this scale.
+    pub(crate) fn accepts(&self, value: f64) -> bool {
+        value.is_finite() && (!self.log || value > 0.0)
+    }
+
+    /// The pixel position of a value, limited to a short distance outside of the range.
+    pub(crate) fn map(&self, value: f64) -> f64 {
+        let (low, high) = (self.transform(self.low), self.transform(self.high));
+        let fraction = (self.transform(value) - low) / (high - low);
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(-0.1, 1.1)
+        };
+        self.start + fraction * (self.end - self.start)
+    }
+
+    /// The fraction of the range covered up to a value, from 0 to 1.
+    pub(crate) fn fraction(&self, value: f64) -> f64 {
+        (self.map(value) - self.start) / (self.end - self.start)
+    }
+
+    /// Positions and labels of the tick marks.
+    pub(crate) fn ticks(&self) -> Vec<(f64, String)> {
+        if self.log {
+            let first = self.low.log10().floor() as i32;
+            let last = self.high.log10().ceil() as i32;
+            let every = ((last - first) / 8).max(1) as usize;
+            (first..=last)
+                .step_by(every)
+                .map(|power| 10f64.powi(power))
+                .filter(|tick| {
+                    *tick >= self.low * (1.0 - 1e-9) && *tick <= self.high * (1.0 + 1e-9)
+                })
+                .map(|tick| (tick, format_power(tick.log10().round() as i32)))
+                .collect()
+        } else {
+            let step = nice_step((self.high - self.low) / 5.0);
+            let decimals = (-step.log10().floor()).max(0.0) as usize;
+            let first = (self.low / step).ceil() as i64;
+            let last = (self.high / step + 1e-9).floor() as i64;
+            (first..=last)
+                .map(|it| {
+                    let tick = it as f64 * step;
+                    (
+                        tick,
+                        format!("{:.*}", decimals, if it == 0 { 0.0 } else { tick }),
+                    )
+                })
+                .collect()
+        }
+    }
+}
+
+/// A round step of 1, 2 or 5 times a power of ten close to the given step.
+fn nice_step(step: f64) -> f64 {
+    if !(step.is_finite() && step > 0.0) {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(step.log10().floor());
+    let fraction = step / magnitude;
+    let nice = if fraction < 1.5 {
+        1.0
+    } else if fraction < 3.0 {
+        2.0
+    } else if fraction < 7.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+fn format_power(power: i32) -> String {
+    if (-3..=4).contains(&power) {
+        10f64.powi(power).to_string()
+    } else {
+        format!("1e{}", power)
+    }
+}
+
+/// The range of the values to show on a scale.
+///
+/// Linear ranges optionally include zero and have a margin above the largest value,
+/// while logarithmic ranges extend to whole decades around the positive values.
+pub(crate) fn range(
+    values: impl Iterator<Item = f64>,
+    log: bool,
+    include_zero: bool,
+) -> (f64, f64) {
+    let values: Vec<f64> = values
+        .filter(|it| it.is_finite() && (!log || *it > 0.0))
+        .collect();
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if log {
+        if values.is_empty() {
+            return (1.0, 10.0);
+        }
+        let low = 10f64.powf(min.log10().floor());
+        let high = 10f64.powf(max.log10().ceil());
+        return (low, if high > low { high } else { low * 10.0 });
+    }
+    if values.is_empty() {
+        return (0.0, 1.0);
+    }
+    let (min, max) = if include_zero {
+        (min.min(0.0), max.max(0.0))
+    } else {
+        (min, max)
+    };
+    if max == min {
+        let half = if max == 0.0 { 1.0 } else { max.abs() / 2.0 };
+        return (min - half, max + half);
+    }
+    let margin = if include_zero {
+        0.05 * (max - min)
+    } else {
+        0.0
+    };
+    (
+        if min < 0.0 { min - margin } else { min },
+        if max > 0.0 { max + margin } else { max },
+    )
+}
+
+/// Escape text for use in SVG content and attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Horizontal alignment of text.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+/// An SVG document being drawn.
+#[derive(Debug)]
+pub(crate) struct Canvas {
+    width: u32,
+    height: u32,
+    body: String,
+}
+
+impl Canvas {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            body: String::new(),
+        }
+    }
+
+    pub(crate) fn raw(&mut self, element: &str) {
+        self.body.push_str(element);
+        self.body.push('\n');
+    }
+
+    pub(crate) fn line(&mut self, from: (f64, f64), to: (f64, f64), stroke: &str, width: f64) {
+        let _ = writeln!(
+            self.body,
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="{}" stroke-width="{}"/>"#,
+            from.0, from.1, to.0, to.1, stroke, width
+        );
+    }
+
+    /// A rectangle between two corners, in any order.
+    pub(crate) fn rect(&mut self, corner: (f64, f64), other: (f64, f64), attributes: &str) {
+        let _ = writeln!(
+            self.body,
+            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" {}/>"#,
+            corner.0.min(other.0),
+            corner.1.min(other.1),
+            (corner.0 - other.0).abs(),
+            (corner.1 - other.1).abs(),
+            attributes
+        );
+    }
+
+    pub(crate) fn path(&mut self, data: &str, stroke: &str, width: f64) {
+        let _ = writeln!(
+            self.body,
+            r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            data, stroke, width
+        );
+    }
+
+    /// Text at a position, rotated to read upwards if vertical is set.
+    pub(crate) fn text(
+        &mut self,
+        position: (f64, f64),
+        text: &str,
+        anchor: Anchor,
+        size: u32,
+        vertical: bool,
+    ) {
+        let anchor = match anchor {
+            Anchor::Start => "start",
+            Anchor::Middle => "middle",
+            Anchor::End => "end",
+        };
+        let rotate = if vertical {
+            format!(
+                r#" transform="rotate(-90 {:.2} {:.2})""#,
+                position.0, position.1
+            )
+        } else {
+            String::new()
+        };
+        let _ = writeln!(
+            self.body,
+            r#"<text x="{:.2}" y="{:.2}" font-size="{}" text-anchor="{}"{}>{}</text>"#,
+            position.0,
+            position.1,
+            size,
+            anchor,
+            rotate,
+            escape(text)
+        );
+    }
+
+    /// Draw the frame of the data area with tick marks and labels on the left and bottom.
+    pub(crate) fn frame(&mut self, area: Area, x: &Scale, y: &Scale) {
+        for (tick, label) in x.ticks() {
+            let position = x.map(tick);
+            self.line(
+                (position, area.bottom),
+                (position, area.bottom + 5.0),
+                "black",
+                1.0,
+            );
+            self.text(
+                (position, area.bottom + 18.0),
+                &label,
+                Anchor::Middle,
+                11,
+                false,
+            );
+        }
+        for (tick, label) in y.ticks() {
+            let position = y.map(tick);
+            self.line(
+                (area.left - 5.0, position),
+                (area.left, position),
+                "black",
+                1.0,
+            );
+            self.text(
+                (area.left - 8.0, position + 4.0),
+                &label,
+                Anchor::End,
+                11,
+                false,
+            );
+        }
+        self.rect(
+            (area.left, area.top),
+            (area.right, area.bottom),
+            r#"fill="none" stroke="black""#,
+        );
+    }
+
+    /// Draw the title and axis labels around the data area.
+    pub(crate) fn labels(&mut self, area: Area, title: &str, x_label: &str, y_label: &str) {
+        let center = (area.left + area.right) / 2.0;
+        if !title.is_empty() {
+            self.text((center, area.top - 14.0), title, Anchor::Middle, 16, false);
+        }
+        if !x_label.is_empty() {
+            self.text(
+                (center, area.bottom + 38.0),
+                x_label,
+                Anchor::Middle,
+                13,
+                false,
+            );
+        }
+        if !y_label.is_empty() {
+            let middle = (area.top + area.bottom) / 2.0;
+            self.text(
+                (area.left - 52.0, middle),
+                y_label,
+                Anchor::Middle,
+                13,
+                true,
+            );
+        }
+    }
+
+    /// The complete SVG document.
+    pub(crate) fn finish(self) -> String {
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" "#,
+                r#"viewBox="0 0 {w} {h}" font-family="sans-serif">"#,
+                "\n",
+                r#"<rect width="{w}" height="{h}" fill="white"/>"#,
+                "\n{body}</svg>\n"
+            ),
+            w = self.width,
+            h = self.height,
+            body = self.body
+        )
+    }
+}
@@ -0,0 +1,584 @@
+//! Rendering of histograms as SVG images
+//!
+//! [Plot1D] draws one or more 1D histograms, overlaid on the same axes, as step or bar
+//! plots with error bars.
+//! [Plot2D] draws a 2D histogram as a heatmap with a colour bar.
+//! Both support axis labels, a title and a logarithmic scale for the bin values.
+//!
+//! Histograms with axes that have [BinInterval]s, such as [Uniform](crate::axis::Uniform)
+//! and [Variable](crate::axis::Variable), may be drawn.
+//! Underflow and overflow bins are not drawn.
+//! The values of the bins are given by [PlotValue], which also provides the
+//! size of the error bars: the standard deviation for [WeightedSum] and the standard
+//! error of the mean for [Mean] and [WeightedMean].
+//!
+//! The images do not depend on external resources and may be embedded in HTML reports.
+//! Requires the "svg" feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, axis::Uniform, value::WeightedSum};
+//! use ndhistogram::io::svg::{Plot1D, Style};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut signal = ndhistogram!(Uniform::new(20, 0.0, 10.0)?; WeightedSum);
+//! let mut background = ndhistogram!(Uniform::new(20, 0.0, 10.0)?; WeightedSum);
+//! for x in 0..100 {
+//!     signal.fill_with(&(5.0 + (x % 7) as f64 * 0.2), 0.5);
+//!     background.fill(&((x % 20) as f64 * 0.5));
+//! }
+//! let svg = Plot1D::new()
+//!     .title("Reconstructed energy")
+//!     .x_label("E [GeV]")
+//!     .y_label("Events")
+//!     .log_y(true)
+//!     .add_with_style(&background, "background", Style::Bar)
+//!     .add(&signal, "signal")
+//!     .to_svg();
+//! assert!(svg.starts_with("<svg"));
+//! // std::fs::write("energy.svg", svg)?;
+//! # Ok(()) }
+//! ```
+
+mod canvas;
+
+use std::io;
+
+use num_traits::{Float, NumCast, Signed, ToPrimitive};
+
+use crate::{
+    axis::{Axis, BinInterval},
+    value::{Mean, Sum, WeightedMean, WeightedSum},
+    Histogram,
+};
+
+use canvas::{colour, range, Anchor, Area, Canvas, Scale, PALETTE};
+
+/// A bin value that can be drawn, with an optional error bar.
+pub trait PlotValue {
+    /// The value drawn for the bin.
+    fn plot_value(&self) -> f64;
+
+    /// The half-height of the error bar of the bin, if any.
+    fn plot_error(&self) -> Option<f64> {
+        None
+    }
+}
+
+macro_rules! impl_plot_value_for_primitive {
+    ($($T:ty),*) => {
+        $(
+            impl PlotValue for $T {
+                fn plot_value(&self) -> f64 {
+                    self.to_f64().unwrap_or(f64::NAN)
+                }
+            }
+        )*
+    };
+}
+
+impl_plot_value_for_primitive! {i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64}
+
+impl<T: NumCast + Copy> PlotValue for Sum<T> {
+    fn plot_value(&self) -> f64 {
+        self.get().to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+impl<T: Copy + Into<f64>> PlotValue for WeightedSum<T> {
+    fn plot_value(&self) -> f64 {
+        self.sum().into()
+    }
+
+    fn plot_error(&self) -> Option<f64> {
+        Some(self.standard_deviation())
+    }
+}
+
+impl<T, O, C> PlotValue for Mean<T, O, C>
+where
+    T: Copy,
+    O: From<T> + From<C> + Float + Signed,
+    C: Copy,
+{
+    fn plot_value(&self) -> f64 {
+        self.get().to_f64().unwrap_or(f64::NAN)
+    }
+
+    fn plot_error(&self) -> Option<f64> {
+        self.standard_error_of_mean().to_f64()
+    }
+}
+
+impl<T, W, O, C> PlotValue for WeightedMean<T, W, O, C>
+where
+    T: Copy,
+    W: Copy,
+    O: From<T> + From<W> + From<C> + Float + Signed,
+    C: Copy,
+{
+    fn plot_value(&self) -> f64 {
+        self.get().to_f64().unwrap_or(f64::NAN)
+    }
+
+    fn plot_error(&self) -> Option<f64> {
+        self.standard_error_of_mean().to_f64()
+    }
+}
+
+/// How the bins of a 1D histogram are drawn.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Style {
+    /// A line following the top of the bins.
+    Step,
+    /// Filled bars from zero to the value of the bins.
+    Bar,
+}
+
+/// Size, title and axis labels of a plot.
+#[derive(Clone, Debug)]
+struct Layout {
+    width: u32,
+    height: u32,
+    title: String,
+    x_label: String,
+    y_label: String,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            width: 640,
+            height: 480,
+            title: String::new(),
+            x_label: String::new(),
+            y_label: String::new(),
+        }
+    }
+}
+
+/// A bin to draw, with its edges and value.
+#[derive(Copy, Clone, Debug)]
+struct Bin {
+    low: f64,
+    high: f64,
+    value: f64,
+    error: Option<f64>,
+}
+
+/// The finite bin edges as f64, or None for underflow and overflow bins.
+fn edges<T: NumCast>(bin: BinInterval<T>) -> Option<(f64, f64)> {
+    match bin {
+        BinInterval::Bin { start, end } => Some((start.to_f64()?, end.to_f64()?)),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Series {
+    label: String,
+    style: Style,
+    bins: Vec<Bin>,
+}
+
+/// A plot of one or more 1D histograms drawn on the same axes.
+///
+/// See the [module documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct Plot1D {
+    layout: Layout,
+    log_y: bool,
+    series: Vec<Series>,
+}
+
+impl Plot1D {
+    /// Factory method for an empty plot with a size of 640x480 pixels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title drawn above the plot.
+    pub fn title(mut self, title: &str) -> Self {
+        self.layout.title = title.to_string();
+        self
+    }
+
+    /// Set the label of the x-axis.
+    pub fn x_label(mut self, label: &str) -> Self {
+        self.layout.x_label = label.to_string();
+        self
+    }
+
+    /// Set the label of the y-axis.
+    pub fn y_label(mut self, label: &str) -> Self {
+        self.layout.y_label = label.to_string();
+        self
+    }
+
+    /// Set the size of the image in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.layout.width = width;
+        self.layout.height = height;
+        self
+    }
+
+    /// Use a logarithmic scale for the bin values.
+    ///
+    /// Bins with values that are not positive are not drawn.
+    pub fn log_y(mut self, log: bool) -> Self {
+        self.log_y = log;
+        self
+    }
+
+    /// Add a histogram drawn with [Style::Step].
+    ///
+    /// The label is shown in the legend, unless it is empty.
+    pub fn add<A, T, V, H>(self, hist: &H, label: &str) -> Self
+    where
+        H: Histogram<A, V>,
+        A: Axis<BinInterval = BinInterval<T>>,
+        T: NumCast,
+        V: PlotValue,
+    {
+        self.add_with_style(hist, label, Style::Step)
+    }
+
+    /// Add a histogram drawn with the given style.
+    ///
+    /// The label is shown in the legend, unless it is empty.
+    pub fn add_with_style<A, T, V, H>(mut self, hist: &H, label: &str, style: Style) -> Self
+    where
+        H: Histogram<A, V>,
+        A: Axis<BinInterval = BinInterval<T>>,
+        T: NumCast,
+        V: PlotValue,
+    {
+        let bins = hist
+            .axes()
+            .iter()
+            .filter_map(|(index, bin)| {
+                let (low, high) = edges(bin)?;
+                let value = hist.value_at_index(index);
+                Some(Bin {
+                    low,
+                    high,
+                    value: value.map_or(0.0, PlotValue::plot_value),
+                    error: value.and_then(PlotValue::plot_error),
+                })
+            })
+            .collect();
+        self.series.push(Series {
+            label: label.to_string(),
+            style,
+            bins,
+        });
+        self
+    }
+
+    /// Render the plot as an SVG document.
+    pub fn to_svg(&self) -> String {
+        let layout = &self.layout;
+        let mut canvas = Canvas::new(layout.width, layout.height);
+        let area = Area {
+            left: 70.0,
+            top: 40.0,
+            right: layout.width as f64 - 20.0,
+            bottom: layout.height as f64 - 50.0,
+        };
+        let bins = || self.series.iter().flat_map(|series| &series.bins);
+        let x = Scale::new(
+            range(bins().flat_map(|bin| [bin.low, bin.high]), false, false),
+            false,
+            area.left,
+            area.right,
+        );
+        let y_values = bins().flat_map(|bin| {
+            let error = bin.error.unwrap_or(0.0);
+            [bin.value, bin.value - error, bin.value + error]
+        });
+        let y = Scale::new(
+            range(y_values, self.log_y, true),
+            self.log_y,
+            area.bottom,
+            area.top,
+        );
+        canvas.raw(&format!(
+            r#"<clipPath id="data-area"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+            area.left,
+            area.top,
+            area.right - area.left,
+            area.bottom - area.top
+        ));
+        canvas.raw(r#"<g clip-path="url(#data-area)">"#);
+        for (series, colour) in self.series.iter().zip(PALETTE.iter().cycle()) {
+            draw_series(&mut canvas, series, colour, &x, &y);
+        }
+        canvas.raw("</g>");
+        canvas.frame(area, &x, &y);
+        canvas.labels(area, &layout.title, &layout.x_label, &layout.y_label);
+        self.draw_legend(&mut canvas, area);
+        canvas.finish()
+    }
+
+    fn draw_legend(&self, canvas: &mut Canvas, area: Area) {
+        let entries = self
+            .series
+            .iter()
+            .zip(PALETTE.iter().cycle())
+            .filter(|(series, _)| !series.label.is_empty());
+        for (row, (series, colour)) in entries.enumerate() {
+            let y = area.top + 16.0 + 18.0 * row as f64;
+            let x = area.right - 150.0;
+            match series.style {
+                Style::Step => canvas.line((x, y - 4.0), (x + 20.0, y - 4.0), colour, 2.0),
+                Style::Bar => canvas.rect(
+                    (x, y - 10.0),
+                    (x + 20.0, y + 2.0),
+                    &format!(r#"fill="{0}" fill-opacity="0.4" stroke="{0}""#, colour),
+                ),
+            }
+            canvas.text((x + 26.0, y), &series.label, Anchor::Start, 12, false);
+        }
+    }
+
+    /// Write the plot as an SVG document.
+    pub fn write<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_svg().as_bytes())
+    }
+}
+
+fn draw_series(canvas: &mut Canvas, series: &Series, colour: &str, x: &Scale, y: &Scale) {
+    let baseline = y.map(if y.accepts(0.0) { 0.0 } else { y.low() });
+    let mut path = String::new();
+    let mut previous: Option<Bin> = None;
+    for bin in &series.bins {
+        if !y.accepts(bin.value) {
+            previous = None;
+            continue;
+        }
+        let (left, right, top) = (x.map(bin.low), x.map(bin.high), y.map(bin.value));
+        match series.style {
+            Style::Step => {
+                if previous.is_some_and(|it| it.high == bin.low) {
+                    path.push_str(&format!(" V{:.2} H{:.2}", top, right));
+                } else {
+                    path.push_str(&format!(" M{:.2},{:.2} H{:.2}", left, top, right));
+                }
+            }
+            Style::Bar => canvas.rect(
+                (left, top),
+                (right, baseline),
+                &format!(r#"fill="{0}" fill-opacity="0.4" stroke="{0}""#, colour),
+            ),
+        }
+        if let Some(error) = bin.error.filter(|it| *it > 0.0) {
+            let center = (left + right) / 2.0;
+            let low = bin.value - error;
+            let low = if y.accepts(low) { y.map(low) } else { baseline };
+            canvas.line(
+                (center, low),
+                (center, y.map(bin.value + error)),
+                colour,
+                1.0,
+            );
+        }
+        previous = Some(*bin);
+    }
+    if !path.is_empty() {
+        canvas.path(path.trim_start(), colour, 2.0);
+    }
+}
+
+/// A cell of a heatmap.
+#[derive(Copy, Clone, Debug)]
+struct Cell {
+    x: (f64, f64),
+    y: (f64, f64),
+    value: f64,
+}
+
+/// A heatmap of a 2D histogram with a colour bar.
+///
+/// Empty bins, with a value of zero, are not drawn.
+/// See the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct Plot2D {
+    layout: Layout,
+    z_label: String,
+    log_z: bool,
+    cells: Vec<Cell>,
+}
+
+impl Plot2D {
+    /// Factory method for a heatmap of the histogram with a size of 640x480 pixels.
+    pub fn new<A, X, Y, V, H>(hist: &H) -> Self
+    where
+        H: Histogram<A, V>,
+        A: Axis<BinInterval = (BinInterval<X>, BinInterval<Y>)>,
+        X: NumCast,
+        Y: NumCast,
+        V: PlotValue,
+    {
+        let cells = hist
+            .axes()
+            .iter()
+            .filter_map(|(index, (x, y))| {
+                Some(Cell {
+                    x: edges(x)?,
+                    y: edges(y)?,
+                    value: hist
+                        .value_at_index(index)
+                        .map_or(0.0, PlotValue::plot_value),
+                })
+            })
+            .collect();
+        Self {
+            layout: Layout::default(),
+            z_label: String::new(),
+            log_z: false,
+            cells,
+        }
+    }
+
+    /// Set the title drawn above the plot.
+    pub fn title(mut self, title: &str) -> Self {
+        self.layout.title = title.to_string();
+        self
+    }
+
+    /// Set the label of the x-axis.
+    pub fn x_label(mut self, label: &str) -> Self {
+        self.layout.x_label = label.to_string();
+        self
+    }
+
+    /// Set the label of the y-axis.
+    pub fn y_label(mut self, label: &str) -> Self {
+        self.layout.y_label = label.to_string();
+        self
+    }
+
+    /// Set the label of the colour bar.
+    pub fn z_label(mut self, label: &str) -> Self {
+        self.z_label = label.to_string();
+        self
+    }
+
+    /// Set the size of the image in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.layout.width = width;
+        self.layout.height = height;
+        self
+    }
+
+    /// Use a logarithmic colour scale.
+    ///
+    /// Bins with values that are not positive are not drawn.
+    pub fn log_z(mut self, log: bool) -> Self {
+        self.log_z = log;
+        self
+    }
+
+    /// Render the plot as an SVG document.
+    pub fn to_svg(&self) -> String {
+        let layout = &self.layout;
+        let mut canvas = Canvas::new(layout.width, layout.height);
+        let width = layout.width as f64;
+        let area = Area {
+            left: 70.0,
+            top: 40.0,
+            right: width - 110.0,
+            bottom: layout.height as f64 - 50.0,
+        };
+        let x = Scale::new(
+            range(
+                self.cells.iter().flat_map(|it| [it.x.0, it.x.1]),
+                false,
+                false,
+            ),
+            false,
+            area.left,
+            area.right,
+        );
+        let y = Scale::new(
+            range(
+                self.cells.iter().flat_map(|it| [it.y.0, it.y.1]),
+                false,
+                false,
+            ),
+            false,
+            area.bottom,
+            area.top,
+        );
+        let drawn = |cell: &&Cell| cell.value != 0.0 && cell.value.is_finite();
+        let z = Scale::new(
+            range(
+                self.cells.iter().filter(drawn).map(|it| it.value),
+                self.log_z,
+                false,
+            ),
+            self.log_z,
+            area.bottom,
+            area.top,
+        );
+        for cell in self.cells.iter().filter(drawn) {
+            if !z.accepts(cell.value) {
+                continue;
+            }
+            canvas.rect(
+                (x.map(cell.x.0), y.map(cell.y.0)),
+                (x.map(cell.x.1), y.map(cell.y.1)),
+                &format!(r#"fill="{}""#, colour(z.fraction(cell.value))),
+            );
+        }
+        canvas.frame(area, &x, &y);
+        canvas.labels(area, &layout.title, &layout.x_label, &layout.y_label);
+        draw_colour_bar(&mut canvas, area, width, &z, &self.z_label);
+        canvas.finish()
+    }
+
+    /// Write the plot as an SVG document.
+    pub fn write<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_svg().as_bytes())
+    }
+}
+
+fn draw_colour_bar(canvas: &mut Canvas, area: Area, width: f64, z: &Scale, label: &str) {
+    let left = width - 95.0;
+    let right = left + 15.0;
+    let stops: String = (0..=4)
+        .map(|it| {
+            let fraction = it as f64 / 4.0;
+            format!(
+                r#"<stop offset="{}" stop-color="{}"/>"#,
+                fraction,
+                colour(fraction)
+            )
+        })
+        .collect();
+    canvas.raw(&format!(
+        r#"<linearGradient id="colour-bar" x1="0" y1="1" x2="0" y2="0">{}</linearGradient>"#,
+        stops
+    ));
+    canvas.rect(
+        (left, area.top),
+        (right, area.bottom),
+        r#"fill="url(#colour-bar)" stroke="black""#,
+    );
+    for (tick, text) in z.ticks() {
+        let position = z.map(tick);
+        canvas.line((right, position), (right + 4.0, position), "black", 1.0);
+        canvas.text(
+            (right + 7.0, position + 4.0),
+            &text,
+            Anchor::Start,
+            11,
+            false,
+        );
+    }
+    if !label.is_empty() {
+        let middle = (area.top + area.bottom) / 2.0;
+        canvas.text((width - 12.0, middle), label, Anchor::Middle, 13, true);
+    }
+}
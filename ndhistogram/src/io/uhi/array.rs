@@ -0,0 +1,132 @@
+use serde::{
+    de::Error as _,
+    ser::{SerializeSeq, Serializer},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::error::ConversionError;
+
+/// An N-dimensional array of bin values in C (row-major) order.
+///
+/// This is serialized as nested lists, as produced by `numpy.ndarray.tolist`,
+/// so that it can be read directly with `numpy.asarray`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NdArray<T> {
+    shape: Vec<usize>,
+    data: Vec<T>,
+}
+
+impl<T> NdArray<T> {
+    /// Factory method to create an array with the given shape from data in C order.
+    ///
+    /// Returns [ConversionError::ShapeMismatch] if the length of data does not match the shape.
+    pub fn new(shape: Vec<usize>, data: Vec<T>) -> Result<Self, ConversionError> {
+        if shape.iter().product::<usize>() != data.len() {
+            return Err(ConversionError::ShapeMismatch);
+        }
+        Ok(Self { shape, data })
+    }
+
+    /// Number of elements along each dimension.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Get the flat data in C order.
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Consume the array and return the flat data in C order.
+    pub fn into_data(self) -> Vec<T> {
+        self.data
+    }
+
+    pub(crate) fn with_shape(self, shape: &[usize]) -> Result<Vec<T>, ConversionError> {
+        if self.shape != shape {
+            return Err(ConversionError::ShapeMismatch);
+        }
+        Ok(self.data)
+    }
+}
+
+struct NestedRef<'a, T> {
+    shape: &'a [usize],
+    data: &'a [T],
+}
+
+impl<T: Serialize> Serialize for NestedRef<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.shape.split_first() {
+            None => self.data[0].serialize(serializer),
+            Some((&len, inner)) => {
+                let mut seq = serializer.serialize_seq(Some(len))?;
+                let stride = inner.iter().product::<usize>();
+                for index in 0..len {
+                    seq.serialize_element(&NestedRef {
+                        shape: inner,
+                        data: &self.data[index * stride..(index + 1) * stride],
+                    })?;
+                }
+                seq.end()
+            }
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for NdArray<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        NestedRef {
+            shape: &self.shape,
+            data: &self.data,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Nested<T> {
+    Scalar(T),
+    List(Vec<Nested<T>>),
+}
+
+impl<T> Nested<T> {
+    fn shape(&self) -> Vec<usize> {
+        let mut shape = Vec::new();
+        let mut current = self;
+        while let Self::List(items) = current {
+            shape.push(items.len());
+            match items.first() {
+                Some(first) => current = first,
+                None => break,
+            }
+        }
+        shape
+    }
+
+    fn flatten_into(self, shape: &[usize], data: &mut Vec<T>) -> Result<(), ConversionError> {
+        match (self, shape.split_first()) {
+            (Self::Scalar(value), None) => {
+                data.push(value);
+                Ok(())
+            }
+            (Self::List(items), Some((&len, inner))) if items.len() == len => items
+                .into_iter()
+                .try_for_each(|item| item.flatten_into(inner, data)),
+            _ => Err(ConversionError::ShapeMismatch),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for NdArray<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let nested = Nested::deserialize(deserializer)?;
+        let shape = nested.shape();
+        let mut data = Vec::with_capacity(shape.iter().product());
+        nested
+            .flatten_into(&shape, &mut data)
+            .map_err(|_| D::Error::custom("array is not rectangular"))?;
+        Ok(Self { shape, data })
+    }
+}
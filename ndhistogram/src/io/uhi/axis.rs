@@ -0,0 +1,454 @@
+use num_traits::Float;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    axis::{
+        Axis, Boolean, Category, CategoryNoFlow, IntCategory, IntCategoryNoFlow, OrderedCategory,
+        OrderedCategoryNoFlow, StrCategory, StrCategoryNoFlow, Uniform, UniformCyclic,
+        UniformNoFlow, Variable, VariableCyclic, VariableNoFlow,
+    },
+    error::ConversionError,
+    Axes, AxesTuple,
+};
+
+use super::Metadata;
+
+/// An axis in the UHI serialization schema.
+///
+/// The `type` field of the serialized axis determines the variant.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AxisSchema {
+    /// Equal sized bins in the range [lower, upper).
+    Regular {
+        /// Low edge of the axis.
+        lower: f64,
+        /// High edge of the axis.
+        upper: f64,
+        /// Number of bins, excluding flow bins.
+        bins: usize,
+        /// Whether the axis has an underflow bin.
+        underflow: bool,
+        /// Whether the axis has an overflow bin.
+        overflow: bool,
+        /// Whether the axis wraps around.
+        circular: bool,
+        /// Axis metadata. Ignored by ndhistogram.
+        #[serde(default)]
+        metadata: Metadata,
+    },
+    /// Variable sized bins given by a set of increasing edges.
+    Variable {
+        /// Bin edges in increasing order.
+        edges: Vec<f64>,
+        /// Whether the axis has an underflow bin.
+        underflow: bool,
+        /// Whether the axis has an overflow bin.
+        overflow: bool,
+        /// Whether the axis wraps around.
+        circular: bool,
+        /// Axis metadata. Ignored by ndhistogram.
+        #[serde(default)]
+        metadata: Metadata,
+    },
+    /// A set of string categories.
+    CategoryStr {
+        /// Category values in bin order.
+        categories: Vec<String>,
+        /// Whether the axis has an overflow bin for all other values.
+        flow: bool,
+        /// Axis metadata. Ignored by ndhistogram.
+        #[serde(default)]
+        metadata: Metadata,
+    },
+    /// A set of integer categories.
+    CategoryInt {
+        /// Category values in bin order.
+        categories: Vec<i64>,
+        /// Whether the axis has an overflow bin for all other values.
+        flow: bool,
+        /// Axis metadata. Ignored by ndhistogram.
+        #[serde(default)]
+        metadata: Metadata,
+    },
+    /// An axis with two bins, false and true.
+    Boolean {
+        /// Axis metadata. Ignored by ndhistogram.
+        #[serde(default)]
+        metadata: Metadata,
+    },
+}
+
+impl AxisSchema {
+    /// Number of bins on this axis, including any flow bins.
+    pub fn num_bins(&self) -> usize {
+        match self {
+            Self::Regular {
+                bins,
+                underflow,
+                overflow,
+                ..
+            } => bins + usize::from(*underflow) + usize::from(*overflow),
+            Self::Variable {
+                edges,
+                underflow,
+                overflow,
+                ..
+            } => edges.len().saturating_sub(1) + usize::from(*underflow) + usize::from(*overflow),
+            Self::CategoryStr {
+                categories, flow, ..
+            } => categories.len() + usize::from(*flow),
+            Self::CategoryInt {
+                categories, flow, ..
+            } => categories.len() + usize::from(*flow),
+            Self::Boolean { .. } => 2,
+        }
+    }
+}
+
+/// An [Axis] that can be converted to and from an [AxisSchema].
+pub trait UhiAxis: Axis + Sized {
+    /// Convert this axis to the UHI schema.
+    fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError>;
+
+    /// Create an axis from the UHI schema.
+    ///
+    /// Returns [ConversionError::IncompatibleAxis] if the schema describes a
+    /// different kind of axis. The axis number is always 0 and is updated
+    /// by [UhiAxes::from_uhi_axes].
+    fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError>;
+}
+
+/// A set of [Axes](crate::Axes) that can be converted to and from a list of [AxisSchema].
+///
+/// This is implemented for [AxesTuple] where each axis implements [UhiAxis].
+pub trait UhiAxes: Axes + Sized {
+    /// Convert each axis to the UHI schema.
+    fn to_uhi_axes(&self) -> Result<Vec<AxisSchema>, ConversionError>;
+
+    /// Create the axes from the UHI schema.
+    fn from_uhi_axes(axes: &[AxisSchema]) -> Result<Self, ConversionError>;
+}
+
+fn to_f64<T: Float>(value: T) -> Result<f64, ConversionError> {
+    value.to_f64().ok_or(ConversionError::ValueOutOfRange)
+}
+
+fn from_f64<T: Float>(value: f64) -> Result<T, ConversionError> {
+    T::from(value).ok_or(ConversionError::ValueOutOfRange)
+}
+
+fn regular<T: Float>(
+    num_bins: usize,
+    low: T,
+    high: T,
+    flow: bool,
+    circular: bool,
+) -> Result<AxisSchema, ConversionError> {
+    Ok(AxisSchema::Regular {
+        lower: to_f64(low)?,
+        upper: to_f64(high)?,
+        bins: num_bins,
+        underflow: flow,
+        overflow: flow,
+        circular,
+        metadata: Metadata::default(),
+    })
+}
+
+fn variable<T: Float>(
+    edges: &[T],
+    flow: bool,
+    circular: bool,
+) -> Result<AxisSchema, ConversionError> {
+    Ok(AxisSchema::Variable {
+        edges: edges
+            .iter()
+            .copied()
+            .map(to_f64)
+            .collect::<Result<_, _>>()?,
+        underflow: flow,
+        overflow: flow,
+        circular,
+        metadata: Metadata::default(),
+    })
+}
+
+/// Get the parameters of a regular axis if it has the given flow bins and circular flag.
+fn as_regular<T: Float>(
+    axis: &AxisSchema,
+    flow: bool,
+    circular: bool,
+) -> Result<(usize, T, T), ConversionError> {
+    match axis {
+        AxisSchema::Regular {
+            lower,
+            upper,
+            bins,
+            underflow,
+            overflow,
+            circular: is_circular,
+            ..
+        } if *underflow == flow && *overflow == flow && *is_circular == circular => {
+            Ok((*bins, from_f64(*lower)?, from_f64(*upper)?))
+        }
+        _ => Err(ConversionError::IncompatibleAxis(0)),
+    }
+}
+
+/// Get the edges of a variable axis if it has the given flow bins and circular flag.
+fn as_variable<T: Float>(
+    axis: &AxisSchema,
+    flow: bool,
+    circular: bool,
+) -> Result<Vec<T>, ConversionError> {
+    match axis {
+        AxisSchema::Variable {
+            edges,
+            underflow,
+            overflow,
+            circular: is_circular,
+            ..
+        } if *underflow == flow && *overflow == flow && *is_circular == circular => {
+            edges.iter().copied().map(from_f64).collect()
+        }
+        _ => Err(ConversionError::IncompatibleAxis(0)),
+    }
+}
+
+impl<T: Float> UhiAxis for Uniform<T> {
+    fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+        regular(self.num_bins() - 2, *self.low(), *self.high(), true, false)
+    }
+
+    fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+        let (num_bins, low, high) = as_regular(axis, true, false)?;
+        Ok(Self::new(num_bins, low, high)?)
+    }
+}
+
+impl<T: Float> UhiAxis for UniformNoFlow<T> {
+    fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+        regular(self.num_bins(), *self.low(), *self.high(), false, false)
+    }
+
+    fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+        let (num_bins, low, high) = as_regular(axis, false, false)?;
+        Ok(Self::new(num_bins, low, high)?)
+    }
+}
+
+impl<T: Float> UhiAxis for UniformCyclic<T> {
+    fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+        regular(self.num_bins(), *self.low(), *self.high(), false, true)
+    }
+
+    fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+        let (num_bins, low, high) = as_regular(axis, false, true)?;
+        Ok(Self::new(num_bins, low, high)?)
+    }
+}
+
+impl<T: Float> UhiAxis for Variable<T> {
+    fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+        variable(self.bin_edges(), true, false)
+    }
+
+    fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+        Ok(Self::new(as_variable(axis, true, false)?)?)
+    }
+}
+
+impl<T: Float> UhiAxis for VariableNoFlow<T> {
+    fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+        variable(self.bin_edges(), false, false)
+    }
+
+    fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+        Ok(Self::new(as_variable(axis, false, false)?)?)
+    }
+}
+
+impl<T: Float> UhiAxis for VariableCyclic<T> {
+    fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+        variable(self.bin_edges(), false, true)
+    }
+
+    fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+        Ok(Self::new(as_variable(axis, false, true)?)?)
+    }
+}
+
+macro_rules! impl_uhi_axis_for_string_category {
+    ($($Axis:ident, $flow:expr);*) => {
+        $(
+            impl UhiAxis for $Axis<String> {
+                fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+                    Ok(AxisSchema::CategoryStr {
+                        categories: self.categories().cloned().collect(),
+                        flow: $flow,
+                        metadata: Metadata::default(),
+                    })
+                }
+
+                fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+                    match axis {
+                        AxisSchema::CategoryStr { categories, flow, .. } if *flow == $flow => {
+                            Ok(Self::new(categories.iter().cloned()))
+                        }
+                        _ => Err(ConversionError::IncompatibleAxis(0)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_uhi_axis_for_string_category! {
+    Category, true;
+    CategoryNoFlow, false;
+    OrderedCategory, true;
+    OrderedCategoryNoFlow, false
+}
+
+macro_rules! impl_uhi_axis_for_str_category {
+    ($($Axis:ident, $flow:expr);*) => {
+        $(
+            impl UhiAxis for $Axis {
+                fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+                    Ok(AxisSchema::CategoryStr {
+                        categories: self.categories().map(String::from).collect(),
+                        flow: $flow,
+                        metadata: Metadata::default(),
+                    })
+                }
+
+                fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+                    match axis {
+                        AxisSchema::CategoryStr { categories, flow, .. } if *flow == $flow => {
+                            Ok(Self::new(categories.iter().map(String::as_str)))
+                        }
+                        _ => Err(ConversionError::IncompatibleAxis(0)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_uhi_axis_for_str_category! {
+    StrCategory, true;
+    StrCategoryNoFlow, false
+}
+
+macro_rules! impl_uhi_axis_for_integer_category {
+    ($Axis:ident, $flow:expr, $($T:ty),*) => {
+        $(
+            impl UhiAxis for $Axis<$T> {
+                fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+                    Ok(AxisSchema::CategoryInt {
+                        categories: self
+                            .categories()
+                            .map(|it| i64::try_from(*it).map_err(|_| ConversionError::ValueOutOfRange))
+                            .collect::<Result<_, _>>()?,
+                        flow: $flow,
+                        metadata: Metadata::default(),
+                    })
+                }
+
+                fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+                    match axis {
+                        AxisSchema::CategoryInt { categories, flow, .. } if *flow == $flow => {
+                            let categories = categories
+                                .iter()
+                                .map(|it| <$T>::try_from(*it).map_err(|_| ConversionError::ValueOutOfRange))
+                                .collect::<Result<Vec<_>, _>>()?;
+                            Ok(Self::new(categories))
+                        }
+                        _ => Err(ConversionError::IncompatibleAxis(0)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_uhi_axis_for_integer_category! {Category, true, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize}
+impl_uhi_axis_for_integer_category! {CategoryNoFlow, false, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize}
+impl_uhi_axis_for_integer_category! {IntCategory, true, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize}
+impl_uhi_axis_for_integer_category! {IntCategoryNoFlow, false, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize}
+impl_uhi_axis_for_integer_category! {OrderedCategory, true, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize}
+impl_uhi_axis_for_integer_category! {OrderedCategoryNoFlow, false, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize}
+
+impl UhiAxis for Boolean {
+    fn to_uhi_axis(&self) -> Result<AxisSchema, ConversionError> {
+        Ok(AxisSchema::Boolean {
+            metadata: Metadata::default(),
+        })
+    }
+
+    fn from_uhi_axis(axis: &AxisSchema) -> Result<Self, ConversionError> {
+        match axis {
+            AxisSchema::Boolean { .. } => Ok(Self::new()),
+            _ => Err(ConversionError::IncompatibleAxis(0)),
+        }
+    }
+}
+
+// Implements UhiAxes for every AxesTuple from 1 dimension up to the full list of type parameters.
+macro_rules! impl_uhi_axes {
+    ([$($done:ident: $done_index:tt),*] $next:ident: $next_index:tt $(, $rest:ident: $rest_index:tt)*) => {
+        impl_uhi_axes!(@IMPL $($done: $done_index,)* $next: $next_index);
+        impl_uhi_axes!([$($done: $done_index,)* $next: $next_index] $($rest: $rest_index),*);
+    };
+    ([$($done:ident: $done_index:tt),*]) => {};
+    (@IMPL $($T:ident: $index:tt),+) => {
+        impl<$($T: UhiAxis),+> UhiAxes for AxesTuple<($($T,)+)> {
+            fn to_uhi_axes(&self) -> Result<Vec<AxisSchema>, ConversionError> {
+                let axes = self.as_tuple();
+                Ok(vec![$(axes.$index.to_uhi_axis()?),+])
+            }
+
+            fn from_uhi_axes(axes: &[AxisSchema]) -> Result<Self, ConversionError> {
+                let expected = [$($index),+].len();
+                if axes.len() != expected {
+                    return Err(ConversionError::WrongNumberOfAxes {
+                        expected,
+                        actual: axes.len(),
+                    });
+                }
+                Ok(($(
+                    $T::from_uhi_axis(&axes[$index]).map_err(|error| match error {
+                        ConversionError::IncompatibleAxis(_) => ConversionError::IncompatibleAxis($index),
+                        other => other,
+                    })?,
+                )+)
+                    .into())
+            }
+        }
+    };
+}
+
+impl_uhi_axes! {
+    [] X: 0,
+    Y: 1,
+    Z: 2,
+    T: 3,
+    D4: 4,
+    D5: 5,
+    D6: 6,
+    D7: 7,
+    D8: 8,
+    D9: 9,
+    D10: 10,
+    D11: 11,
+    D12: 12,
+    D13: 13,
+    D14: 14,
+    D15: 15,
+    D16: 16,
+    D17: 17,
+    D18: 18,
+    D19: 19,
+    D20: 20
+}
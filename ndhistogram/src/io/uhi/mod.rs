@@ -0,0 +1,205 @@
+//! Conversion to and from the UHI serialization schema
+//!
+//! The Scikit-HEP [Unified Histogram Interface](https://uhi.readthedocs.io/en/latest/serialization.html)
+//! (UHI) defines a versioned schema to exchange histograms between libraries.
+//! Unlike the "serde" implementations of the ndhistogram types, which depend on the internal
+//! layout of each type, this schema is stable and documented.
+//! Histograms written by ndhistogram with this schema may be read by
+//! [boost-histogram](https://github.com/scikit-hep/boost-histogram) and
+//! [hist](https://github.com/scikit-hep/hist) in Python and vice versa.
+//!
+//! A [HistogramSchema] is created from a [VecHistogram] or [HashHistogram] with [TryFrom],
+//! and may then be serialized with any serde format, for example JSON with `serde_json`.
+//! The bin values of each storage array include the underflow and overflow bins and are
+//! stored in C order (the last axis varies fastest), as nested lists.
+//!
+//! The following axes are supported:
+//!
+//! - [Uniform], [UniformNoFlow] and [UniformCyclic] with floating point coordinates: "regular" axis.
+//! - [Variable], [VariableNoFlow] and [VariableCyclic] with floating point coordinates: "variable" axis.
+//! - [Category] and [CategoryNoFlow] of [String]: "category_str" axis.
+//! - [Category] and [CategoryNoFlow] of primitive integers: "category_int" axis.
+//! - [IntCategory] and [IntCategoryNoFlow]: "category_int" axis.
+//!   The schema does not record whether an axis is extendable, so an [IntCategoryNoFlow]
+//!   is always read as not extendable.
+//! - [OrderedCategory] and [OrderedCategoryNoFlow] of [String] or primitive integers:
+//!   "category_str" or "category_int" axis. The categories are sorted when read.
+//! - [StrCategory] and [StrCategoryNoFlow]: "category_str" axis.
+//! - [Boolean]: "boolean" axis.
+//!
+//! A [Hexagonal](crate::axis::Hexagonal) axis has no equivalent in the schema,
+//! so histograms with such an axis cannot be converted.
+//!
+//! An axis is only read if its flow bins match the requested axis type, for example
+//! a regular axis with underflow and overflow bins may be read as a [Uniform] but not
+//! as a [UniformNoFlow].
+//!
+//! The following bin value types are supported:
+//!
+//! - Primitive integers: "int" storage.
+//! - Primitive floating point numbers and [Sum](crate::value::Sum): "double" storage.
+//! - [WeightedSum](crate::value::WeightedSum): "weighted" storage.
+//! - [Mean](crate::value::Mean): "mean" storage.
+//! - [WeightedMean](crate::value::WeightedMean): "weighted_mean" storage.
+//!   The number of fills is not part of the schema, so it is estimated as
+//!   the effective number of entries when read.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, Hist1D, axis::Uniform, io::uhi::HistogramSchema};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut hist = ndhistogram!(Uniform::new(4, 0.0, 4.0)?);
+//! hist.fill(&1.5);
+//! let json = serde_json::to_string(&HistogramSchema::try_from(&hist)?)?;
+//! let schema: HistogramSchema = serde_json::from_str(&json)?;
+//! let read: Hist1D<Uniform> = schema.try_into()?;
+//! assert_eq!(read, hist);
+//! # Ok(()) }
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ConversionError, io::c_order_indices, HashHistogram, Histogram, VecHistogram};
+
+mod array;
+pub use array::NdArray;
+mod axis;
+pub use axis::{AxisSchema, UhiAxes, UhiAxis};
+mod storage;
+pub use storage::{StorageSchema, UhiValue};
+
+#[cfg(doc)]
+use crate::axis::{
+    Boolean, Category, CategoryNoFlow, IntCategory, IntCategoryNoFlow, OrderedCategory,
+    OrderedCategoryNoFlow, StrCategory, StrCategoryNoFlow, Uniform, UniformCyclic, UniformNoFlow,
+    Variable, VariableCyclic, VariableNoFlow,
+};
+
+/// The version of the UHI schema written by this module.
+pub const UHI_SCHEMA_VERSION: u32 = 1;
+
+/// User metadata attached to a histogram or axis.
+///
+/// This is written as an empty object. Metadata in the input is ignored.
+#[derive(Default, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Metadata {}
+
+/// A histogram in the UHI serialization schema.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HistogramSchema {
+    /// The schema version.
+    pub uhi_schema: u32,
+    /// Name and version of the libraries that wrote this histogram.
+    #[serde(default)]
+    pub writer_info: BTreeMap<String, BTreeMap<String, String>>,
+    /// The axes of the histogram.
+    pub axes: Vec<AxisSchema>,
+    /// The bin values of the histogram.
+    pub storage: StorageSchema,
+    /// Histogram metadata. Ignored by ndhistogram.
+    #[serde(default)]
+    pub metadata: Metadata,
+}
+
+impl HistogramSchema {
+    fn new<'a, A: UhiAxes, V: UhiValue + 'a>(
+        axes: &A,
+        value: impl Fn(usize) -> Option<&'a V>,
+    ) -> Result<Self, ConversionError> {
+        let axes = axes.to_uhi_axes()?;
+        let shape = shape(&axes);
+        let values = c_order_indices(&shape)
+            .into_iter()
+            .map(|index| value(index).ok_or(ConversionError::ShapeMismatch))
+            .collect::<Result<Vec<_>, _>>()?;
+        let writer_info = BTreeMap::from([(
+            env!("CARGO_PKG_NAME").to_string(),
+            BTreeMap::from([("version".to_string(), env!("CARGO_PKG_VERSION").to_string())]),
+        )]);
+        Ok(Self {
+            uhi_schema: UHI_SCHEMA_VERSION,
+            writer_info,
+            storage: V::to_uhi_storage(&values, &shape)?,
+            axes,
+            metadata: Metadata::default(),
+        })
+    }
+
+    /// Returns the axes and the bin values paired with their ndhistogram bin index.
+    fn into_parts<A: UhiAxes, V: UhiValue>(self) -> Result<(A, Vec<(usize, V)>), ConversionError> {
+        if self.uhi_schema > UHI_SCHEMA_VERSION {
+            return Err(ConversionError::UnsupportedVersion);
+        }
+        let axes = A::from_uhi_axes(&self.axes)?;
+        let shape = shape(&self.axes);
+        if shape.iter().product::<usize>() != axes.num_bins() {
+            return Err(ConversionError::ShapeMismatch);
+        }
+        let values = V::from_uhi_storage(self.storage, &shape)?;
+        let mut values: Vec<_> = c_order_indices(&shape).into_iter().zip(values).collect();
+        values.sort_unstable_by_key(|(index, _)| *index);
+        Ok((axes, values))
+    }
+}
+
+fn shape(axes: &[AxisSchema]) -> Vec<usize> {
+    axes.iter().map(AxisSchema::num_bins).collect()
+}
+
+impl<A: UhiAxes, V: UhiValue> TryFrom<&VecHistogram<A, V>> for HistogramSchema {
+    type Error = ConversionError;
+
+    fn try_from(hist: &VecHistogram<A, V>) -> Result<Self, Self::Error> {
+        Self::new(hist.axes(), |index| hist.as_slice().get(index))
+    }
+}
+
+impl<A: UhiAxes, V: UhiValue + Default, S: BuildHasher> TryFrom<&HashHistogram<A, V, S>>
+    for HistogramSchema
+{
+    type Error = ConversionError;
+
+    fn try_from(hist: &HashHistogram<A, V, S>) -> Result<Self, Self::Error> {
+        let default = V::default();
+        Self::new(hist.axes(), |index| {
+            Some(hist.as_map().get(&index).unwrap_or(&default))
+        })
+    }
+}
+
+impl<A: UhiAxes, V: UhiValue> TryFrom<HistogramSchema> for VecHistogram<A, V> {
+    type Error = ConversionError;
+
+    fn try_from(schema: HistogramSchema) -> Result<Self, Self::Error> {
+        let (axes, values) = schema.into_parts()?;
+        Self::from_vec(axes, values.into_iter().map(|(_, value)| value).collect())
+            .map_err(|_| ConversionError::ShapeMismatch)
+    }
+}
+
+/// Only bins with a value different from the default value are stored in the HashHistogram.
+impl<A, V, S> TryFrom<HistogramSchema> for HashHistogram<A, V, S>
+where
+    A: UhiAxes,
+    V: UhiValue + Default + PartialEq,
+    S: BuildHasher + Default,
+{
+    type Error = ConversionError;
+
+    fn try_from(schema: HistogramSchema) -> Result<Self, Self::Error> {
+        let (axes, values) = schema.into_parts()?;
+        let default = V::default();
+        let values: HashMap<usize, V, S> = values
+            .into_iter()
+            .filter(|(_, value)| *value != default)
+            .collect();
+        Self::from_map(axes, values).map_err(|_| ConversionError::ShapeMismatch)
+    }
+}
@@ -0,0 +1,346 @@
+use num_traits::{Float, NumCast, NumOps, Signed};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ConversionError,
+    io::nearest,
+    value::{Mean, Sum, WeightedMean, WeightedSum},
+};
+
+use super::NdArray;
+
+/// The bin value storage in the UHI serialization schema.
+///
+/// The `type` field of the serialized storage determines the variant.
+/// Each array includes the flow bins of the axes.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StorageSchema {
+    /// Integer bin counts.
+    Int {
+        /// Count in each bin.
+        values: NdArray<i64>,
+    },
+    /// Floating point bin values.
+    Double {
+        /// Value of each bin.
+        values: NdArray<f64>,
+    },
+    /// Sum of weights and sum of weights squared.
+    Weighted {
+        /// Sum of weights in each bin.
+        values: NdArray<f64>,
+        /// Sum of weights squared in each bin.
+        variances: NdArray<f64>,
+    },
+    /// Mean of the samples filled in each bin.
+    Mean {
+        /// Number of samples in each bin.
+        counts: NdArray<f64>,
+        /// Mean of the samples in each bin.
+        values: NdArray<f64>,
+        /// Sample variance (with Bessel's correction) of the samples in each bin.
+        variances: NdArray<f64>,
+    },
+    /// Weighted mean of the samples filled in each bin.
+    WeightedMean {
+        /// Sum of weights in each bin.
+        sum_of_weights: NdArray<f64>,
+        /// Sum of weights squared in each bin.
+        sum_of_weights_squared: NdArray<f64>,
+        /// Weighted mean of the samples in each bin.
+        values: NdArray<f64>,
+        /// Weighted variance of the samples in each bin.
+        variances: NdArray<f64>,
+    },
+}
+
+/// A bin value type that can be converted to and from a [StorageSchema].
+pub trait UhiValue: Sized {
+    /// Convert bin values in C order to the UHI storage with the given shape.
+    fn to_uhi_storage(values: &[&Self], shape: &[usize]) -> Result<StorageSchema, ConversionError>;
+
+    /// Convert the UHI storage to bin values in C order.
+    ///
+    /// Returns [ConversionError::ShapeMismatch] if the arrays do not have the given shape and
+    /// [ConversionError::IncompatibleStorage] if the storage type cannot be represented by Self.
+    fn from_uhi_storage(
+        storage: StorageSchema,
+        shape: &[usize],
+    ) -> Result<Vec<Self>, ConversionError>;
+}
+
+fn array<T, F>(values: &[&T], shape: &[usize], f: F) -> Result<NdArray<f64>, ConversionError>
+where
+    F: Fn(&T) -> Result<f64, ConversionError>,
+{
+    NdArray::new(
+        shape.to_vec(),
+        values.iter().map(|it| f(it)).collect::<Result<_, _>>()?,
+    )
+}
+
+fn to_f64<T: NumCast>(value: T) -> Result<f64, ConversionError> {
+    value.to_f64().ok_or(ConversionError::ValueOutOfRange)
+}
+
+macro_rules! impl_uhi_value_for_integer {
+    ($($T:ty),*) => {
+        $(
+            impl UhiValue for $T {
+                fn to_uhi_storage(values: &[&Self], shape: &[usize]) -> Result<StorageSchema, ConversionError> {
+                    let values = values
+                        .iter()
+                        .map(|it| i64::try_from(**it).map_err(|_| ConversionError::ValueOutOfRange))
+                        .collect::<Result<_, _>>()?;
+                    Ok(StorageSchema::Int { values: NdArray::new(shape.to_vec(), values)? })
+                }
+
+                fn from_uhi_storage(storage: StorageSchema, shape: &[usize]) -> Result<Vec<Self>, ConversionError> {
+                    match storage {
+                        StorageSchema::Int { values } => values
+                            .with_shape(shape)?
+                            .into_iter()
+                            .map(|it| Self::try_from(it).map_err(|_| ConversionError::ValueOutOfRange))
+                            .collect(),
+                        _ => Err(ConversionError::IncompatibleStorage),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_uhi_value_for_integer! {i8, i16, i32, i64, isize, u8, u16, u32, u64, usize}
+
+macro_rules! impl_uhi_value_for_float {
+    ($($T:ty),*) => {
+        $(
+            impl UhiValue for $T {
+                fn to_uhi_storage(values: &[&Self], shape: &[usize]) -> Result<StorageSchema, ConversionError> {
+                    Ok(StorageSchema::Double { values: array(values, shape, |it| Ok(<f64 as From<$T>>::from(*it)))? })
+                }
+
+                fn from_uhi_storage(storage: StorageSchema, shape: &[usize]) -> Result<Vec<Self>, ConversionError> {
+                    float_values(storage, shape)
+                }
+            }
+        )*
+    };
+}
+
+impl_uhi_value_for_float! {f32, f64}
+
+/// Read double or integer storage as floating point values.
+fn float_values<T: NumCast>(
+    storage: StorageSchema,
+    shape: &[usize],
+) -> Result<Vec<T>, ConversionError> {
+    match storage {
+        StorageSchema::Double { values } => values
+            .with_shape(shape)?
+            .into_iter()
+            .map(|it| T::from(it).ok_or(ConversionError::ValueOutOfRange))
+            .collect(),
+        StorageSchema::Int { values } => values
+            .with_shape(shape)?
+            .into_iter()
+            .map(|it| T::from(it).ok_or(ConversionError::ValueOutOfRange))
+            .collect(),
+        _ => Err(ConversionError::IncompatibleStorage),
+    }
+}
+
+impl<T: Float> UhiValue for Sum<T> {
+    fn to_uhi_storage(values: &[&Self], shape: &[usize]) -> Result<StorageSchema, ConversionError> {
+        Ok(StorageSchema::Double {
+            values: array(values, shape, |it| to_f64(it.get()))?,
+        })
+    }
+
+    fn from_uhi_storage(
+        storage: StorageSchema,
+        shape: &[usize],
+    ) -> Result<Vec<Self>, ConversionError> {
+        Ok(float_values(storage, shape)?
+            .into_iter()
+            .map(Self::with_sum)
+            .collect())
+    }
+}
+
+impl<T: Float> UhiValue for WeightedSum<T> {
+    fn to_uhi_storage(values: &[&Self], shape: &[usize]) -> Result<StorageSchema, ConversionError> {
+        Ok(StorageSchema::Weighted {
+            values: array(values, shape, |it| to_f64(it.get()))?,
+            variances: array(values, shape, |it| to_f64(it.variance()))?,
+        })
+    }
+
+    fn from_uhi_storage(
+        storage: StorageSchema,
+        shape: &[usize],
+    ) -> Result<Vec<Self>, ConversionError> {
+        match storage {
+            StorageSchema::Weighted { values, variances } => values
+                .with_shape(shape)?
+                .into_iter()
+                .zip(variances.with_shape(shape)?)
+                .map(|(sum, variance)| {
+                    Ok(Self::with_sum_and_variance(
+                        nearest(sum)?,
+                        nearest(variance)?,
+                    ))
+                })
+                .collect(),
+            _ => Err(ConversionError::IncompatibleStorage),
+        }
+    }
+}
+
+/// Mean and sample variance from the number of samples, sum and sum of squares.
+fn mean_and_variance(count: f64, sum: f64, sum_of_squares: f64) -> (f64, f64) {
+    if count <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let mean = sum / count;
+    let variance = if count > 1.0 {
+        (sum_of_squares - count * mean * mean) / (count - 1.0)
+    } else {
+        0.0
+    };
+    (mean, variance)
+}
+
+impl<T, O, C> UhiValue for Mean<T, O, C>
+where
+    T: NumCast + Copy,
+    O: From<T> + From<C> + NumOps + Signed + Copy,
+    C: NumCast + Copy,
+{
+    fn to_uhi_storage(values: &[&Self], shape: &[usize]) -> Result<StorageSchema, ConversionError> {
+        let stats = values
+            .iter()
+            .map(|it| {
+                let count = to_f64(it.num_samples())?;
+                let (mean, variance) =
+                    mean_and_variance(count, to_f64(it.sum())?, to_f64(it.sum_of_squares())?);
+                Ok((count, mean, variance))
+            })
+            .collect::<Result<Vec<_>, ConversionError>>()?;
+        let stats: Vec<_> = stats.iter().collect();
+        Ok(StorageSchema::Mean {
+            counts: array(&stats, shape, |it| Ok(it.0))?,
+            values: array(&stats, shape, |it| Ok(it.1))?,
+            variances: array(&stats, shape, |it| Ok(it.2))?,
+        })
+    }
+
+    fn from_uhi_storage(
+        storage: StorageSchema,
+        shape: &[usize],
+    ) -> Result<Vec<Self>, ConversionError> {
+        match storage {
+            StorageSchema::Mean {
+                counts,
+                values,
+                variances,
+            } => counts
+                .with_shape(shape)?
+                .into_iter()
+                .zip(values.with_shape(shape)?)
+                .zip(variances.with_shape(shape)?)
+                .map(|((count, mean), variance)| {
+                    let sum_of_squares = (count - 1.0).max(0.0) * variance + count * mean * mean;
+                    Ok(Self::with_sums(
+                        nearest(count * mean)?,
+                        nearest(sum_of_squares)?,
+                        nearest(count)?,
+                    ))
+                })
+                .collect(),
+            _ => Err(ConversionError::IncompatibleStorage),
+        }
+    }
+}
+
+impl<T, W, O, C> UhiValue for WeightedMean<T, W, O, C>
+where
+    T: NumCast + Copy,
+    W: NumCast + Copy,
+    O: From<T> + From<W> + From<C> + NumOps + Signed + Copy,
+    C: NumCast + Copy,
+{
+    fn to_uhi_storage(values: &[&Self], shape: &[usize]) -> Result<StorageSchema, ConversionError> {
+        let stats = values
+            .iter()
+            .map(|it| {
+                let sumw = to_f64(it.sum_of_weights())?;
+                let sumw2 = to_f64(it.sum_of_weights_squared())?;
+                let sumwt = to_f64(it.sum_of_weighted_values())?;
+                let sumwt2 = to_f64(it.sum_of_weighted_squared_values())?;
+                let mean = if sumw != 0.0 { sumwt / sumw } else { 0.0 };
+                let denominator = if sumw != 0.0 {
+                    sumw - sumw2 / sumw
+                } else {
+                    0.0
+                };
+                let variance = if denominator != 0.0 {
+                    (sumwt2 - sumw * mean * mean) / denominator
+                } else {
+                    0.0
+                };
+                Ok([sumw, sumw2, mean, variance])
+            })
+            .collect::<Result<Vec<_>, ConversionError>>()?;
+        let stats: Vec<_> = stats.iter().collect();
+        Ok(StorageSchema::WeightedMean {
+            sum_of_weights: array(&stats, shape, |it| Ok(it[0]))?,
+            sum_of_weights_squared: array(&stats, shape, |it| Ok(it[1]))?,
+            values: array(&stats, shape, |it| Ok(it[2]))?,
+            variances: array(&stats, shape, |it| Ok(it[3]))?,
+        })
+    }
+
+    fn from_uhi_storage(
+        storage: StorageSchema,
+        shape: &[usize],
+    ) -> Result<Vec<Self>, ConversionError> {
+        match storage {
+            StorageSchema::WeightedMean {
+                sum_of_weights,
+                sum_of_weights_squared,
+                values,
+                variances,
+            } => sum_of_weights
+                .with_shape(shape)?
+                .into_iter()
+                .zip(sum_of_weights_squared.with_shape(shape)?)
+                .zip(values.with_shape(shape)?)
+                .zip(variances.with_shape(shape)?)
+                .map(|(((sumw, sumw2), mean), variance)| {
+                    let denominator = if sumw != 0.0 {
+                        sumw - sumw2 / sumw
+                    } else {
+                        0.0
+                    };
+                    let sumwt2 = variance * denominator + sumw * mean * mean;
+                    // the number of fills is not stored so use the effective number of entries
+                    let count = if sumw2 != 0.0 {
+                        (sumw * sumw / sumw2).round()
+                    } else {
+                        0.0
+                    };
+                    Ok(Self::with_sums(
+                        nearest(sumw * mean)?,
+                        nearest(sumwt2)?,
+                        nearest(sumw)?,
+                        nearest(sumw2)?,
+                        nearest(count)?,
+                    ))
+                })
+                .collect(),
+            _ => Err(ConversionError::IncompatibleStorage),
+        }
+    }
+}
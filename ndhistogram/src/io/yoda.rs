@@ -0,0 +1,791 @@
+//! Writing and reading histograms in the YODA text format
+//!
+//! [YodaFile] holds histograms and profiles in the plain text format of
+//! [YODA](https://yoda.hepforge.org), as used by Rivet for reference data.
+//! The object type is chosen from the bin value and the number of axes:
+//!
+//! | Axes | [WeightedSum] | [WeightedMean] |
+//! |------|---------------|----------------|
+//! | 1    | `Histo1D`     | `Profile1D`    |
+//! | 2    | `Histo2D`     | `Profile2D`    |
+//!
+//! Histograms with [Uniform], [UniformNoFlow], [Variable] or [VariableNoFlow] axes may be
+//! written.
+//! Objects are written in the `V2` format of YODA 1, which is read by both YODA 1 and YODA 2.
+//!
+//! YODA stores the sums of weights, squared weights and weighted coordinates of each bin.
+//! As ndhistogram does not keep track of the coordinates that were filled, the
+//! weighted coordinates are written as if all fills were at the bin center,
+//! or at the edge for the underflow and overflow bins.
+//! For [WeightedSum], the number of entries is set to the effective number of entries,
+//! `sum(w)^2 / sum(w^2)`.
+//! The underflow and overflow bins of 1D objects are stored, while YODA 1 does not store
+//! the underflow and overflow bins of 2D objects; their contents are only included in the
+//! total and are empty when read back.
+//!
+//! Axes are rebuilt from the bin edges, so reading a [Uniform] or [UniformNoFlow] axis
+//! requires equally sized bins.
+//! Other object types, such as `Scatter2D` and `Counter`, are skipped when reading.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ndhistogram::{ndhistogram, Histogram, Hist1D, axis::Uniform, value::WeightedSum};
+//! use ndhistogram::io::yoda::YodaFile;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut hist = ndhistogram!(Uniform::new(10, 0.0, 1.0)?; WeightedSum);
+//! hist.fill_with(&0.5, 2.0);
+//! let mut file = YodaFile::new();
+//! file.add("/ANALYSIS/d01-x01-y01", "An example histogram", &hist)?;
+//! let text = file.to_string();
+//! assert!(text.starts_with("BEGIN YODA_HISTO1D_V2 /ANALYSIS/d01-x01-y01\n"));
+//!
+//! let read: YodaFile = text.parse()?;
+//! let read: Hist1D<Uniform, WeightedSum> = read.get("/ANALYSIS/d01-x01-y01")?;
+//! assert_eq!(read, hist);
+//! # Ok(()) }
+//! ```
+
+use std::{
+    fmt::{self, Display},
+    io,
+    str::FromStr,
+};
+
+use num_traits::{Float, NumCast, Signed};
+
+use crate::{
+    axis::{Axis, BinInterval, Uniform, UniformNoFlow, Variable, VariableNoFlow},
+    error::ConversionError,
+    io::nearest,
+    value::{WeightedMean, WeightedSum},
+    Axes, AxesTuple, Histogram, VecHistogram,
+};
+
+/// The sums stored for each bin of a YODA histogram or profile, excluding the sums of
+/// weighted coordinates.
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+pub struct BinSums {
+    /// Sum of weights.
+    pub sumw: f64,
+    /// Sum of squared weights.
+    pub sumw2: f64,
+    /// Sum of weighted values of a profile.
+    pub sumwy: f64,
+    /// Sum of weighted squared values of a profile.
+    pub sumwy2: f64,
+    /// Number of entries.
+    pub num_entries: f64,
+}
+
+/// A bin value that can be stored in a YODA histogram or profile.
+pub trait YodaValue: Sized {
+    /// Whether the value is stored as a profile, with sums of the weighted values,
+    /// or as a histogram.
+    const IS_PROFILE: bool;
+
+    /// The sums of the bin.
+    fn to_yoda_value(&self) -> Result<BinSums, ConversionError>;
+
+    /// Create a value from the sums of a bin.
+    fn from_yoda_value(sums: &BinSums) -> Result<Self, ConversionError>;
+}
+
+fn to_f64<T: NumCast>(value: T) -> Result<f64, ConversionError> {
+    value.to_f64().ok_or(ConversionError::ValueOutOfRange)
+}
+
+impl<T: NumCast + Copy> YodaValue for WeightedSum<T> {
+    const IS_PROFILE: bool = false;
+
+    fn to_yoda_value(&self) -> Result<BinSums, ConversionError> {
+        let (sumw, sumw2) = (to_f64(self.sum())?, to_f64(self.variance())?);
+        Ok(BinSums {
+            sumw,
+            sumw2,
+            num_entries: if sumw2 > 0.0 {
+                sumw * sumw / sumw2
+            } else {
+                0.0
+            },
+            ..BinSums::default()
+        })
+    }
+
+    fn from_yoda_value(sums: &BinSums) -> Result<Self, ConversionError> {
+        Ok(Self::with_sum_and_variance(
+            nearest(sums.sumw)?,
+            nearest(sums.sumw2)?,
+        ))
+    }
+}
+
+impl<T, W, O, C> YodaValue for WeightedMean<T, W, O, C>
+where
+    T: NumCast + Copy,
+    W: NumCast + Copy,
+    O: From<T> + From<W> + From<C> + Float + Signed,
+    C: NumCast + Copy,
+{
+    const IS_PROFILE: bool = true;
+
+    fn to_yoda_value(&self) -> Result<BinSums, ConversionError> {
+        Ok(BinSums {
+            sumw: to_f64(self.sum_of_weights())?,
+            sumw2: to_f64(self.sum_of_weights_squared())?,
+            sumwy: to_f64(self.sum_of_weighted_values())?,
+            sumwy2: to_f64(self.sum_of_weighted_squared_values())?,
+            num_entries: to_f64(self.num_samples())?,
+        })
+    }
+
+    fn from_yoda_value(sums: &BinSums) -> Result<Self, ConversionError> {
+        Ok(Self::with_sums(
+            nearest(sums.sumwy)?,
+            nearest(sums.sumwy2)?,
+            nearest(sums.sumw)?,
+            nearest(sums.sumw2)?,
+            nearest(sums.num_entries)?,
+        ))
+    }
+}
+
+/// An [Axis] that can be stored on a YODA histogram or profile.
+pub trait YodaAxis: Axis + Sized {
+    /// The bin at the index, with edges converted to f64.
+    fn yoda_bin(&self, index: usize) -> Result<BinInterval<f64>, ConversionError>;
+
+    /// Create an axis from the edges of the bins between the underflow and overflow.
+    ///
+    /// Returns [ConversionError::IncompatibleAxis] if the axis cannot represent the
+    /// binning. The axis number is always 0 and is updated by [YodaAxes::from_yoda_edges].
+    fn from_yoda_edges(edges: &[f64]) -> Result<Self, ConversionError>;
+}
+
+/// A set of [Axes] that can be stored on a YODA histogram or profile.
+///
+/// This is implemented for [AxesTuple] with 1 or 2 axes that implement [YodaAxis].
+pub trait YodaAxes: Axes + Sized {
+    /// The bins of each axis, in order of bin index.
+    fn yoda_bins(&self) -> Result<Vec<Vec<BinInterval<f64>>>, ConversionError>;
+
+    /// Create the axes from the edges of the bins on each axis.
+    fn from_yoda_edges(edges: &[Vec<f64>]) -> Result<Self, ConversionError>;
+}
+
+fn yoda_bin<T: Float>(bin: Option<BinInterval<T>>) -> Result<BinInterval<f64>, ConversionError> {
+    Ok(match bin.ok_or(ConversionError::ShapeMismatch)? {
+        BinInterval::Underflow { end } => BinInterval::underflow(to_f64(end)?),
+        BinInterval::Overflow { start } => BinInterval::overflow(to_f64(start)?),
+        BinInterval::Bin { start, end } => BinInterval::new(to_f64(start)?, to_f64(end)?),
+    })
+}
+
+fn from_f64<T: Float>(value: f64) -> Result<T, ConversionError> {
+    T::from(value).ok_or(ConversionError::ValueOutOfRange)
+}
+
+/// Get the number of bins and range of equally sized bins.
+///
+/// The text format may round the edges, so they are compared with a tolerance.
+fn as_uniform<T: Float>(edges: &[f64]) -> Result<(usize, T, T), ConversionError> {
+    let (low, high) = match edges {
+        [low, .., high] => (*low, *high),
+        _ => return Err(ConversionError::IncompatibleAxis(0)),
+    };
+    let num_bins = edges.len() - 1;
+    let width = (high - low) / num_bins as f64;
+    let uniform = edges
+        .iter()
+        .enumerate()
+        .all(|(index, edge)| (low + index as f64 * width - edge).abs() <= 1e-6 * width.abs());
+    if !uniform {
+        return Err(ConversionError::IncompatibleAxis(0));
+    }
+    Ok((num_bins, from_f64(low)?, from_f64(high)?))
+}
+
+fn as_variable<T: Float>(edges: &[f64]) -> Result<Vec<T>, ConversionError> {
+    edges.iter().copied().map(from_f64).collect()
+}
+
+macro_rules! impl_yoda_axis {
+    ($($Axis:ident),* => |$edges:ident| $from_edges:block) => {
+        $(
+            impl<T: Float> YodaAxis for $Axis<T> {
+                fn yoda_bin(&self, index: usize) -> Result<BinInterval<f64>, ConversionError> {
+                    yoda_bin(self.bin(index))
+                }
+
+                fn from_yoda_edges($edges: &[f64]) -> Result<Self, ConversionError> {
+                    $from_edges
+                }
+            }
+        )*
+    };
+}
+
+impl_yoda_axis! {Uniform, UniformNoFlow => |edges| {
+    let (num_bins, low, high) = as_uniform(edges)?;
+    Ok(Self::new(num_bins, low, high)?)
+}}
+
+impl_yoda_axis! {Variable, VariableNoFlow => |edges| {
+    Ok(Self::new(as_variable(edges)?)?)
+}}
+
+fn with_axis_number<T>(
+    result: Result<T, ConversionError>,
+    axis: usize,
+) -> Result<T, ConversionError> {
+    result.map_err(|error| match error {
+        ConversionError::IncompatibleAxis(_) => ConversionError::IncompatibleAxis(axis),
+        error => error,
+    })
+}
+
+macro_rules! impl_yoda_axes {
+    ($($T:ident: $index:tt),+) => {
+        impl<$($T: YodaAxis),+> YodaAxes for AxesTuple<($($T,)+)>
+        where
+            Self: Axes,
+        {
+            fn yoda_bins(&self) -> Result<Vec<Vec<BinInterval<f64>>>, ConversionError> {
+                let axes = self.as_tuple();
+                Ok(vec![$(
+                    (0..axes.$index.num_bins())
+                        .map(|index| axes.$index.yoda_bin(index))
+                        .collect::<Result<_, _>>()?
+                ),+])
+            }
+
+            fn from_yoda_edges(edges: &[Vec<f64>]) -> Result<Self, ConversionError> {
+                const NUM_AXES: usize = [$($index),+].len();
+                if edges.len() != NUM_AXES {
+                    return Err(ConversionError::WrongNumberOfAxes {
+                        expected: NUM_AXES,
+                        actual: edges.len(),
+                    });
+                }
+                Ok(($(with_axis_number($T::from_yoda_edges(&edges[$index]), $index)?,)+).into())
+            }
+        }
+    };
+}
+
+impl_yoda_axes! {X: 0}
+impl_yoda_axes! {X: 0, Y: 1}
+
+/// One row of statistics: a bin, the total or a flow bin.
+#[derive(Clone, PartialEq, Debug)]
+struct Row {
+    /// The low and high edge of the bin on each axis, empty for the total and flow bins.
+    edges: Vec<(f64, f64)>,
+    /// The statistics columns in the order of [YodaObject::columns].
+    stats: Vec<f64>,
+}
+
+/// A histogram or profile as it is stored in a YODA file.
+#[derive(Clone, PartialEq, Debug)]
+struct YodaObject {
+    path: String,
+    title: String,
+    dimensions: usize,
+    is_profile: bool,
+    total: Vec<f64>,
+    /// The underflow and overflow rows of 1D objects.
+    flow: Option<(Vec<f64>, Vec<f64>)>,
+    bins: Vec<Row>,
+}
+
+impl YodaObject {
+    fn type_name(&self) -> &'static str {
+        match (self.is_profile, self.dimensions) {
+            (false, 1) => "Histo1D",
+            (false, _) => "Histo2D",
+            (true, 1) => "Profile1D",
+            (true, _) => "Profile2D",
+        }
+    }
+
+    fn from_type_name(name: &str) -> Option<(bool, usize)> {
+        match name {
+            "HISTO1D" => Some((false, 1)),
+            "HISTO2D" => Some((false, 2)),
+            "PROFILE1D" => Some((true, 1)),
+            "PROFILE2D" => Some((true, 2)),
+            _ => None,
+        }
+    }
+
+    /// Names of the statistics columns.
+    fn columns(&self) -> &'static [&'static str] {
+        match (self.is_profile, self.dimensions) {
+            (false, 1) => &["sumw", "sumw2", "sumwx", "sumwx2", "numEntries"],
+            (false, _) => &[
+                "sumw",
+                "sumw2",
+                "sumwx",
+                "sumwx2",
+                "sumwy",
+                "sumwy2",
+                "sumwxy",
+                "numEntries",
+            ],
+            (true, 1) => &[
+                "sumw",
+                "sumw2",
+                "sumwx",
+                "sumwx2",
+                "sumwy",
+                "sumwy2",
+                "numEntries",
+            ],
+            (true, _) => &[
+                "sumw",
+                "sumw2",
+                "sumwx",
+                "sumwx2",
+                "sumwy",
+                "sumwy2",
+                "sumwxy",
+                "sumwz",
+                "sumwz2",
+                "numEntries",
+            ],
+        }
+    }
+
+    /// The statistics columns of a bin with the given sums and coordinates.
+    fn stats(&self, sums: &BinSums, coordinates: &[f64]) -> Vec<f64> {
+        let w = sums.sumw;
+        let mut stats = vec![w, sums.sumw2];
+        for x in coordinates {
+            stats.push(w * x);
+            stats.push(w * x * x);
+        }
+        if let [x, y] = coordinates {
+            stats.push(w * x * y);
+        }
+        if self.is_profile {
+            stats.push(sums.sumwy);
+            stats.push(sums.sumwy2);
+        }
+        stats.push(sums.num_entries);
+        stats
+    }
+
+    fn sums(&self, stats: &[f64]) -> BinSums {
+        let last = stats.len() - 1;
+        let (sumwy, sumwy2) = if self.is_profile {
+            (stats[last - 2], stats[last - 1])
+        } else {
+            (0.0, 0.0)
+        };
+        BinSums {
+            sumw: stats[0],
+            sumw2: stats[1],
+            sumwy,
+            sumwy2,
+            num_entries: stats[last],
+        }
+    }
+
+    /// The sorted bin edges of each axis.
+    fn edges(&self) -> Vec<Vec<f64>> {
+        (0..self.dimensions)
+            .map(|axis| {
+                let mut edges: Vec<f64> = self
+                    .bins
+                    .iter()
+                    .flat_map(|row| [row.edges[axis].0, row.edges[axis].1])
+                    .collect();
+                edges.sort_by(f64::total_cmp);
+                edges.dedup();
+                edges
+            })
+            .collect()
+    }
+}
+
+/// A bin of an axis as a YODA row: the underflow bin, the n-th bin between the edges,
+/// or the overflow bin.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum FlowBin {
+    Underflow,
+    Inner(usize),
+    Overflow,
+}
+
+fn flow_bins(bins: &[BinInterval<f64>]) -> Vec<FlowBin> {
+    let mut inner = 0;
+    bins.iter()
+        .map(|bin| match bin {
+            BinInterval::Underflow { .. } => FlowBin::Underflow,
+            BinInterval::Overflow { .. } => FlowBin::Overflow,
+            BinInterval::Bin { .. } => {
+                inner += 1;
+                FlowBin::Inner(inner - 1)
+            }
+        })
+        .collect()
+}
+
+/// The coordinate used for the weighted sums: the center of a bin or the edge of a flow bin.
+fn coordinate(bin: &BinInterval<f64>) -> f64 {
+    match bin {
+        BinInterval::Underflow { end } => *end,
+        BinInterval::Overflow { start } => *start,
+        BinInterval::Bin { start, end } => (start + end) / 2.0,
+    }
+}
+
+/// A set of histograms and profiles that can be written to and read from a YODA file.
+///
+/// See the [module documentation](self).
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct YodaFile {
+    objects: Vec<YodaObject>,
+}
+
+impl YodaFile {
+    /// Factory method for an empty YodaFile.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The paths of the histograms and profiles in the file, in the order they were added.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.objects.iter().map(|object| object.path.as_str())
+    }
+
+    /// The title of the histogram or profile with the given path.
+    pub fn title(&self, path: &str) -> Option<&str> {
+        self.find(path).map(|object| object.title.as_str())
+    }
+
+    fn find(&self, path: &str) -> Option<&YodaObject> {
+        self.objects.iter().find(|object| object.path == path)
+    }
+
+    /// Add a histogram with the given path and title.
+    ///
+    /// A histogram or profile with the same path is replaced.
+    pub fn add<A, V, H>(&mut self, path: &str, title: &str, hist: &H) -> Result<(), ConversionError>
+    where
+        H: Histogram<A, V>,
+        A: YodaAxes,
+        V: YodaValue,
+    {
+        let bins = hist.axes().yoda_bins()?;
+        let kinds: Vec<Vec<FlowBin>> = bins.iter().map(|bins| flow_bins(bins)).collect();
+        let mut object = YodaObject {
+            path: path.to_string(),
+            title: title.to_string(),
+            dimensions: bins.len(),
+            is_profile: V::IS_PROFILE,
+            total: Vec::new(),
+            flow: None,
+            bins: Vec::new(),
+        };
+        object.total = vec![0.0; object.columns().len()];
+        let mut underflow = object.total.clone();
+        let mut overflow = object.total.clone();
+        let mut rows = Vec::new();
+        for item in hist.iter() {
+            let mut remainder = item.index;
+            let mut intervals = Vec::with_capacity(bins.len());
+            let mut positions = Vec::with_capacity(bins.len());
+            for (bins, kinds) in bins.iter().zip(&kinds) {
+                intervals.push(bins[remainder % bins.len()]);
+                positions.push(kinds[remainder % bins.len()]);
+                remainder /= bins.len();
+            }
+            let coordinates: Vec<f64> = intervals.iter().map(coordinate).collect();
+            let stats = object.stats(&item.value.to_yoda_value()?, &coordinates);
+            object
+                .total
+                .iter_mut()
+                .zip(&stats)
+                .for_each(|(total, it)| *total += it);
+            match positions.as_slice() {
+                [FlowBin::Underflow] => underflow = stats,
+                [FlowBin::Overflow] => overflow = stats,
+                _ if positions.iter().all(|it| matches!(it, FlowBin::Inner(_))) => {
+                    let edges = intervals
+                        .iter()
+                        .map(|bin| {
+                            (
+                                bin.start().unwrap_or_default(),
+                                bin.end().unwrap_or_default(),
+                            )
+                        })
+                        .collect();
+                    rows.push((positions, Row { edges, stats }));
+                }
+                _ => {}
+            }
+        }
+        // YODA orders bins by their low edges, with the first axis varying slowest
+        rows.sort_by(|(a, _), (b, _)| a.cmp(b));
+        object.bins = rows.into_iter().map(|(_, row)| row).collect();
+        if object.dimensions == 1 {
+            object.flow = Some((underflow, overflow));
+        }
+        self.insert(object);
+        Ok(())
+    }
+
+    fn insert(&mut self, object: YodaObject) {
+        match self
+            .objects
+            .iter_mut()
+            .find(|existing| existing.path == object.path)
+        {
+            Some(existing) => *existing = object,
+            None => self.objects.push(object),
+        }
+    }
+
+    /// Get the histogram or profile with the given path.
+    ///
+    /// Returns [ConversionError::NotFound] if there is no object with this path,
+    /// [ConversionError::IncompatibleStorage] if a histogram is requested for a profile
+    /// or the other way around, [ConversionError::WrongNumberOfAxes] if it has a different
+    /// number of dimensions than A and [ConversionError::IncompatibleAxis] if the binning of
+    /// an axis cannot be represented by the corresponding axis of A.
+    pub fn get<A, V>(&self, path: &str) -> Result<VecHistogram<A, V>, ConversionError>
+    where
+        A: YodaAxes,
+        V: YodaValue + Clone + Default,
+    {
+        let object = self.find(path).ok_or(ConversionError::NotFound)?;
+        if object.is_profile != V::IS_PROFILE {
+            return Err(ConversionError::IncompatibleStorage);
+        }
+        let edges = object.edges();
+        let axes = A::from_yoda_edges(&edges)?;
+        let bins = axes.yoda_bins()?;
+        let kinds: Vec<Vec<FlowBin>> = bins.iter().map(|bins| flow_bins(bins)).collect();
+        let strides: Vec<usize> = bins
+            .iter()
+            .scan(1, |stride, bins| {
+                let current = *stride;
+                *stride *= bins.len();
+                Some(current)
+            })
+            .collect();
+        let index_of = |axis: usize, bin: FlowBin| {
+            kinds[axis]
+                .iter()
+                .position(|it| *it == bin)
+                .map(|index| index * strides[axis])
+        };
+        let mut values = vec![V::default(); axes.num_bins()];
+        for row in &object.bins {
+            let mut index = Some(0);
+            for (axis, (low, high)) in row.edges.iter().enumerate() {
+                let position = edges[axis]
+                    .iter()
+                    .position(|edge| edge == low)
+                    .filter(|position| edges[axis].get(position + 1) == Some(high))
+                    .ok_or(ConversionError::ShapeMismatch)?;
+                index = index
+                    .zip(index_of(axis, FlowBin::Inner(position)))
+                    .map(|(a, b)| a + b);
+            }
+            let index = index.ok_or(ConversionError::ShapeMismatch)?;
+            values[index] = V::from_yoda_value(&object.sums(&row.stats))?;
+        }
+        if let Some((underflow, overflow)) = &object.flow {
+            for (bin, stats) in [
+                (FlowBin::Underflow, underflow),
+                (FlowBin::Overflow, overflow),
+            ] {
+                if let Some(index) = index_of(0, bin) {
+                    values[index] = V::from_yoda_value(&object.sums(stats))?;
+                }
+            }
+        }
+        VecHistogram::from_vec(axes, values).map_err(|_| ConversionError::ShapeMismatch)
+    }
+
+    /// Write the YODA file.
+    pub fn write<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "{}", self)
+    }
+}
+
+fn write_row(f: &mut fmt::Formatter<'_>, cells: &[String]) -> fmt::Result {
+    writeln!(f, "{}", cells.join("\t"))
+}
+
+fn write_stats(f: &mut fmt::Formatter<'_>, label: &str, stats: &[f64]) -> fmt::Result {
+    let mut cells = vec![format!("{:<8}", label), format!("{:<8}", label)];
+    cells.extend(stats.iter().map(|it| format!("{:e}", it)));
+    write_row(f, &cells)
+}
+
+impl Display for YodaFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for object in &self.objects {
+            let block = format!("YODA_{}_V2", object.type_name().to_uppercase());
+            writeln!(f, "BEGIN {} {}", block, object.path)?;
+            writeln!(f, "Path: {}", object.path)?;
+            writeln!(f, "Title: {}", object.title)?;
+            writeln!(f, "Type: {}", object.type_name())?;
+            writeln!(f, "---")?;
+            let columns = object.columns();
+            writeln!(f, "# ID\t ID\t {}", columns.join("\t "))?;
+            write_stats(f, "Total", &object.total)?;
+            match &object.flow {
+                Some((underflow, overflow)) => {
+                    write_stats(f, "Underflow", underflow)?;
+                    write_stats(f, "Overflow", overflow)?;
+                }
+                None => writeln!(f, "# 2D outflow persistency not currently supported")?,
+            }
+            let edges = ["xlow\t xhigh", "ylow\t yhigh"];
+            writeln!(
+                f,
+                "# {}\t {}",
+                edges[..object.dimensions].join("\t "),
+                columns.join("\t ")
+            )?;
+            for row in &object.bins {
+                let cells: Vec<String> = row
+                    .edges
+                    .iter()
+                    .flat_map(|(low, high)| [low, high])
+                    .chain(&row.stats)
+                    .map(|it| format!("{:e}", it))
+                    .collect();
+                write_row(f, &cells)?;
+            }
+            writeln!(f, "END {}", block)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the histograms and profiles in a YODA file.
+///
+/// Other objects are skipped.
+/// Returns [ConversionError::InvalidRow] with the line number, counting from 1, of a line
+/// that cannot be parsed and [ConversionError::InvalidData] if an object is not terminated.
+impl FromStr for YodaFile {
+    type Err = ConversionError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut file = Self::new();
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(number, line)| (number + 1, line.trim()));
+        while let Some((number, line)) = lines.next() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let (Some("BEGIN"), Some(block)) = (words.next(), words.next()) else {
+                return Err(ConversionError::InvalidRow(number));
+            };
+            let path = words.next().unwrap_or_default().to_string();
+            let end = format!("END {}", block);
+            let object = block
+                .strip_prefix("YODA_")
+                .and_then(|block| block.strip_suffix("_V2"))
+                .and_then(YodaObject::from_type_name)
+                .map(|(is_profile, dimensions)| YodaObject {
+                    path: path.clone(),
+                    title: String::new(),
+                    dimensions,
+                    is_profile,
+                    total: Vec::new(),
+                    flow: None,
+                    bins: Vec::new(),
+                });
+            let mut body = Vec::new();
+            let mut terminated = false;
+            for (number, line) in lines.by_ref() {
+                if line == end {
+                    terminated = true;
+                    break;
+                }
+                body.push((number, line));
+            }
+            if !terminated {
+                return Err(ConversionError::InvalidData);
+            }
+            if let Some(mut object) = object {
+                read_object(&mut object, &body)?;
+                file.insert(object);
+            }
+        }
+        Ok(file)
+    }
+}
+
+fn read_object(object: &mut YodaObject, body: &[(usize, &str)]) -> Result<(), ConversionError> {
+    let separator = body
+        .iter()
+        .position(|(_, line)| *line == "---")
+        .unwrap_or(0);
+    for (number, line) in &body[..separator] {
+        let (key, value) = line
+            .split_once(':')
+            .ok_or(ConversionError::InvalidRow(*number))?;
+        match key.trim() {
+            "Path" => object.path = value.trim().to_string(),
+            "Title" => object.title = value.trim().to_string(),
+            _ => {}
+        }
+    }
+    let num_columns = object.columns().len();
+    let mut underflow = vec![0.0; num_columns];
+    let mut overflow = vec![0.0; num_columns];
+    object.total = vec![0.0; num_columns];
+    for (number, line) in &body[separator..] {
+        if line.is_empty() || line.starts_with('#') || *line == "---" {
+            continue;
+        }
+        let cells: Vec<&str> = line.split_whitespace().collect();
+        let parse = |cells: &[&str]| -> Result<Vec<f64>, ConversionError> {
+            cells
+                .iter()
+                .map(|cell| {
+                    cell.parse()
+                        .map_err(|_| ConversionError::InvalidRow(*number))
+                })
+                .collect()
+        };
+        let label = cells.first().copied().unwrap_or_default();
+        if matches!(label, "Total" | "Underflow" | "Overflow") {
+            let stats = parse(cells.get(2..).unwrap_or_default())?;
+            if stats.len() != num_columns {
+                return Err(ConversionError::InvalidRow(*number));
+            }
+            match label {
+                "Total" => object.total = stats,
+                "Underflow" => underflow = stats,
+                _ => overflow = stats,
+            }
+            continue;
+        }
+        let values = parse(&cells)?;
+        if values.len() != 2 * object.dimensions + num_columns {
+            return Err(ConversionError::InvalidRow(*number));
+        }
+        let (edges, stats) = values.split_at(2 * object.dimensions);
+        object.bins.push(Row {
+            edges: edges.chunks(2).map(|it| (it[0], it[1])).collect(),
+            stats: stats.to_vec(),
+        });
+    }
+    if object.dimensions == 1 {
+        object.flow = Some((underflow, overflow));
+    }
+    Ok(())
+}
@@ -12,6 +12,11 @@
 //! - Unweighted and weighted filling of histograms.
 //! - Flexible, user-definable axis types.
 //! - Sparse histograms to reduce the memory footprint of high bin count, mostly empty, histograms.
+//! - Terminal display of histograms, including heatmaps of 2D histograms and summary tables of bin values, totals and means.
+//! - Statistical comparison of histograms with chi-square, Kolmogorov-Smirnov and Anderson-Darling tests.
+//! - Named collections of histograms of mixed types that are merged, written and read as one unit.
+//! - Merging of many serialized histograms into one, like ROOT's `hadd`, with a library function or the `ndhist-merge` command line tool.
+//! - Export to and import from CSV/TSV tables and Apache Arrow record batches, export to NumPy `.npy`/`.npz` files and the Prometheus text exposition format, a compact versioned binary format with run-length encoding of empty bins, rendering of 1D and 2D histograms as SVG images, writing and reading TH1D/TH2D/TH3D histograms in ROOT files and YODA histograms and profiles for Rivet, and exchange of histograms with boost-histogram and hist in Python with the versioned UHI serialization schema.
 //!
 //! ## Table of Contents
 //!
@@ -98,6 +103,11 @@
 //! - [Variable](axis::Variable)/[VariableNoFlow](axis::VariableNoFlow): variable sized bins with optional underflow/overflow bins.
 //! - [UniformCyclic](axis::UniformCyclic)/[VariableCyclic](axis::VariableCyclic): cyclic or periodic versions of the Uniform and Variable axes.
 //! - [Category](axis::Category)/[CategoryNoFlow](axis::CategoryNoFlow): a finite set of discrete values with optional overflow bin.
+//! - [IntCategory](axis::IntCategory)/[IntCategoryNoFlow](axis::IntCategoryNoFlow): integer categories that are looked up without hashing, with an optional overflow bin, or extendable while filling.
+//! - [OrderedCategory](axis::OrderedCategory)/[OrderedCategoryNoFlow](axis::OrderedCategoryNoFlow): categories kept in sorted order, with selection of bins by a range of values and optional overflow bin.
+//! - [StrCategory](axis::StrCategory)/[StrCategoryNoFlow](axis::StrCategoryNoFlow): string categories filled from `&str` without allocating, with optional overflow bin.
+//! - [Boolean](axis::Boolean): two bins for false and true.
+//! - [Hexagonal](axis::Hexagonal): a grid of regular hexagons over (x, y) coordinates, for hexbin histograms in a single two-dimensional axis.
 //!
 //! User defined axes types are possible by implementing the [Axis](axis::Axis) trait.
 //!
@@ -269,8 +279,14 @@
 //! All cargo features of this crate are off by default.
 //! The following features can be enabled in your `Cargo.toml`:
 //!
-//! - [serde] : enable support for histogram serialization and deserialization.
+//! - [serde] : enable support for histogram serialization and deserialization,
+//!   including the [UHI serialization schema](io::uhi).
 //! - [rayon] : enable parallel iteration over histograms.
+//! - arrow : enable conversion to and from [Apache Arrow](io::arrow) record batches.
+//! - numpy : enable [export to NumPy](io::numpy) `.npy` and `.npz` files.
+//! - svg : enable [rendering of histograms as SVG images](io::svg).
+//! - ndarray : enable N-dimensional [ndarray](https://docs.rs/ndarray) views of histogram values
+//!   with [VecHistogram::as_array_view].
 //!
 //! ## How to contribute
 //!
@@ -305,6 +321,14 @@ mod histogram;
 
 pub mod value;
 
+pub mod compare;
+
+pub mod display;
+
+pub mod io;
+
+pub mod book;
+
 pub use axes::Axes;
 pub use axes::AxesTuple;
 pub use histogram::fill::Fill;
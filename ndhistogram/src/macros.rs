@@ -146,3 +146,57 @@ macro_rules! sparsehistogram {
     };
 
 }
+
+/// Asserts that two [Histogram](crate::Histogram)s are approximately equal.
+///
+/// The histograms must have identical axes and every bin value must be equal within a
+/// [Tolerance](crate::compare::Tolerance).
+/// On failure, the panic message lists the index, bin interval, expected and actual value
+/// of every bin that differs (see [approx_eq_report](crate::compare::approx_eq_report)).
+/// The optional third argument sets the tolerance, otherwise the
+/// [default tolerance](crate::compare::Tolerance::default) is used.
+/// Bin values must implement [ApproxEq](crate::compare::ApproxEq).
+///
+/// # Example
+///
+/// ```rust
+/// use ndhistogram::{ndhistogram, assert_histogram_approx_eq, Histogram, axis::Uniform, compare::Tolerance};
+///
+/// # fn main() -> Result<(), ndhistogram::Error> {
+/// let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0)?);
+/// let mut actual = ndhistogram!(Uniform::new(10, 0.0, 1.0)?);
+/// expected.fill_with(&0.05, 0.3);
+/// actual.fill_with(&0.05, 0.1 + 0.2);
+/// assert_histogram_approx_eq!(expected, actual);
+/// actual.fill_with(&0.05, 1e-6);
+/// assert_histogram_approx_eq!(expected, actual, Tolerance::absolute(1e-3));
+/// # Ok(()) }
+/// ```
+#[macro_export]
+macro_rules! assert_histogram_approx_eq {
+    ($expected:expr, $actual:expr $(,)?) => {
+        $crate::assert_histogram_approx_eq!(
+            $expected,
+            $actual,
+            $crate::compare::Tolerance::default()
+        )
+    };
+    ($expected:expr, $actual:expr, $tolerance:expr $(,)?) => {
+        match $crate::compare::approx_eq_report(&$expected, &$actual, $tolerance) {
+            Ok(report) => {
+                if !report.is_approx_eq() {
+                    panic!(
+                        "assertion failed: histograms are not approximately equal\n{}",
+                        report
+                    );
+                }
+            }
+            Err(error) => {
+                panic!(
+                    "assertion failed: histograms are not approximately equal\n{}",
+                    error
+                );
+            }
+        }
+    };
+}
@@ -5,6 +5,7 @@ use std::{
 
 use num_traits::{abs, Float, NumOps, One, Signed};
 
+use crate::compare::{ApproxEq, Tolerance};
 use crate::FillWith;
 
 /// This ndhistogram bin value computes the mean of the data samples provided when
@@ -65,6 +66,21 @@ where
         r
     }
 
+    /// Factory method to create a Mean from the sum of the samples,
+    /// the sum of the squares of the samples and the number of samples.
+    ///
+    /// This is the inverse of [Mean::sum], [Mean::sum_of_squares] and
+    /// [Mean::num_samples], and is intended for reading histograms
+    /// stored in other formats.
+    pub fn with_sums(sum: T, sum_of_squares: T, count: C) -> Self {
+        Self {
+            sumw: sum,
+            sumw2: sum_of_squares,
+            count,
+            phantom_output_type: PhantomData,
+        }
+    }
+
     /// Get the current value of the mean.
     pub fn get(&self) -> O {
         self.mean()
@@ -80,6 +96,16 @@ where
         self.count
     }
 
+    /// Get the sum of the samples.
+    pub fn sum(&self) -> T {
+        self.sumw
+    }
+
+    /// Get the sum of the squares of the samples.
+    pub fn sum_of_squares(&self) -> T {
+        self.sumw2
+    }
+
     /// Compute the variance of the samples.
     pub fn variance_of_samples(&self) -> O {
         let mean = self.mean();
@@ -122,3 +148,25 @@ where
         self.count += C::one();
     }
 }
+
+impl<T, O, C> AddAssign<&Self> for Mean<T, O, C>
+where
+    T: AddAssign + Copy,
+    C: AddAssign + Copy,
+{
+    /// Add the samples of another bin, for example when adding histograms.
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        self.sumw += rhs.sumw;
+        self.sumw2 += rhs.sumw2;
+        self.count += rhs.count;
+    }
+}
+
+impl<T: ApproxEq, O, C: ApproxEq> ApproxEq for Mean<T, O, C> {
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.sumw.approx_eq(&other.sumw, tolerance)
+            && self.sumw2.approx_eq(&other.sumw2, tolerance)
+            && self.count.approx_eq(&other.count, tolerance)
+    }
+}
@@ -1,11 +1,18 @@
 use num_traits::Float;
 
+use crate::compare::{ApproxEq, Tolerance};
 use crate::Fill;
 use crate::FillWith;
 
 /// ndhistogram bin value type for filling unweighted values.
 /// Analogous to [WeightedSum](crate::value::WeightedSum). Methods returning variance and standard
 /// deviation assume Poisson statistics.
+///
+/// Unlike the other bin value types, Sum does not implement `AddAssign<&Sum>`,
+/// as it would conflict with its implementation of [FillWith], so histograms of Sum
+/// cannot be added together.
+/// A histogram of the primitive number type, which has the same binary representation,
+/// can be used instead.
 #[derive(Copy, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sum<T = f64> {
@@ -21,6 +28,11 @@ impl<T: Copy> Sum<T> {
         Self::default()
     }
 
+    /// Factory method to create a Sum with the given value.
+    pub fn with_sum(sum: T) -> Self {
+        Self { sum }
+    }
+
     /// Get the current value of the sum.
     pub fn get(&self) -> T {
         self.sum()
@@ -63,3 +75,9 @@ where
         self.sum.fill_with(weight);
     }
 }
+
+impl<T: ApproxEq> ApproxEq for Sum<T> {
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.sum.approx_eq(&other.sum, tolerance)
+    }
+}
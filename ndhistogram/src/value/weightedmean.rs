@@ -5,6 +5,7 @@ use std::{
 
 use num_traits::{Float, NumOps, One, Signed};
 
+use crate::compare::{ApproxEq, Tolerance};
 use crate::FillWithWeighted;
 
 /// ndhistogram bin value computes the mean of the data samples provided when
@@ -71,6 +72,29 @@ where
         r
     }
 
+    /// Factory method to create a WeightedMean from its sums.
+    ///
+    /// This is the inverse of [WeightedMean::sum_of_weighted_values],
+    /// [WeightedMean::sum_of_weighted_squared_values], [WeightedMean::sum_of_weights],
+    /// [WeightedMean::sum_of_weights_squared] and [WeightedMean::num_samples],
+    /// and is intended for reading histograms stored in other formats.
+    pub fn with_sums(
+        sum_of_weighted_values: T,
+        sum_of_weighted_squared_values: T,
+        sum_of_weights: W,
+        sum_of_weights_squared: W,
+        count: C,
+    ) -> Self {
+        Self {
+            sumwt: sum_of_weighted_values,
+            sumwt2: sum_of_weighted_squared_values,
+            sumw: sum_of_weights,
+            sumw2: sum_of_weights_squared,
+            count,
+            phantom_output_type: PhantomData,
+        }
+    }
+
     /// Get the current value of the mean.
     pub fn get(&self) -> O {
         self.mean()
@@ -86,6 +110,26 @@ where
         self.count
     }
 
+    /// Get the sum of value times weight.
+    pub fn sum_of_weighted_values(&self) -> T {
+        self.sumwt
+    }
+
+    /// Get the sum of value squared times weight.
+    pub fn sum_of_weighted_squared_values(&self) -> T {
+        self.sumwt2
+    }
+
+    /// Get the sum of weights.
+    pub fn sum_of_weights(&self) -> W {
+        self.sumw
+    }
+
+    /// Get the sum of weights squared.
+    pub fn sum_of_weights_squared(&self) -> W {
+        self.sumw2
+    }
+
     /// Compute the variance of the samples.
     pub fn variance_of_samples(&self) -> O {
         // weighted variance is:
@@ -133,3 +177,30 @@ where
         self.count += C::one();
     }
 }
+
+impl<T, W, O, C> AddAssign<&Self> for WeightedMean<T, W, O, C>
+where
+    T: AddAssign + Copy,
+    W: AddAssign + Copy,
+    C: AddAssign + Copy,
+{
+    /// Add the samples of another bin, for example when adding histograms.
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        self.sumwt += rhs.sumwt;
+        self.sumwt2 += rhs.sumwt2;
+        self.sumw += rhs.sumw;
+        self.sumw2 += rhs.sumw2;
+        self.count += rhs.count;
+    }
+}
+
+impl<T: ApproxEq, W: ApproxEq, O, C: ApproxEq> ApproxEq for WeightedMean<T, W, O, C> {
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.sumwt.approx_eq(&other.sumwt, tolerance)
+            && self.sumwt2.approx_eq(&other.sumwt2, tolerance)
+            && self.sumw.approx_eq(&other.sumw, tolerance)
+            && self.sumw2.approx_eq(&other.sumw2, tolerance)
+            && self.count.approx_eq(&other.count, tolerance)
+    }
+}
@@ -1,7 +1,8 @@
-use std::ops::Mul;
+use std::ops::{AddAssign, Mul};
 
 use num_traits::Float;
 
+use crate::compare::{ApproxEq, Tolerance};
 use crate::{Fill, FillWith};
 
 /// ndhistogram bin value type that calculates a weight sum.
@@ -26,6 +27,15 @@ impl<T: Copy> WeightedSum<T> {
         Self::default()
     }
 
+    /// Factory method to create a WeightedSum from the sum of weights and
+    /// the sum of weights squared (ie the variance).
+    pub fn with_sum_and_variance(sum: T, variance: T) -> Self {
+        Self {
+            sumw: sum,
+            sumw2: variance,
+        }
+    }
+
     /// Get the current value of the weighted sum.
     pub fn get(&self) -> T {
         self.sum()
@@ -52,6 +62,16 @@ impl<T: Copy> WeightedSum<T> {
     }
 }
 
+impl<T: AddAssign + Copy> AddAssign<&Self> for WeightedSum<T> {
+    /// Add the sum of weights and sum of weights squared of another bin,
+    /// for example when adding histograms.
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        self.sumw += rhs.sumw;
+        self.sumw2 += rhs.sumw2;
+    }
+}
+
 impl<T: Copy + Fill> Fill for WeightedSum<T> {
     #[inline]
     fn fill(&mut self) {
@@ -71,3 +91,9 @@ where
         self.sumw2.fill_with(weight * weight);
     }
 }
+
+impl<T: ApproxEq> ApproxEq for WeightedSum<T> {
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        self.sumw.approx_eq(&other.sumw, tolerance) && self.sumw2.approx_eq(&other.sumw2, tolerance)
+    }
+}
@@ -1,10 +1,23 @@
+mod test_approx_eq;
 mod test_axes;
+mod test_axis_compatible;
+mod test_binary;
+mod test_binary_operation_error;
 mod test_binrange;
+mod test_book;
+mod test_boolean_axis;
 mod test_category_axis;
 mod test_category_binrange;
 mod test_categorynoflow_axis;
+mod test_compare;
+mod test_csv;
+mod test_display_heatmap;
+mod test_display_summary;
 mod test_hashhistogram;
+mod test_hexagonal_axis;
 mod test_high_dimensional_axes;
+mod test_intcategory_axis;
+mod test_merge;
 mod test_ndhistogram_1d;
 mod test_ndhistogram_1d_category;
 mod test_ndhistogram_2d;
@@ -12,15 +25,30 @@ mod test_ndhistogram_binary_ops;
 mod test_ndhistogram_display;
 mod test_ndhistogram_macro;
 mod test_noflow_axis;
+mod test_orderedcategory_axis;
+mod test_prometheus;
+mod test_root;
+mod test_strcategory_axis;
 
+#[cfg(feature = "arrow")]
+mod test_arrow;
+#[cfg(feature = "ndarray")]
+mod test_ndarray;
+#[cfg(feature = "numpy")]
+mod test_numpy;
 #[cfg(feature = "rayon")]
 mod test_parallel_iterators;
 #[cfg(feature = "serde")]
 mod test_serialization;
+#[cfg(feature = "svg")]
+mod test_svg;
+#[cfg(feature = "serde")]
+mod test_uhi;
 
 mod test_axis_nan_and_inf_value;
 mod test_fill_overflow;
 mod test_trait_object_safety;
+mod test_try_binary_op_assign;
 mod test_uniform_axis;
 mod test_uniform_axis_integer;
 mod test_uniformcyclic_axis;
@@ -31,3 +59,4 @@ mod test_value_weightedsum;
 mod test_variable_axis;
 mod test_variablecyclic_axis;
 mod test_variablenoflow_axis;
+mod test_yoda;
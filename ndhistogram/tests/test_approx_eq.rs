@@ -0,0 +1,191 @@
+use ndhistogram::{
+    assert_histogram_approx_eq,
+    axis::{BinInterval, Uniform},
+    compare::{approx_eq, approx_eq_report, ApproxEq, BinDifference, Tolerance},
+    error::{AxisMismatch, BinaryOperationError},
+    ndhistogram, sparsehistogram,
+    value::{Mean, WeightedSum},
+    Histogram,
+};
+
+#[test]
+fn test_tolerance_is_close() {
+    let tolerance = Tolerance::new(0.1, 0.01);
+    assert!(tolerance.is_close(0.0, 0.1));
+    assert!(!tolerance.is_close(0.0, 0.11));
+    assert!(tolerance.is_close(100.0, 101.1));
+    assert!(!tolerance.is_close(100.0, 101.2));
+    assert!(tolerance.is_close(f64::NAN, f64::NAN));
+    assert!(!tolerance.is_close(f64::NAN, 0.0));
+    assert!(tolerance.is_close(f64::INFINITY, f64::INFINITY));
+    assert!(!tolerance.is_close(f64::INFINITY, f64::NEG_INFINITY));
+}
+
+#[test]
+fn test_default_tolerance_is_relative_with_small_absolute() {
+    let tolerance = Tolerance::default();
+    assert!(tolerance.is_close(0.1 + 0.2, 0.3));
+    assert!(tolerance.is_close(0.0, 1e-17));
+    assert!(!tolerance.is_close(0.0, 1e-11));
+    assert!(!tolerance.is_close(1e6, 1e6 + 1e-2));
+}
+
+#[test]
+fn test_approx_eq_with_rounding_errors() {
+    let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    let mut actual = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    expected.fill_with(&0.05, 0.3);
+    actual.fill_with(&0.05, 0.1);
+    actual.fill_with(&0.05, 0.2);
+    assert_ne!(expected, actual);
+    assert!(approx_eq(&expected, &actual, Tolerance::default()));
+    assert!(!approx_eq(&expected, &actual, Tolerance::new(0.0, 0.0)));
+}
+
+#[test]
+fn test_approx_eq_different_axes_is_false() {
+    let expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    let actual = ndhistogram!(Uniform::new(10, 0.0, 2.0).unwrap());
+    assert!(!approx_eq(&expected, &actual, Tolerance::default()));
+    assert_eq!(
+        approx_eq_report(&expected, &actual, Tolerance::default()),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 0,
+            reason: AxisMismatch::Range
+        })
+    );
+}
+
+#[test]
+fn test_approx_eq_report_lists_differing_bins() {
+    let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    let mut actual = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    expected.fill_with(&0.05, 1.0);
+    actual.fill_with(&0.05, 1.0 + 1e-12);
+    actual.fill_with(&0.55, 2.0);
+    actual.fill_with(&2.0, 3.0);
+    let report = approx_eq_report(&expected, &actual, Tolerance::default()).unwrap();
+    assert!(!report.is_approx_eq());
+    assert_eq!(report.num_bins, 12);
+    assert_eq!(
+        report.differences,
+        vec![
+            BinDifference {
+                index: 6,
+                bin: BinInterval::new(0.5, 0.6),
+                expected: 0.0,
+                actual: 2.0
+            },
+            BinDifference {
+                index: 11,
+                bin: BinInterval::overflow(1.0),
+                expected: 0.0,
+                actual: 3.0
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_approx_eq_report_display() {
+    let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    let actual = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    expected.fill_with(&2.0, 3.0);
+    let report = approx_eq_report(&expected, &actual, Tolerance::new(0.5, 0.0)).unwrap();
+    assert_eq!(
+        report.to_string(),
+        "1 of 12 bins differ (absolute tolerance=0.5, relative tolerance=0)\n  index=11, bin=[1, inf), expected=3.0, actual=0.0"
+    );
+}
+
+#[test]
+fn test_approx_eq_2d() {
+    let mut expected = ndhistogram!(
+        Uniform::new(2, 0.0, 2.0).unwrap(),
+        Uniform::new(2, 0.0, 2.0).unwrap()
+    );
+    let mut actual = expected.clone();
+    expected.fill(&(0.5, 1.5));
+    actual.fill(&(1.5, 0.5));
+    let report = approx_eq_report(&expected, &actual, Tolerance::default()).unwrap();
+    assert_eq!(report.differences.len(), 2);
+    assert_eq!(
+        report.differences[0].bin,
+        (BinInterval::new(1.0, 2.0), BinInterval::new(0.0, 1.0))
+    );
+    assert_eq!(
+        report.differences[1].bin,
+        (BinInterval::new(0.0, 1.0), BinInterval::new(1.0, 2.0))
+    );
+}
+
+#[test]
+fn test_approx_eq_sparse_and_vec_histograms() {
+    let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    let mut actual = sparsehistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    expected.fill_with(&0.05, 0.3);
+    actual.fill_with(&0.05, 0.1 + 0.2);
+    assert!(approx_eq(&expected, &actual, Tolerance::default()));
+    actual.fill(&0.55);
+    assert!(!approx_eq(&expected, &actual, Tolerance::default()));
+}
+
+#[test]
+fn test_approx_eq_weighted_sum_compares_variance() {
+    let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap(); WeightedSum);
+    let mut actual = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap(); WeightedSum);
+    expected.fill_with(&0.05, 2.0);
+    actual.fill_with(&0.05, 1.0);
+    actual.fill_with(&0.05, 1.0);
+    assert_eq!(
+        expected.value(&0.05).unwrap().get(),
+        actual.value(&0.05).unwrap().get()
+    );
+    assert!(!approx_eq(&expected, &actual, Tolerance::default()));
+}
+
+#[test]
+fn test_approx_eq_mean() {
+    let expected: Mean = Mean::new(vec![0.1, 0.2, 0.3]);
+    let actual: Mean = Mean::new(vec![0.3, 0.2, 0.1]);
+    assert!(expected.approx_eq(&actual, &Tolerance::default()));
+    let actual: Mean = Mean::new(vec![0.3, 0.2, 0.1, 0.2]);
+    assert!(!expected.approx_eq(&actual, &Tolerance::default()));
+}
+
+#[test]
+fn test_approx_eq_integer_values() {
+    let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap(); i32);
+    let mut actual = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap(); i32);
+    expected.fill_with(&0.05, 100);
+    actual.fill_with(&0.05, 101);
+    assert!(!approx_eq(&expected, &actual, Tolerance::default()));
+    assert!(approx_eq(&expected, &actual, Tolerance::relative(0.01)));
+}
+
+#[test]
+fn test_assert_histogram_approx_eq_passes() {
+    let mut expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    let mut actual = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    expected.fill_with(&0.05, 0.3);
+    actual.fill_with(&0.05, 0.1 + 0.2);
+    assert_histogram_approx_eq!(expected, actual);
+    assert_histogram_approx_eq!(expected, actual, Tolerance::absolute(1e-12));
+}
+
+#[test]
+#[should_panic(expected = "index=6, bin=[0.5, 0.6), expected=0.0, actual=1.0")]
+fn test_assert_histogram_approx_eq_fails_with_report() {
+    let expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    let mut actual = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    actual.fill(&0.55);
+    assert_histogram_approx_eq!(expected, actual);
+}
+
+#[test]
+#[should_panic(expected = "axis 0 has different number of bins")]
+fn test_assert_histogram_approx_eq_fails_with_different_axes() {
+    let expected = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    let actual = ndhistogram!(Uniform::new(11, 0.0, 1.0).unwrap());
+    assert_histogram_approx_eq!(expected, actual);
+}
@@ -0,0 +1,262 @@
+#[cfg(feature = "arrow")]
+mod arrow_tests {
+
+    use std::sync::Arc;
+
+    use arrow_array::{
+        Array, ArrayRef, Float64Array, Int32Array, RecordBatch, StringArray, StructArray,
+        UInt64Array,
+    };
+    use arrow_schema::{DataType, Field, Schema};
+    use ndhistogram::{
+        assert_histogram_approx_eq,
+        axis::{Category, Uniform, UniformNoFlow, Variable},
+        error::ConversionError,
+        io::arrow::{from_record_batch, from_record_batches, schema, to_record_batch},
+        ndhistogram, sparsehistogram,
+        value::{Mean, Sum, WeightedMean, WeightedSum},
+        AxesTuple, Hist1D, Hist2D, Histogram,
+    };
+
+    fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> &'a T {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<T>()
+            .unwrap()
+    }
+
+    fn struct_field<'a, T: 'static>(batch: &'a RecordBatch, name: &str, field: &str) -> &'a T {
+        column::<StructArray>(batch, name)
+            .column_by_name(field)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<T>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_record_batch_columns_1d() {
+        let mut hist = ndhistogram!(Variable::new(vec![0.0, 0.5, 2.0]).unwrap());
+        hist.fill(&0.25);
+        hist.fill_with(&1.0, 2.5);
+        let batch = to_record_batch(&hist).unwrap();
+        let names: Vec<_> = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        assert_eq!(names, vec!["index", "axis0", "value"]);
+        assert_eq!(
+            column::<UInt64Array>(&batch, "index").values().to_vec(),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(
+            struct_field::<Float64Array>(&batch, "axis0", "start")
+                .values()
+                .to_vec(),
+            vec![f64::NEG_INFINITY, 0.0, 0.5, 2.0]
+        );
+        assert_eq!(
+            struct_field::<Float64Array>(&batch, "axis0", "end")
+                .values()
+                .to_vec(),
+            vec![0.0, 0.5, 2.0, f64::INFINITY]
+        );
+        assert_eq!(
+            column::<Float64Array>(&batch, "value").values().to_vec(),
+            vec![0.0, 1.0, 2.5, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_record_batch_columns_2d_with_category() {
+        let mut hist = ndhistogram!(
+            UniformNoFlow::new(1, 0.0, 1.0).unwrap(),
+            Category::new(vec!["red", "green"]);
+            i32
+        );
+        hist.fill(&(0.5, "green"));
+        hist.fill(&(0.5, "other"));
+        let batch = to_record_batch(&hist).unwrap();
+        assert_eq!(
+            batch.schema().as_ref(),
+            &schema::<AxesTuple<(UniformNoFlow, Category<&str>)>, i32>()
+        );
+        assert_eq!(
+            batch.schema().field(1).data_type(),
+            &DataType::Struct(
+                vec![
+                    Field::new("start", DataType::Float64, false),
+                    Field::new("end", DataType::Float64, false)
+                ]
+                .into()
+            )
+        );
+        let categories = struct_field::<StringArray>(&batch, "axis1", "value");
+        assert_eq!(categories.value(0), "red");
+        assert_eq!(categories.value(1), "green");
+        assert!(categories.is_null(2));
+        assert_eq!(
+            column::<Int32Array>(&batch, "value").values().to_vec(),
+            vec![0, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_record_batch_weighted_sum_columns() {
+        let mut hist = ndhistogram!(UniformNoFlow::new(1, 0.0, 1.0).unwrap(); WeightedSum);
+        hist.fill_with(&0.5, 3.0);
+        hist.fill_with(&0.5, 1.0);
+        let batch = to_record_batch(&hist).unwrap();
+        assert_eq!(column::<Float64Array>(&batch, "sum").value(0), 4.0);
+        assert_eq!(column::<Float64Array>(&batch, "variance").value(0), 10.0);
+    }
+
+    #[test]
+    fn test_record_batch_sparse_histogram_in_index_order() {
+        let mut hist = sparsehistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+        for x in [7.5, 0.5, 3.5] {
+            hist.fill(&x);
+        }
+        let batch = to_record_batch(&hist).unwrap();
+        assert_eq!(
+            column::<UInt64Array>(&batch, "index").values().to_vec(),
+            vec![1, 4, 8]
+        );
+        let read: Hist1D<Uniform> =
+            from_record_batch((Uniform::new(10, 0.0, 10.0).unwrap(),).into(), &batch).unwrap();
+        assert_eq!(read.values().sum::<f64>(), 3.0);
+        assert_eq!(read.value(&7.5), Some(&1.0));
+    }
+
+    #[test]
+    fn test_record_batch_round_trip() {
+        let axes = (
+            Uniform::new(3, -1.0, 1.0).unwrap(),
+            Category::new(vec!["a".to_string(), "b".to_string()]),
+        );
+        let mut hist = ndhistogram!(axes.0.clone(), axes.1.clone(); WeightedSum);
+        hist.fill_with(&(0.1, "a".to_string()), 0.1);
+        hist.fill_with(&(-5.0, "b".to_string()), 2.0);
+        hist.fill_with(&(0.9, "c".to_string()), 1e-20);
+        let batch = to_record_batch(&hist).unwrap();
+        let read: Hist2D<_, _, WeightedSum> = from_record_batch(axes.into(), &batch).unwrap();
+        assert_eq!(read, hist);
+    }
+
+    #[test]
+    fn test_record_batch_round_trip_sum_and_integers() {
+        let axis = Uniform::new(4, 0.0, 1.0).unwrap();
+        let mut hist = ndhistogram!(axis.clone(); Sum<u64>);
+        hist.fill(&0.3);
+        hist.fill(&2.0);
+        let read: Hist1D<Uniform, Sum<u64>> =
+            from_record_batch((axis,).into(), &to_record_batch(&hist).unwrap()).unwrap();
+        assert_eq!(read, hist);
+    }
+
+    #[test]
+    fn test_record_batch_round_trip_means() {
+        let axis = Uniform::new(2, 0.0, 2.0).unwrap();
+        let mut hist = ndhistogram!(axis.clone(); Mean);
+        for value in [0.1, 0.7, 0.3] {
+            hist.fill_with(&0.5, value);
+        }
+        let read: Hist1D<Uniform, Mean> =
+            from_record_batch((axis.clone(),).into(), &to_record_batch(&hist).unwrap()).unwrap();
+        assert_histogram_approx_eq!(read, hist);
+        assert_eq!(read.value(&0.5).unwrap().num_samples(), 3);
+
+        let mut hist = ndhistogram!(axis.clone(); WeightedMean);
+        hist.fill_with_weighted(&0.5, 1.0, 2.0);
+        hist.fill_with_weighted(&0.5, 3.0, 0.5);
+        hist.fill_with_weighted(&1.5, 3.0, 1.0);
+        let read: Hist1D<Uniform, WeightedMean> =
+            from_record_batch((axis,).into(), &to_record_batch(&hist).unwrap()).unwrap();
+        assert_histogram_approx_eq!(read, hist);
+    }
+
+    #[test]
+    fn test_from_record_batches_split_batches() {
+        let axis = Uniform::new(4, 0.0, 4.0).unwrap();
+        let mut hist = ndhistogram!(axis.clone());
+        for x in [-1.0, 0.5, 1.5, 2.5, 3.5, 10.0] {
+            hist.fill_with(&x, x);
+        }
+        let batch = to_record_batch(&hist).unwrap();
+        let batches = [batch.slice(0, 2), batch.slice(2, 4)];
+        let read: Hist1D<Uniform> = from_record_batches((axis,).into(), &batches).unwrap();
+        assert_eq!(read, hist);
+    }
+
+    fn value_batch(indices: Vec<u64>, values: Vec<f64>) -> RecordBatch {
+        let bins = to_record_batch(&ndhistogram!(Uniform::new(2, 0.0, 1.0).unwrap()))
+            .unwrap()
+            .column(1)
+            .slice(0, indices.len());
+        RecordBatch::try_new(
+            Arc::new(schema::<AxesTuple<(Uniform,)>, f64>()),
+            vec![
+                Arc::new(UInt64Array::from(indices)) as ArrayRef,
+                bins,
+                Arc::new(Float64Array::from(values)),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_record_batch_errors() {
+        let axes = || (Uniform::new(2, 0.0, 1.0).unwrap(),).into();
+        let read = |batch: &RecordBatch| -> Result<Hist1D<Uniform>, ConversionError> {
+            from_record_batch(axes(), batch)
+        };
+        let wrong_type = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new(
+                "index",
+                DataType::Int32,
+                false,
+            )])),
+            vec![Arc::new(Int32Array::from(vec![0]))],
+        )
+        .unwrap();
+        assert_eq!(read(&wrong_type), Err(ConversionError::InvalidColumn(0)));
+        let integer_values =
+            to_record_batch(&ndhistogram!(Uniform::new(2, 0.0, 1.0).unwrap(); i32)).unwrap();
+        assert_eq!(
+            read(&integer_values),
+            Err(ConversionError::InvalidColumn(2))
+        );
+        assert_eq!(
+            ConversionError::InvalidColumn(2).to_string(),
+            "invalid column 2"
+        );
+    }
+
+    #[test]
+    fn test_from_record_batch_invalid_rows() {
+        let axes = || (Uniform::new(2, 0.0, 1.0).unwrap(),).into();
+        let read = |batch: &RecordBatch| -> Result<Hist1D<Uniform>, ConversionError> {
+            from_record_batch(axes(), batch)
+        };
+        assert_eq!(
+            read(&value_batch(vec![0, 4], vec![1.0, 1.0])),
+            Err(ConversionError::InvalidRow(1))
+        );
+        assert_eq!(
+            read(&value_batch(vec![1, 2, 1], vec![1.0, 1.0, 1.0])),
+            Err(ConversionError::InvalidRow(2))
+        );
+        assert_eq!(
+            read(&value_batch(vec![3, 0], vec![1.0, 2.0]))
+                .unwrap()
+                .values()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![2.0, 0.0, 0.0, 1.0]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use ndhistogram::{
+    axis::{
+        Category, Compatible, Uniform, UniformCyclic, UniformNoFlow, Variable, VariableCyclic,
+        VariableNoFlow,
+    },
+    compare::Tolerance,
+    error::{AxisMismatch, BinaryOperationError},
+    ndhistogram, sparsehistogram, Histogram,
+};
+
+#[test]
+fn test_uniform_is_compatible() {
+    let tolerance = Tolerance::default();
+    let axis = Uniform::new(10, 0.0, 0.3).unwrap();
+    let rounded = Uniform::new(10, 0.0, 0.1 + 0.2).unwrap();
+    assert_ne!(axis, rounded);
+    assert!(axis.is_compatible(&rounded, &tolerance));
+    assert!(axis.is_compatible(&axis, &tolerance));
+    assert!(!axis.is_compatible(&Uniform::new(11, 0.0, 0.3).unwrap(), &tolerance));
+    assert!(!axis.is_compatible(&Uniform::new(10, 0.0, 0.31).unwrap(), &tolerance));
+    assert!(axis.is_compatible(
+        &Uniform::new(10, 0.0, 0.31).unwrap(),
+        &Tolerance::absolute(0.02)
+    ));
+}
+
+#[test]
+fn test_uniform_edge_near_zero_is_compatible() {
+    let axis = Uniform::new(10, 0.0, 1.0).unwrap();
+    let rounded = Uniform::new(10, 0.1 * 3.0 - 0.3, 1.0).unwrap();
+    assert_ne!(axis, rounded);
+    assert!(axis.is_compatible(&rounded, &Tolerance::default()));
+    assert!(!axis.is_compatible(&rounded, &Tolerance::relative(1e-9)));
+}
+
+#[test]
+fn test_uniform_integer_is_compatible() {
+    let axis = Uniform::with_step_size(10, 0, 2).unwrap();
+    assert!(axis.is_compatible(&axis.clone(), &Tolerance::default()));
+    assert!(!axis.is_compatible(
+        &Uniform::with_step_size(10, 1, 2).unwrap(),
+        &Tolerance::default()
+    ));
+}
+
+#[test]
+fn test_uniform_noflow_and_cyclic_is_compatible() {
+    let tolerance = Tolerance::default();
+    let axis = UniformNoFlow::new(10, 0.0, 0.3).unwrap();
+    assert!(axis.is_compatible(&UniformNoFlow::new(10, 0.0, 0.1 + 0.2).unwrap(), &tolerance));
+    assert!(!axis.is_compatible(&UniformNoFlow::new(10, 0.1, 0.3).unwrap(), &tolerance));
+    let axis = UniformCyclic::new(10, 0.0, 0.3).unwrap();
+    assert!(axis.is_compatible(&UniformCyclic::new(10, 0.0, 0.1 + 0.2).unwrap(), &tolerance));
+    assert!(!axis.is_compatible(&UniformCyclic::new(9, 0.0, 0.3).unwrap(), &tolerance));
+}
+
+#[test]
+fn test_variable_is_compatible() {
+    let tolerance = Tolerance::default();
+    let axis = Variable::new(vec![0.0, 0.3, 0.6]).unwrap();
+    assert!(axis.is_compatible(
+        &Variable::new(vec![0.0, 0.1 + 0.2, 0.6]).unwrap(),
+        &tolerance
+    ));
+    assert!(!axis.is_compatible(&Variable::new(vec![0.0, 0.4, 0.6]).unwrap(), &tolerance));
+    assert!(!axis.is_compatible(
+        &Variable::new(vec![0.0, 0.3, 0.6, 0.9]).unwrap(),
+        &tolerance
+    ));
+    let axis = VariableNoFlow::new(vec![0.0, 0.3, 0.6]).unwrap();
+    assert!(axis.is_compatible(
+        &VariableNoFlow::new(vec![0.0, 0.1 + 0.2, 0.6]).unwrap(),
+        &tolerance
+    ));
+    let axis = VariableCyclic::new(vec![0.0, 0.3, 0.6]).unwrap();
+    assert!(!axis.is_compatible(
+        &VariableCyclic::new(vec![0.0, 0.4, 0.6]).unwrap(),
+        &tolerance
+    ));
+}
+
+#[test]
+fn test_category_is_compatible_only_if_equal() {
+    let axis = Category::new(vec!["a", "b"]);
+    assert!(axis.is_compatible(&Category::new(vec!["a", "b"]), &Tolerance::default()));
+    assert!(!axis.is_compatible(&Category::new(vec!["b", "a"]), &Tolerance::default()));
+}
+
+#[test]
+fn test_axes_tuple_is_compatible() {
+    let hist1 = ndhistogram!(
+        Uniform::new(10, 0.0, 0.3).unwrap(),
+        Variable::new(vec![0.0, 0.3, 0.6]).unwrap(),
+        Category::new(vec!["a", "b"])
+    );
+    let hist2 = ndhistogram!(
+        Uniform::new(10, 0.0, 0.1 + 0.2).unwrap(),
+        Variable::new(vec![0.0, 0.1 + 0.2, 0.6]).unwrap(),
+        Category::new(vec!["a", "b"])
+    );
+    let hist3 = ndhistogram!(
+        Uniform::new(10, 0.0, 0.1 + 0.2).unwrap(),
+        Variable::new(vec![0.0, 0.1 + 0.2, 0.6]).unwrap(),
+        Category::new(vec!["a", "c"])
+    );
+    let tolerance = Tolerance::default();
+    assert!(hist1.axes().is_compatible(hist2.axes(), &tolerance));
+    assert!(!hist1.axes().is_compatible(hist3.axes(), &tolerance));
+}
+
+#[test]
+fn test_vechistogram_merge_with_tolerance() {
+    let mut hist1 = ndhistogram!(
+        Uniform::new(10, 0.0, 0.3).unwrap(),
+        Uniform::new(2, 0.0, 1.0).unwrap()
+    );
+    let mut hist2 = ndhistogram!(
+        Uniform::new(10, 0.0, 0.1 + 0.2).unwrap(),
+        Uniform::new(2, 0.0, 1.0).unwrap()
+    );
+    hist1.fill(&(0.05, 0.25));
+    hist2.fill_with(&(0.05, 0.25), 2.0);
+    let axes = hist1.axes().clone();
+    assert!(hist1.try_add_assign(&hist2).is_err());
+    hist1
+        .merge_with_tolerance(&hist2, &Tolerance::default())
+        .unwrap();
+    assert_eq!(hist1.value(&(0.05, 0.25)), Some(&3.0));
+    assert_eq!(hist1.axes(), &axes);
+}
+
+#[test]
+fn test_vechistogram_merge_with_tolerance_error_leaves_self_unchanged() {
+    let mut hist1 = ndhistogram!(
+        Uniform::new(10, 0.0, 0.3).unwrap(),
+        Uniform::new(2, 0.0, 1.0).unwrap()
+    );
+    let hist2 = ndhistogram!(
+        Uniform::new(10, 0.0, 0.3).unwrap(),
+        Uniform::new(2, 0.0, 1.1).unwrap()
+    );
+    hist1.fill(&(0.05, 0.25));
+    let before = hist1.clone();
+    assert_eq!(
+        hist1.merge_with_tolerance(&hist2, &Tolerance::default()),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 1,
+            reason: AxisMismatch::Range
+        })
+    );
+    assert_eq!(hist1, before);
+}
+
+#[test]
+fn test_hashhistogram_merge_with_tolerance() {
+    let mut hist1 = sparsehistogram!(Variable::new(vec![0.0, 0.3, 0.6]).unwrap());
+    let mut hist2 = sparsehistogram!(Variable::new(vec![0.0, 0.1 + 0.2, 0.6]).unwrap());
+    hist1.fill(&0.1);
+    hist2.fill(&0.1);
+    hist2.fill(&0.5);
+    hist1
+        .merge_with_tolerance(&hist2, &Tolerance::default())
+        .unwrap();
+    assert_eq!(hist1.value(&0.1), Some(&2.0));
+    assert_eq!(hist1.value(&0.5), Some(&1.0));
+    let hist3 = sparsehistogram!(Variable::new(vec![0.0, 0.4, 0.6]).unwrap());
+    assert_eq!(
+        hist1.merge_with_tolerance(&hist3, &Tolerance::default()),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 0,
+            reason: AxisMismatch::BinEdges
+        })
+    );
+}
@@ -0,0 +1,407 @@
+use std::io;
+
+use ndhistogram::{
+    axis::{
+        Boolean, Category, CategoryNoFlow, HexagonOrientation, Hexagonal, IntCategory,
+        IntCategoryNoFlow, OrderedCategory, OrderedCategoryNoFlow, StrCategory, StrCategoryNoFlow,
+        Uniform, UniformCyclic, Variable, VariableNoFlow,
+    },
+    error::ConversionError,
+    io::binary::{merge_binary, read_binary, write_binary, BinaryAxes, BinaryValue},
+    ndhistogram, sparsehistogram,
+    value::{Mean, Sum, WeightedMean, WeightedSum},
+    Hist1D, Hist2D, Histogram, VecHistogram,
+};
+
+fn to_binary<H, A, V>(hist: &H, run_length_encoding: bool) -> Vec<u8>
+where
+    H: Histogram<A, V>,
+    A: BinaryAxes,
+    V: BinaryValue,
+{
+    let mut output = Vec::new();
+    write_binary(hist, run_length_encoding, &mut output).unwrap();
+    output
+}
+
+fn conversion_error<T>(result: io::Result<T>) -> Option<ConversionError> {
+    let error = result.err()?;
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    error.get_ref()?.downcast_ref().copied()
+}
+
+#[test]
+fn test_binary_round_trip_f64() {
+    let mut hist = ndhistogram!(Uniform::new(10, -1.0, 1.0).unwrap());
+    hist.fill(&-2.0);
+    hist.fill_with(&0.25, 2.5);
+    for run_length_encoding in [false, true] {
+        let bytes = to_binary(&hist, run_length_encoding);
+        let actual: Hist1D<Uniform> = read_binary(bytes.as_slice()).unwrap();
+        assert_eq!(actual, hist);
+    }
+}
+
+#[test]
+fn test_binary_header() {
+    let hist = ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap(); i32);
+    let bytes = to_binary(&hist, false);
+    assert_eq!(&bytes[..8], b"\x89NDH\r\n\x1a\n");
+    assert_eq!(&bytes[8..10], &[1, 0]);
+    assert_eq!(&bytes[10..12], &[0, 0]);
+    // axes length, then 1 axis of 1 uniform f64 bin from 0 to 1
+    assert_eq!(&bytes[12..20], &34u64.to_le_bytes());
+    assert_eq!(&bytes[20..28], &1u64.to_le_bytes());
+    assert_eq!(&bytes[28..30], &[1, 10]);
+    assert_eq!(&bytes[30..38], &1u64.to_le_bytes());
+    assert_eq!(&bytes[38..46], &0.0f64.to_le_bytes());
+    assert_eq!(&bytes[46..54], &1.0f64.to_le_bytes());
+    // value layout, number of values and the values
+    assert_eq!(&bytes[54..58], &[1, 3, 0, 0]);
+    assert_eq!(&bytes[58..66], &3u64.to_le_bytes());
+    assert_eq!(bytes.len(), 66 + 3 * 4);
+}
+
+#[test]
+fn test_binary_round_trip_weighted_sum() {
+    let mut hist = ndhistogram!(
+        Variable::new(vec![0.0, 1.0, 10.0]).unwrap(),
+        UniformCyclic::new(4, 0.0, 360.0).unwrap();
+        WeightedSum
+    );
+    hist.fill_with(&(0.5, 45.0), 2.0);
+    hist.fill_with(&(5.0, 400.0), 3.0);
+    hist.fill_with(&(50.0, 100.0), 0.5);
+    for run_length_encoding in [false, true] {
+        let bytes = to_binary(&hist, run_length_encoding);
+        let actual: Hist2D<Variable, UniformCyclic, WeightedSum> =
+            read_binary(bytes.as_slice()).unwrap();
+        assert_eq!(actual, hist);
+    }
+}
+
+#[test]
+fn test_binary_round_trip_mean_with_category() {
+    let mut hist = ndhistogram!(
+        Category::new(vec!["red".to_string(), "green".to_string()]),
+        VariableNoFlow::new(vec![0, 5, 10]).unwrap();
+        Mean
+    );
+    hist.fill_with(&("red".to_string(), 1), 2.0);
+    hist.fill_with(&("red".to_string(), 1), 4.0);
+    hist.fill_with(&("blue".to_string(), 7), -1.0);
+    for run_length_encoding in [false, true] {
+        let bytes = to_binary(&hist, run_length_encoding);
+        let actual: Hist2D<Category<String>, VariableNoFlow<i32>, Mean> =
+            read_binary(bytes.as_slice()).unwrap();
+        assert_eq!(actual, hist);
+    }
+}
+
+#[test]
+fn test_binary_round_trip_weighted_mean_with_integer_category() {
+    let mut hist = ndhistogram!(CategoryNoFlow::new(vec![2u8, 4, 8]); WeightedMean);
+    hist.fill_with_weighted(&4, 3.0, 2.0);
+    hist.fill_with_weighted(&4, 5.0, 1.0);
+    hist.fill_with_weighted(&8, 1.0, 0.5);
+    let bytes = to_binary(&hist, true);
+    let actual: Hist1D<CategoryNoFlow<u8>, WeightedMean> = read_binary(bytes.as_slice()).unwrap();
+    assert_eq!(actual, hist);
+}
+
+#[test]
+fn test_binary_round_trip_boolean() {
+    let mut hist = ndhistogram!(Boolean::new(), Uniform::new(3, 0.0, 3.0).unwrap(); u32);
+    hist.fill(&(true, 1.5));
+    hist.fill_with(&(false, -1.0), 4);
+    let bytes = to_binary(&hist, false);
+    let actual: Hist2D<Boolean, Uniform, u32> = read_binary(bytes.as_slice()).unwrap();
+    assert_eq!(actual, hist);
+    let other: io::Result<Hist2D<CategoryNoFlow<u8>, Uniform, u32>> = read_binary(bytes.as_slice());
+    assert_eq!(
+        conversion_error(other),
+        Some(ConversionError::IncompatibleAxis(0))
+    );
+}
+
+#[test]
+fn test_binary_round_trip_intcategory() {
+    let mut hist = ndhistogram!(
+        IntCategory::new(0..64u16),
+        IntCategoryNoFlow::new(vec![-11i64, 11]),
+        IntCategoryNoFlow::extendable(vec![5i32]);
+        u32
+    );
+    hist.grow(&(3, 11, 9));
+    hist.fill(&(3, 11, 9));
+    hist.fill(&(100, -11, 5));
+    let bytes = to_binary(&hist, true);
+    let actual: VecHistogram<_, u32> = read_binary(bytes.as_slice()).unwrap();
+    assert_eq!(actual, hist);
+    assert!(actual.axes().as_tuple().2.is_extendable());
+    let other: io::Result<Hist1D<IntCategory<u16>, u32>> =
+        read_binary(to_binary(&ndhistogram!(Category::new(0..64u16); u32), false).as_slice());
+    assert_eq!(
+        conversion_error(other),
+        Some(ConversionError::IncompatibleAxis(0))
+    );
+}
+
+#[test]
+fn test_binary_round_trip_orderedcategory() {
+    let mut hist = ndhistogram!(
+        OrderedCategory::new(vec!["b".to_string(), "a".to_string()]),
+        OrderedCategoryNoFlow::new(vec![2024u32, 2023]);
+        f64
+    );
+    hist.fill(&("b".to_string(), 2023));
+    hist.fill(&("c".to_string(), 2024));
+    let bytes = to_binary(&hist, false);
+    let actual: Hist2D<OrderedCategory<String>, OrderedCategoryNoFlow<u32>> =
+        read_binary(bytes.as_slice()).unwrap();
+    assert_eq!(actual, hist);
+    let other: io::Result<Hist2D<Category<String>, OrderedCategoryNoFlow<u32>>> =
+        read_binary(bytes.as_slice());
+    assert_eq!(
+        conversion_error(other),
+        Some(ConversionError::IncompatibleAxis(0))
+    );
+}
+
+#[test]
+fn test_binary_round_trip_strcategory() {
+    let mut hist = ndhistogram!(StrCategory::new(["electron", "muon"]); u32);
+    hist.fill_str("muon");
+    hist.fill_str("photon");
+    let bytes = to_binary(&hist, false);
+    let actual: Hist1D<StrCategory, u32> = read_binary(bytes.as_slice()).unwrap();
+    assert_eq!(actual, hist);
+    let category: Hist1D<Category<String>, u32> = read_binary(bytes.as_slice()).unwrap();
+    assert_eq!(category.value(&"muon".to_string()), Some(&1));
+    assert_eq!(category.value(&"photon".to_string()), Some(&1));
+    let other: io::Result<Hist1D<StrCategoryNoFlow, u32>> = read_binary(bytes.as_slice());
+    assert_eq!(
+        conversion_error(other),
+        Some(ConversionError::IncompatibleAxis(0))
+    );
+}
+
+#[test]
+fn test_binary_round_trip_hexagonal() {
+    for orientation in [HexagonOrientation::PointyTop, HexagonOrientation::FlatTop] {
+        let axis = Hexagonal::new(0.5, orientation, (-2.0, 2.0), (0.0, 3.0)).unwrap();
+        let mut hist = ndhistogram!(axis; u32);
+        hist.fill(&(0.3, 1.2));
+        hist.fill(&(9.0, 9.0));
+        let bytes = to_binary(&hist, true);
+        let actual: Hist1D<Hexagonal, u32> = read_binary(bytes.as_slice()).unwrap();
+        assert_eq!(actual, hist);
+        assert_eq!(actual.axes().as_tuple().0.orientation(), orientation);
+    }
+    let bytes = to_binary(
+        &ndhistogram!(Uniform::new(3, 0.0, 3.0).unwrap(); u32),
+        false,
+    );
+    let other: io::Result<Hist1D<Hexagonal, u32>> = read_binary(bytes.as_slice());
+    assert_eq!(
+        conversion_error(other),
+        Some(ConversionError::IncompatibleAxis(0))
+    );
+}
+
+#[test]
+fn test_binary_sum_and_primitive_are_interchangeable() {
+    let mut hist = ndhistogram!(Uniform::new(3, 0.0, 3.0).unwrap(); Sum);
+    hist.fill(&1.5);
+    hist.fill(&1.5);
+    let bytes = to_binary(&hist, false);
+    let actual: Hist1D<Uniform, f64> = read_binary(bytes.as_slice()).unwrap();
+    assert_eq!(actual.value(&1.5), Some(&2.0));
+}
+
+#[test]
+fn test_binary_run_length_encoding_is_smaller_for_empty_bins() {
+    let mut hist = ndhistogram!(
+        Uniform::new(1000, 0.0, 1.0).unwrap(),
+        Uniform::new(1000, 0.0, 1.0).unwrap()
+    );
+    hist.fill(&(0.1, 0.2));
+    hist.fill(&(0.1, 0.2));
+    hist.fill(&(0.9, 0.3));
+    let plain = to_binary(&hist, false);
+    let encoded = to_binary(&hist, true);
+    assert!(plain.len() > 1002 * 1002 * 8);
+    assert!(encoded.len() < 200);
+    let actual: Hist2D<Uniform, Uniform> = read_binary(encoded.as_slice()).unwrap();
+    assert_eq!(actual, hist);
+}
+
+#[test]
+fn test_binary_run_length_encoding_of_full_histogram() {
+    let mut hist = ndhistogram!(Uniform::new(10000, 0.0, 1.0).unwrap(); u16);
+    for index in 0..10002 {
+        hist.fill(&((index as f64 - 0.5) / 10000.0));
+    }
+    let plain = to_binary(&hist, false);
+    let encoded = to_binary(&hist, true);
+    assert!(encoded.len() - plain.len() < 16 * 2);
+    let actual: Hist1D<Uniform, u16> = read_binary(encoded.as_slice()).unwrap();
+    assert_eq!(actual, hist);
+}
+
+#[test]
+fn test_binary_sparse_histogram() {
+    let mut hist = sparsehistogram!(Uniform::new(100, 0.0, 100.0).unwrap(); i64);
+    hist.fill_with(&50.0, -3);
+    hist.fill(&500.0);
+    for run_length_encoding in [false, true] {
+        let bytes = to_binary(&hist, run_length_encoding);
+        let actual: VecHistogram<_, i64> = read_binary(bytes.as_slice()).unwrap();
+        let expected: VecHistogram<_, i64> = {
+            let mut expected = ndhistogram!(Uniform::new(100, 0.0, 100.0).unwrap(); i64);
+            expected.fill_with(&50.0, -3);
+            expected.fill(&500.0);
+            expected
+        };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_binary_wrong_magic_number() {
+    let hist = ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap());
+    let mut bytes = to_binary(&hist, false);
+    bytes[1] = b'X';
+    let actual: io::Result<Hist1D<Uniform>> = read_binary(bytes.as_slice());
+    assert_eq!(conversion_error(actual), Some(ConversionError::InvalidData));
+}
+
+#[test]
+fn test_binary_unsupported_version() {
+    let hist = ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap());
+    for position in [8, 10] {
+        let mut bytes = to_binary(&hist, false);
+        bytes[position] = 2;
+        let actual: io::Result<Hist1D<Uniform>> = read_binary(bytes.as_slice());
+        assert_eq!(
+            conversion_error(actual),
+            Some(ConversionError::UnsupportedVersion)
+        );
+    }
+}
+
+#[test]
+fn test_binary_wrong_value_type() {
+    let hist = ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap(); WeightedSum);
+    let bytes = to_binary(&hist, false);
+    let actual: io::Result<Hist1D<Uniform>> = read_binary(bytes.as_slice());
+    assert_eq!(
+        conversion_error(actual),
+        Some(ConversionError::IncompatibleStorage)
+    );
+    let actual: io::Result<Hist1D<Uniform, WeightedSum<f32>>> = read_binary(bytes.as_slice());
+    assert_eq!(
+        conversion_error(actual),
+        Some(ConversionError::IncompatibleStorage)
+    );
+}
+
+#[test]
+fn test_binary_wrong_axes() {
+    let hist = ndhistogram!(
+        Uniform::new(1, 0.0, 1.0).unwrap(),
+        Variable::new(vec![0.0, 1.0]).unwrap()
+    );
+    let bytes = to_binary(&hist, false);
+    let actual: io::Result<Hist2D<Uniform, Uniform>> = read_binary(bytes.as_slice());
+    assert_eq!(
+        conversion_error(actual),
+        Some(ConversionError::IncompatibleAxis(1))
+    );
+    let actual: io::Result<Hist2D<Uniform<f32>, Variable>> = read_binary(bytes.as_slice());
+    assert_eq!(
+        conversion_error(actual),
+        Some(ConversionError::IncompatibleAxis(0))
+    );
+    let actual: io::Result<Hist1D<Uniform>> = read_binary(bytes.as_slice());
+    assert_eq!(
+        conversion_error(actual),
+        Some(ConversionError::WrongNumberOfAxes {
+            expected: 1,
+            actual: 2
+        })
+    );
+}
+
+#[test]
+fn test_binary_truncated_input() {
+    let mut hist = ndhistogram!(Category::new(vec!["a".to_string(), "b".to_string()]));
+    hist.fill(&"a".to_string());
+    for run_length_encoding in [false, true] {
+        let bytes = to_binary(&hist, run_length_encoding);
+        for length in 0..bytes.len() {
+            let actual: io::Result<Hist1D<Category<String>>> = read_binary(&bytes[..length]);
+            let error = actual.unwrap_err();
+            assert!(
+                matches!(
+                    error.kind(),
+                    io::ErrorKind::UnexpectedEof | io::ErrorKind::InvalidData
+                ),
+                "{length}: {error}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_binary_malformed_run_lengths() {
+    let hist = ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap());
+    let mut bytes = to_binary(&hist, true);
+    let run = bytes.len() - 16;
+    bytes[run..run + 8].copy_from_slice(&4u64.to_le_bytes());
+    let actual: io::Result<Hist1D<Uniform>> = read_binary(bytes.as_slice());
+    assert_eq!(conversion_error(actual), Some(ConversionError::InvalidData));
+}
+
+#[test]
+fn test_binary_empty_run() {
+    let hist = ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap());
+    let mut bytes = to_binary(&hist, true);
+    let run = bytes.len() - 16;
+    bytes.splice(run..run, [0; 16]);
+    let actual: io::Result<Hist1D<Uniform>> = read_binary(bytes.as_slice());
+    assert_eq!(conversion_error(actual), Some(ConversionError::InvalidData));
+}
+
+/// A histogram with a single uniform axis whose header declares the given
+/// number of bins, but whose values are only the given bytes.
+fn declare_uniform_bins(num_bins: u64, run_length_encoding: bool, values: &[u8]) -> Vec<u8> {
+    let hist = ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap());
+    let mut bytes = to_binary(&hist, run_length_encoding);
+    bytes[30..38].copy_from_slice(&num_bins.to_le_bytes());
+    bytes[58..66].copy_from_slice(&(num_bins + 2).to_le_bytes());
+    bytes.truncate(66);
+    bytes.extend_from_slice(values);
+    bytes
+}
+
+#[test]
+fn test_binary_truncated_input_with_huge_axis() {
+    let bytes = declare_uniform_bins(1 << 50, false, &[]);
+    assert_eq!(bytes.len(), 66);
+    let actual: io::Result<Hist1D<Uniform>> = read_binary(bytes.as_slice());
+    assert_eq!(actual.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    let actual = merge_binary([bytes.as_slice()], false, io::sink());
+    assert_eq!(actual.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_binary_huge_empty_run() {
+    let num_bins: u64 = 1 << 62;
+    let run = [(num_bins + 2).to_le_bytes(), 0u64.to_le_bytes()].concat();
+    let bytes = declare_uniform_bins(num_bins, true, &run);
+    let actual: io::Result<Hist1D<Uniform>> = read_binary(bytes.as_slice());
+    assert_eq!(actual.unwrap_err().kind(), io::ErrorKind::OutOfMemory);
+    let actual = merge_binary([bytes.as_slice()], false, io::sink());
+    assert_eq!(actual.unwrap_err().kind(), io::ErrorKind::OutOfMemory);
+}
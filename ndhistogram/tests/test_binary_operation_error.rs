@@ -0,0 +1,265 @@
+use ndhistogram::{
+    axis::{
+        Axis, Category, CategoryNoFlow, HexagonOrientation, Hexagonal, Uniform, UniformCyclic,
+        UniformNoFlow, Variable, VariableCyclic, VariableNoFlow,
+    },
+    error::{AxisMismatch, BinaryOperationError},
+    ndhistogram, sparsehistogram, Histogram,
+};
+
+fn incompatible(axis: usize, reason: AxisMismatch) -> Result<(), BinaryOperationError> {
+    Err(BinaryOperationError::IncompatibleAxis { axis, reason })
+}
+
+#[test]
+fn test_uniform_check_compatible() {
+    let axis = Uniform::new(10, 0.0, 1.0).unwrap();
+    assert_eq!(axis.check_compatible(&axis.clone()), Ok(()));
+    assert_eq!(
+        axis.check_compatible(&Uniform::new(11, 0.0, 1.0).unwrap()),
+        incompatible(0, AxisMismatch::NumberOfBins)
+    );
+    assert_eq!(
+        axis.check_compatible(&Uniform::new(10, 0.0, 2.0).unwrap()),
+        incompatible(0, AxisMismatch::Range)
+    );
+}
+
+#[test]
+fn test_uniform_noflow_and_cyclic_check_compatible() {
+    let axis = UniformNoFlow::new(10, 0.0, 1.0).unwrap();
+    assert_eq!(
+        axis.check_compatible(&UniformNoFlow::new(10, -1.0, 1.0).unwrap()),
+        incompatible(0, AxisMismatch::Range)
+    );
+    let axis = UniformCyclic::new(10, 0.0, 1.0).unwrap();
+    assert_eq!(
+        axis.check_compatible(&UniformCyclic::new(5, 0.0, 1.0).unwrap()),
+        incompatible(0, AxisMismatch::NumberOfBins)
+    );
+}
+
+#[test]
+fn test_variable_check_compatible() {
+    let axis = Variable::new(vec![0.0, 1.0, 3.0]).unwrap();
+    assert_eq!(axis.check_compatible(&axis.clone()), Ok(()));
+    assert_eq!(
+        axis.check_compatible(&Variable::new(vec![0.0, 1.0, 2.0, 3.0]).unwrap()),
+        incompatible(0, AxisMismatch::NumberOfBins)
+    );
+    assert_eq!(
+        axis.check_compatible(&Variable::new(vec![0.0, 1.0, 4.0]).unwrap()),
+        incompatible(0, AxisMismatch::Range)
+    );
+    assert_eq!(
+        axis.check_compatible(&Variable::new(vec![0.0, 2.0, 3.0]).unwrap()),
+        incompatible(0, AxisMismatch::BinEdges)
+    );
+}
+
+#[test]
+fn test_variable_noflow_and_cyclic_check_compatible() {
+    let axis = VariableNoFlow::new(vec![0.0, 1.0, 3.0]).unwrap();
+    assert_eq!(
+        axis.check_compatible(&VariableNoFlow::new(vec![0.0, 2.0, 3.0]).unwrap()),
+        incompatible(0, AxisMismatch::BinEdges)
+    );
+    let axis = VariableCyclic::new(vec![0.0, 1.0, 3.0]).unwrap();
+    assert_eq!(
+        axis.check_compatible(&VariableCyclic::new(vec![-1.0, 1.0, 3.0]).unwrap()),
+        incompatible(0, AxisMismatch::Range)
+    );
+}
+
+#[test]
+fn test_category_check_compatible() {
+    let axis = Category::new(vec!["a", "b"]);
+    assert_eq!(
+        axis.check_compatible(&Category::new(vec!["a", "b"])),
+        Ok(())
+    );
+    assert_eq!(
+        axis.check_compatible(&Category::new(vec!["a", "b", "c"])),
+        incompatible(0, AxisMismatch::NumberOfBins)
+    );
+    assert_eq!(
+        axis.check_compatible(&Category::new(vec!["b", "a"])),
+        incompatible(0, AxisMismatch::Categories)
+    );
+    let axis = CategoryNoFlow::new(vec!["a", "b"]);
+    assert_eq!(
+        axis.check_compatible(&CategoryNoFlow::new(vec!["a", "c"])),
+        incompatible(0, AxisMismatch::Categories)
+    );
+}
+
+#[test]
+fn test_axes_check_compatible_reports_first_differing_axis() {
+    let hist1 = ndhistogram!(
+        Uniform::new(10, 0.0, 1.0).unwrap(),
+        Variable::new(vec![0.0, 1.0, 3.0]).unwrap(),
+        Category::new(vec!["a", "b"]),
+        Uniform::new(10, 0.0, 1.0).unwrap()
+    );
+    let hist2 = ndhistogram!(
+        Uniform::new(10, 0.0, 1.0).unwrap(),
+        Variable::new(vec![0.0, 1.0, 3.0]).unwrap(),
+        Category::new(vec!["a", "c"]),
+        Uniform::new(10, 0.0, 2.0).unwrap()
+    );
+    assert_eq!(hist1.axes().check_compatible(hist1.axes()), Ok(()));
+    assert_eq!(
+        hist1.axes().check_compatible(hist2.axes()),
+        incompatible(2, AxisMismatch::Categories)
+    );
+}
+
+#[test]
+fn test_axes_check_compatible_counts_dimensions_of_hexagonal_axis() {
+    let hexagonal =
+        |size| Hexagonal::new(size, HexagonOrientation::PointyTop, (0.0, 2.0), (0.0, 2.0)).unwrap();
+    let hist1 = ndhistogram!(hexagonal(1.0), Uniform::new(10, 0.0, 1.0).unwrap());
+    let hist2 = ndhistogram!(hexagonal(1.0), Uniform::new(10, 0.0, 2.0).unwrap());
+    let hist3 = ndhistogram!(hexagonal(0.5), Uniform::new(10, 0.0, 1.0).unwrap());
+    assert_eq!(
+        (&hist1 + &hist2).map(|_| ()),
+        incompatible(2, AxisMismatch::Range)
+    );
+    assert_eq!(
+        (&hist1 + &hist3).map(|_| ()),
+        incompatible(0, AxisMismatch::NumberOfBins)
+    );
+
+    let hist1 = ndhistogram!(
+        Uniform::new(10, 0.0, 1.0).unwrap(),
+        hexagonal(1.0),
+        Category::new(vec!["a", "b"])
+    );
+    let hist2 = ndhistogram!(
+        Uniform::new(10, 0.0, 1.0).unwrap(),
+        hexagonal(1.0),
+        Category::new(vec!["a", "c"])
+    );
+    assert_eq!(
+        hist1.axes().check_compatible(hist2.axes()),
+        incompatible(3, AxisMismatch::Categories)
+    );
+}
+
+#[test]
+fn test_vechistogram_binary_operation_error() {
+    let hist1 = ndhistogram!(
+        Uniform::new(10, 0.0, 1.0).unwrap(),
+        Uniform::new(10, 0.0, 1.0).unwrap()
+    );
+    let hist2 = ndhistogram!(
+        Uniform::new(10, 0.0, 1.0).unwrap(),
+        Uniform::new(12, 0.0, 1.0).unwrap()
+    );
+    assert_eq!(
+        (&hist1 + &hist2).map(|_| ()),
+        incompatible(1, AxisMismatch::NumberOfBins)
+    );
+    assert_eq!(
+        (hist1 - &hist2).map(|_| ()),
+        incompatible(1, AxisMismatch::NumberOfBins)
+    );
+}
+
+#[test]
+fn test_hashhistogram_binary_operation_error() {
+    let hist1 = sparsehistogram!(Variable::new(vec![0.0, 1.0, 3.0]).unwrap());
+    let hist2 = sparsehistogram!(Variable::new(vec![0.0, 2.0, 3.0]).unwrap());
+    assert_eq!(
+        (&hist1 * &hist2).map(|_| ()),
+        incompatible(0, AxisMismatch::BinEdges)
+    );
+    assert_eq!(
+        (hist1 / &hist2).map(|_| ()),
+        incompatible(0, AxisMismatch::BinEdges)
+    );
+}
+
+#[test]
+#[should_panic(expected = "axis 1 has different range")]
+fn test_vechistogram_assign_panic_describes_error() {
+    let mut hist1 = ndhistogram!(
+        Uniform::new(10, 0.0, 1.0).unwrap(),
+        Uniform::new(10, 0.0, 1.0).unwrap()
+    );
+    let hist2 = ndhistogram!(
+        Uniform::new(10, 0.0, 1.0).unwrap(),
+        Uniform::new(10, 0.0, 3.0).unwrap()
+    );
+    hist1 += &hist2;
+}
+
+#[test]
+fn test_binary_operation_error_display() {
+    let error = BinaryOperationError::IncompatibleAxis {
+        axis: 3,
+        reason: AxisMismatch::BinEdges,
+    };
+    assert_eq!(
+        error.to_string(),
+        "histogram binary operation failed: axis 3 has different bin edges"
+    );
+}
+
+#[derive(Clone, PartialEq)]
+struct UserAxis {
+    num_bins: usize,
+    offset: i32,
+}
+
+impl Axis for UserAxis {
+    type Coordinate = i32;
+    type BinInterval = i32;
+
+    fn index(&self, coordinate: &Self::Coordinate) -> Option<usize> {
+        usize::try_from(coordinate - self.offset).ok()
+    }
+
+    fn num_bins(&self) -> usize {
+        self.num_bins
+    }
+
+    fn bin(&self, index: usize) -> Option<Self::BinInterval> {
+        Some(i32::try_from(index).ok()? + self.offset)
+    }
+}
+
+#[test]
+fn test_user_axis_binary_operation_error() {
+    let hist1 = ndhistogram!(UserAxis {
+        num_bins: 2,
+        offset: 0
+    });
+    let hist2 = ndhistogram!(UserAxis {
+        num_bins: 3,
+        offset: 0
+    });
+    let hist3 = ndhistogram!(UserAxis {
+        num_bins: 2,
+        offset: 1
+    });
+    assert_eq!(
+        (&hist1 + &hist2).map(|_| ()),
+        incompatible(0, AxisMismatch::NumberOfBins)
+    );
+    assert_eq!(
+        (&hist1 + &hist3).map(|_| ()),
+        Err(BinaryOperationError::IncompatibleAxes)
+    );
+}
+
+#[test]
+fn test_binary_operation_error_converts_to_error() {
+    let hist1 = ndhistogram!(Uniform::new(10, 0.0, 1.0).unwrap());
+    let hist2 = ndhistogram!(Uniform::new(10, 0.0, 2.0).unwrap());
+    let result: Result<_, ndhistogram::Error> = (&hist1 + &hist2).map_err(Into::into);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "histogram binary operation failed: axis 0 has different range"
+    );
+}
@@ -0,0 +1,273 @@
+use std::io;
+
+use ndhistogram::{
+    axis::{Category, Uniform, Variable},
+    book::HistogramBook,
+    error::{BinaryOperationError, BookError, ConversionError},
+    ndhistogram, sparsehistogram,
+    value::{Mean, WeightedSum},
+    Hist1D, Hist2D, Histogram, SparseHist1D,
+};
+
+fn booked() -> HistogramBook {
+    let mut book = HistogramBook::new();
+    book.insert(
+        "electrons/pt",
+        ndhistogram!(Uniform::new(10, 0.0, 100.0).unwrap(); WeightedSum),
+    );
+    book.insert(
+        "electrons/eta_phi",
+        ndhistogram!(
+            Variable::new(vec![-2.5, 0.0, 2.5]).unwrap(),
+            Uniform::new(4, -4.0, 4.0).unwrap();
+            Mean
+        ),
+    );
+    book.insert(
+        "muons/charge",
+        sparsehistogram!(Category::new(vec![-1, 1]); u64),
+    );
+    book
+}
+
+type Pt = Hist1D<Uniform, WeightedSum>;
+type EtaPhi = Hist2D<Variable, Uniform, Mean>;
+type Charge = SparseHist1D<Category<i32>, u64>;
+
+fn fill(book: &mut HistogramBook) {
+    book.get_mut::<Pt>("electrons/pt")
+        .unwrap()
+        .fill_with(&25.0, 0.5);
+    book.get_mut::<EtaPhi>("electrons/eta_phi")
+        .unwrap()
+        .fill_with(&(1.0, 1.0), 3.0);
+    book.get_mut::<Charge>("muons/charge").unwrap().fill(&-1);
+}
+
+#[test]
+fn test_book_lookup_by_name_and_type() {
+    let mut book = booked();
+    assert_eq!(book.len(), 3);
+    assert!(!book.is_empty());
+    assert!(book.contains("electrons/pt"));
+    assert!(!book.contains("electrons"));
+    assert!(book.get::<Pt>("electrons/pt").is_some());
+    assert!(book.get::<Hist1D<Uniform>>("electrons/pt").is_none());
+    assert!(book.get::<Pt>("muons/pt").is_none());
+    assert!(book.remove("muons/charge").is_some());
+    assert_eq!(book.len(), 2);
+    assert!(HistogramBook::new().is_empty());
+}
+
+#[test]
+fn test_book_iteration_is_in_order_of_names() {
+    let mut book = booked();
+    assert_eq!(
+        book.names().collect::<Vec<_>>(),
+        vec!["electrons/eta_phi", "electrons/pt", "muons/charge"]
+    );
+    fill(&mut book);
+    let pt: Vec<_> = book
+        .iter()
+        .filter_map(|(name, hist)| Some((name, hist.downcast_ref::<Pt>()?)))
+        .collect();
+    assert_eq!(pt.len(), 1);
+    assert_eq!(pt[0].0, "electrons/pt");
+    for (_, hist) in book.iter_mut() {
+        if let Some(hist) = hist.downcast_mut::<Charge>() {
+            hist.fill(&1);
+        }
+    }
+    assert_eq!(
+        book.get::<Charge>("muons/charge").unwrap().value(&1),
+        Some(&1)
+    );
+}
+
+#[test]
+fn test_book_merge() {
+    let mut book = booked();
+    fill(&mut book);
+    let mut other = booked();
+    fill(&mut other);
+    other.insert("extra", ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap()));
+    book.merge(&other).unwrap();
+
+    let pt = book.get::<Pt>("electrons/pt").unwrap();
+    assert_eq!(
+        pt.value(&25.0),
+        Some(&WeightedSum::with_sum_and_variance(1.0, 0.5))
+    );
+    let eta_phi = book.get::<EtaPhi>("electrons/eta_phi").unwrap();
+    assert_eq!(eta_phi.value(&(1.0, 1.0)).unwrap().num_samples(), 2);
+    let charge = book.get::<Charge>("muons/charge").unwrap();
+    assert_eq!(charge.value(&-1), Some(&2));
+    assert!(book.contains("extra"));
+    assert_eq!(book.len(), 4);
+}
+
+#[test]
+fn test_book_merge_is_checked_before_any_histogram_is_changed() {
+    let mut book = booked();
+    fill(&mut book);
+    let mut other = booked();
+    fill(&mut other);
+    other.insert(
+        "electrons/pt",
+        ndhistogram!(Uniform::new(10, 0.0, 200.0).unwrap(); WeightedSum),
+    );
+    other.insert("new", ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap()));
+    let before = book.get::<EtaPhi>("electrons/eta_phi").unwrap().clone();
+    let actual = book.merge(&other);
+    assert!(matches!(
+        actual,
+        Err(BookError {
+            name,
+            source: BinaryOperationError::IncompatibleAxis { axis: 0, .. }
+        }) if name == "electrons/pt"
+    ));
+    assert_eq!(book.get::<EtaPhi>("electrons/eta_phi"), Some(&before));
+    assert!(!book.contains("new"));
+
+    let mut other = booked();
+    other.insert(
+        "electrons/pt",
+        ndhistogram!(Uniform::new(10, 0.0, 100.0).unwrap()),
+    );
+    assert_eq!(
+        book.merge(&other),
+        Err(BookError {
+            name: "electrons/pt".to_string(),
+            source: BinaryOperationError::IncompatibleTypes
+        })
+    );
+}
+
+#[test]
+fn test_book_write_and_read() {
+    let mut book = booked();
+    fill(&mut book);
+    for run_length_encoding in [false, true] {
+        let mut bytes = Vec::new();
+        book.write_binary(run_length_encoding, &mut bytes).unwrap();
+        let mut actual = booked();
+        actual.read_binary(bytes.as_slice()).unwrap();
+        assert_eq!(
+            actual.get::<Pt>("electrons/pt"),
+            book.get::<Pt>("electrons/pt")
+        );
+        assert_eq!(
+            actual.get::<EtaPhi>("electrons/eta_phi"),
+            book.get::<EtaPhi>("electrons/eta_phi")
+        );
+        assert_eq!(
+            actual.get::<Charge>("muons/charge"),
+            book.get::<Charge>("muons/charge")
+        );
+        assert_eq!(
+            actual.get::<Charge>("muons/charge").unwrap().as_map().len(),
+            1
+        );
+    }
+}
+
+#[test]
+fn test_book_read_errors() {
+    let mut book = booked();
+    fill(&mut book);
+    let mut bytes = Vec::new();
+    book.write_binary(false, &mut bytes).unwrap();
+    let conversion_error = |result: io::Result<()>| {
+        result
+            .unwrap_err()
+            .get_ref()
+            .and_then(|it| it.downcast_ref::<ConversionError>())
+            .copied()
+    };
+
+    let mut missing = booked();
+    missing.remove("muons/charge");
+    assert_eq!(
+        conversion_error(missing.read_binary(bytes.as_slice())),
+        Some(ConversionError::NotFound)
+    );
+
+    let mut wrong_type = booked();
+    wrong_type.insert(
+        "electrons/pt",
+        ndhistogram!(Uniform::new(10, 0.0, 100.0).unwrap()),
+    );
+    assert_eq!(
+        conversion_error(wrong_type.read_binary(bytes.as_slice())),
+        Some(ConversionError::IncompatibleStorage)
+    );
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[2] = b'X';
+    assert_eq!(
+        conversion_error(booked().read_binary(wrong_magic.as_slice())),
+        Some(ConversionError::InvalidData)
+    );
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[8] = 2;
+    assert_eq!(
+        conversion_error(booked().read_binary(wrong_version.as_slice())),
+        Some(ConversionError::UnsupportedVersion)
+    );
+
+    for length in 0..bytes.len() {
+        let error = booked().read_binary(&bytes[..length]).unwrap_err();
+        assert!(
+            matches!(
+                error.kind(),
+                io::ErrorKind::UnexpectedEof | io::ErrorKind::InvalidData
+            ),
+            "{length}: {error}"
+        );
+    }
+}
+
+#[test]
+fn test_book_read_error_leaves_book_unchanged() {
+    let mut book = booked();
+    fill(&mut book);
+    let mut bytes = Vec::new();
+    book.write_binary(false, &mut bytes).unwrap();
+    let empty = booked();
+
+    // the histograms named electrons/* are read before the missing muons/charge
+    let mut missing = booked();
+    missing.remove("muons/charge");
+    assert!(missing.read_binary(bytes.as_slice()).is_err());
+    assert_eq!(
+        missing.get::<Pt>("electrons/pt"),
+        empty.get::<Pt>("electrons/pt")
+    );
+    assert_eq!(
+        missing.get::<EtaPhi>("electrons/eta_phi"),
+        empty.get::<EtaPhi>("electrons/eta_phi")
+    );
+
+    let mut truncated = booked();
+    assert!(truncated.read_binary(&bytes[..bytes.len() - 1]).is_err());
+    assert_eq!(
+        truncated.get::<Pt>("electrons/pt"),
+        empty.get::<Pt>("electrons/pt")
+    );
+}
+
+#[test]
+fn test_book_clone_and_debug() {
+    let mut book = booked();
+    let copy = book.clone();
+    fill(&mut book);
+    assert_eq!(
+        copy.get::<Pt>("electrons/pt").unwrap().value(&25.0),
+        Some(&WeightedSum::new())
+    );
+    assert_eq!(
+        format!("{copy:?}"),
+        "HistogramBook { names: [\"electrons/eta_phi\", \"electrons/pt\", \"muons/charge\"] }"
+    );
+}
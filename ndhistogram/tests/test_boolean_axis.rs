@@ -0,0 +1,83 @@
+use ndhistogram::{
+    axis::{Axis, Boolean, Category, CategoryNoFlow, SingleValueBinInterval, Uniform},
+    ndhistogram, Histogram,
+};
+
+#[test]
+fn test_boolean_num_bins() {
+    assert_eq!(Boolean::new().num_bins(), 2);
+}
+
+#[test]
+fn test_boolean_get_index() {
+    let ax = Boolean::new();
+    assert_eq!(ax.index(&false), Some(0));
+    assert_eq!(ax.index(&true), Some(1));
+}
+
+#[test]
+fn test_boolean_get_bin() {
+    let ax = Boolean::new();
+    let actual: Vec<_> = (0..3).map(|it| ax.bin(it)).collect();
+    let expected = vec![
+        Some(SingleValueBinInterval::new(false)),
+        Some(SingleValueBinInterval::new(true)),
+        None,
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_boolean_iterate_bins() {
+    let ax = Boolean::new();
+    let actual: Vec<_> = ax.into_iter().collect();
+    let expected = vec![
+        (0, SingleValueBinInterval::new(false)),
+        (1, SingleValueBinInterval::new(true)),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_boolean_display() {
+    let ax = Boolean::new();
+    assert_eq!(format!("{ax}"), "{{false}, {true}}");
+    let bins: Vec<_> = ax.bins().map(|it| it.to_string()).collect();
+    assert_eq!(bins, vec!["{false}", "{true}"]);
+}
+
+#[test]
+fn test_boolean_matches_categorynoflow() {
+    let category = CategoryNoFlow::new([false, true]);
+    let ax = Boolean::new();
+    for value in [false, true] {
+        assert_eq!(ax.index(&value), category.index(&value));
+    }
+    assert_eq!(
+        ax.bins().collect::<Vec<_>>(),
+        category.bins().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_boolean_histogram_fill() {
+    let mut hist = ndhistogram!(Uniform::new(4, 0.0, 4.0).unwrap(), Boolean::new(); i32);
+    hist.fill(&(1.5, true));
+    hist.fill(&(1.5, true));
+    hist.fill_with(&(2.5, false), 3);
+    assert_eq!(hist.value(&(1.5, true)), Some(&2));
+    assert_eq!(hist.value(&(1.5, false)), Some(&0));
+    assert_eq!(hist.value(&(2.5, false)), Some(&3));
+    assert_eq!(hist.values().count(), 12);
+}
+
+#[test]
+fn test_boolean_histogram_binary_operation() {
+    let mut hist1 = ndhistogram!(Boolean::new(), Category::new(vec!["a"]); i32);
+    let mut hist2 = hist1.clone();
+    hist1.fill(&(true, "a"));
+    hist2.fill(&(false, "a"));
+    let sum = (&hist1 + &hist2).unwrap();
+    assert_eq!(sum.value(&(true, "a")), Some(&1));
+    assert_eq!(sum.value(&(false, "a")), Some(&1));
+}
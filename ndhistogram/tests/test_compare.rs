@@ -0,0 +1,329 @@
+use ndhistogram::{
+    axis::{HexagonOrientation, Hexagonal, Uniform, UniformNoFlow},
+    compare::{
+        anderson_darling, chi2_unweighted, chi2_unweighted_weighted, chi2_weighted,
+        kolmogorov_smirnov,
+    },
+    error::{AxisMismatch, BinaryOperationError},
+    ndhistogram, sparsehistogram,
+    value::WeightedSum,
+    Fill, Hist1D, Histogram,
+};
+use rand::{prelude::StdRng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+
+fn assert_float_eq(left: f64, right: f64, tolerance: f64) {
+    assert!(
+        (left - right).abs() < tolerance,
+        "left={left} does not equal right={right}"
+    )
+}
+
+fn generate_normal_hist(mu: f64, num: usize, seed: u64) -> Hist1D<Uniform> {
+    let mut hist = ndhistogram!(Uniform::new(20, -5.0, 5.0).unwrap());
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(mu, 1.0).unwrap();
+    (0..num).for_each(|_| hist.fill(&normal.sample(&mut rng)));
+    hist
+}
+
+fn generate_weighted_hist(values: &[(f64, f64, usize)]) -> Hist1D<UniformNoFlow, WeightedSum> {
+    let mut hist = ndhistogram!(UniformNoFlow::new(4, 0.0, 4.0).unwrap(); WeightedSum);
+    for (x, weight, num) in values {
+        (0..*num).for_each(|_| hist.fill_with(x, *weight));
+    }
+    hist
+}
+
+#[test]
+fn test_chi2_unweighted_identical_histograms() {
+    let hist = generate_normal_hist(0.0, 1000, 1);
+    let result = chi2_unweighted(&hist, &hist).unwrap();
+    assert_eq!(result.chi2, 0.0);
+    assert_eq!(result.p_value, 1.0);
+}
+
+#[test]
+fn test_chi2_unweighted_known_value() {
+    let mut h1 = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0).unwrap());
+    let mut h2 = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0).unwrap());
+    h1.fill_with(&0.5, 10.0);
+    h1.fill_with(&1.5, 30.0);
+    h2.fill_with(&0.5, 30.0);
+    h2.fill_with(&1.5, 10.0);
+    let result = chi2_unweighted(&h1, &h2).unwrap();
+    assert_float_eq(result.chi2, 20.0, 1e-12);
+    assert_eq!(result.ndf, 1);
+    assert_float_eq(result.p_value, 7.744e-6, 1e-9);
+}
+
+#[test]
+fn test_chi2_unweighted_p_value_matches_closed_form() {
+    let mut h1 = ndhistogram!(UniformNoFlow::new(5, 0.0, 5.0).unwrap());
+    let mut h2 = ndhistogram!(UniformNoFlow::new(5, 0.0, 5.0).unwrap());
+    (0..5).for_each(|x| {
+        h1.fill_with(&f64::from(x), 100.0);
+        h2.fill_with(&f64::from(x), 100.0);
+    });
+    let base = chi2_unweighted(&h1, &h2).unwrap();
+    assert_eq!(base.ndf, 4);
+    h2.fill_with(&0.0, 40.0);
+    h2.fill_with(&4.0, -40.0);
+    let result = chi2_unweighted(&h1, &h2).unwrap();
+    // compute the expected p-value independently with the closed form for 4 degrees of freedom
+    let expected = (-result.chi2 / 2.0).exp() * (1.0 + result.chi2 / 2.0);
+    assert_float_eq(result.p_value, expected, 1e-9);
+}
+
+#[test]
+fn test_chi2_unweighted_same_distribution_has_large_p_value() {
+    let h1 = generate_normal_hist(0.0, 10000, 1);
+    let h2 = generate_normal_hist(0.0, 5000, 2);
+    let result = chi2_unweighted(&h1, &h2).unwrap();
+    assert!(result.p_value > 0.01, "{result:?}");
+}
+
+#[test]
+fn test_chi2_unweighted_different_distribution_has_small_p_value() {
+    let h1 = generate_normal_hist(0.0, 10000, 1);
+    let h2 = generate_normal_hist(0.5, 10000, 2);
+    let result = chi2_unweighted(&h1, &h2).unwrap();
+    assert!(result.p_value < 1e-6, "{result:?}");
+}
+
+#[test]
+fn test_chi2_unweighted_skips_empty_bins() {
+    let mut h1 = ndhistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    let mut h2 = ndhistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    h1.fill_with(&0.5, 10.0);
+    h1.fill_with(&1.5, 10.0);
+    h2.fill_with(&0.5, 10.0);
+    h2.fill_with(&1.5, 10.0);
+    let result = chi2_unweighted(&h1, &h2).unwrap();
+    assert_eq!(result.ndf, 1);
+}
+
+#[test]
+fn test_chi2_unweighted_empty_histogram_is_nan() {
+    let h1 = ndhistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    let h2 = ndhistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    let result = chi2_unweighted(&h1, &h2).unwrap();
+    assert!(result.chi2.is_nan());
+    assert!(result.p_value.is_nan());
+}
+
+#[test]
+fn test_chi2_unweighted_sparse_and_vec_histograms() {
+    let mut h1 = ndhistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    let mut h2 = sparsehistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    h1.fill_with(&0.5, 10.0);
+    h1.fill_with(&1.5, 30.0);
+    h2.fill_with(&0.5, 30.0);
+    h2.fill_with(&1.5, 10.0);
+    let result = chi2_unweighted(&h1, &h2).unwrap();
+    assert_float_eq(result.chi2, 20.0, 1e-12);
+    assert_eq!(result.ndf, 1);
+}
+
+#[test]
+fn test_chi2_unweighted_weighted_proportional_histograms() {
+    let mut h1 = ndhistogram!(UniformNoFlow::new(4, 0.0, 4.0).unwrap());
+    let values = [(0.5, 10), (1.5, 20), (2.5, 30), (3.5, 40)];
+    values
+        .iter()
+        .for_each(|(x, num)| h1.fill_with(x, f64::from(*num)));
+    let h2 = generate_weighted_hist(&values.map(|(x, num)| (x, 0.5, num as usize)));
+    let result = chi2_unweighted_weighted(&h1, &h2).unwrap();
+    assert_float_eq(result.chi2, 0.0, 1e-9);
+    assert_eq!(result.ndf, 3);
+}
+
+#[test]
+fn test_chi2_unweighted_weighted_unit_weights_is_close_to_unweighted() {
+    let h1 = generate_normal_hist(0.0, 10000, 1);
+    let h2 = generate_normal_hist(0.2, 10000, 2);
+    let mut h2_weighted = ndhistogram!(Uniform::new(20, -5.0, 5.0).unwrap(); WeightedSum);
+    h2.iter().for_each(|item| {
+        (0..(*item.value as usize)).for_each(|_| {
+            h2_weighted.value_at_index_mut(item.index).unwrap().fill();
+        })
+    });
+    let unweighted = chi2_unweighted(&h1, &h2).unwrap();
+    let weighted = chi2_unweighted_weighted(&h1, &h2_weighted).unwrap();
+    assert_eq!(unweighted.ndf, weighted.ndf);
+    assert_float_eq(unweighted.chi2, weighted.chi2, 0.1 * unweighted.chi2);
+}
+
+#[test]
+fn test_chi2_weighted_proportional_histograms() {
+    let h1 = generate_weighted_hist(&[(0.5, 2.0, 10), (1.5, 2.0, 20), (2.5, 2.0, 30)]);
+    let h2 = generate_weighted_hist(&[(0.5, 0.5, 10), (1.5, 0.5, 20), (2.5, 0.5, 30)]);
+    let result = chi2_weighted(&h1, &h2).unwrap();
+    assert_float_eq(result.chi2, 0.0, 1e-9);
+    assert_eq!(result.ndf, 2);
+    assert_float_eq(result.p_value, 1.0, 1e-9);
+}
+
+#[test]
+fn test_chi2_weighted_known_value() {
+    let h1 = generate_weighted_hist(&[(0.5, 1.0, 10), (1.5, 1.0, 30)]);
+    let h2 = generate_weighted_hist(&[(0.5, 2.0, 15), (1.5, 2.0, 5)]);
+    let result = chi2_weighted(&h1, &h2).unwrap();
+    // W1 = 40, W2 = 40
+    // bin 1: (40*30 - 40*10)^2 / (40^2 * 60 + 40^2 * 10) = 640000 / 112000
+    // bin 2: (40*10 - 40*30)^2 / (40^2 * 20 + 40^2 * 30) = 640000 / 80000
+    assert_float_eq(result.chi2, 640000.0 / 112000.0 + 8.0, 1e-9);
+    assert_eq!(result.ndf, 1);
+}
+
+#[test]
+fn test_chi2_incompatible_axes_returns_error() {
+    let h1 = ndhistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    let h2 = ndhistogram!(Uniform::new(10, 0.0, 11.0).unwrap());
+    assert_eq!(
+        chi2_unweighted(&h1, &h2),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 0,
+            reason: AxisMismatch::Range
+        })
+    );
+    let h1 = generate_weighted_hist(&[]);
+    let h2 = ndhistogram!(UniformNoFlow::new(4, 0.0, 5.0).unwrap(); WeightedSum);
+    assert_eq!(
+        chi2_weighted(&h1, &h2),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 0,
+            reason: AxisMismatch::Range
+        })
+    );
+}
+
+#[test]
+fn test_kolmogorov_smirnov_identical_histograms() {
+    let hist = generate_normal_hist(0.0, 1000, 1);
+    let result = kolmogorov_smirnov(&hist, &hist).unwrap();
+    assert_eq!(result.statistic, 0.0);
+    assert_eq!(result.p_value, 1.0);
+}
+
+#[test]
+fn test_kolmogorov_smirnov_known_value() {
+    let mut h1 = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0).unwrap());
+    let mut h2 = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0).unwrap());
+    h1.fill_with(&0.5, 60.0);
+    h1.fill_with(&1.5, 40.0);
+    h2.fill_with(&0.5, 40.0);
+    h2.fill_with(&1.5, 60.0);
+    let result = kolmogorov_smirnov(&h1, &h2).unwrap();
+    assert_float_eq(result.statistic, 0.2, 1e-12);
+    // z = 0.2 * sqrt(50) = 1.414, Kolmogorov distribution Q(1.414) = 0.0367
+    assert_float_eq(result.p_value, 0.0367, 1e-4);
+}
+
+#[test]
+fn test_kolmogorov_smirnov_same_and_different_distributions() {
+    let h1 = generate_normal_hist(0.0, 10000, 1);
+    let same = generate_normal_hist(0.0, 10000, 2);
+    let different = generate_normal_hist(0.2, 10000, 3);
+    assert!(kolmogorov_smirnov(&h1, &same).unwrap().p_value > 0.01);
+    assert!(kolmogorov_smirnov(&h1, &different).unwrap().p_value < 1e-6);
+}
+
+#[test]
+fn test_kolmogorov_smirnov_requires_1d_histograms() {
+    let h1 = ndhistogram!(
+        Uniform::new(10, 0.0, 10.0).unwrap(),
+        Uniform::new(10, 0.0, 10.0).unwrap()
+    );
+    assert_eq!(
+        kolmogorov_smirnov(&h1, &h1),
+        Err(BinaryOperationError::UnsupportedNumberOfDimensions {
+            expected: 1,
+            actual: 2
+        })
+    );
+}
+
+#[test]
+fn test_1d_comparisons_reject_hexagonal_axis() {
+    let hexagonal =
+        Hexagonal::new(1.0, HexagonOrientation::PointyTop, (0.0, 2.0), (0.0, 2.0)).unwrap();
+    let h1 = ndhistogram!(hexagonal.clone());
+    let expected = Err(BinaryOperationError::UnsupportedNumberOfDimensions {
+        expected: 1,
+        actual: 2,
+    });
+    assert_eq!(kolmogorov_smirnov(&h1, &h1), expected);
+    assert_eq!(anderson_darling(&h1, &h1), expected);
+
+    let h2 = ndhistogram!(hexagonal, Uniform::new(2, 0.0, 2.0).unwrap());
+    let expected = Err(BinaryOperationError::UnsupportedNumberOfDimensions {
+        expected: 1,
+        actual: 3,
+    });
+    assert_eq!(kolmogorov_smirnov(&h2, &h2), expected);
+    assert_eq!(anderson_darling(&h2, &h2), expected);
+}
+
+#[test]
+fn test_kolmogorov_smirnov_incompatible_axes_returns_error() {
+    let h1 = ndhistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    let h2 = ndhistogram!(Uniform::new(11, 0.0, 10.0).unwrap());
+    assert_eq!(
+        kolmogorov_smirnov(&h1, &h2),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 0,
+            reason: AxisMismatch::NumberOfBins
+        })
+    );
+}
+
+#[test]
+fn test_anderson_darling_same_and_different_distributions() {
+    let h1 = generate_normal_hist(0.0, 10000, 1);
+    let same = generate_normal_hist(0.0, 10000, 2);
+    let different = generate_normal_hist(0.2, 10000, 3);
+    let same = anderson_darling(&h1, &same).unwrap();
+    let different = anderson_darling(&h1, &different).unwrap();
+    assert!(same.p_value > 0.01, "{same:?}");
+    assert!(different.p_value < 0.001, "{different:?}");
+    assert!(different.statistic > same.statistic);
+}
+
+#[test]
+fn test_anderson_darling_identical_histograms() {
+    let hist = generate_normal_hist(0.0, 1000, 1);
+    let result = anderson_darling(&hist, &hist).unwrap();
+    assert!(result.statistic < 0.0);
+    assert!(result.p_value > 0.25);
+}
+
+#[test]
+fn test_anderson_darling_fewer_than_four_entries_is_nan() {
+    let mut h1 = ndhistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    let mut h2 = ndhistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    h1.fill(&1.0);
+    h2.fill(&2.0);
+    h2.fill(&3.0);
+    let result = anderson_darling(&h1, &h2).unwrap();
+    assert!(result.statistic.is_nan(), "{result:?}");
+    assert!(result.p_value.is_nan(), "{result:?}");
+    h2.fill(&4.0);
+    let result = anderson_darling(&h1, &h2).unwrap();
+    assert!(result.statistic.is_finite(), "{result:?}");
+}
+
+#[test]
+fn test_anderson_darling_requires_1d_histograms() {
+    let h1 = ndhistogram!(
+        Uniform::new(10, 0.0, 10.0).unwrap(),
+        Uniform::new(10, 0.0, 10.0).unwrap()
+    );
+    assert_eq!(
+        anderson_darling(&h1, &h1),
+        Err(BinaryOperationError::UnsupportedNumberOfDimensions {
+            expected: 1,
+            actual: 2
+        })
+    );
+}
@@ -0,0 +1,234 @@
+use std::io;
+
+use ndhistogram::{
+    assert_histogram_approx_eq,
+    axis::{Category, CategoryNoFlow, Uniform, UniformNoFlow, Variable},
+    error::ConversionError,
+    io::csv::{read_csv, read_delimited, read_tsv, write_csv, write_delimited, write_tsv},
+    ndhistogram, sparsehistogram,
+    value::{Mean, WeightedMean, WeightedSum},
+    Hist1D, Hist2D, Histogram,
+};
+
+fn to_csv<H, A, V>(hist: &H) -> String
+where
+    H: Histogram<A, V>,
+    A: ndhistogram::axis::Axis,
+    A::BinInterval: ndhistogram::io::csv::CsvBin,
+    V: ndhistogram::io::csv::CsvValue,
+{
+    let mut output = Vec::new();
+    write_csv(hist, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn test_write_csv_1d() {
+    let mut hist = ndhistogram!(Variable::new(vec![0.0, 0.5, 2.0]).unwrap());
+    hist.fill(&0.25);
+    hist.fill_with(&1.0, 2.5);
+    assert_eq!(
+        to_csv(&hist),
+        "index,axis0_start,axis0_end,value\n\
+         0,-inf,0,0\n\
+         1,0,0.5,1\n\
+         2,0.5,2,2.5\n\
+         3,2,inf,0\n"
+    );
+}
+
+#[test]
+fn test_write_csv_2d_with_category() {
+    let mut hist = ndhistogram!(
+        UniformNoFlow::new(2, 0.0, 2.0).unwrap(),
+        Category::new(vec!["red", "green, blue", "\"quoted\""]);
+        i32
+    );
+    hist.fill(&(1.5, "green, blue"));
+    hist.fill(&(0.5, "other"));
+    assert_eq!(
+        to_csv(&hist),
+        "index,axis0_start,axis0_end,axis1,value\n\
+         0,0,1,red,0\n\
+         1,1,2,red,0\n\
+         2,0,1,\"green, blue\",0\n\
+         3,1,2,\"green, blue\",1\n\
+         4,0,1,\"\"\"quoted\"\"\",0\n\
+         5,1,2,\"\"\"quoted\"\"\",0\n\
+         6,0,1,,1\n\
+         7,1,2,,0\n"
+    );
+}
+
+#[test]
+fn test_write_tsv_weighted_sum() {
+    let mut hist = ndhistogram!(CategoryNoFlow::new(vec![1, 2]); WeightedSum);
+    hist.fill_with(&2, 3.0);
+    hist.fill_with(&2, 1.0);
+    let mut output = Vec::new();
+    write_tsv(&hist, &mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "index\taxis0\tsum\tvariance\n\
+         0\t1\t0\t0\n\
+         1\t2\t4\t10\n"
+    );
+}
+
+#[test]
+fn test_write_csv_mean_columns() {
+    let mut hist = ndhistogram!(CategoryNoFlow::new(vec!["a"]); Mean);
+    hist.fill_with(&"a", 1.0);
+    hist.fill_with(&"a", 3.0);
+    assert_eq!(
+        to_csv(&hist),
+        "index,axis0,mean,error,count\n0,a,2,0.7071067811865476,2\n"
+    );
+}
+
+#[test]
+fn test_write_csv_sparse_histogram_in_index_order() {
+    let mut hist = sparsehistogram!(Uniform::new(10, 0.0, 10.0).unwrap());
+    for x in [7.5, 0.5, 3.5] {
+        hist.fill(&x);
+    }
+    assert_eq!(
+        to_csv(&hist),
+        "index,axis0_start,axis0_end,value\n\
+         1,0,1,1\n\
+         4,3,4,1\n\
+         8,7,8,1\n"
+    );
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let axes = (
+        Uniform::new(3, -1.0, 1.0).unwrap(),
+        Category::new(vec!["a,b".to_string(), "c".to_string()]),
+    );
+    let mut hist = ndhistogram!(axes.0.clone(), axes.1.clone(); WeightedSum);
+    hist.fill_with(&(0.1, "a,b".to_string()), 0.1);
+    hist.fill_with(&(-5.0, "c".to_string()), 2.0);
+    hist.fill_with(&(0.9, "d".to_string()), 1e-20);
+    let table = to_csv(&hist);
+    let read: Hist2D<_, _, WeightedSum> = read_csv(axes.into(), table.as_bytes()).unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_tsv_round_trip() {
+    let axis = Uniform::new(4, 0.0, 1.0).unwrap();
+    let mut hist = ndhistogram!(axis.clone(); u64);
+    hist.fill(&0.3);
+    hist.fill_with(&2.0, 5);
+    let mut output = Vec::new();
+    write_tsv(&hist, &mut output).unwrap();
+    let read: Hist1D<Uniform, u64> = read_tsv((axis,).into(), output.as_slice()).unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_csv_round_trip_mean() {
+    let axis = Uniform::new(2, 0.0, 2.0).unwrap();
+    let mut hist = ndhistogram!(axis.clone(); Mean);
+    for value in [0.1, 0.7, 0.3] {
+        hist.fill_with(&0.5, value);
+    }
+    hist.fill_with(&1.5, 4.0);
+    let read: Hist1D<Uniform, Mean> = read_csv((axis,).into(), to_csv(&hist).as_bytes()).unwrap();
+    assert_histogram_approx_eq!(read, hist);
+    assert_eq!(read.value(&0.5).unwrap().num_samples(), 3);
+    assert_eq!(read.value(&-1.0), Some(&Mean::default()));
+}
+
+#[test]
+fn test_csv_round_trip_weighted_mean() {
+    let axis = Uniform::new(2, 0.0, 2.0).unwrap();
+    let mut hist = ndhistogram!(axis.clone(); WeightedMean);
+    hist.fill_with_weighted(&0.5, 1.0, 2.0);
+    hist.fill_with_weighted(&0.5, 3.0, 0.5);
+    hist.fill_with_weighted(&1.5, 3.0, 1.0);
+    let read: Hist1D<Uniform, WeightedMean> =
+        read_csv((axis,).into(), to_csv(&hist).as_bytes()).unwrap();
+    assert_histogram_approx_eq!(read, hist);
+}
+
+#[test]
+fn test_read_csv_missing_rows_are_default_and_crlf() {
+    let table = "index,axis0_start,axis0_end,value\r\n2,\"0.5\",1,4\r\n\r\n";
+    let read: Hist1D<Uniform> = read_csv(
+        (Uniform::new(2, 0.0, 1.0).unwrap(),).into(),
+        table.as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(
+        read.values().copied().collect::<Vec<_>>(),
+        vec![0.0, 0.0, 4.0, 0.0]
+    );
+}
+
+#[test]
+fn test_read_delimited_semicolon() {
+    let axis = Uniform::new(2, 0.0, 1.0).unwrap();
+    let mut hist = ndhistogram!(axis.clone());
+    hist.fill_with(&0.75, 0.5);
+    let mut output = Vec::new();
+    write_delimited(&hist, &mut output, ';').unwrap();
+    let table = String::from_utf8(output).unwrap();
+    assert!(table.starts_with("index;axis0_start;axis0_end;value\n"));
+    let read: Hist1D<Uniform> = read_delimited((axis,).into(), table.as_bytes(), ';').unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_read_csv_from_buffered_reader() {
+    let axis = Uniform::new(3, 0.0, 3.0).unwrap();
+    let mut hist = ndhistogram!(axis.clone(); u64);
+    hist.fill_with(&1.5, 7);
+    let table = to_csv(&hist);
+    let reader = io::BufReader::with_capacity(4, table.as_bytes());
+    let read: Hist1D<Uniform, u64> = read_csv((axis,).into(), reader).unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_read_csv_invalid_utf8() {
+    let axes = (Uniform::new(2, 0.0, 1.0).unwrap(),).into();
+    let result: io::Result<Hist1D<Uniform>> = read_csv(axes, &[0xff, 0xfe, b'\n'][..]);
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_read_csv_errors() {
+    let axes = || (Uniform::new(2, 0.0, 1.0).unwrap(),).into();
+    let header = "index,axis0_start,axis0_end,value\n";
+    let read = |table: &str| -> Result<Hist1D<Uniform>, ConversionError> {
+        read_csv(axes(), table.as_bytes()).map_err(|error| {
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            *error.get_ref().unwrap().downcast_ref().unwrap()
+        })
+    };
+    assert_eq!(read(""), Err(ConversionError::InvalidRow(1)));
+    assert_eq!(
+        read("index,axis0,value\n0,a,1\n"),
+        Err(ConversionError::InvalidRow(1))
+    );
+    assert_eq!(
+        read(&format!("{header}0,-inf,0,1\n4,1,inf,1\n")),
+        Err(ConversionError::InvalidRow(3))
+    );
+    assert_eq!(
+        read(&format!("{header}1,0,0.5,1\n1,0,0.5,1\n")),
+        Err(ConversionError::InvalidRow(3))
+    );
+    assert_eq!(
+        read(&format!("{header}1,0,0.5\n")),
+        Err(ConversionError::InvalidRow(2))
+    );
+    assert_eq!(
+        read(&format!("{header}1,0,0.5,one\n")),
+        Err(ConversionError::InvalidRow(2))
+    );
+    assert_eq!(ConversionError::InvalidRow(2).to_string(), "invalid row 2");
+}
@@ -0,0 +1,107 @@
+use ndhistogram::{
+    axis::{Category, Uniform, UniformNoFlow},
+    display::Heatmap,
+    ndhistogram, sparsehistogram,
+    value::WeightedSum,
+    Error, Histogram,
+};
+
+#[test]
+fn test_heatmap_with_flow_bins() -> Result<(), Error> {
+    let mut hist = ndhistogram!(Uniform::new(1, 0.0, 1.0)?, Uniform::new(1, 0.0, 1.0)?);
+    hist.fill_with(&(0.5, 0.5), 8.0);
+    hist.fill_with(&(-1.0, 0.5), 3.0);
+    hist.fill_with(&(2.0, 2.0), 5.0);
+    hist.fill_with(&(0.5, -1.0), 7.0);
+    let actual = format!("{}", Heatmap::new(&hist));
+    println!("{actual}");
+    let expected = "Heatmap(3x3 bins, max=8)
+ [1.00, inf) |    ▓▓|
+[0.00, 1.00) |▒▒██  |
+(-inf, 0.00) |  ██  |
+             +------+
+               0 1 2
+  0: (-inf, 0.00)
+  1: [0.00, 1.00)
+  2: [1.00, inf)";
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_heatmap_hide_flow_bins() -> Result<(), Error> {
+    let mut hist = ndhistogram!(Uniform::new(1, 0.0, 1.0)?, Uniform::new(1, 0.0, 1.0)?);
+    hist.fill_with(&(0.5, 0.5), 2.0);
+    hist.fill_with(&(-1.0, 0.5), 30.0);
+    let actual = format!("{}", Heatmap::new(&hist).show_flow(false));
+    let expected = "Heatmap(1x1 bins, max=2)
+[0.00, 1.00) |██|
+             +--+
+               0
+  0: [0.00, 1.00)";
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_heatmap_category_rows_and_sparse_histogram() -> Result<(), Error> {
+    let mut hist = sparsehistogram!(
+        UniformNoFlow::new(2, 0.0, 2.0)?,
+        Category::new(vec!["a", "b"]);
+        i32
+    );
+    hist.fill(&(0.5, "a"));
+    hist.fill_with(&(1.5, "b"), 4);
+    hist.fill_with(&(1.5, "c"), -1);
+    let actual = format!("{:.0}", Heatmap::new(&hist));
+    let expected = "Heatmap(2x3 bins, max=4)
+{overflow} |    |
+       {b} |  ██|
+       {a} |░░  |
+           +----+
+             0 1
+  0: [0, 1)
+  1: [1, 2)";
+    assert_eq!(actual, expected);
+    let actual = format!("{:.0}", Heatmap::new(&hist).show_flow(false));
+    assert!(actual.starts_with("Heatmap(2x2 bins, max=4)\n{b} |  ██|\n{a} |░░  |"));
+    Ok(())
+}
+
+#[test]
+fn test_heatmap_ansi_colours() -> Result<(), Error> {
+    let mut hist = ndhistogram!(
+        UniformNoFlow::new(2, 0.0, 2.0)?,
+        UniformNoFlow::new(1, 0.0, 1.0)?
+    );
+    hist.fill_with(&(0.5, 0.5), 1.0);
+    hist.fill_with(&(1.5, 0.5), 8.0);
+    let actual = format!("{}", Heatmap::new(&hist).colour(true));
+    assert!(actual.contains("|\x1b[38;5;54m░░\x1b[0m\x1b[38;5;226m██\x1b[0m|"));
+    Ok(())
+}
+
+#[test]
+fn test_heatmap_empty_histogram() -> Result<(), Error> {
+    let hist = ndhistogram!(
+        UniformNoFlow::new(2, 0.0, 2.0)?,
+        UniformNoFlow::new(1, 0.0, 1.0)?
+    );
+    let actual = format!("{}", Heatmap::new(&hist));
+    assert!(actual.starts_with("Heatmap(2x1 bins, max=0)\n[0.00, 1.00) |    |"));
+    Ok(())
+}
+
+#[test]
+fn test_heatmap_weightedsum_values() -> Result<(), Error> {
+    let mut hist = ndhistogram!(
+        UniformNoFlow::new(2, 0.0, 2.0)?,
+        UniformNoFlow::new(1, 0.0, 1.0)?;
+        WeightedSum
+    );
+    hist.fill_with(&(0.5, 0.5), 1.0);
+    hist.fill_with(&(1.5, 0.5), 2.0);
+    let actual = format!("{}", Heatmap::new(&hist));
+    assert!(actual.starts_with("Heatmap(2x1 bins, max=2)\n[0.00, 1.00) |▒▒██|"));
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use ndhistogram::{
+    axis::{Category, HexagonOrientation, Hexagonal, Uniform, UniformNoFlow},
+    display::Summary,
+    ndhistogram, sparsehistogram,
+    value::Mean,
+    Error, Histogram,
+};
+
+#[test]
+fn test_summary_1d_with_flow_bins() -> Result<(), Error> {
+    let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0)?);
+    hist.fill(&-1.0);
+    hist.fill(&0.5);
+    hist.fill_with(&1.5, 3.0);
+    hist.fill(&5.0);
+    let actual = format!("{}", hist.summary());
+    println!("{actual}");
+    let expected = "Summary(1D, 4 bins)
+axis 0: Axis{# bins=2, range=[0, 2), class=Uniform}
+axis 0       | value
+(-inf, 0.00) | 1.00
+[0.00, 1.00) | 1.00
+[1.00, 2.00) | 3.00
+[2.00, inf)  | 1.00
+total: 6.00 (in range: 4.00, flow: 2.00)
+entries: 6.00, non-empty bins: 4
+mean: axis 0 = 1.25";
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_summary_2d_with_category_axis() -> Result<(), Error> {
+    let mut hist = ndhistogram!(
+        UniformNoFlow::new(2, 0.0, 2.0)?,
+        Category::new(vec!["a", "bb"]);
+        u32
+    );
+    hist.fill(&(0.5, "a"));
+    hist.fill_with(&(1.5, "bb"), 2);
+    hist.fill(&(1.5, "other"));
+    let actual = format!("{:.1}", hist.summary());
+    println!("{actual}");
+    let expected = "Summary(2D, 6 bins)
+axis 0: Axis{# bins=2, range=[0, 2), class=UniformNoFlow}
+axis 1: {{a}, {bb}, {overflow}}
+axis 0     axis 1     | value
+[0.0, 1.0) {a}        | 1.0
+[1.0, 2.0) {a}        | 0.0
+[0.0, 1.0) {bb}       | 0.0
+[1.0, 2.0) {bb}       | 2.0
+[0.0, 1.0) {overflow} | 0.0
+[1.0, 2.0) {overflow} | 1.0
+total: 4.0 (in range: 3.0, flow: 1.0)
+entries: 4.0, non-empty bins: 3
+mean: axis 0 = 1.2, axis 1 = -";
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_summary_lower_exp() -> Result<(), Error> {
+    let mut hist = ndhistogram!(UniformNoFlow::new(1, 0.0, 1000.0)?);
+    hist.fill_with(&10.0, 12345.0);
+    let actual = format!("{:.2e}", Summary::new(&hist));
+    println!("{actual}");
+    assert!(actual.contains("\n[0.00e0, 1.00e3) | 1.23e4\n"));
+    assert!(actual.contains("\ntotal: 1.23e4 (in range: 1.23e4, flow: 0.00e0)"));
+    assert!(actual.ends_with("\nmean: axis 0 = 5.00e2"));
+    Ok(())
+}
+
+#[test]
+fn test_summary_mean_values_and_sparse_histogram() -> Result<(), Error> {
+    let mut hist = sparsehistogram!(UniformNoFlow::new(3, 0.0, 3.0)?; Mean);
+    hist.fill_with(&0.5, 1.0);
+    hist.fill_with(&0.5, 3.0);
+    hist.fill_with(&2.5, 4.0);
+    let actual = format!("{}", hist.summary());
+    println!("{actual}");
+    let expected = "Summary(1D, 3 bins)
+axis 0: Axis{# bins=3, range=[0, 3), class=UniformNoFlow}
+axis 0       | value
+[0.00, 1.00) | 2.00 ± 0.71
+[2.00, 3.00) | 4.00 ± 0.00
+total: 6.00 (in range: 6.00, flow: 0.00)
+entries: 3.00, non-empty bins: 2
+mean: axis 0 = 1.83";
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_summary_counts_hexagonal_axis_as_two_dimensions() -> Result<(), Error> {
+    let hexagonal = Hexagonal::new(10.0, HexagonOrientation::PointyTop, (0.0, 1.0), (0.0, 1.0))?;
+    let mut hist = ndhistogram!(hexagonal.clone());
+    hist.fill(&(0.5, 0.5));
+    let actual = format!("{:.1}", hist.summary());
+    assert!(actual.starts_with("Summary(2D, 2 bins)\n"), "{actual}");
+
+    let mut hist = ndhistogram!(hexagonal, UniformNoFlow::new(1, 0.0, 1.0)?);
+    hist.fill(&((0.5, 0.5), 0.5));
+    let actual = format!("{:.1}", hist.summary());
+    println!("{actual}");
+    let expected = "Summary(3D, 2 bins)
+axis 0: Axis{# bins=1x1, x range=[0, 1), y range=[0, 1), size=10, orientation=PointyTop, class=Hexagonal}
+axis 1: Axis{# bins=1, range=[0, 1), class=UniformNoFlow}
+axis 0                  axis 1     | value
+{hexagon at (0.0, 0.0)} [0.0, 1.0) | 1.0
+{overflow}              [0.0, 1.0) | 0.0
+total: 1.0 (in range: 1.0, flow: 0.0)
+entries: 1.0, non-empty bins: 1
+mean: axis 0 = -, axis 1 = 0.5";
+    assert_eq!(actual, expected);
+    Ok(())
+}
@@ -1,7 +1,4 @@
-use std::{
-    collections::HashMap,
-    hash::{BuildHasher, RandomState},
-};
+use std::{collections::HashMap, hash::BuildHasher};
 
 use ndhistogram::{
     axis::Uniform, error::AxisError, ndhistogram, sparsehistogram, AxesTuple, DefaultHasher, Error,
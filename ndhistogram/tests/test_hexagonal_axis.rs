@@ -0,0 +1,223 @@
+use ndhistogram::{
+    axis::{Axis, Compatible, HexagonBinInterval, HexagonOrientation, Hexagonal, Uniform},
+    compare::Tolerance,
+    error::{AxisError, AxisMismatch, BinaryOperationError},
+    ndhistogram, sparsehistogram, Histogram,
+};
+use rand::{prelude::StdRng, Rng, SeedableRng};
+
+const ORIENTATIONS: [HexagonOrientation; 2] =
+    [HexagonOrientation::PointyTop, HexagonOrientation::FlatTop];
+
+fn distance((x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> f64 {
+    (x1 - x2).hypot(y1 - y2)
+}
+
+fn centre(axis: &Hexagonal, index: usize) -> (f64, f64) {
+    *axis.bin(index).unwrap().centre().unwrap()
+}
+
+#[test]
+fn test_hexagonal_num_bins() {
+    let axis = Hexagonal::new(1.0, HexagonOrientation::PointyTop, (0.0, 10.0), (0.0, 5.0)).unwrap();
+    assert_eq!(axis.grid_size(), (7, 4));
+    assert_eq!(axis.num_bins(), 29);
+    let axis = Hexagonal::new(1.0, HexagonOrientation::FlatTop, (0.0, 10.0), (0.0, 5.0)).unwrap();
+    assert_eq!(axis.grid_size(), (8, 4));
+    let axis = Hexagonal::new(10.0, HexagonOrientation::PointyTop, (0.0, 1.0), (0.0, 1.0)).unwrap();
+    assert_eq!(axis.grid_size(), (1, 1));
+    assert_eq!(axis.num_bins(), 2);
+}
+
+#[test]
+fn test_hexagonal_index_is_nearest_centre() {
+    let mut rng = StdRng::seed_from_u64(12);
+    for orientation in ORIENTATIONS {
+        let axis = Hexagonal::new(0.7, orientation, (-3.0, 4.0), (1.0, 6.0)).unwrap();
+        let centres: Vec<_> = (0..axis.num_bins() - 1)
+            .map(|index| centre(&axis, index))
+            .collect();
+        for _ in 0..10000 {
+            let point = (rng.gen_range(-3.0..4.0), rng.gen_range(1.0..6.0));
+            let index = axis.index(&point).unwrap();
+            assert!(index < centres.len(), "{point:?} is not in a hexagon");
+            let nearest = centres
+                .iter()
+                .map(|it| distance(*it, point))
+                .fold(f64::INFINITY, f64::min);
+            assert!((distance(centres[index], point) - nearest).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_hexagonal_centres_map_to_their_bin() {
+    for orientation in ORIENTATIONS {
+        let axis = Hexagonal::new(0.5, orientation, (0.0, 3.0), (0.0, 2.0)).unwrap();
+        for index in 0..axis.num_bins() - 1 {
+            assert_eq!(axis.index(&centre(&axis, index)), Some(index));
+        }
+    }
+}
+
+#[test]
+fn test_hexagonal_bins_are_numbered_with_x_varying_fastest() {
+    for orientation in ORIENTATIONS {
+        let axis = Hexagonal::new(0.5, orientation, (0.0, 3.0), (0.0, 2.0)).unwrap();
+        let (num_x, num_y) = axis.grid_size();
+        for iy in 0..num_y {
+            for ix in 1..num_x {
+                let (left, right) = (
+                    centre(&axis, ix - 1 + num_x * iy),
+                    centre(&axis, ix + num_x * iy),
+                );
+                assert!(left.0 < right.0);
+            }
+        }
+        assert_eq!(centre(&axis, 0), (0.0, 0.0));
+    }
+}
+
+#[test]
+fn test_hexagonal_vertices() {
+    let size = 2.0;
+    let half_width = 3.0f64.sqrt();
+    let axis = Hexagonal::new(size, HexagonOrientation::PointyTop, (0.0, 1.0), (0.0, 1.0)).unwrap();
+    assert_eq!(
+        axis.bin(0),
+        Some(HexagonBinInterval::new(
+            (0.0, 0.0),
+            [
+                (half_width, 1.0),
+                (0.0, 2.0),
+                (-half_width, 1.0),
+                (-half_width, -1.0),
+                (0.0, -2.0),
+                (half_width, -1.0)
+            ]
+        ))
+    );
+    for orientation in ORIENTATIONS {
+        let axis = Hexagonal::new(size, orientation, (-5.0, 5.0), (-5.0, 5.0)).unwrap();
+        for (_, bin) in axis.into_iter().filter(|(index, _)| *index < 5) {
+            let centre = *bin.centre().unwrap();
+            let vertices = bin.vertices().unwrap();
+            for (index, vertex) in vertices.iter().enumerate() {
+                assert!((distance(centre, *vertex) - size).abs() < 1e-12);
+                let next = vertices[(index + 1) % 6];
+                assert!((distance(*vertex, next) - size).abs() < 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_hexagonal_overflow() {
+    let axis = Hexagonal::new(1.0, HexagonOrientation::PointyTop, (0.0, 10.0), (0.0, 5.0)).unwrap();
+    let overflow = Some(axis.num_bins() - 1);
+    assert_eq!(axis.index(&(-2.0, 0.0)), overflow);
+    assert_eq!(axis.index(&(0.0, 7.0)), overflow);
+    assert_eq!(axis.index(&(f64::NAN, 1.0)), overflow);
+    assert_eq!(axis.index(&(1.0, f64::INFINITY)), overflow);
+    assert_eq!(axis.index(&(-f64::INFINITY, 1.0)), overflow);
+    assert_eq!(axis.index(&(1e300, -1e300)), overflow);
+    assert_eq!(axis.bin(28), Some(HexagonBinInterval::overflow()));
+    assert_eq!(axis.bin(29), None);
+}
+
+#[test]
+fn test_hexagonal_invalid_parameters() {
+    let new = |size, x, y| Hexagonal::new(size, HexagonOrientation::PointyTop, x, y);
+    assert_eq!(
+        new(0.0, (0.0, 1.0), (0.0, 1.0)),
+        Err(AxisError::InvalidStepSize)
+    );
+    assert_eq!(
+        new(-1.0, (0.0, 1.0), (0.0, 1.0)),
+        Err(AxisError::InvalidStepSize)
+    );
+    assert_eq!(
+        new(f64::NAN, (0.0, 1.0), (0.0, 1.0)),
+        Err(AxisError::InvalidStepSize)
+    );
+    assert_eq!(
+        new(1.0, (1.0, 1.0), (0.0, 1.0)),
+        Err(AxisError::InvalidAxisRange)
+    );
+    assert_eq!(
+        new(1.0, (0.0, 1.0), (0.0, f64::INFINITY)),
+        Err(AxisError::InvalidAxisRange)
+    );
+    assert_eq!(
+        new(1e-300, (0.0, 1e300), (0.0, 1e300)),
+        Err(AxisError::InvalidNumberOfBins)
+    );
+    let reversed = new(1.0, (1.0, 0.0), (2.0, -2.0)).unwrap();
+    assert_eq!(reversed.x_range(), (0.0, 1.0));
+    assert_eq!(reversed.y_range(), (-2.0, 2.0));
+}
+
+#[test]
+fn test_hexagonal_histogram() {
+    let axis = Hexagonal::new(1.0, HexagonOrientation::FlatTop, (0.0, 10.0), (0.0, 10.0)).unwrap();
+    let mut hist = ndhistogram!(axis.clone(); u64);
+    hist.fill(&(5.0, 5.0));
+    hist.fill_with(&(5.1, 4.9), 2);
+    hist.fill(&(100.0, 0.0));
+    assert_eq!(hist.value(&(5.0, 5.0)), Some(&3));
+    assert_eq!(hist.value_at_index(axis.num_bins() - 1), Some(&1));
+    assert_eq!(hist.axes().num_dim(), 2);
+
+    let mut hist = sparsehistogram!(axis, Uniform::new(4, 0.0, 4.0).unwrap(); u64);
+    hist.fill(&((5.0, 5.0), 1.5));
+    assert_eq!(hist.value(&((5.0, 5.0), 1.0)), Some(&1));
+    assert_eq!(hist.axes().num_dim(), 3);
+}
+
+#[test]
+fn test_hexagonal_compatible() {
+    let new = |size, orientation, x_high| {
+        Hexagonal::new(size, orientation, (0.0, x_high), (0.0, 4.0)).unwrap()
+    };
+    let axis = new(1.0, HexagonOrientation::PointyTop, 10.0);
+    assert_eq!(axis.check_compatible(&axis.clone()), Ok(()));
+    let mismatch = |other: &Hexagonal| match axis.check_compatible(other) {
+        Err(BinaryOperationError::IncompatibleAxis { reason, .. }) => Some(reason),
+        _ => None,
+    };
+    assert_eq!(
+        mismatch(&new(1.0, HexagonOrientation::PointyTop, 20.0)),
+        Some(AxisMismatch::NumberOfBins)
+    );
+    assert_eq!(
+        mismatch(&new(1.0, HexagonOrientation::PointyTop, 10.1)),
+        Some(AxisMismatch::Range)
+    );
+    assert_eq!(
+        mismatch(&new(1.01, HexagonOrientation::PointyTop, 10.0)),
+        Some(AxisMismatch::Binning)
+    );
+    let tolerance = Tolerance::default();
+    assert!(axis.is_compatible(
+        &new(1.0, HexagonOrientation::PointyTop, 10.0 + 1e-12),
+        &tolerance
+    ));
+    assert!(!axis.is_compatible(&new(1.0, HexagonOrientation::PointyTop, 10.1), &tolerance));
+}
+
+#[test]
+fn test_hexagonal_display() {
+    let axis = Hexagonal::new(1.0, HexagonOrientation::PointyTop, (0.0, 10.0), (0.0, 5.0)).unwrap();
+    assert_eq!(
+        axis.to_string(),
+        "Axis{# bins=7x4, x range=[0, 10), y range=[0, 5), size=1, orientation=PointyTop, class=Hexagonal}"
+    );
+    assert_eq!(
+        HexagonBinInterval::new((1.5, 0.0), [(0.0, 0.0); 6]).to_string(),
+        "{hexagon at (1.5, 0)}"
+    );
+    assert_eq!(
+        HexagonBinInterval::<f64>::overflow().to_string(),
+        "{overflow}"
+    );
+}
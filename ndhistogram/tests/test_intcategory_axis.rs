@@ -0,0 +1,360 @@
+use ndhistogram::{
+    axis::{Axis, Category, IntCategory, IntCategoryNoFlow, SingleValueBinInterval, Uniform},
+    error::{AxisMismatch, BinaryOperationError},
+    ndhistogram, sparsehistogram,
+    value::WeightedMean,
+    Histogram,
+};
+use rand::{prelude::StdRng, Rng, SeedableRng};
+
+#[test]
+fn test_intcategory_num_bins() {
+    assert_eq!(IntCategory::new(0..10).num_bins(), 11);
+    assert_eq!(IntCategoryNoFlow::new(0..10).num_bins(), 10);
+    assert_eq!(IntCategory::<i32>::new([]).num_bins(), 1);
+}
+
+#[test]
+fn test_intcategory_dense_get_index() {
+    let ax = IntCategory::new(0..4096);
+    assert_eq!(ax.index(&0), Some(0));
+    assert_eq!(ax.index(&4095), Some(4095));
+    assert_eq!(ax.index(&4096), Some(4096));
+    assert_eq!(ax.index(&-1), Some(4096));
+    assert_eq!(ax.index(&i32::MIN), Some(4096));
+    assert_eq!(ax.index(&i32::MAX), Some(4096));
+}
+
+#[test]
+fn test_intcategory_sparse_get_index() {
+    let codes = vec![2212, 11, -11, 13, -13, 1_000_000_000];
+    let ax = IntCategory::new(codes.clone());
+    for (index, code) in codes.iter().enumerate() {
+        assert_eq!(ax.index(code), Some(index));
+    }
+    assert_eq!(ax.index(&22), Some(6));
+    assert_eq!(ax.index(&-1_000_000_000), Some(6));
+}
+
+#[test]
+fn test_intcategory_matches_category() {
+    let mut rng = StdRng::seed_from_u64(12);
+    for span in [10, 1000, 1_000_000] {
+        let values: Vec<i64> = (0..50).map(|_| rng.gen_range(-span..span)).collect();
+        let category = Category::new(values.clone());
+        let ax = IntCategory::new(values);
+        for _ in 0..1000 {
+            let value = rng.gen_range(-span..span);
+            assert_eq!(
+                ax.index(&value).and_then(|it| ax.bin(it)),
+                category.index(&value).and_then(|it| category.bin(it))
+            );
+        }
+    }
+}
+
+#[test]
+fn test_intcategory_extreme_values() {
+    let ax = IntCategory::new([i8::MIN, i8::MAX, 0]);
+    assert_eq!(ax.index(&i8::MIN), Some(0));
+    assert_eq!(ax.index(&i8::MAX), Some(1));
+    assert_eq!(ax.index(&0), Some(2));
+    assert_eq!(ax.index(&1), Some(3));
+    let ax = IntCategory::new([u64::MAX, 0]);
+    assert_eq!(ax.index(&u64::MAX), Some(0));
+    assert_eq!(ax.index(&0), Some(1));
+    assert_eq!(ax.index(&1), Some(2));
+    let ax = IntCategory::new([i128::MIN, i128::MAX]);
+    assert_eq!(ax.index(&i128::MAX), Some(1));
+    assert_eq!(ax.index(&0), Some(2));
+    let ax = IntCategory::new([u128::MAX, 7]);
+    assert_eq!(ax.index(&u128::MAX), Some(0));
+    assert_eq!(ax.index(&7), Some(1));
+}
+
+#[test]
+fn test_intcategory_repeated_values_are_ignored() {
+    let ax = IntCategory::new([3, 1, 3, 2, 1]);
+    assert_eq!(ax.categories().copied().collect::<Vec<_>>(), vec![3, 1, 2]);
+    assert_eq!(ax.index(&2), Some(2));
+}
+
+#[test]
+fn test_intcategory_get_bin() {
+    let ax = IntCategory::new([5, 3]);
+    let actual: Vec<_> = (0..4).map(|it| ax.bin(it)).collect();
+    let expected = vec![
+        Some(SingleValueBinInterval::new(5)),
+        Some(SingleValueBinInterval::new(3)),
+        Some(SingleValueBinInterval::overflow()),
+        None,
+    ];
+    assert_eq!(actual, expected);
+    let ax = IntCategoryNoFlow::new([5, 3]);
+    let actual: Vec<_> = ax.into_iter().collect();
+    let expected = vec![
+        (0, SingleValueBinInterval::new(5)),
+        (1, SingleValueBinInterval::new(3)),
+    ];
+    assert_eq!(actual, expected);
+    assert_eq!(ax.bin(2), None);
+    assert_eq!(ax.index(&4), None);
+}
+
+#[test]
+fn test_intcategory_display_and_debug() {
+    let ax = IntCategory::new([1, 2]);
+    assert_eq!(ax.to_string(), "{{1}, {2}, {overflow}}");
+    assert_eq!(IntCategoryNoFlow::new([1, 2]).to_string(), "{{1}, {2}}");
+    assert_eq!(format!("{ax:?}"), "IntCategory { categories: [1, 2] }");
+}
+
+#[test]
+fn test_intcategory_check_compatible() {
+    let ax = IntCategory::new([1, 2, 3]);
+    assert_eq!(ax.check_compatible(&IntCategory::new([1, 2, 3])), Ok(()));
+    assert_eq!(
+        ax.check_compatible(&IntCategory::new([1, 2])),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 0,
+            reason: AxisMismatch::NumberOfBins
+        })
+    );
+    assert_eq!(
+        ax.check_compatible(&IntCategory::new([3, 2, 1])),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 0,
+            reason: AxisMismatch::Categories
+        })
+    );
+}
+
+#[test]
+fn test_intcategory_histogram_fill() {
+    let mut hist = ndhistogram!(IntCategory::new(0..16), Uniform::new(2, 0.0, 2.0).unwrap(); u32);
+    hist.fill(&(3, 0.5));
+    hist.fill(&(3, 0.5));
+    hist.fill(&(20, 1.5));
+    assert_eq!(hist.value(&(3, 0.5)), Some(&2));
+    assert_eq!(hist.value(&(100, 1.5)), Some(&1));
+    let sum = (&hist + &hist).unwrap();
+    assert_eq!(sum.value(&(3, 0.5)), Some(&4));
+}
+
+#[test]
+fn test_intcategory_extendable_axis() {
+    let mut ax = IntCategoryNoFlow::extendable(vec![1]);
+    assert!(ax.is_extendable());
+    assert_eq!(ax.index(&5), None);
+    assert!(ax.grow(&5));
+    assert!(!ax.grow(&5));
+    assert!(ax.grow(&1_000_000));
+    assert!(ax.grow(&-1_000_000));
+    assert_eq!(
+        ax.categories().copied().collect::<Vec<_>>(),
+        vec![1, 5, 1_000_000, -1_000_000]
+    );
+    assert_eq!(ax.index(&1_000_000), Some(2));
+    assert_eq!(ax.index(&-1_000_000), Some(3));
+
+    let mut fixed = IntCategoryNoFlow::new(vec![1]);
+    assert!(!fixed.is_extendable());
+    assert!(!fixed.grow(&5));
+    assert_eq!(fixed.num_bins(), 1);
+}
+
+#[test]
+fn test_intcategory_extendable_vec_histogram_keeps_values() {
+    let mut hist = ndhistogram!(
+        Uniform::new(3, 0.0, 3.0).unwrap(),
+        IntCategoryNoFlow::extendable(vec![10]),
+        IntCategoryNoFlow::extendable(vec![]);
+        u32
+    );
+    let mut rng = StdRng::seed_from_u64(12);
+    let mut expected = std::collections::HashMap::new();
+    for _ in 0..1000 {
+        let coordinate = (
+            rng.gen_range(-1.0f64..4.0),
+            rng.gen_range(0..20),
+            rng.gen_range(-5..5),
+        );
+        hist.grow(&coordinate);
+        hist.fill(&coordinate);
+        let x = coordinate.0.clamp(-1.0, 3.0).floor() as i32;
+        *expected.entry((x, coordinate.1, coordinate.2)).or_insert(0) += 1;
+    }
+    assert_eq!(hist.axes().num_bins(), 5 * 20 * 10);
+    for ((x, y, z), count) in expected {
+        assert_eq!(hist.value(&(x as f64 + 0.5, y, z)), Some(&count));
+    }
+    assert_eq!(hist.values().sum::<u32>(), 1000);
+}
+
+#[test]
+fn test_intcategory_extendable_hash_histogram_keeps_values() {
+    let mut hist = sparsehistogram!(
+        IntCategoryNoFlow::extendable(vec![]),
+        IntCategoryNoFlow::extendable(vec![]);
+        u32
+    );
+    let coordinates = [(1, 1), (2, 1), (1, 2), (3, 3), (1, 1)];
+    for coordinate in coordinates {
+        hist.grow(&coordinate);
+        hist.fill(&coordinate);
+    }
+    assert_eq!(hist.value(&(1, 1)), Some(&2));
+    assert_eq!(hist.value(&(2, 1)), Some(&1));
+    assert_eq!(hist.value(&(1, 2)), Some(&1));
+    assert_eq!(hist.value(&(3, 3)), Some(&1));
+    assert_eq!(hist.value(&(2, 2)), None);
+    assert_eq!(hist.values().sum::<u32>(), 5);
+}
+
+#[test]
+fn test_intcategory_histogram_with_fixed_axes_does_not_grow() {
+    let mut hist =
+        ndhistogram!(Uniform::new(2, 0.0, 2.0).unwrap(), IntCategoryNoFlow::new([1]); u32);
+    assert!(!hist.grow(&(10.0, 2)));
+    assert_eq!(hist.axes().num_bins(), 4);
+    hist.fill(&(0.5, 2));
+    assert_eq!(hist.values().sum::<u32>(), 0);
+}
+
+#[test]
+fn test_intcategory_extendable_axis_grown_one_at_a_time() {
+    let ascending: Vec<i64> = (0..100_000).collect();
+    let descending: Vec<i64> = (0..100_000).rev().collect();
+    let sparse: Vec<i64> = (0..20_000).map(|it| it * 1_000 - 10_000_000).collect();
+    let mixed: Vec<i64> = (0..20_000)
+        .map(|it| if it % 2 == 0 { it } else { -it })
+        .chain([i64::MIN, i64::MAX])
+        .collect();
+    for values in [ascending, descending, sparse, mixed] {
+        let mut ax = IntCategoryNoFlow::extendable(vec![]);
+        for value in &values {
+            assert!(ax.grow(value));
+        }
+        let expected = IntCategoryNoFlow::new(values.clone());
+        assert_eq!(ax.num_bins(), values.len());
+        for (index, value) in values.iter().enumerate() {
+            assert_eq!(ax.index(value), Some(index));
+            assert_eq!(ax.index(value), expected.index(value));
+        }
+        assert_eq!(ax.index(&(values.len() as i64 * 1_000_000)), None);
+    }
+}
+
+#[test]
+fn test_intcategory_extendable_axis_grown_at_extreme_values() {
+    for values in [
+        vec![i128::MIN, i128::MIN + 1, i128::MIN + 300],
+        vec![i128::MAX, i128::MAX - 1, i128::MAX - 300],
+        vec![i128::MAX, i128::MIN],
+    ] {
+        let mut ax = IntCategoryNoFlow::extendable(vec![]);
+        for (index, value) in values.iter().enumerate() {
+            assert!(ax.grow(value));
+            assert_eq!(ax.index(value), Some(index));
+        }
+        assert_eq!(ax.index(&0), None);
+    }
+}
+
+#[test]
+fn test_intcategory_extendable_histogram_does_not_grow_on_fill() {
+    let mut hist = ndhistogram!(IntCategoryNoFlow::extendable(vec![1]); u32);
+    hist.fill(&2);
+    assert_eq!(hist.axes().num_bins(), 1);
+    assert_eq!(hist.value(&2), None);
+    assert!(hist.grow(&2));
+    hist.fill(&2);
+    assert_eq!(hist.value(&2), Some(&1));
+}
+
+#[test]
+fn test_intcategory_fill_growing_matches_grow_then_fill() {
+    let axes = || {
+        (
+            IntCategoryNoFlow::extendable(vec![]),
+            Uniform::new(3, 0.0, 3.0).unwrap(),
+            IntCategoryNoFlow::extendable(vec![]),
+        )
+    };
+    let mut expected = ndhistogram!(axes().0, axes().1, axes().2; f64);
+    let mut actual = expected.clone();
+    let mut sparse = sparsehistogram!(axes().0, axes().1, axes().2; f64);
+    let mut rng = StdRng::seed_from_u64(13);
+    for _ in 0..1000 {
+        let coordinate = (
+            rng.gen_range(0..20),
+            rng.gen_range(-1.0..4.0),
+            rng.gen_range(-5..5),
+        );
+        let weight = rng.gen_range(0.0..1.0);
+        expected.grow(&coordinate);
+        expected.fill_with(&coordinate, weight);
+        actual.fill_with_growing(&coordinate, weight);
+        sparse.fill_with_growing(&coordinate, weight);
+    }
+    assert_eq!(actual, expected);
+    assert_eq!(sparse.axes(), expected.axes());
+    for item in expected.iter() {
+        assert_eq!(
+            sparse.value_at_index(item.index).unwrap_or(&0.0),
+            item.value
+        );
+    }
+}
+
+#[test]
+fn test_intcategory_fill_growing_books_channels_on_the_fly() {
+    let mut hist = ndhistogram!(
+        Uniform::new(10, 0.0, 1.0).unwrap(),
+        IntCategoryNoFlow::extendable(vec![]);
+        u32
+    );
+    for channel in (0..100_000).rev() {
+        hist.fill_growing(&(0.5, channel));
+        hist.fill_growing(&(0.5, channel));
+    }
+    assert_eq!(hist.axes().num_bins(), 12 * 100_000);
+    assert_eq!(hist.value(&(0.5, 0)), Some(&2));
+    assert_eq!(hist.value(&(0.5, 99_999)), Some(&2));
+    assert_eq!(hist.values().sum::<u32>(), 200_000);
+
+    let mut sparse = sparsehistogram!(IntCategoryNoFlow::extendable(vec![]); u32);
+    for channel in 0..100_000 {
+        sparse.fill_growing(&channel);
+    }
+    assert_eq!(sparse.axes().num_bins(), 100_000);
+    assert_eq!(sparse.values().sum::<u32>(), 100_000);
+}
+
+#[test]
+fn test_intcategory_fill_growing_with_fixed_axes_does_not_grow() {
+    let mut hist =
+        ndhistogram!(Uniform::new(2, 0.0, 2.0).unwrap(), IntCategoryNoFlow::new([1]); u32);
+    hist.fill_growing(&(0.5, 2));
+    hist.fill_with_growing(&(0.5, 2), 1);
+    assert_eq!(hist.axes().num_bins(), 4);
+    assert_eq!(hist.values().sum::<u32>(), 0);
+    let mut sparse = sparsehistogram!(IntCategoryNoFlow::new([1]); u32);
+    sparse.fill_growing(&2);
+    assert_eq!(sparse.values().count(), 0);
+}
+
+#[test]
+fn test_intcategory_fill_with_weighted_growing() {
+    let mut hist = ndhistogram!(IntCategoryNoFlow::extendable(vec![]); WeightedMean);
+    let mut sparse = sparsehistogram!(IntCategoryNoFlow::extendable(vec![]); WeightedMean);
+    for (channel, data, weight) in [(3, 1.0, 1.0), (5, 2.0, 1.0), (3, 4.0, 3.0)] {
+        hist.fill_with_weighted_growing(&channel, data, weight);
+        sparse.fill_with_weighted_growing(&channel, data, weight);
+    }
+    for hist in [&hist as &dyn Histogram<_, WeightedMean>, &sparse] {
+        assert_eq!(hist.axes().num_bins(), 2);
+        assert_eq!(hist.value(&3).unwrap().get(), 3.25);
+        assert_eq!(hist.value(&5).unwrap().get(), 2.0);
+    }
+}
@@ -0,0 +1,212 @@
+use std::{fs, io};
+
+use ndhistogram::{
+    axis::{Category, Uniform},
+    error::{BinaryOperationError, ConversionError},
+    io::{
+        binary::{merge_binary, read_binary, write_binary, BinaryAxes, BinaryValue},
+        merge::{input_files, merge_files, merge_readers, Merge},
+    },
+    ndhistogram, sparsehistogram,
+    value::{Mean, WeightedMean, WeightedSum},
+    Hist1D, Hist2D, Histogram, SparseHist1D,
+};
+
+fn to_binary<H, A, V>(hist: &H) -> Vec<u8>
+where
+    H: Histogram<A, V>,
+    A: BinaryAxes,
+    V: BinaryValue,
+{
+    let mut output = Vec::new();
+    write_binary(hist, false, &mut output).unwrap();
+    output
+}
+
+fn filled(xs: &[f64]) -> Hist1D<Uniform, WeightedSum> {
+    let mut hist = ndhistogram!(Uniform::new(4, 0.0, 4.0).unwrap(); WeightedSum);
+    for x in xs {
+        hist.fill_with(x, *x);
+    }
+    hist
+}
+
+fn error_of<E: std::error::Error + Copy + 'static, T>(result: io::Result<T>) -> Option<E> {
+    let error = result.err()?;
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    error.get_ref()?.downcast_ref().copied()
+}
+
+#[test]
+fn test_merge_readers() {
+    let inputs = [filled(&[0.5, 1.5]), filled(&[1.5]), filled(&[10.0])];
+    let bytes: Vec<_> = inputs.iter().map(to_binary).collect();
+    let actual: Hist1D<Uniform, WeightedSum> =
+        merge_readers(bytes.iter().map(|it| it.as_slice()), read_binary).unwrap();
+    assert_eq!(actual, filled(&[0.5, 1.5, 1.5, 10.0]));
+}
+
+#[test]
+fn test_merge_readers_of_sparse_histograms() {
+    let inputs = ["a", "b", "a"].map(str::as_bytes);
+    let actual: SparseHist1D<Category<&str>, WeightedSum> =
+        merge_readers(inputs, |input: &[u8]| {
+            let mut hist = sparsehistogram!(Category::new(vec!["a", "b"]); WeightedSum);
+            hist.fill_with(&std::str::from_utf8(input).unwrap(), 2.0);
+            Ok(hist)
+        })
+        .unwrap();
+    assert_eq!(
+        actual.value(&"a"),
+        Some(&WeightedSum::with_sum_and_variance(4.0, 8.0))
+    );
+    assert_eq!(
+        actual.value(&"b"),
+        Some(&WeightedSum::with_sum_and_variance(2.0, 4.0))
+    );
+    assert_eq!(actual.as_map().len(), 2);
+}
+
+#[test]
+fn test_add_assign_values() {
+    let mut sum = WeightedSum::with_sum_and_variance(1.0, 0.5);
+    sum += &WeightedSum::with_sum_and_variance(2.0, 4.0);
+    assert_eq!(sum, WeightedSum::with_sum_and_variance(3.0, 4.5));
+    let mut mean: Mean = Mean::new(vec![1.0, 2.0]);
+    mean += &Mean::new(vec![6.0]);
+    assert_eq!(mean, Mean::new(vec![1.0, 2.0, 6.0]));
+    let mut mean: WeightedMean = WeightedMean::new(vec![(1.0, 2.0)]);
+    mean += &WeightedMean::new(vec![(3.0, 0.5), (2.0, 1.0)]);
+    assert_eq!(
+        mean,
+        WeightedMean::new(vec![(1.0, 2.0), (3.0, 0.5), (2.0, 1.0)])
+    );
+}
+
+#[test]
+fn test_merge_is_try_add_assign() {
+    let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0).unwrap(); Mean);
+    hist.fill_with(&0.5, 1.0);
+    let mut other = hist.clone();
+    other.fill_with(&1.5, 4.0);
+    let mut merged = hist.clone();
+    merged.merge(&other).unwrap();
+    hist.try_add_assign(&other).unwrap();
+    assert_eq!(merged, hist);
+    assert_eq!(merged.value(&0.5).unwrap().num_samples(), 2);
+    assert_eq!(merged.value(&1.5).unwrap().get(), 4.0);
+}
+
+#[test]
+fn test_merge_readers_with_incompatible_axes() {
+    let other = ndhistogram!(Uniform::new(4, 0.0, 5.0).unwrap(); WeightedSum);
+    let bytes = [to_binary(&filled(&[1.0])), to_binary(&other)];
+    let actual: io::Result<Hist1D<Uniform, WeightedSum>> =
+        merge_readers(bytes.iter().map(|it| it.as_slice()), read_binary);
+    assert!(matches!(
+        error_of(actual),
+        Some(BinaryOperationError::IncompatibleAxis { axis: 0, .. })
+    ));
+}
+
+#[test]
+fn test_merge_readers_with_read_error() {
+    let bytes = [
+        to_binary(&filled(&[1.0])),
+        b"this is not a histogram in the binary format".to_vec(),
+    ];
+    let actual: io::Result<Hist1D<Uniform, WeightedSum>> =
+        merge_readers(bytes.iter().map(|it| it.as_slice()), read_binary);
+    assert_eq!(error_of(actual), Some(ConversionError::InvalidData));
+}
+
+#[test]
+fn test_merge_readers_without_inputs() {
+    let actual: io::Result<Hist1D<Uniform>> = merge_readers(Vec::<&[u8]>::new(), read_binary);
+    assert_eq!(actual.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_merge_files_and_directories() {
+    let directory = std::env::temp_dir().join(format!("ndhistogram-merge-{}", std::process::id()));
+    let subdirectory = directory.join("inputs");
+    fs::create_dir_all(subdirectory.join("ignored")).unwrap();
+    fs::write(directory.join("first.ndh"), to_binary(&filled(&[0.5]))).unwrap();
+    fs::write(subdirectory.join("b.ndh"), to_binary(&filled(&[2.5]))).unwrap();
+    fs::write(subdirectory.join("a.ndh"), to_binary(&filled(&[3.5]))).unwrap();
+
+    let paths = [directory.join("first.ndh"), subdirectory.clone()];
+    assert_eq!(
+        input_files(&paths).unwrap(),
+        vec![
+            directory.join("first.ndh"),
+            subdirectory.join("a.ndh"),
+            subdirectory.join("b.ndh")
+        ]
+    );
+    let actual: io::Result<Hist1D<Uniform, WeightedSum>> = merge_files(&paths, read_binary);
+    let missing: io::Result<Hist1D<Uniform, WeightedSum>> =
+        merge_files([directory.join("missing.ndh")], read_binary);
+    fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(actual.unwrap(), filled(&[0.5, 2.5, 3.5]));
+    assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_merge_binary_weighted_mean() {
+    let mut inputs = Vec::new();
+    let mut expected = ndhistogram!(
+        Uniform::new(100, 0.0, 1.0).unwrap(),
+        Category::new(vec!["x".to_string()]);
+        WeightedMean
+    );
+    for (x, value, weight) in [(0.25, 1.0, 2.0), (0.25, 3.0, 1.0), (0.75, 4.0, 0.5)] {
+        let mut hist = ndhistogram!(
+            Uniform::new(100, 0.0, 1.0).unwrap(),
+            Category::new(vec!["x".to_string()]);
+            WeightedMean
+        );
+        hist.fill_with_weighted(&(x, "x".to_string()), value, weight);
+        expected.try_add_assign(&hist).unwrap();
+        let mut bytes = Vec::new();
+        write_binary(&hist, true, &mut bytes).unwrap();
+        inputs.push(bytes);
+    }
+    for run_length_encoding in [false, true] {
+        let mut output = Vec::new();
+        merge_binary(
+            inputs.iter().map(|it| it.as_slice()),
+            run_length_encoding,
+            &mut output,
+        )
+        .unwrap();
+        let actual: Hist2D<Uniform, Category<String>, WeightedMean> =
+            read_binary(output.as_slice()).unwrap();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_merge_binary_errors() {
+    let merge =
+        |inputs: &[Vec<u8>]| merge_binary(inputs.iter().map(|it| it.as_slice()), false, io::sink());
+    let hist = filled(&[1.0]);
+    let other_axis = ndhistogram!(Uniform::new(4, 0.0, 5.0).unwrap(); WeightedSum);
+    assert_eq!(
+        error_of(merge(&[to_binary(&hist), to_binary(&other_axis)])),
+        Some(BinaryOperationError::IncompatibleAxes)
+    );
+    let other_value = ndhistogram!(Uniform::new(4, 0.0, 4.0).unwrap(); f64);
+    assert_eq!(
+        error_of(merge(&[to_binary(&hist), to_binary(&other_value)])),
+        Some(ConversionError::IncompatibleStorage)
+    );
+    let mut overflow = ndhistogram!(Uniform::new(4, 0.0, 4.0).unwrap(); u8);
+    overflow.fill_with(&1.0, 200);
+    assert_eq!(
+        error_of(merge(&[to_binary(&overflow), to_binary(&overflow)])),
+        Some(ConversionError::ValueOutOfRange)
+    );
+    assert_eq!(merge(&[]).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,110 @@
+#[cfg(feature = "ndarray")]
+mod ndarray_tests {
+
+    use ndarray::{array, Array3};
+    use ndhistogram::{
+        axis::{Category, Uniform, UniformNoFlow},
+        error::ConversionError,
+        ndhistogram, Axes, AxesTuple, Error, Hist2D, Histogram,
+    };
+
+    #[test]
+    fn test_array_view_shape_includes_flow_bins() {
+        let hist = ndhistogram!(
+            Uniform::new(3, 0.0, 1.0).unwrap(),
+            UniformNoFlow::new(2, 0.0, 1.0).unwrap(),
+            Category::new(vec!["a", "b"])
+        );
+        assert_eq!(hist.axes().shape(), &[5, 2, 3]);
+        assert_eq!(hist.as_array_view().shape(), &[5, 2, 3]);
+    }
+
+    #[test]
+    fn test_array_view_matches_value_at_index() {
+        let mut hist = ndhistogram!(
+            Uniform::new(3, 0.0, 3.0).unwrap(),
+            Uniform::new(2, 0.0, 2.0).unwrap()
+        );
+        for (index, value) in hist.values_mut().enumerate() {
+            *value = index as f64;
+        }
+        let view = hist.as_array_view();
+        for i in 0..5 {
+            for j in 0..4 {
+                assert_eq!(
+                    view[[i, j]],
+                    *hist.value_at_index(i + 5 * j).unwrap(),
+                    "{i}, {j}"
+                );
+            }
+        }
+        assert_eq!(view[[2, 1]], *hist.value(&(1.5, 0.5)).unwrap());
+    }
+
+    #[test]
+    fn test_array_view_mut_modifies_histogram() {
+        let mut hist = ndhistogram!(
+            UniformNoFlow::new(2, 0.0, 2.0).unwrap(),
+            UniformNoFlow::new(3, 0.0, 3.0).unwrap();
+            i32
+        );
+        hist.as_array_view_mut()
+            .indexed_iter_mut()
+            .for_each(|(position, value)| *value = 10 * position[0] as i32 + position[1] as i32);
+        assert_eq!(hist.value(&(1.5, 2.5)), Some(&12));
+        assert_eq!(hist.value(&(0.5, 1.5)), Some(&1));
+        hist.as_array_view_mut()
+            .index_axis_mut(ndarray::Axis(0), 0)
+            .fill(-1);
+        assert_eq!(hist.value(&(0.5, 0.5)), Some(&-1));
+        assert_eq!(hist.value(&(1.5, 0.5)), Some(&10));
+    }
+
+    #[test]
+    fn test_array_view_sum_over_axis() {
+        let mut hist = ndhistogram!(
+            UniformNoFlow::new(2, 0.0, 2.0).unwrap(),
+            UniformNoFlow::new(2, 0.0, 2.0).unwrap()
+        );
+        hist.fill_with(&(0.5, 0.5), 1.0);
+        hist.fill_with(&(0.5, 1.5), 2.0);
+        hist.fill_with(&(1.5, 1.5), 4.0);
+        let projection = hist.as_array_view().sum_axis(ndarray::Axis(1));
+        assert_eq!(projection.into_raw_vec_and_offset().0, vec![3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_from_array_round_trip() {
+        let axes: AxesTuple<_> = (
+            UniformNoFlow::new(2, 0.0, 2.0).unwrap(),
+            UniformNoFlow::new(3, 0.0, 3.0).unwrap(),
+        )
+            .into();
+        let array = array![[1, 2, 3], [4, 5, 6]];
+        let hist: Hist2D<UniformNoFlow, UniformNoFlow, i32> =
+            Hist2D::from_array(axes, array.clone()).unwrap();
+        assert_eq!(hist.value(&(0.5, 2.5)), Some(&3));
+        assert_eq!(hist.value(&(1.5, 0.5)), Some(&4));
+        assert_eq!(hist.as_array_view(), array.into_dyn());
+    }
+
+    #[test]
+    fn test_from_array_shape_mismatch() {
+        let axes = || {
+            (
+                Uniform::new(2, 0.0, 2.0).unwrap(),
+                Uniform::new(3, 0.0, 3.0).unwrap(),
+            )
+                .into()
+        };
+        let result: Result<Hist2D<Uniform, Uniform>, _> =
+            Hist2D::from_array(axes(), Array3::<f64>::zeros((4, 5, 1)));
+        assert_eq!(
+            result,
+            Err(Error::ConversionError(ConversionError::ShapeMismatch))
+        );
+        let result: Result<Hist2D<Uniform, Uniform>, _> =
+            Hist2D::from_array(axes(), ndarray::Array2::<f64>::zeros((5, 4)));
+        assert!(result.is_err());
+    }
+}
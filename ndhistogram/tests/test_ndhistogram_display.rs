@@ -1,4 +1,9 @@
-use ndhistogram::{axis::Uniform, ndhistogram, Error, Histogram};
+use ndhistogram::{
+    axis::{Uniform, UniformNoFlow},
+    ndhistogram, sparsehistogram,
+    value::{Mean, Sum, WeightedMean, WeightedSum},
+    Error, Histogram,
+};
 
 #[test]
 fn test_histogram_display_blank() -> Result<(), Error> {
@@ -41,3 +46,65 @@ fn test_histogram_display_filled_positive() -> Result<(), Error> {
     assert_eq!(actual, expected);
     Ok(())
 }
+
+#[test]
+fn test_histogram_display_weightedsum_shows_uncertainty() -> Result<(), Error> {
+    let mut hist = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0)?; WeightedSum);
+    hist.fill_with(&0.5, 3.0);
+    hist.fill_with(&0.5, 4.0);
+    hist.fill_with(&1.5, 2.0);
+    let actual = format!("{hist:.1}");
+    println!("{actual}");
+    let expected = format!(
+        "VecHistogram1D(2 bins, sum=9)
+      [0.0, 1.0) | {} 7.0 ± 5.0
+      [1.0, 2.0) | {:<50} 2.0 ± 2.0",
+        "#".repeat(50),
+        "#".repeat(14)
+    );
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_histogram_display_sum_and_integer_values() -> Result<(), Error> {
+    let mut hist = ndhistogram!(UniformNoFlow::new(1, 0.0, 1.0)?; Sum);
+    hist.fill_with(&0.5, 4.0);
+    assert!(format!("{hist}").ends_with(&format!("| {} 4.00 ± 2.00", "#".repeat(50))));
+    let mut hist = ndhistogram!(UniformNoFlow::new(1, 0.0, 1.0)?; i64);
+    hist.fill_with(&0.5, 4);
+    assert_eq!(
+        format!("{hist}"),
+        "VecHistogram1D(1 bins, sum=4)\n    [0.00, 1.00) | ".to_string() + &"#".repeat(50)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_histogram_display_mean_values() -> Result<(), Error> {
+    let mut hist = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0)?; Mean);
+    hist.fill_with(&0.5, 1.0);
+    hist.fill_with(&0.5, 3.0);
+    hist.fill_with(&1.5, 1.0);
+    let actual = format!("{hist}");
+    println!("{actual}");
+    assert!(actual.starts_with("VecHistogram1D(2 bins, sum=3)\n"));
+    assert!(actual.contains(&format!("[0.00, 1.00) | {} 2.00 ± 0.71", "#".repeat(50))));
+    assert!(actual.contains(&format!(
+        "[1.00, 2.00) | {:<50} 1.00 ± 0.00",
+        "#".repeat(25)
+    )));
+    let mut hist = ndhistogram!(UniformNoFlow::new(1, 0.0, 1.0)?; WeightedMean);
+    hist.fill_with_weighted(&0.5, 2.0, 1.0);
+    assert!(format!("{hist}").starts_with("VecHistogram1D(1 bins, sum=2)"));
+    Ok(())
+}
+
+#[test]
+fn test_sparse_histogram_display_weightedsum() -> Result<(), Error> {
+    let mut hist = sparsehistogram!(Uniform::new(2, 0.0, 2.0)?; WeightedSum);
+    hist.fill_with(&0.5, 3.0);
+    hist.fill_with(&1.5, 2.0);
+    assert_eq!(format!("{hist}"), "HashHistogram1D(4 bins, sum=5)");
+    Ok(())
+}
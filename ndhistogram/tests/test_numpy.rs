@@ -0,0 +1,170 @@
+#[cfg(feature = "numpy")]
+mod numpy_tests {
+
+    use ndhistogram::{
+        axis::{Category, Uniform, UniformNoFlow, Variable},
+        io::numpy::{write_npy, write_npy_with, write_npz},
+        ndhistogram,
+        value::Mean,
+        Histogram,
+    };
+
+    fn npy_header(bytes: &[u8]) -> (String, &[u8]) {
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        let length = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + length) % 64, 0);
+        let header = String::from_utf8(bytes[10..10 + length].to_vec()).unwrap();
+        assert!(header.ends_with('\n'));
+        (header.trim_end().to_string(), &bytes[10 + length..])
+    }
+
+    fn f64_data(data: &[u8]) -> Vec<f64> {
+        data.chunks(8)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_write_npy_1d_without_flow() {
+        let mut hist = ndhistogram!(Uniform::new(3, 0.0, 3.0).unwrap());
+        hist.fill_with(&-1.0, 10.0);
+        hist.fill_with(&0.5, 1.0);
+        hist.fill_with(&2.5, 3.0);
+        let mut output = Vec::new();
+        write_npy(&hist, false, &mut output).unwrap();
+        let (header, data) = npy_header(&output);
+        assert_eq!(
+            header,
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (3,), }"
+        );
+        assert_eq!(f64_data(data), vec![1.0, 0.0, 3.0]);
+    }
+
+    #[test]
+    fn test_write_npy_2d_is_c_order() {
+        let mut hist = ndhistogram!(
+            UniformNoFlow::new(2, 0.0, 2.0).unwrap(),
+            UniformNoFlow::new(3, 0.0, 3.0).unwrap();
+            i32
+        );
+        for x in 0..2 {
+            for y in 0..3 {
+                hist.fill_with(&(x as f64 + 0.5, y as f64 + 0.5), 10 * x + y);
+            }
+        }
+        let mut output = Vec::new();
+        write_npy(&hist, false, &mut output).unwrap();
+        let (header, data) = npy_header(&output);
+        assert_eq!(
+            header,
+            "{'descr': '<i4', 'fortran_order': False, 'shape': (2, 3), }"
+        );
+        let values: Vec<_> = data
+            .chunks(4)
+            .map(|chunk| i32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(values, vec![0, 1, 2, 10, 11, 12]);
+    }
+
+    #[test]
+    fn test_write_npy_with_flow() {
+        let mut hist = ndhistogram!(Uniform::new(1, 0.0, 1.0).unwrap(), Category::new(vec!["a"]));
+        hist.fill_with(&(-1.0, "a"), 1.0);
+        hist.fill_with(&(0.5, "b"), 2.0);
+        hist.fill_with(&(2.0, "a"), 3.0);
+        let mut output = Vec::new();
+        write_npy(&hist, true, &mut output).unwrap();
+        let (header, data) = npy_header(&output);
+        assert_eq!(
+            header,
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (3, 2), }"
+        );
+        assert_eq!(f64_data(data), vec![1.0, 0.0, 0.0, 2.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn test_write_npy_with_function_of_value() {
+        let mut hist = ndhistogram!(UniformNoFlow::new(2, 0.0, 2.0).unwrap(); Mean);
+        hist.fill_with(&0.5, 1.0);
+        hist.fill_with(&0.5, 3.0);
+        let mut output = Vec::new();
+        write_npy_with(&hist, false, &mut output, |value| {
+            value.num_samples() as u64
+        })
+        .unwrap();
+        let (header, data) = npy_header(&output);
+        assert_eq!(
+            header,
+            "{'descr': '<u8', 'fortran_order': False, 'shape': (2,), }"
+        );
+        assert_eq!(data, [2_u64.to_le_bytes(), 0_u64.to_le_bytes()].concat());
+    }
+
+    fn zip_entries(bytes: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let u16_at =
+            |offset: usize| u16::from_le_bytes([bytes[offset], bytes[offset + 1]]) as usize;
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize
+        };
+        let end = bytes.len() - 22;
+        assert_eq!(&bytes[end..end + 4], b"PK\x05\x06");
+        let num_entries = u16_at(end + 10);
+        let mut directory = u32_at(end + 16);
+        let mut entries = Vec::new();
+        for _ in 0..num_entries {
+            assert_eq!(&bytes[directory..directory + 4], b"PK\x01\x02");
+            let size = u32_at(directory + 24);
+            let name_length = u16_at(directory + 28);
+            let local = u32_at(directory + 42);
+            let name =
+                String::from_utf8(bytes[directory + 46..directory + 46 + name_length].to_vec())
+                    .unwrap();
+            assert_eq!(&bytes[local..local + 4], b"PK\x03\x04");
+            let data_start = local + 30 + u16_at(local + 26) + u16_at(local + 28);
+            entries.push((name, bytes[data_start..data_start + size].to_vec()));
+            directory += 46 + name_length;
+        }
+        entries
+    }
+
+    #[test]
+    fn test_write_npz_contains_hist_and_edges() {
+        let mut hist = ndhistogram!(
+            Variable::new(vec![0.0, 1.0, 3.0]).unwrap(),
+            UniformNoFlow::new(1, 0.0, 1.0).unwrap()
+        );
+        hist.fill(&(2.0, 0.5));
+        let mut output = Vec::new();
+        write_npz(&hist, false, &mut output).unwrap();
+        let entries = zip_entries(&output);
+        let names: Vec<_> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["hist.npy", "edges_0.npy", "edges_1.npy"]);
+
+        let (header, data) = npy_header(&entries[0].1);
+        assert!(header.contains("'shape': (2, 1)"));
+        assert_eq!(f64_data(data), vec![0.0, 1.0]);
+        let (header, data) = npy_header(&entries[1].1);
+        assert!(header.contains("'shape': (3,)"));
+        assert_eq!(f64_data(data), vec![0.0, 1.0, 3.0]);
+        let (_, data) = npy_header(&entries[2].1);
+        assert_eq!(f64_data(data), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_write_npz_edges_with_flow() {
+        let hist = ndhistogram!(
+            Uniform::new(2, 0.0, 1.0).unwrap(),
+            Category::new(vec![1, 2])
+        );
+        let mut output = Vec::new();
+        write_npz(&hist, true, &mut output).unwrap();
+        let entries = zip_entries(&output);
+        let (_, data) = npy_header(&entries[1].1);
+        assert_eq!(
+            f64_data(data),
+            vec![f64::NEG_INFINITY, 0.0, 0.5, 1.0, f64::INFINITY]
+        );
+        let (_, data) = npy_header(&entries[2].1);
+        assert_eq!(f64_data(data), vec![0.0, 1.0, 2.0, 3.0]);
+    }
+}
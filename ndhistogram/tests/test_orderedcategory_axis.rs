@@ -0,0 +1,145 @@
+use ndhistogram::{
+    axis::{Axis, OrderedCategory, OrderedCategoryNoFlow, SingleValueBinInterval, Uniform},
+    error::{AxisMismatch, BinaryOperationError},
+    ndhistogram, Histogram,
+};
+
+#[test]
+fn test_orderedcategory_num_bins() {
+    assert_eq!(OrderedCategory::new(vec!["A", "B", "C"]).num_bins(), 4);
+    assert_eq!(
+        OrderedCategoryNoFlow::new(vec!["A", "B", "C"]).num_bins(),
+        3
+    );
+}
+
+#[test]
+fn test_orderedcategory_bins_are_sorted() {
+    let ax = OrderedCategory::new(vec![30, 10, 20, 10]);
+    assert_eq!(
+        ax.categories().copied().collect::<Vec<_>>(),
+        vec![10, 20, 30]
+    );
+    let actual: Vec<_> = ax.bins().collect();
+    let expected = vec![
+        SingleValueBinInterval::new(10),
+        SingleValueBinInterval::new(20),
+        SingleValueBinInterval::new(30),
+        SingleValueBinInterval::overflow(),
+    ];
+    assert_eq!(actual, expected);
+    assert_eq!(ax.bin(4), None);
+    assert_eq!(ax, OrderedCategory::new(vec![20, 30, 10]));
+}
+
+#[test]
+fn test_orderedcategory_get_index() {
+    let ax = OrderedCategory::new(vec!["c", "a", "b"]);
+    assert_eq!(ax.index(&"a"), Some(0));
+    assert_eq!(ax.index(&"c"), Some(2));
+    assert_eq!(ax.index(&"d"), Some(3));
+    let ax = OrderedCategoryNoFlow::new(vec!["c", "a", "b"]);
+    assert_eq!(ax.index(&"b"), Some(1));
+    assert_eq!(ax.index(&"d"), None);
+    assert_eq!(ax.bin(3), None);
+}
+
+#[test]
+fn test_orderedcategory_index_range() {
+    let ax = OrderedCategory::new(vec![1001, 1002, 1105, 1210, 1300]);
+    assert_eq!(ax.index_range(1002..1210), 1..3);
+    assert_eq!(ax.index_range(1002..=1210), 1..4);
+    assert_eq!(ax.index_range(1003..1100), 2..2);
+    assert_eq!(ax.index_range(..1002), 0..1);
+    assert_eq!(ax.index_range(1200..), 3..5);
+    assert_eq!(ax.index_range(..), 0..5);
+    assert_eq!(ax.index_range(2000..), 5..5);
+    let (high, low) = (1300, 1000);
+    assert!(ax.index_range(high..low).is_empty());
+    assert_eq!(
+        ax.index_range((
+            std::ops::Bound::Excluded(1002),
+            std::ops::Bound::Included(1210)
+        )),
+        2..4
+    );
+    let ax = OrderedCategoryNoFlow::new(vec!["2024-03-01", "2024-01-15", "2024-02-10"]);
+    assert_eq!(ax.index_range("2024-01".."2024-03"), 0..2);
+}
+
+#[test]
+fn test_orderedcategory_display_is_sorted() {
+    let ax = OrderedCategory::new(vec!["b", "c", "a"]);
+    assert_eq!(ax.to_string(), "{{a}, {b}, {c}, {overflow}}");
+    assert_eq!(
+        OrderedCategoryNoFlow::new(vec!["b", "c", "a"]).to_string(),
+        "{{a}, {b}, {c}}"
+    );
+}
+
+#[test]
+fn test_orderedcategory_display_marks_truncation() {
+    let ax = OrderedCategoryNoFlow::new((0..10).rev());
+    assert_eq!(
+        ax.to_string(),
+        "{{0}, {1}, {2}, {3}, {4}, {5}, {6}, {7}, {8}, {9}}"
+    );
+    let ax = OrderedCategory::new((0..10).rev());
+    assert_eq!(
+        ax.to_string(),
+        "{{0}, {1}, {2}, {3}, {4}, {5}, {6}, {7}, {8}, {9}, ... (11 bins)}"
+    );
+    let ax = OrderedCategoryNoFlow::new((0..25).rev());
+    assert_eq!(
+        ax.to_string(),
+        "{{0}, {1}, {2}, {3}, {4}, {5}, {6}, {7}, {8}, {9}, ... (25 bins)}"
+    );
+}
+
+#[test]
+fn test_orderedcategory_check_compatible() {
+    let ax = OrderedCategory::new(vec![1, 2, 3]);
+    assert_eq!(
+        ax.check_compatible(&OrderedCategory::new(vec![3, 2, 1])),
+        Ok(())
+    );
+    assert_eq!(
+        ax.check_compatible(&OrderedCategory::new(vec![1, 2])),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 0,
+            reason: AxisMismatch::NumberOfBins
+        })
+    );
+    assert_eq!(
+        ax.check_compatible(&OrderedCategory::new(vec![1, 2, 4])),
+        Err(BinaryOperationError::IncompatibleAxis {
+            axis: 0,
+            reason: AxisMismatch::Categories
+        })
+    );
+}
+
+#[test]
+fn test_orderedcategory_histogram_iterates_in_order() {
+    let mut hist = ndhistogram!(
+        OrderedCategoryNoFlow::new(vec!["run3".to_string(), "run1".to_string(), "run2".to_string()]),
+        Uniform::new(1, 0.0, 1.0).unwrap();
+        u32
+    );
+    hist.fill_with(&("run2".to_string(), 0.5), 2);
+    hist.fill_with(&("run1".to_string(), 0.5), 1);
+    hist.fill_with(&("run3".to_string(), 0.5), 3);
+    let actual: Vec<_> = hist
+        .iter()
+        .filter(|it| *it.value > 0)
+        .map(|it| (it.bin.0.value().cloned().unwrap(), *it.value))
+        .collect();
+    assert_eq!(
+        actual,
+        vec![
+            ("run1".to_string(), 1),
+            ("run2".to_string(), 2),
+            ("run3".to_string(), 3)
+        ]
+    );
+}
@@ -0,0 +1,135 @@
+use ndhistogram::{
+    axis::{Category, CategoryNoFlow, Uniform, UniformNoFlow, Variable},
+    io::prometheus::{write_prometheus, write_prometheus_with_label},
+    ndhistogram, sparsehistogram,
+    value::WeightedSum,
+    Histogram,
+};
+
+#[test]
+fn test_prometheus_uniform_axis() {
+    let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0).unwrap());
+    hist.fill(&-1.0);
+    hist.fill(&0.5);
+    hist.fill(&1.5);
+    hist.fill(&1.5);
+    hist.fill(&3.0);
+    let mut output = Vec::new();
+    write_prometheus(&hist, "latency", "Latency in seconds.", &mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "# HELP latency Latency in seconds.\n\
+         # TYPE latency histogram\n\
+         latency_bucket{le=\"0\"} 1\n\
+         latency_bucket{le=\"1\"} 2\n\
+         latency_bucket{le=\"2\"} 4\n\
+         latency_bucket{le=\"+Inf\"} 5\n\
+         latency_sum 5.5\n\
+         latency_count 5\n"
+    );
+}
+
+#[test]
+fn test_prometheus_noflow_axis_weighted() {
+    let mut hist = ndhistogram!(UniformNoFlow::new(2, 0.0, 1.0).unwrap(); WeightedSum);
+    hist.fill_with(&0.25, 1.5);
+    hist.fill_with(&0.75, 0.5);
+    let mut output = Vec::new();
+    write_prometheus(&hist, "weighted", "", &mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "# HELP weighted \n\
+         # TYPE weighted histogram\n\
+         weighted_bucket{le=\"0.5\"} 1.5\n\
+         weighted_bucket{le=\"1\"} 2\n\
+         weighted_bucket{le=\"+Inf\"} 2\n\
+         weighted_sum 0.75\n\
+         weighted_count 2\n"
+    );
+}
+
+#[test]
+fn test_prometheus_sparse_histogram_is_ordered_by_bin() {
+    let mut hist = sparsehistogram!(Variable::new(vec![1.0, 2.0, 4.0]).unwrap());
+    hist.fill(&3.0);
+    hist.fill(&1.5);
+    let mut output = Vec::new();
+    write_prometheus(&hist, "sparse", "help", &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let buckets: Vec<&str> = output
+        .lines()
+        .filter(|line| line.starts_with("sparse_bucket"))
+        .collect();
+    assert_eq!(
+        buckets,
+        vec![
+            "sparse_bucket{le=\"1\"} 0",
+            "sparse_bucket{le=\"2\"} 1",
+            "sparse_bucket{le=\"4\"} 2",
+            "sparse_bucket{le=\"+Inf\"} 2",
+        ]
+    );
+}
+
+#[test]
+fn test_prometheus_with_category_label() {
+    let mut hist = ndhistogram!(
+        Variable::new(vec![0.0, 1.0]).unwrap(),
+        Category::new(vec!["GET", "PO\"ST"]);
+        u32
+    );
+    hist.fill(&(0.5, "GET"));
+    hist.fill(&(2.0, "PO\"ST"));
+    hist.fill(&(0.5, "DELETE"));
+    let mut output = Vec::new();
+    write_prometheus_with_label(&hist, "http_seconds", "Duration.", "method", &mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "# HELP http_seconds Duration.\n\
+         # TYPE http_seconds histogram\n\
+         http_seconds_bucket{method=\"GET\",le=\"0\"} 0\n\
+         http_seconds_bucket{method=\"GET\",le=\"1\"} 1\n\
+         http_seconds_bucket{method=\"GET\",le=\"+Inf\"} 1\n\
+         http_seconds_sum{method=\"GET\"} 0.5\n\
+         http_seconds_count{method=\"GET\"} 1\n\
+         http_seconds_bucket{method=\"PO\\\"ST\",le=\"0\"} 0\n\
+         http_seconds_bucket{method=\"PO\\\"ST\",le=\"1\"} 0\n\
+         http_seconds_bucket{method=\"PO\\\"ST\",le=\"+Inf\"} 1\n\
+         http_seconds_sum{method=\"PO\\\"ST\"} 1\n\
+         http_seconds_count{method=\"PO\\\"ST\"} 1\n\
+         http_seconds_bucket{method=\"\",le=\"0\"} 0\n\
+         http_seconds_bucket{method=\"\",le=\"1\"} 1\n\
+         http_seconds_bucket{method=\"\",le=\"+Inf\"} 1\n\
+         http_seconds_sum{method=\"\"} 0.5\n\
+         http_seconds_count{method=\"\"} 1\n"
+    );
+}
+
+#[test]
+fn test_prometheus_help_is_escaped() {
+    let hist = ndhistogram!(UniformNoFlow::new(1, 0.0, 1.0).unwrap());
+    let mut output = Vec::new();
+    write_prometheus(&hist, "name", "line\\one\nline two", &mut output).unwrap();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .starts_with("# HELP name line\\\\one\\nline two\n"));
+}
+
+#[test]
+fn test_prometheus_invalid_names() {
+    let hist = ndhistogram!(UniformNoFlow::new(1, 0.0, 1.0).unwrap());
+    for name in ["", "1st", "with space", "dash-name"] {
+        let error = write_prometheus(&hist, name, "", Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+    let labelled = ndhistogram!(
+        UniformNoFlow::new(1, 0.0, 1.0).unwrap(),
+        CategoryNoFlow::new(vec![1, 2])
+    );
+    for label in ["le", "a:b", ""] {
+        let error =
+            write_prometheus_with_label(&labelled, "name", "", label, Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+    assert!(write_prometheus_with_label(&labelled, "ns:name", "", "_id", Vec::new()).is_ok());
+}
@@ -0,0 +1,168 @@
+use ndhistogram::{
+    axis::{Uniform, UniformNoFlow, Variable, VariableNoFlow},
+    error::ConversionError,
+    io::root::RootFile,
+    ndhistogram,
+    value::{Sum, WeightedSum},
+    Hist1D, Hist2D, Hist3D, Histogram,
+};
+
+fn round_trip(file: &RootFile) -> RootFile {
+    RootFile::from_bytes(&file.to_bytes().unwrap()).unwrap()
+}
+
+#[test]
+fn test_root_file_has_root_header() {
+    let hist = ndhistogram!(Uniform::new(5, 0.0, 1.0).unwrap());
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "title", &hist).unwrap();
+    let bytes = file.to_bytes().unwrap();
+    assert_eq!(&bytes[..4], b"root");
+}
+
+#[test]
+fn test_root_round_trip_1d_uniform_weightedsum() {
+    let mut hist = ndhistogram!(Uniform::new(10, -1.0, 1.0).unwrap(); WeightedSum);
+    hist.fill_with(&-5.0, 2.0);
+    hist.fill_with(&0.25, 3.0);
+    hist.fill_with(&0.25, 0.5);
+    hist.fill(&5.0);
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "A histogram", &hist).unwrap();
+    let read: Hist1D<Uniform, WeightedSum> = round_trip(&file).get("hist").unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_root_round_trip_1d_variable_f64() {
+    let mut hist = ndhistogram!(Variable::new(vec![0.0, 1.0, 4.0, 10.0]).unwrap());
+    hist.fill(&0.5);
+    hist.fill_with(&5.0, 1.5);
+    hist.fill(&-1.0);
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "", &hist).unwrap();
+    let read: Hist1D<Variable> = round_trip(&file).get("hist").unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_root_round_trip_2d_noflow_sum() {
+    let mut hist = ndhistogram!(
+        UniformNoFlow::new(3, 0.0, 3.0).unwrap(),
+        VariableNoFlow::new(vec![0.0, 1.0, 10.0]).unwrap();
+        Sum
+    );
+    hist.fill(&(0.5, 0.5));
+    hist.fill(&(2.5, 5.0));
+    hist.fill(&(2.5, 5.0));
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "", &hist).unwrap();
+    let read: Hist2D<UniformNoFlow, VariableNoFlow, Sum> = round_trip(&file).get("hist").unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_root_round_trip_3d_mixed_axes() {
+    let mut hist = ndhistogram!(
+        Uniform::new(2, 0.0, 2.0).unwrap(),
+        UniformNoFlow::new(3, 0.0, 3.0).unwrap(),
+        Variable::new(vec![0.0, 1.0, 3.0]).unwrap();
+        WeightedSum
+    );
+    hist.fill_with(&(-1.0, 0.5, 2.0), 2.0);
+    hist.fill_with(&(1.5, 2.5, 5.0), 0.25);
+    hist.fill(&(0.5, 1.5, 0.5));
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "", &hist).unwrap();
+    let read: Hist3D<Uniform, UniformNoFlow, Variable, WeightedSum> =
+        round_trip(&file).get("hist").unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_root_read_noflow_axis_from_flow_axis_drops_flow_bins() {
+    let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0).unwrap());
+    hist.fill(&-1.0);
+    hist.fill(&0.5);
+    hist.fill(&5.0);
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "", &hist).unwrap();
+    let read: Hist1D<UniformNoFlow> = round_trip(&file).get("hist").unwrap();
+    assert_eq!(read.values().copied().collect::<Vec<_>>(), vec![1.0, 0.0]);
+}
+
+#[test]
+fn test_root_names_and_titles() {
+    let hist = ndhistogram!(Uniform::new(5, 0.0, 1.0).unwrap());
+    let mut file = RootFile::new("test.root");
+    file.add("first", "First histogram", &hist).unwrap();
+    file.add("second", "Second histogram", &hist).unwrap();
+    file.add("first", "Replaced histogram", &hist).unwrap();
+    let read = round_trip(&file);
+    assert_eq!(read.name(), "test.root");
+    assert_eq!(read.names().collect::<Vec<_>>(), vec!["first", "second"]);
+    assert_eq!(read.title("first"), Some("Replaced histogram"));
+    assert_eq!(read.title("second"), Some("Second histogram"));
+    assert_eq!(read.title("third"), None);
+}
+
+#[test]
+fn test_root_write_matches_to_bytes_length() {
+    let hist = ndhistogram!(Uniform::new(5, 0.0, 1.0).unwrap());
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "", &hist).unwrap();
+    let mut output = Vec::new();
+    file.write(&mut output).unwrap();
+    assert_eq!(output.len(), file.to_bytes().unwrap().len());
+    assert_eq!(RootFile::from_bytes(&output).unwrap(), file);
+}
+
+#[test]
+fn test_root_get_missing_histogram_is_not_found() {
+    let file = RootFile::new("test.root");
+    let result: Result<Hist1D<Uniform>, _> = file.get("hist");
+    assert_eq!(result, Err(ConversionError::NotFound));
+}
+
+#[test]
+fn test_root_get_with_wrong_number_of_axes() {
+    let hist = ndhistogram!(Uniform::new(5, 0.0, 1.0).unwrap());
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "", &hist).unwrap();
+    let result: Result<Hist2D<Uniform, Uniform>, _> = round_trip(&file).get("hist");
+    assert_eq!(
+        result,
+        Err(ConversionError::WrongNumberOfAxes {
+            expected: 2,
+            actual: 1
+        })
+    );
+}
+
+#[test]
+fn test_root_get_uniform_from_variable_binning_is_incompatible() {
+    let hist = ndhistogram!(
+        Uniform::new(2, 0.0, 2.0).unwrap(),
+        Variable::new(vec![0.0, 1.0, 3.0]).unwrap()
+    );
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "", &hist).unwrap();
+    let result: Result<Hist2D<Uniform, Uniform>, _> = round_trip(&file).get("hist");
+    assert_eq!(result, Err(ConversionError::IncompatibleAxis(1)));
+}
+
+#[test]
+fn test_root_from_invalid_bytes() {
+    assert_eq!(
+        RootFile::from_bytes(b"not a root file"),
+        Err(ConversionError::InvalidData)
+    );
+    let hist = ndhistogram!(Uniform::new(5, 0.0, 1.0).unwrap());
+    let mut file = RootFile::new("test.root");
+    file.add("hist", "", &hist).unwrap();
+    let bytes = file.to_bytes().unwrap();
+    assert_eq!(
+        RootFile::from_bytes(&bytes[..bytes.len() / 2]),
+        Err(ConversionError::InvalidData)
+    );
+}
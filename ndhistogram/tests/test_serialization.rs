@@ -2,7 +2,12 @@
 mod serde_tests {
 
     use ndhistogram::{
-        axis::{Category, CategoryNoFlow, Uniform, UniformNoFlow, Variable, VariableNoFlow},
+        axis::Axis,
+        axis::{
+            Category, CategoryNoFlow, IntCategory, IntCategoryNoFlow, OrderedCategory,
+            OrderedCategoryNoFlow, StrCategory, StrCategoryNoFlow, Uniform, UniformNoFlow,
+            Variable, VariableNoFlow,
+        },
         ndhistogram,
         value::{Mean, Sum, WeightedMean, WeightedSum},
         Hist1D, HistND, Histogram,
@@ -42,6 +47,58 @@ mod serde_tests {
         )
     }
 
+    test_serialize_empty_impl! {
+        test_serialized_vec_histogram_intcategory;
+        HistND<(IntCategory<i32>, IntCategoryNoFlow<u8>, IntCategoryNoFlow<i64>)>;
+        ndhistogram!(
+            IntCategory::new(0..100),
+            IntCategoryNoFlow::new(vec![1, 2, 200]),
+            IntCategoryNoFlow::extendable(vec![-1_000_000, 1_000_000]);
+            f64
+        )
+    }
+
+    #[test]
+    fn test_serialized_intcategory_lookup() {
+        let axis = IntCategoryNoFlow::extendable(vec![-1_000_000, 7, 1_000_000]);
+        let serialized = serde_json::to_string(&axis).unwrap();
+        let deserialized: IntCategoryNoFlow<i64> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, axis);
+        assert_eq!(deserialized.index(&7), Some(1));
+        assert!(deserialized.is_extendable());
+    }
+
+    test_serialize_empty_impl! {
+        test_serialized_vec_histogram_orderedcategory;
+        HistND<(OrderedCategory<&str>, OrderedCategoryNoFlow<i32>)>;
+        ndhistogram!(
+            OrderedCategory::new(vec!["B", "A"]),
+            OrderedCategoryNoFlow::new(vec![3, 1, 2]);
+            f64
+        )
+    }
+
+    #[test]
+    fn test_deserialized_orderedcategory_is_sorted() {
+        let axis: OrderedCategory<i32> = serde_json::from_str("[3, 1, 2, 1]").unwrap();
+        assert_eq!(axis, OrderedCategory::new(vec![1, 2, 3]));
+        assert_eq!(axis.index(&2), Some(1));
+    }
+
+    #[test]
+    fn test_serialized_strcategory_lookup() {
+        let mut hist = ndhistogram!(StrCategoryNoFlow::new(["electron", "muon"]); u32);
+        hist.fill_str("muon");
+        let serialized = serde_json::to_string(&hist).unwrap();
+        let deserialized: Hist1D<StrCategoryNoFlow, u32> =
+            serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, hist);
+        assert_eq!(deserialized.value_str("muon"), Some(&1));
+        let axis: StrCategory = serde_json::from_str(r#"["b", "a", "b"]"#).unwrap();
+        assert_eq!(axis.categories().collect::<Vec<_>>(), vec!["b", "a"]);
+        assert_eq!(axis.index(&"a".to_string()), Some(1));
+    }
+
     macro_rules! test_serialize_filled_value_impl {
     ($fnname:ident; $Type:ty; $hist:ident; $rng:ident; $fillexpr:expr) => {
         #[test]
@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use ndhistogram::{
+    axis::{
+        Axis, Category, SingleValueBinInterval, StrAxis, StrCategory, StrCategoryNoFlow, Uniform,
+    },
+    error::{AxisMismatch, BinaryOperationError},
+    ndhistogram, sparsehistogram,
+    value::WeightedMean,
+    Histogram,
+};
+
+fn particles() -> StrCategory {
+    StrCategory::new(["electron", "muon", "tau"])
+}
+
+#[test]
+fn test_strcategory_num_bins() {
+    assert_eq!(particles().num_bins(), 4);
+    assert_eq!(StrCategoryNoFlow::new(["electron", "muon"]).num_bins(), 2);
+    assert_eq!(StrCategory::new(Vec::<String>::new()).num_bins(), 1);
+}
+
+#[test]
+fn test_strcategory_get_index() {
+    let ax = particles();
+    assert_eq!(ax.index_str("electron"), Some(0));
+    assert_eq!(ax.index_str("tau"), Some(2));
+    assert_eq!(ax.index_str("photon"), Some(3));
+    assert_eq!(ax.index_str(""), Some(3));
+    assert_eq!(ax.index(&String::from("muon")), Some(1));
+    assert_eq!(ax.index(&String::from("photon")), Some(3));
+    let ax = StrCategoryNoFlow::new(["electron", "muon"]);
+    assert_eq!(ax.index_str("muon"), Some(1));
+    assert_eq!(ax.index(&"muon".to_string()), Some(1));
+    assert_eq!(ax.index_str("photon"), None);
+}
+
+#[test]
+fn test_strcategory_get_bin() {
+    let ax = particles();
+    assert_eq!(
+        ax.bin(1),
+        Some(SingleValueBinInterval::new(Arc::from("muon")))
+    );
+    assert_eq!(ax.bin(3), Some(SingleValueBinInterval::overflow()));
+    assert_eq!(ax.bin(4), None);
+    let ax = StrCategoryNoFlow::new(["electron", "muon"]);
+    assert_eq!(
+        ax.bin(0),
+        Some(SingleValueBinInterval::new(Arc::from("electron")))
+    );
+    assert_eq!(ax.bin(2), None);
+}
+
+#[test]
+fn test_strcategory_matches_category() {
+    let labels = ["b", "a", "c", "a"];
+    let ax = StrCategory::new(labels);
+    let category = Category::new(labels.map(String::from));
+    for value in ["a", "b", "c", "d"] {
+        assert_eq!(
+            ax.index_str(value)
+                .and_then(|it| ax.bin(it))
+                .map(|it| it.to_string()),
+            category
+                .index(&value.to_string())
+                .and_then(|it| category.bin(it))
+                .map(|it| it.to_string())
+        );
+    }
+}
+
+#[test]
+fn test_strcategory_repeated_values_are_ignored() {
+    let ax = StrCategory::new(vec!["muon".to_string(), "electron".into(), "muon".into()]);
+    assert_eq!(
+        ax.categories().collect::<Vec<_>>(),
+        vec!["muon", "electron"]
+    );
+    assert_eq!(ax.num_bins(), 3);
+}
+
+#[test]
+fn test_strcategory_labels_are_shared_by_bins() {
+    let ax = particles();
+    match (ax.bin(0), ax.bin(0)) {
+        (
+            Some(SingleValueBinInterval::Bin { value: first }),
+            Some(SingleValueBinInterval::Bin { value: second }),
+        ) => assert!(Arc::ptr_eq(&first, &second)),
+        bins => panic!("unexpected bins {bins:?}"),
+    }
+}
+
+#[test]
+fn test_strcategory_fill_from_str() {
+    let mut hist = ndhistogram!(particles(); u64);
+    let events = "muon electron muon photon muon".to_string();
+    for name in events.split(' ') {
+        hist.fill_str(name);
+    }
+    assert_eq!(hist.value_str("muon"), Some(&3));
+    assert_eq!(hist.value_str("electron"), Some(&1));
+    assert_eq!(hist.value_str("tau"), Some(&0));
+    assert_eq!(hist.value_str("photon"), Some(&1));
+    assert_eq!(hist.value(&"muon".to_string()), Some(&3));
+    assert_eq!(hist.value_at_index(3), Some(&1));
+
+    let mut hist = sparsehistogram!(StrCategoryNoFlow::new(["electron", "muon"]); f64);
+    hist.fill_with_str("muon", 2.0);
+    hist.fill_str("photon");
+    assert_eq!(hist.value_str("muon"), Some(&2.0));
+    assert_eq!(hist.value_str("photon"), None);
+    assert_eq!(hist.value_str("tau"), None);
+
+    let mut hist = ndhistogram!(particles(); WeightedMean);
+    hist.fill_with_weighted_str("electron", 1.0, 1.0);
+    hist.fill_with_weighted_str("electron", 4.0, 3.0);
+    assert_eq!(hist.value_str("electron").unwrap().get(), 3.25);
+}
+
+#[test]
+fn test_strcategory_fill_from_reused_buffer() {
+    let input = "muon\nelectron\nmuon\nphoton\n";
+    let mut hist = ndhistogram!(particles(); u64);
+    let mut sparse = sparsehistogram!(particles(); u64);
+    let mut owned = ndhistogram!(particles(); u64);
+    let mut line = String::new();
+    let mut reader = input.as_bytes();
+    while std::io::BufRead::read_line(&mut reader, &mut line).unwrap() > 0 {
+        hist.fill_str(line.trim_end());
+        sparse.fill_str(line.trim_end());
+        line.truncate(line.trim_end().len());
+        owned.fill(&line);
+        line.clear();
+    }
+    for hist in [&hist, &owned] {
+        assert_eq!(hist.value_str("muon"), Some(&2));
+        assert_eq!(hist.value_str("electron"), Some(&1));
+        assert_eq!(hist.value_str("photon"), Some(&1));
+    }
+    assert_eq!(sparse.value_str("muon"), Some(&2));
+    assert_eq!(sparse.value_str("photon"), Some(&1));
+}
+
+#[test]
+fn test_strcategory_compatible() {
+    let ax = particles();
+    assert_eq!(ax.check_compatible(&particles()), Ok(()));
+    assert!(matches!(
+        ax.check_compatible(&StrCategory::new(["electron", "muon"])),
+        Err(BinaryOperationError::IncompatibleAxis {
+            reason: AxisMismatch::NumberOfBins,
+            ..
+        })
+    ));
+    assert!(matches!(
+        ax.check_compatible(&StrCategory::new(["electron", "tau", "muon"])),
+        Err(BinaryOperationError::IncompatibleAxis {
+            reason: AxisMismatch::Categories,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_strcategory_display() {
+    assert_eq!(
+        particles().to_string(),
+        "{{electron}, {muon}, {tau}, {overflow}}"
+    );
+    assert_eq!(
+        StrCategoryNoFlow::new(["electron", "muon"]).to_string(),
+        "{{electron}, {muon}}"
+    );
+    assert_eq!(
+        format!("{:?}", StrCategory::new(["a"])),
+        "StrCategory { categories: [\"a\"] }"
+    );
+}
+
+#[test]
+fn test_strcategory_iterate_bins() {
+    let ax = StrCategoryNoFlow::new(["electron", "muon"]);
+    let bins: Vec<_> = ax
+        .into_iter()
+        .map(|(index, bin)| (index, bin.to_string()))
+        .collect();
+    assert_eq!(
+        bins,
+        vec![(0, "{electron}".to_string()), (1, "{muon}".to_string())]
+    );
+}
+
+#[test]
+fn test_strcategory_nd_histogram() {
+    let mut hist = ndhistogram!(
+        particles(),
+        StrCategoryNoFlow::new(["barrel", "endcap"]),
+        Uniform::new(2, 0.0, 2.0).unwrap();
+        u64
+    );
+    let events = "muon barrel 0.5\nmuon endcap 1.5\nphoton barrel 0.5\nmuon barrel 0.5";
+    let mut event = (String::new(), String::new(), 0.0);
+    for line in events.lines() {
+        let fields: Vec<_> = line.split(' ').collect();
+        event.0.clear();
+        event.0.push_str(fields[0]);
+        event.1.clear();
+        event.1.push_str(fields[1]);
+        event.2 = fields[2].parse().unwrap();
+        hist.fill(&event);
+    }
+    let value = |particle: &str, region: &str, x| {
+        hist.value(&(particle.to_string(), region.to_string(), x))
+            .copied()
+    };
+    assert_eq!(hist.axes().num_dim(), 3);
+    assert_eq!(value("muon", "barrel", 0.5), Some(2));
+    assert_eq!(value("muon", "endcap", 1.5), Some(1));
+    assert_eq!(value("photon", "barrel", 0.5), Some(1));
+    assert_eq!(value("tau", "forward", 0.5), None);
+}
@@ -0,0 +1,117 @@
+#[cfg(feature = "svg")]
+mod svg_tests {
+    use ndhistogram::{
+        axis::{Uniform, UniformNoFlow, Variable},
+        io::svg::{Plot1D, Plot2D, Style},
+        ndhistogram, sparsehistogram,
+        value::{Mean, WeightedSum},
+        Histogram,
+    };
+
+    fn count(svg: &str, pattern: &str) -> usize {
+        svg.matches(pattern).count()
+    }
+
+    #[test]
+    fn test_svg_1d_step_plot_has_labels_and_one_path() {
+        let mut hist = ndhistogram!(Uniform::new(4, 0.0, 4.0).unwrap());
+        hist.fill(&0.5);
+        hist.fill(&1.5);
+        hist.fill(&1.5);
+        let svg = Plot1D::new()
+            .title("A & B <title>")
+            .x_label("x [cm]")
+            .y_label("entries")
+            .add(&hist, "data")
+            .to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"640\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">A &amp; B &lt;title&gt;</text>"));
+        assert!(svg.contains(">x [cm]</text>"));
+        assert!(svg.contains("rotate(-90"));
+        assert!(svg.contains(">entries</text>"));
+        assert!(svg.contains(">data</text>"));
+        assert_eq!(count(&svg, "<path "), 1);
+    }
+
+    #[test]
+    fn test_svg_1d_error_bars_from_weighted_sum() {
+        let mut hist = ndhistogram!(UniformNoFlow::new(3, 0.0, 3.0).unwrap(); WeightedSum);
+        hist.fill_with(&0.5, 2.0);
+        hist.fill_with(&2.5, 1.0);
+        let svg = Plot1D::new().add(&hist, "").to_svg();
+        let error_bars = svg
+            .lines()
+            .filter(|line| line.starts_with("<line") && line.contains("#1f77b4"))
+            .count();
+        assert_eq!(error_bars, 2);
+    }
+
+    #[test]
+    fn test_svg_1d_bar_style_and_overlay() {
+        let mut first = ndhistogram!(Variable::new(vec![0.0, 1.0, 3.0]).unwrap());
+        first.fill(&0.5);
+        first.fill(&2.0);
+        let mut second = sparsehistogram!(Variable::new(vec![0.0, 1.0, 3.0]).unwrap());
+        second.fill(&2.0);
+        let svg = Plot1D::new()
+            .size(800, 600)
+            .add_with_style(&first, "first", Style::Bar)
+            .add(&second, "second")
+            .to_svg();
+        assert!(svg.contains("width=\"800\" height=\"600\""));
+        assert_eq!(count(&svg, "fill-opacity=\"0.4\" stroke=\"#1f77b4\""), 3);
+        assert_eq!(count(&svg, "stroke=\"#ff7f0e\""), 2);
+        assert!(svg.contains(">first</text>") && svg.contains(">second</text>"));
+    }
+
+    #[test]
+    fn test_svg_1d_log_scale_skips_empty_bins() {
+        let mut hist = ndhistogram!(Uniform::new(3, 0.0, 3.0).unwrap());
+        hist.fill_with(&0.5, 1000.0);
+        hist.fill_with(&2.5, 1.0);
+        let svg = Plot1D::new().log_y(true).add(&hist, "").to_svg();
+        assert!(svg.contains(">1000</text>"));
+        assert!(svg.contains(">1</text>"));
+        // the empty middle bin splits the line in two
+        assert_eq!(count(&svg, " M"), 1);
+        assert!(!svg.contains("NaN"));
+    }
+
+    #[test]
+    fn test_svg_1d_mean_values_and_empty_plot() {
+        let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0).unwrap(); Mean);
+        hist.fill_with(&0.5, 1.0);
+        hist.fill_with(&0.5, 3.0);
+        let svg = Plot1D::new().add(&hist, "mean").to_svg();
+        assert!(!svg.contains("NaN"));
+        let empty = Plot1D::new().to_svg();
+        assert!(empty.starts_with("<svg") && !empty.contains("NaN"));
+    }
+
+    #[test]
+    fn test_svg_2d_heatmap_with_colour_bar() {
+        let mut hist = ndhistogram!(
+            Uniform::new(3, 0.0, 3.0).unwrap(),
+            Variable::new(vec![0.0, 1.0, 5.0]).unwrap()
+        );
+        hist.fill(&(0.5, 0.5));
+        hist.fill_with(&(2.5, 3.0), 4.0);
+        hist.fill(&(-1.0, 0.5));
+        let svg = Plot2D::new(&hist)
+            .title("occupancy")
+            .x_label("column")
+            .y_label("row")
+            .z_label("hits")
+            .to_svg();
+        // one cell per non-empty bin, excluding the underflow bin
+        assert_eq!(count(&svg, "<rect") - count(&svg, "<rect width"), 4);
+        assert!(svg.contains("fill=\"#440154\""));
+        assert!(svg.contains("fill=\"#fde725\""));
+        assert!(svg.contains("<linearGradient id=\"colour-bar\""));
+        assert!(svg.contains(">hits</text>"));
+        let mut output = Vec::new();
+        Plot2D::new(&hist).log_z(true).write(&mut output).unwrap();
+        assert!(String::from_utf8(output).unwrap().contains(">1</text>"));
+    }
+}
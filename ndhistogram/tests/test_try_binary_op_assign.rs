@@ -0,0 +1,72 @@
+use ndhistogram::{
+    axis::{Uniform, Variable},
+    error::{AxisMismatch, BinaryOperationError},
+    ndhistogram, sparsehistogram, Histogram,
+};
+
+#[test]
+fn test_vechistogram_try_assign_ops() {
+    let mut hist = ndhistogram!(Uniform::new(4, 0.0, 4.0).unwrap());
+    let mut other = ndhistogram!(Uniform::new(4, 0.0, 4.0).unwrap());
+    hist.fill_with(&1.0, 6.0);
+    other.fill_with(&1.0, 2.0);
+    hist.try_add_assign(&other).unwrap();
+    assert_eq!(hist.value(&1.0), Some(&8.0));
+    hist.try_sub_assign(&other).unwrap();
+    assert_eq!(hist.value(&1.0), Some(&6.0));
+    hist.try_mul_assign(&other).unwrap();
+    assert_eq!(hist.value(&1.0), Some(&12.0));
+    hist.try_div_assign(&other).unwrap();
+    assert_eq!(hist.value(&1.0), Some(&6.0));
+}
+
+#[test]
+fn test_vechistogram_try_assign_ops_leave_self_unchanged_on_error() {
+    let mut hist = ndhistogram!(Uniform::new(4, 0.0, 4.0).unwrap(); i64);
+    let mut other = ndhistogram!(Uniform::new(4, 0.0, 5.0).unwrap(); i64);
+    hist.fill_with(&1.0, 2);
+    other.fill_with(&1.0, 3);
+    let before = hist.clone();
+    let expected = Err(BinaryOperationError::IncompatibleAxis {
+        axis: 0,
+        reason: AxisMismatch::Range,
+    });
+    assert_eq!(hist.try_add_assign(&other), expected);
+    assert_eq!(hist.try_sub_assign(&other), expected);
+    assert_eq!(hist, before);
+}
+
+#[test]
+fn test_hashhistogram_try_assign_ops() {
+    let mut hist = sparsehistogram!(Variable::new(vec![0.0, 1.0, 2.0]).unwrap());
+    let mut other = sparsehistogram!(Variable::new(vec![0.0, 1.0, 2.0]).unwrap());
+    hist.fill_with(&0.5, 6.0);
+    other.fill_with(&0.5, 2.0);
+    other.fill_with(&1.5, 1.0);
+    hist.try_add_assign(&other).unwrap();
+    assert_eq!(hist.value(&0.5), Some(&8.0));
+    assert_eq!(hist.value(&1.5), Some(&1.0));
+    hist.try_sub_assign(&other).unwrap();
+    assert_eq!(hist.value(&0.5), Some(&6.0));
+    hist.try_mul_assign(&other).unwrap();
+    assert_eq!(hist.value(&0.5), Some(&12.0));
+    hist.try_div_assign(&other).unwrap();
+    assert_eq!(hist.value(&0.5), Some(&6.0));
+}
+
+#[test]
+fn test_hashhistogram_try_assign_ops_leave_self_unchanged_on_error() {
+    let mut hist = sparsehistogram!(Variable::new(vec![0.0, 1.0, 2.0]).unwrap());
+    let mut other = sparsehistogram!(Variable::new(vec![0.0, 1.5, 2.0]).unwrap());
+    hist.fill(&0.5);
+    other.fill(&0.5);
+    other.fill(&1.75);
+    let before = hist.clone();
+    let expected = Err(BinaryOperationError::IncompatibleAxis {
+        axis: 0,
+        reason: AxisMismatch::BinEdges,
+    });
+    assert_eq!(hist.try_mul_assign(&other), expected);
+    assert_eq!(hist.try_div_assign(&other), expected);
+    assert_eq!(hist, before);
+}
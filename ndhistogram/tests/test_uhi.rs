@@ -0,0 +1,378 @@
+#[cfg(feature = "serde")]
+mod uhi_tests {
+    use ndhistogram::{
+        axis::{
+            Boolean, Category, CategoryNoFlow, IntCategory, IntCategoryNoFlow, OrderedCategory,
+            OrderedCategoryNoFlow, StrCategory, StrCategoryNoFlow, Uniform, UniformCyclic,
+            UniformNoFlow, Variable, VariableCyclic, VariableNoFlow,
+        },
+        error::ConversionError,
+        io::uhi::{AxisSchema, HistogramSchema, StorageSchema},
+        ndhistogram, sparsehistogram,
+        value::{Mean, Sum, WeightedMean, WeightedSum},
+        Hist1D, Hist2D, HistND, Histogram, SparseHist2D,
+    };
+    use serde_json::json;
+
+    fn round_trip<H>(hist: &H) -> H
+    where
+        for<'a> HistogramSchema: TryFrom<&'a H, Error = ConversionError>,
+        H: TryFrom<HistogramSchema, Error = ConversionError>,
+    {
+        let json = serde_json::to_string(&HistogramSchema::try_from(hist).unwrap()).unwrap();
+        let schema: HistogramSchema = serde_json::from_str(&json).unwrap();
+        H::try_from(schema).unwrap()
+    }
+
+    #[test]
+    fn test_uhi_round_trip_uniform_f64() {
+        let mut hist = ndhistogram!(Uniform::new(5, 0.0, 5.0).unwrap());
+        hist.fill(&-1.0);
+        hist.fill_with(&2.5, 3.0);
+        hist.fill(&10.0);
+        assert_eq!(round_trip(&hist), hist);
+    }
+
+    #[test]
+    fn test_uhi_round_trip_all_axis_types() {
+        let mut hist = ndhistogram!(
+            Uniform::new(2, 0.0, 1.0).unwrap(),
+            UniformNoFlow::new(2, 0.0, 1.0).unwrap(),
+            UniformCyclic::new(2, 0.0, 1.0).unwrap(),
+            Variable::new(vec![0.0, 1.0, 3.0]).unwrap(),
+            VariableNoFlow::new(vec![0.0, 1.0, 3.0]).unwrap(),
+            VariableCyclic::new(vec![0.0, 1.0, 3.0]).unwrap(),
+            Category::new(vec!["a".to_string(), "b".to_string()]),
+            CategoryNoFlow::new(vec![3, 1, 2]);
+            i32
+        );
+        hist.fill(&(0.1, 0.6, 0.1, 2.0, 0.5, 1.5, "b".to_string(), 1));
+        hist.fill_with(&(2.0, 0.1, 0.6, -1.0, 2.5, 0.5, "c".to_string(), 3), 7);
+        assert_eq!(round_trip(&hist), hist);
+    }
+
+    #[test]
+    fn test_uhi_round_trip_boolean() {
+        let mut hist = ndhistogram!(Boolean::new(), Uniform::new(2, 0.0, 1.0).unwrap());
+        hist.fill(&(true, 0.25));
+        hist.fill_with(&(false, 5.0), 2.0);
+        let schema = HistogramSchema::try_from(&hist).unwrap();
+        assert!(matches!(schema.axes[0], AxisSchema::Boolean { .. }));
+        assert_eq!(
+            serde_json::to_value(&schema.axes[0]).unwrap(),
+            json!({"type": "boolean", "metadata": {}})
+        );
+        assert_eq!(round_trip(&hist), hist);
+    }
+
+    #[test]
+    fn test_uhi_round_trip_intcategory() {
+        let mut hist =
+            ndhistogram!(IntCategory::new(0..8u32), IntCategoryNoFlow::new(vec![-11i32, 11]); i64);
+        hist.fill(&(3, 11));
+        hist.fill(&(30, -11));
+        let schema = HistogramSchema::try_from(&hist).unwrap();
+        assert!(matches!(
+            schema.axes[1],
+            AxisSchema::CategoryInt { flow: false, .. }
+        ));
+        assert_eq!(round_trip(&hist), hist);
+    }
+
+    #[test]
+    fn test_uhi_round_trip_orderedcategory() {
+        let mut hist = ndhistogram!(
+            OrderedCategory::new(vec!["b".to_string(), "a".to_string()]),
+            OrderedCategoryNoFlow::new(vec![20i64, 10]);
+            i64
+        );
+        hist.fill(&("a".to_string(), 20));
+        hist.fill(&("z".to_string(), 10));
+        let schema = HistogramSchema::try_from(&hist).unwrap();
+        assert_eq!(
+            serde_json::to_value(&schema.axes[0]).unwrap()["categories"],
+            json!(["a", "b"])
+        );
+        assert_eq!(round_trip(&hist), hist);
+    }
+
+    #[test]
+    fn test_uhi_round_trip_strcategory() {
+        let mut hist = ndhistogram!(StrCategory::new(["electron", "muon"]); i64);
+        hist.fill_str("muon");
+        hist.fill_str("photon");
+        let schema = HistogramSchema::try_from(&hist).unwrap();
+        assert_eq!(
+            serde_json::to_value(&schema.axes[0]).unwrap(),
+            json!({"type": "category_str", "categories": ["electron", "muon"], "flow": true, "metadata": {}})
+        );
+        assert_eq!(round_trip(&hist), hist);
+        let hist = ndhistogram!(StrCategoryNoFlow::new(["tau"]); i64);
+        assert_eq!(round_trip(&hist), hist);
+    }
+
+    #[test]
+    fn test_uhi_round_trip_value_types() {
+        let mut hist = ndhistogram!(Uniform::new(3, 0.0, 3.0).unwrap(); Sum);
+        hist.fill(&1.5);
+        assert_eq!(round_trip(&hist), hist);
+
+        let mut hist = ndhistogram!(Uniform::new(3, 0.0, 3.0).unwrap(); WeightedSum);
+        hist.fill_with(&1.5, 2.0);
+        hist.fill_with(&1.5, 3.0);
+        assert_eq!(round_trip(&hist), hist);
+
+        let mut hist = ndhistogram!(Uniform::new(3, 0.0, 3.0).unwrap(); Mean);
+        hist.fill_with(&1.5, 1.0);
+        hist.fill_with(&1.5, 2.0);
+        hist.fill_with(&1.5, 6.0);
+        hist.fill_with(&2.5, 4.0);
+        assert_eq!(round_trip(&hist), hist);
+
+        let mut hist = ndhistogram!(Uniform::new(3, 0.0, 3.0).unwrap(); Mean<i32>);
+        hist.fill_with(&1.5, 1);
+        hist.fill_with(&1.5, 2);
+        assert_eq!(round_trip(&hist), hist);
+
+        let mut hist = ndhistogram!(Uniform::new(3, 0.0, 3.0).unwrap(); WeightedMean);
+        hist.fill_with_weighted(&1.5, 1.0, 2.0);
+        hist.fill_with_weighted(&1.5, 4.0, 2.0);
+        assert_eq!(round_trip(&hist), hist);
+    }
+
+    #[test]
+    fn test_uhi_round_trip_sparse_histogram() {
+        let mut hist: SparseHist2D<Uniform, Variable> = sparsehistogram!(
+            Uniform::new(3, 0.0, 3.0).unwrap(),
+            Variable::new(vec![0.0, 1.0, 10.0]).unwrap()
+        );
+        hist.fill(&(0.5, 5.0));
+        hist.fill(&(1.5, 0.5));
+        let read = round_trip(&hist);
+        assert_eq!(read, hist);
+        assert_eq!(read.as_map().len(), 2);
+    }
+
+    #[test]
+    fn test_uhi_json_structure() {
+        let mut hist = ndhistogram!(
+            Uniform::new(2, 0.0, 2.0).unwrap(),
+            CategoryNoFlow::new(vec!["x".to_string(), "y".to_string(), "z".to_string()]);
+            i64
+        );
+        hist.fill(&(-1.0, "y".to_string()));
+        hist.fill_with(&(1.5, "z".to_string()), 2);
+        let mut json = serde_json::to_value(HistogramSchema::try_from(&hist).unwrap()).unwrap();
+        json["writer_info"]["ndhistogram"]["version"] = json!("");
+        assert_eq!(
+            json,
+            json!({
+                "uhi_schema": 1,
+                "writer_info": {"ndhistogram": {"version": ""}},
+                "axes": [
+                    {
+                        "type": "regular",
+                        "lower": 0.0,
+                        "upper": 2.0,
+                        "bins": 2,
+                        "underflow": true,
+                        "overflow": true,
+                        "circular": false,
+                        "metadata": {}
+                    },
+                    {
+                        "type": "category_str",
+                        "categories": ["x", "y", "z"],
+                        "flow": false,
+                        "metadata": {}
+                    }
+                ],
+                "storage": {
+                    "type": "int",
+                    "values": [[0, 1, 0], [0, 0, 0], [0, 0, 2], [0, 0, 0]]
+                },
+                "metadata": {}
+            })
+        );
+    }
+
+    #[test]
+    fn test_uhi_writer_info_has_crate_version() {
+        let hist = ndhistogram!(Uniform::new(2, 0.0, 2.0).unwrap());
+        let schema = HistogramSchema::try_from(&hist).unwrap();
+        assert_eq!(
+            schema.writer_info["ndhistogram"]["version"],
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+
+    #[test]
+    fn test_uhi_read_boost_histogram_json() {
+        // as written by boost-histogram with
+        // bh.Histogram(bh.axis.Regular(2, 0, 1), bh.axis.Variable([0, 1, 5]),
+        //     storage=bh.storage.Weight())
+        let json = r#"{
+            "uhi_schema": 1,
+            "writer_info": {"boost-histogram": {"version": "1.5.0"}},
+            "axes": [
+                {"type": "regular", "lower": 0.0, "upper": 1.0, "bins": 2,
+                 "underflow": true, "overflow": true, "circular": false,
+                 "metadata": {"name": "x", "label": "x [cm]"}},
+                {"type": "variable", "edges": [0, 1, 5],
+                 "underflow": true, "overflow": true, "circular": false}
+            ],
+            "storage": {
+                "type": "weighted",
+                "values": [[0, 0, 0, 0], [0, 1, 2, 0], [0, 0, 0, 0], [0, 0, 0, 4]],
+                "variances": [[0, 0, 0, 0], [0, 1, 2, 0], [0, 0, 0, 0], [0, 0, 0, 8]]
+            },
+            "metadata": {"name": "hist"}
+        }"#;
+        let schema: HistogramSchema = serde_json::from_str(json).unwrap();
+        let hist: Hist2D<Uniform, Variable, WeightedSum> = schema.clone().try_into().unwrap();
+        assert_eq!(
+            hist.value(&(0.25, 0.5)),
+            Some(&WeightedSum::with_sum_and_variance(1.0, 1.0))
+        );
+        assert_eq!(
+            hist.value(&(0.25, 3.0)),
+            Some(&WeightedSum::with_sum_and_variance(2.0, 2.0))
+        );
+        assert_eq!(
+            hist.value(&(2.0, 10.0)),
+            Some(&WeightedSum::with_sum_and_variance(4.0, 8.0))
+        );
+        // the variable axis has flow bins so it cannot be read as VariableNoFlow
+        let result: Result<Hist2D<Uniform, VariableNoFlow, WeightedSum>, _> = schema.try_into();
+        assert_eq!(result, Err(ConversionError::IncompatibleAxis(1)));
+    }
+
+    #[test]
+    fn test_uhi_read_axis_with_only_overflow_is_rejected() {
+        let json = r#"{
+            "uhi_schema": 1,
+            "axes": [{"type": "variable", "edges": [0, 1, 5],
+                      "underflow": false, "overflow": true, "circular": false}],
+            "storage": {"type": "double", "values": [1, 2, 3]}
+        }"#;
+        let schema: HistogramSchema = serde_json::from_str(json).unwrap();
+        let result: Result<Hist1D<Variable>, _> = schema.clone().try_into();
+        assert_eq!(result, Err(ConversionError::IncompatibleAxis(0)));
+        let result: Result<Hist1D<VariableNoFlow>, _> = schema.try_into();
+        assert_eq!(result, Err(ConversionError::IncompatibleAxis(0)));
+    }
+
+    #[test]
+    fn test_uhi_read_mean_storage() {
+        let json = r#"{
+            "uhi_schema": 1,
+            "axes": [{"type": "category_int", "categories": [1, 2], "flow": false}],
+            "storage": {
+                "type": "mean",
+                "counts": [3, 0],
+                "values": [2.0, 0.0],
+                "variances": [1.0, 0.0]
+            }
+        }"#;
+        let schema: HistogramSchema = serde_json::from_str(json).unwrap();
+        let hist: Hist1D<CategoryNoFlow<i32>, Mean> = schema.try_into().unwrap();
+        let expected: Mean = Mean::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!(hist.value(&1), Some(&expected));
+        assert_eq!(hist.value(&2), Some(&Mean::default()));
+    }
+
+    #[test]
+    fn test_uhi_float_histogram_reads_int_storage() {
+        let schema = HistogramSchema {
+            uhi_schema: 1,
+            writer_info: Default::default(),
+            axes: vec![AxisSchema::Boolean {
+                metadata: Default::default(),
+            }],
+            storage: StorageSchema::Int {
+                values: ndhistogram::io::uhi::NdArray::new(vec![2], vec![1, 2]).unwrap(),
+            },
+            metadata: Default::default(),
+        };
+        let result: Result<Hist1D<Uniform>, _> = schema.try_into();
+        assert_eq!(result, Err(ConversionError::IncompatibleAxis(0)));
+
+        let hist = ndhistogram!(UniformNoFlow::new(2, 0.0, 1.0).unwrap(); i64);
+        let schema = HistogramSchema::try_from(&hist).unwrap();
+        let read: Hist1D<UniformNoFlow, f64> = schema.try_into().unwrap();
+        assert_eq!(read.values().copied().collect::<Vec<_>>(), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_uhi_errors() {
+        let hist = ndhistogram!(Uniform::new(2, 0.0, 1.0).unwrap());
+        let schema = HistogramSchema::try_from(&hist).unwrap();
+
+        let mut future = schema.clone();
+        future.uhi_schema = 2;
+        let result: Result<Hist1D<Uniform>, _> = future.try_into();
+        assert_eq!(result, Err(ConversionError::UnsupportedVersion));
+
+        let result: Result<Hist2D<Uniform, Uniform>, _> = schema.clone().try_into();
+        assert_eq!(
+            result,
+            Err(ConversionError::WrongNumberOfAxes {
+                expected: 2,
+                actual: 1
+            })
+        );
+
+        let result: Result<Hist1D<Uniform, i32>, _> = schema.clone().try_into();
+        assert_eq!(result, Err(ConversionError::IncompatibleStorage));
+
+        let result: Result<Hist1D<Uniform, WeightedSum>, _> = schema.try_into();
+        assert_eq!(result, Err(ConversionError::IncompatibleStorage));
+    }
+
+    #[test]
+    fn test_uhi_shape_mismatch() {
+        let json = r#"{
+            "uhi_schema": 1,
+            "axes": [{"type": "regular", "lower": 0, "upper": 1, "bins": 2,
+                      "underflow": true, "overflow": true, "circular": false}],
+            "storage": {"type": "double", "values": [1, 2, 3]}
+        }"#;
+        let schema: HistogramSchema = serde_json::from_str(json).unwrap();
+        let result: Result<Hist1D<Uniform>, _> = schema.try_into();
+        assert_eq!(result, Err(ConversionError::ShapeMismatch));
+    }
+
+    #[test]
+    fn test_uhi_ragged_array_is_rejected() {
+        let json = r#"{
+            "uhi_schema": 1,
+            "axes": [],
+            "storage": {"type": "double", "values": [[1, 2], [3]]}
+        }"#;
+        assert!(serde_json::from_str::<HistogramSchema>(json).is_err());
+    }
+
+    #[test]
+    fn test_uhi_high_dimensional_round_trip() {
+        let mut hist: HistND<(Uniform, Uniform, Uniform)> = ndhistogram!(
+            Uniform::new(2, 0.0, 2.0).unwrap(),
+            Uniform::new(3, 0.0, 3.0).unwrap(),
+            Uniform::new(4, 0.0, 4.0).unwrap()
+        );
+        for (i, x) in [0.5, 1.5].iter().enumerate() {
+            for (j, y) in [0.5, 1.5, 2.5].iter().enumerate() {
+                hist.fill_with(&(*x, *y, 3.5), (i * 10 + j) as f64);
+            }
+        }
+        assert_eq!(round_trip(&hist), hist);
+        let schema = HistogramSchema::try_from(&hist).unwrap();
+        match schema.storage {
+            StorageSchema::Double { values } => {
+                assert_eq!(values.shape(), &[4, 5, 6]);
+                // x = 1.5, y = 2.5, z = 3.5
+                assert_eq!(values.data()[2 * 30 + 3 * 6 + 4], 12.0);
+            }
+            _ => panic!("expected double storage"),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use ndhistogram::{
+    axis::{Uniform, UniformNoFlow, Variable, VariableNoFlow},
+    error::ConversionError,
+    io::yoda::YodaFile,
+    ndhistogram,
+    value::{WeightedMean, WeightedSum},
+    Hist1D, Hist2D, Histogram,
+};
+
+fn round_trip(file: &YodaFile) -> YodaFile {
+    file.to_string().parse().unwrap()
+}
+
+#[test]
+fn test_yoda_write_histo1d() {
+    let mut hist = ndhistogram!(Uniform::new(2, 0.0, 2.0).unwrap(); WeightedSum);
+    hist.fill_with(&0.5, 2.0);
+    hist.fill_with(&5.0, 1.0);
+    let mut file = YodaFile::new();
+    file.add("/TEST/h", "My title", &hist).unwrap();
+    assert_eq!(
+        file.to_string(),
+        "BEGIN YODA_HISTO1D_V2 /TEST/h\n\
+         Path: /TEST/h\n\
+         Title: My title\n\
+         Type: Histo1D\n\
+         ---\n\
+         # ID\t ID\t sumw\t sumw2\t sumwx\t sumwx2\t numEntries\n\
+         Total   \tTotal   \t3e0\t5e0\t3e0\t4.5e0\t2e0\n\
+         Underflow\tUnderflow\t0e0\t0e0\t0e0\t0e0\t0e0\n\
+         Overflow\tOverflow\t1e0\t1e0\t2e0\t4e0\t1e0\n\
+         # xlow\t xhigh\t sumw\t sumw2\t sumwx\t sumwx2\t numEntries\n\
+         0e0\t1e0\t2e0\t4e0\t1e0\t5e-1\t1e0\n\
+         1e0\t2e0\t0e0\t0e0\t0e0\t0e0\t0e0\n\
+         END YODA_HISTO1D_V2\n\
+         \n"
+    );
+}
+
+#[test]
+fn test_yoda_round_trip_histo1d() {
+    let mut hist = ndhistogram!(Variable::new(vec![0.0, 0.5, 2.0, 10.0]).unwrap(); WeightedSum);
+    hist.fill_with(&-1.0, 0.5);
+    hist.fill_with(&1.0, 2.0);
+    hist.fill_with(&1.0, 3.0);
+    hist.fill_with(&11.0, 1.5);
+    let mut file = YodaFile::new();
+    file.add("/TEST/h", "", &hist).unwrap();
+    let read: Hist1D<Variable, WeightedSum> = round_trip(&file).get("/TEST/h").unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_yoda_round_trip_histo2d() {
+    let mut hist = ndhistogram!(
+        UniformNoFlow::new(3, 0.0, 3.0).unwrap(),
+        VariableNoFlow::new(vec![0.0, 1.0, 5.0]).unwrap();
+        WeightedSum
+    );
+    hist.fill_with(&(0.5, 0.5), 2.0);
+    hist.fill_with(&(2.5, 3.0), 0.25);
+    hist.fill(&(1.5, 0.5));
+    let mut file = YodaFile::new();
+    file.add("/TEST/h2", "", &hist).unwrap();
+    let text = file.to_string();
+    assert!(text.contains("0e0\t1e0\t0e0\t1e0\t2e0\t4e0\t"));
+    let read: Hist2D<UniformNoFlow, VariableNoFlow, WeightedSum> =
+        round_trip(&file).get("/TEST/h2").unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_yoda_histo2d_flow_bins_are_not_stored() {
+    let mut hist = ndhistogram!(
+        Uniform::new(2, 0.0, 2.0).unwrap(),
+        Uniform::new(2, 0.0, 2.0).unwrap();
+        WeightedSum
+    );
+    hist.fill(&(0.5, 0.5));
+    hist.fill(&(-1.0, 0.5));
+    let mut file = YodaFile::new();
+    file.add("/TEST/h2", "", &hist).unwrap();
+    let read: Hist2D<Uniform, Uniform, WeightedSum> = round_trip(&file).get("/TEST/h2").unwrap();
+    assert_eq!(read.value(&(0.5, 0.5)), hist.value(&(0.5, 0.5)));
+    assert_eq!(read.value(&(-1.0, 0.5)), Some(&WeightedSum::new()));
+}
+
+#[test]
+fn test_yoda_round_trip_profile1d() {
+    let mut hist = ndhistogram!(Uniform::new(4, 0.0, 4.0).unwrap(); WeightedMean);
+    hist.fill_with_weighted(&0.5, 2.0, 1.0);
+    hist.fill_with_weighted(&0.5, 4.0, 0.5);
+    hist.fill_with_weighted(&3.5, -1.0, 2.0);
+    hist.fill_with_weighted(&10.0, 3.0, 1.0);
+    let mut file = YodaFile::new();
+    file.add("/TEST/p", "Profile", &hist).unwrap();
+    assert!(file
+        .to_string()
+        .starts_with("BEGIN YODA_PROFILE1D_V2 /TEST/p\n"));
+    let read: Hist1D<Uniform, WeightedMean> = round_trip(&file).get("/TEST/p").unwrap();
+    assert_eq!(read, hist);
+}
+
+#[test]
+fn test_yoda_read_yoda_formatted_file() {
+    let input = "\
+# a comment before the first object
+BEGIN YODA_HISTO1D_V2 /REF/d01-x01-y01
+Path: /REF/d01-x01-y01
+ScaledBy: 1.000000e+00
+Title: Jet $p_T$
+Type: Histo1D
+---
+# Mean: 1.500000e+00
+# Area: 3.000000e+00
+# ID\t ID\t sumw\t sumw2\t sumwx\t sumwx2\t numEntries
+Total   \tTotal   \t4.000000e+00\t4.000000e+00\t5.000000e+00\t7.500000e+00\t4.000000e+00
+Underflow\tUnderflow\t1.000000e+00\t1.000000e+00\t-1.000000e+00\t1.000000e+00\t1.000000e+00
+Overflow\tOverflow\t0.000000e+00\t0.000000e+00\t0.000000e+00\t0.000000e+00\t0.000000e+00
+# xlow\t xhigh\t sumw\t sumw2\t sumwx\t sumwx2\t numEntries
+0.000000e+00\t1.000000e+00\t2.000000e+00\t2.000000e+00\t1.000000e+00\t5.000000e-01\t2.000000e+00
+1.000000e+00\t2.000000e+00\t1.000000e+00\t1.000000e+00\t1.500000e+00\t2.250000e+00\t1.000000e+00
+END YODA_HISTO1D_V2
+
+BEGIN YODA_SCATTER2D_V2 /REF/d02-x01-y01
+Path: /REF/d02-x01-y01
+Type: Scatter2D
+---
+# xval\t xerr-\t xerr+\t yval\t yerr-\t yerr+
+1.000000e+00\t5.000000e-01\t5.000000e-01\t2.000000e+00\t1.000000e-01\t1.000000e-01
+END YODA_SCATTER2D_V2
+";
+    let file: YodaFile = input.parse().unwrap();
+    assert_eq!(file.paths().collect::<Vec<_>>(), vec!["/REF/d01-x01-y01"]);
+    assert_eq!(file.title("/REF/d01-x01-y01"), Some("Jet $p_T$"));
+    let hist: Hist1D<Uniform, WeightedSum> = file.get("/REF/d01-x01-y01").unwrap();
+    assert_eq!(hist.axes().as_tuple().0, Uniform::new(2, 0.0, 2.0).unwrap());
+    assert_eq!(
+        hist.values().copied().collect::<Vec<_>>(),
+        vec![
+            WeightedSum::with_sum_and_variance(1.0, 1.0),
+            WeightedSum::with_sum_and_variance(2.0, 2.0),
+            WeightedSum::with_sum_and_variance(1.0, 1.0),
+            WeightedSum::with_sum_and_variance(0.0, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn test_yoda_paths_titles_and_write() {
+    let hist = ndhistogram!(Uniform::new(2, 0.0, 2.0).unwrap(); WeightedSum);
+    let mut file = YodaFile::new();
+    file.add("/A/first", "First", &hist).unwrap();
+    file.add("/A/second", "Second", &hist).unwrap();
+    file.add("/A/first", "Replaced", &hist).unwrap();
+    let mut output = Vec::new();
+    file.write(&mut output).unwrap();
+    let read: YodaFile = String::from_utf8(output).unwrap().parse().unwrap();
+    assert_eq!(
+        read.paths().collect::<Vec<_>>(),
+        vec!["/A/first", "/A/second"]
+    );
+    assert_eq!(read.title("/A/first"), Some("Replaced"));
+    assert_eq!(read.title("/A/third"), None);
+    assert_eq!(read, file);
+}
+
+#[test]
+fn test_yoda_get_errors() {
+    let hist = ndhistogram!(Variable::new(vec![0.0, 1.0, 3.0]).unwrap(); WeightedSum);
+    let mut file = YodaFile::new();
+    file.add("/TEST/h", "", &hist).unwrap();
+    let file = round_trip(&file);
+    let missing: Result<Hist1D<Variable, WeightedSum>, _> = file.get("/TEST/missing");
+    assert_eq!(missing, Err(ConversionError::NotFound));
+    let profile: Result<Hist1D<Variable, WeightedMean>, _> = file.get("/TEST/h");
+    assert_eq!(profile, Err(ConversionError::IncompatibleStorage));
+    let two_dimensional: Result<Hist2D<Variable, Variable, WeightedSum>, _> = file.get("/TEST/h");
+    assert_eq!(
+        two_dimensional,
+        Err(ConversionError::WrongNumberOfAxes {
+            expected: 2,
+            actual: 1
+        })
+    );
+    let uniform: Result<Hist1D<Uniform, WeightedSum>, _> = file.get("/TEST/h");
+    assert_eq!(uniform, Err(ConversionError::IncompatibleAxis(0)));
+}
+
+#[test]
+fn test_yoda_parse_errors() {
+    assert_eq!(
+        "not yoda".parse::<YodaFile>(),
+        Err(ConversionError::InvalidRow(1))
+    );
+    assert_eq!(
+        "BEGIN YODA_HISTO1D_V2 /h\nPath: /h\n---\n0 1 2\nEND YODA_HISTO1D_V2\n".parse::<YodaFile>(),
+        Err(ConversionError::InvalidRow(4))
+    );
+    assert_eq!(
+        "BEGIN YODA_HISTO1D_V2 /h\nPath: /h\n---\n".parse::<YodaFile>(),
+        Err(ConversionError::InvalidData)
+    );
+}